@@ -0,0 +1,96 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generator of the commit encoding test vectors for genesis, state
+//! transition and state extension fixtures, as well as of the strict encoding
+//! test vectors for seal types.
+//!
+//! Usage: `rgbcore-vectors [--force] [DIR]`
+//!
+//! Writes vectors which are absent in the `DIR` (defaults to
+//! `fixtures/commit`). Existing vectors which differ from the current encoding
+//! or commitment are reported and left untouched unless `--force` flag is
+//! given, since changing them means changing consensus.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use rgbcore::commit_vectors::{samples, VECTORS_DIR};
+
+fn write(path: &Path, value: &str, force: bool) -> bool {
+    let name = path.display();
+    match fs::read_to_string(path) {
+        Ok(existing) if existing.trim() == value => {
+            println!("{name}: unchanged");
+            return true;
+        }
+        Ok(existing) if !force => {
+            eprintln!("{name}: has changed");
+            eprintln!("  vector:  {}", existing.trim());
+            eprintln!("  current: {value}");
+            return false;
+        }
+        Ok(_) => println!("{name}: overwritten"),
+        Err(_) => println!("{name}: created"),
+    }
+    fs::write(path, format!("{value}\n")).expect("unable to write test vector file");
+    true
+}
+
+fn main() {
+    let mut force = false;
+    let mut dir = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--force" => force = true,
+            _ if dir.is_none() => dir = Some(PathBuf::from(arg)),
+            _ => {
+                eprintln!("usage: rgbcore-vectors [--force] [DIR]");
+                process::exit(2);
+            }
+        }
+    }
+    let dir = dir.unwrap_or_else(|| PathBuf::from(VECTORS_DIR));
+    fs::create_dir_all(&dir).expect("unable to create test vectors directory");
+
+    let mut changed = 0usize;
+    for sample in samples() {
+        let name = sample.name;
+        if !write(&dir.join(format!("{name}.hex")), &sample.data, force) {
+            changed += 1;
+        }
+        let Some(commitment) = &sample.commitment else {
+            continue;
+        };
+        if !write(&dir.join(format!("{name}.id")), commitment, force) {
+            changed += 1;
+        }
+    }
+
+    if changed > 0 {
+        eprintln!(
+            "{changed} test vector(s) differ from the current commit encoding; re-run with \
+             --force if the consensus change is intended"
+        );
+        process::exit(1);
+    }
+}
@@ -21,16 +21,18 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 use bp::dbc::opret::{OpretError, OpretProof};
 use bp::dbc::tapret::TapretProof;
 use bp::dbc::Method;
+use bp::seals::txout::CloseMethod;
 use bp::{dbc, Tx};
-use commit_verify::mpc::Commitment;
-use commit_verify::{mpc, ConvolveVerifyError, EmbedVerifyError};
+use commit_verify::mpc::{Commitment, InvalidProof, MessageMap};
+use commit_verify::{mpc, CommitId, ConvolveVerifyError, EmbedVerifyError};
 use strict_encoding::{StrictDeserialize, StrictDumb, StrictSerialize};
 
-use crate::{WitnessOrd, XWitnessId, LIB_NAME_RGB};
+use crate::{BundleId, ContractId, WitnessOrd, XWitnessId, LIB_NAME_RGB};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
 #[cfg_attr(
@@ -86,6 +88,16 @@ pub enum DbcProof {
 impl StrictSerialize for DbcProof {}
 impl StrictDeserialize for DbcProof {}
 
+impl DbcProof {
+    /// Returns closing method used by the proof.
+    pub fn method(&self) -> Method {
+        match self {
+            DbcProof::Tapret(_) => Method::TapretFirst,
+            DbcProof::Opret(_) => Method::OpretFirst,
+        }
+    }
+}
+
 impl dbc::Proof for DbcProof {
     type Error = DbcError;
     const METHOD: Method = Method::OpretFirst;
@@ -115,6 +127,175 @@ impl dbc::Proof for DbcProof {
 /// Anchor which DBC proof is either Tapret or Opret.
 pub type EAnchor<P = mpc::MerkleProof> = dbc::Anchor<P, DbcProof>;
 
+/// Errors assembling multi-protocol commitment messages from contract bundles.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MpcAssemblyError {
+    /// contract {0} commits more than a single transition bundle into the same
+    /// witness transaction.
+    DuplicateContract(ContractId),
+
+    /// number of contracts committing into the same witness transaction exceeds
+    /// the multi-protocol commitment limit.
+    TooManyContracts,
+}
+
+/// Errors verifying that a contract is committed into an anchor.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display(doc_comments)]
+pub enum AnchorContractError {
+    /// contract {0} is not committed into the anchor, or its commitment is
+    /// concealed.
+    ContractAbsent(ContractId),
+
+    /// contract {contract_id} commits to bundle {found} in the anchor instead
+    /// of {expected}.
+    MessageMismatch {
+        contract_id: ContractId,
+        expected: BundleId,
+        found: BundleId,
+    },
+
+    /// multi-protocol commitment proof for contract {0} is invalid: {1}
+    InvalidProof(ContractId, InvalidProof),
+
+    /// multi-protocol commitment proof for contract {0} doesn't match the
+    /// commitment of the anchor.
+    CommitmentMismatch(ContractId),
+}
+
+/// Errors verifying that an anchor commits to a transition bundle of a
+/// contract.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display(doc_comments)]
+pub enum AnchorError {
+    /// DBC proof of the anchor for the bundle {bundle_id} doesn't match the
+    /// witness transaction: {details}
+    Dbc {
+        bundle_id: BundleId,
+        details: DbcError,
+    },
+
+    /// {0}
+    MpcInclusion(AnchorContractError),
+
+    /// contract {contract_id} commits to bundle {found} in the anchor instead
+    /// of {expected}.
+    MessageMismatch {
+        contract_id: ContractId,
+        expected: BundleId,
+        found: BundleId,
+    },
+}
+
+impl From<AnchorContractError> for AnchorError {
+    fn from(err: AnchorContractError) -> Self {
+        match err {
+            AnchorContractError::MessageMismatch {
+                contract_id,
+                expected,
+                found,
+            } => AnchorError::MessageMismatch {
+                contract_id,
+                expected,
+                found,
+            },
+            err => AnchorError::MpcInclusion(err),
+        }
+    }
+}
+
+/// Assembles multi-protocol commitment messages for a witness transaction
+/// from the transition bundles of several contracts.
+///
+/// # Errors
+///
+/// If the same contract id is provided more than once, or the number of
+/// contracts exceeds the multi-protocol commitment limit.
+pub fn build_mpc_messages(
+    entries: impl IntoIterator<Item = (ContractId, BundleId)>,
+) -> Result<MessageMap, MpcAssemblyError> {
+    let mut messages = BTreeMap::new();
+    for (contract_id, bundle_id) in entries {
+        if messages
+            .insert(mpc::ProtocolId::from(contract_id), mpc::Message::from(bundle_id))
+            .is_some()
+        {
+            return Err(MpcAssemblyError::DuplicateContract(contract_id));
+        }
+    }
+    MessageMap::try_from(messages).map_err(|_| MpcAssemblyError::TooManyContracts)
+}
+
+/// Verifies that the anchor commits to the `expected_msg` under the protocol
+/// id of the contract, returning the multi-protocol commitment.
+///
+/// # Errors
+///
+/// If the contract is absent from the anchor, commits to a different bundle,
+/// or if the merkle proof for the contract doesn't match the anchor.
+pub fn verify_contract_in_anchor<D: dbc::Proof>(
+    anchor: &dbc::Anchor<mpc::MerkleBlock, D>,
+    contract_id: ContractId,
+    expected_msg: BundleId,
+) -> Result<Commitment, AnchorContractError> {
+    let protocol_id = mpc::ProtocolId::from(contract_id);
+    let found = anchor
+        .mpc_proof
+        .to_known_message_map()
+        .get(&protocol_id)
+        .copied()
+        .ok_or(AnchorContractError::ContractAbsent(contract_id))?;
+    if found != mpc::Message::from(expected_msg) {
+        return Err(AnchorContractError::MessageMismatch {
+            contract_id,
+            expected: expected_msg,
+            found: BundleId::from(found),
+        });
+    }
+    let commitment = anchor
+        .mpc_proof
+        .to_merkle_proof(protocol_id)
+        .map_err(|_| AnchorContractError::ContractAbsent(contract_id))?
+        .convolve(protocol_id, found)
+        .map_err(|err| AnchorContractError::InvalidProof(contract_id, err))?;
+    if commitment != anchor.mpc_proof.commit_id() {
+        return Err(AnchorContractError::CommitmentMismatch(contract_id));
+    }
+    Ok(commitment)
+}
+
+/// Verifies that the anchor commits to the bundle `bundle_id` of the contract
+/// `contract_id` and that the commitment is embedded into the `witness_tx`,
+/// returning the closing method used by the anchor.
+///
+/// # Errors
+///
+/// If the multi-protocol commitment doesn't include the contract, commits to
+/// a different bundle, or if the DBC proof doesn't match the witness
+/// transaction.
+pub fn verify_anchor(
+    anchor: &EAnchor<mpc::MerkleBlock>,
+    contract_id: ContractId,
+    bundle_id: BundleId,
+    witness_tx: &Tx,
+) -> Result<CloseMethod, AnchorError> {
+    let commitment = verify_contract_in_anchor(anchor, contract_id, bundle_id)?;
+    dbc::Proof::verify(&anchor.dbc_proof, &commitment, witness_tx)
+        .map_err(|details| AnchorError::Dbc { bundle_id, details })?;
+    Ok(anchor.dbc_proof.method())
+}
+
 /// Txid and height information ordered according to the RGB consensus rules.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
@@ -148,6 +329,23 @@ impl Ord for WitnessAnchor {
 }
 
 impl WitnessAnchor {
+    pub fn new(witness_id: XWitnessId, witness_ord: WitnessOrd) -> Self {
+        WitnessAnchor {
+            witness_ord,
+            witness_id,
+        }
+    }
+
+    /// Constructs witness anchor from the data provided by a witness resolver:
+    /// the block height and timestamp of the witness transaction. Zero height
+    /// (or invalid timestamp) indicates that the witness is not mined yet.
+    pub fn with_mempool_or_height(witness_id: XWitnessId, height: u32, timestamp: i64) -> Self {
+        WitnessAnchor {
+            witness_ord: WitnessOrd::with_mempool_or_height(height, timestamp),
+            witness_id,
+        }
+    }
+
     pub fn from_mempool(witness_id: XWitnessId) -> Self {
         WitnessAnchor {
             witness_ord: WitnessOrd::OffChain,
@@ -170,4 +368,209 @@ pub enum Layer1 {
     #[strict_type(dumb)]
     Bitcoin = 0,
     Liquid = 1,
+    #[cfg(feature = "prime-seals")]
+    Abraxas = 0x10,
+    #[cfg(feature = "prime-seals")]
+    Prime = 0x11,
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::ByteArray;
+    use bp::{Sats, ScriptPubkey, TxOut, Txid, VarIntArray};
+    use commit_verify::mpc::{MerkleBlock, MerkleTree, MultiSource};
+    use commit_verify::TryCommitVerify;
+
+    use super::*;
+
+    fn txid(byte: u8) -> Txid { Txid::from([byte; 32]) }
+
+    #[test]
+    fn witness_anchor_ordering() {
+        let timestamp = 1231006505 + 1000;
+        let early_liquid = WitnessAnchor::with_mempool_or_height(
+            XWitnessId::Liquid(txid(0xFF)),
+            10,
+            timestamp - 1,
+        );
+        let btc_low =
+            WitnessAnchor::with_mempool_or_height(XWitnessId::Bitcoin(txid(0x02)), 100, timestamp);
+        let btc_high =
+            WitnessAnchor::with_mempool_or_height(XWitnessId::Bitcoin(txid(0x03)), 100, timestamp);
+        let liquid_same_ts =
+            WitnessAnchor::with_mempool_or_height(XWitnessId::Liquid(txid(0x01)), 200, timestamp);
+        let late_btc = WitnessAnchor::with_mempool_or_height(
+            XWitnessId::Bitcoin(txid(0x00)),
+            101,
+            timestamp + 600,
+        );
+        let mempool_btc = WitnessAnchor::from_mempool(XWitnessId::Bitcoin(txid(0xFF)));
+        let mempool_liquid =
+            WitnessAnchor::with_mempool_or_height(XWitnessId::Liquid(txid(0x00)), 0, timestamp);
+        assert_eq!(mempool_liquid.witness_ord, WitnessOrd::OffChain);
+
+        // Ties between equal timestamps are broken by the witness id, which
+        // orders liquid witnesses before bitcoin ones
+        let expected = vec![
+            early_liquid,
+            liquid_same_ts,
+            btc_low,
+            btc_high,
+            late_btc,
+            mempool_liquid,
+            mempool_btc,
+        ];
+        for (i, a) in expected.iter().enumerate() {
+            for (j, b) in expected.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a} vs {b}");
+                assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)));
+            }
+        }
+
+        let mut anchors = expected.clone();
+        anchors.reverse();
+        anchors.sort();
+        assert_eq!(anchors, expected);
+    }
+
+    fn anchor_with(
+        messages: MessageMap,
+        dbc_proof: DbcProof,
+    ) -> dbc::Anchor<MerkleBlock, DbcProof> {
+        let source = MultiSource {
+            messages,
+            static_entropy: Some(0xFEED),
+            ..default!()
+        };
+        let tree = MerkleTree::try_commit(&source).unwrap();
+        dbc::Anchor::new(MerkleBlock::from(&tree), dbc_proof)
+    }
+
+    fn anchor(messages: MessageMap) -> dbc::Anchor<MerkleBlock, DbcProof> {
+        anchor_with(messages, strict_dumb!())
+    }
+
+    fn opret_tx(commitment: Commitment) -> Tx {
+        let mut tx = Tx::strict_dumb();
+        let script = ScriptPubkey::op_return(&commitment.to_byte_array());
+        tx.outputs = VarIntArray::try_from(vec![TxOut::new(script, Sats::ZERO)]).unwrap();
+        tx
+    }
+
+    #[test]
+    fn two_contracts_in_anchor() {
+        let (c1, c2, c3) =
+            (ContractId::from([1; 32]), ContractId::from([2; 32]), ContractId::from([3; 32]));
+        let (b1, b2) = (BundleId::from([0x11; 32]), BundleId::from([0x22; 32]));
+
+        let messages = build_mpc_messages([(c1, b1), (c2, b2)]).unwrap();
+        assert_eq!(messages.len(), 2);
+        let anchor = anchor(messages);
+        let commitment = anchor.mpc_proof.commit_id();
+
+        assert_eq!(verify_contract_in_anchor(&anchor, c1, b1), Ok(commitment));
+        assert_eq!(verify_contract_in_anchor(&anchor, c2, b2), Ok(commitment));
+        assert_eq!(
+            verify_contract_in_anchor(&anchor, c1, b2),
+            Err(AnchorContractError::MessageMismatch {
+                contract_id: c1,
+                expected: b2,
+                found: b1
+            })
+        );
+        assert_eq!(
+            verify_contract_in_anchor(&anchor, c3, b1),
+            Err(AnchorContractError::ContractAbsent(c3))
+        );
+    }
+
+    #[test]
+    fn duplicate_contract() {
+        let contract_id = ContractId::from([1; 32]);
+        let entries = [
+            (contract_id, BundleId::from([0x11; 32])),
+            (ContractId::from([2; 32]), BundleId::from([0x22; 32])),
+            (contract_id, BundleId::from([0x33; 32])),
+        ];
+        assert_eq!(
+            build_mpc_messages(entries),
+            Err(MpcAssemblyError::DuplicateContract(contract_id))
+        );
+        // Even the same bundle can't be committed twice under the same contract
+        let entries = [(contract_id, BundleId::from([0x11; 32])); 2];
+        assert_eq!(
+            build_mpc_messages(entries),
+            Err(MpcAssemblyError::DuplicateContract(contract_id))
+        );
+    }
+
+    #[test]
+    fn anchor_verification() {
+        let (c1, c2) = (ContractId::from([1; 32]), ContractId::from([2; 32]));
+        let (b1, b2) = (BundleId::from([0x11; 32]), BundleId::from([0x22; 32]));
+        let messages = build_mpc_messages([(c1, b1), (c2, b2)]).unwrap();
+        let anchor = anchor_with(messages, DbcProof::Opret(strict_dumb!()));
+        let tx = opret_tx(anchor.mpc_proof.commit_id());
+
+        assert_eq!(verify_anchor(&anchor, c1, b1, &tx), Ok(CloseMethod::OpretFirst));
+        assert_eq!(verify_anchor(&anchor, c2, b2, &tx), Ok(CloseMethod::OpretFirst));
+    }
+
+    #[test]
+    fn anchor_dbc_failure() {
+        let contract_id = ContractId::from([1; 32]);
+        let bundle_id = BundleId::from([0x11; 32]);
+        let messages = build_mpc_messages([(contract_id, bundle_id)]).unwrap();
+        let anchor = anchor_with(messages, DbcProof::Opret(strict_dumb!()));
+
+        assert_eq!(
+            verify_anchor(&anchor, contract_id, bundle_id, &Tx::strict_dumb()),
+            Err(AnchorError::Dbc {
+                bundle_id,
+                details: DbcError::NoOpretOutput
+            })
+        );
+        let tx = opret_tx(Commitment::from([0xAA; 32]));
+        assert_eq!(
+            verify_anchor(&anchor, contract_id, bundle_id, &tx),
+            Err(AnchorError::Dbc {
+                bundle_id,
+                details: DbcError::CommitmentMismatch
+            })
+        );
+    }
+
+    #[test]
+    fn anchor_mpc_failure() {
+        let (c1, c2) = (ContractId::from([1; 32]), ContractId::from([2; 32]));
+        let bundle_id = BundleId::from([0x11; 32]);
+        let messages = build_mpc_messages([(c1, bundle_id)]).unwrap();
+        let anchor = anchor_with(messages, DbcProof::Opret(strict_dumb!()));
+        let tx = opret_tx(anchor.mpc_proof.commit_id());
+
+        let err = verify_anchor(&anchor, c2, bundle_id, &tx).unwrap_err();
+        assert_eq!(err, AnchorError::MpcInclusion(AnchorContractError::ContractAbsent(c2)));
+        assert_eq!(
+            crate::validation::Failure::from(err.clone()),
+            crate::validation::Failure::AnchorInvalid(err)
+        );
+    }
+
+    #[test]
+    fn anchor_message_mismatch() {
+        let contract_id = ContractId::from([1; 32]);
+        let (b1, b2) = (BundleId::from([0x11; 32]), BundleId::from([0x22; 32]));
+        let messages = build_mpc_messages([(contract_id, b1)]).unwrap();
+        let anchor = anchor_with(messages, DbcProof::Opret(strict_dumb!()));
+        let tx = opret_tx(anchor.mpc_proof.commit_id());
+
+        assert_eq!(
+            verify_anchor(&anchor, contract_id, b2, &tx),
+            Err(AnchorError::MessageMismatch {
+                contract_id,
+                expected: b2,
+                found: b1
+            })
+        );
+    }
 }
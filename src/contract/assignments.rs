@@ -22,18 +22,21 @@
 
 use core::cmp::Ordering;
 use core::fmt::Debug;
-use std::collections::{btree_map, BTreeSet};
+use std::collections::{btree_map, BTreeMap, BTreeSet};
 use std::hash::Hash;
 
 use amplify::confinement::{Confined, SmallVec, TinyOrdMap};
 use commit_verify::{Conceal, ReservedBytes};
-use strict_encoding::{StrictDumb, StrictEncode};
+use secp256k1_zkp::rand::{thread_rng, Rng, RngCore};
+use strict_encoding::{StrictDeserialize, StrictDumb, StrictEncode, StrictSerialize};
 
 use super::ExposedState;
 use crate::contract::seal::GenesisSeal;
 use crate::{
-    AssignmentType, ExposedSeal, GraphSeal, RevealedAttach, RevealedData, RevealedValue,
-    SecretSeal, StateType, VoidState, XChain, LIB_NAME_RGB,
+    AssetTag, AssignmentIndex, AssignmentType, BlindingFactor, ConcealedAttach, ConcealedData,
+    ConcealedState, ConcealedValue, ConfidentialState, CtEq, ExposedSeal, FungibleState, GraphSeal,
+    OwnedStateSchema, RevealedAttach, RevealedData, RevealedState, RevealedValue, SecretSeal,
+    StateType, VoidState, XChain, XGraphSeal, LIB_NAME_RGB,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
@@ -41,6 +44,20 @@ use crate::{
 /// the requested data are not present.
 pub struct UnknownDataError;
 
+/// Errors summing fungible values of assignments.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum FungibleSumError {
+    /// assignments don't contain fungible state.
+    NotFungible,
+
+    /// {0} of the fungible assignments have confidential state.
+    Confidential(u16),
+
+    /// sum of the fungible values overflows 64-bit unsigned integer.
+    Overflow,
+}
+
 pub type AssignRights<Seal> = Assign<VoidState, Seal>;
 pub type AssignFungible<Seal> = Assign<RevealedValue, Seal>;
 pub type AssignData<Seal> = Assign<RevealedData, Seal>;
@@ -95,6 +112,60 @@ pub enum Assign<State: ExposedState, Seal: ExposedSeal> {
     },
 }
 
+/// Which parts of an assignment are revealed, corresponding to the variants of
+/// [`Assign`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub enum RevealStatus {
+    /// both seal and state are concealed.
+    #[display("confidential")]
+    Confidential,
+
+    /// both seal and state are revealed.
+    #[display("revealed")]
+    Revealed,
+
+    /// seal is concealed, while state is revealed.
+    #[display("confidential seal")]
+    ConfidentialSeal,
+
+    /// seal is revealed, while state is concealed.
+    #[display("confidential state")]
+    ConfidentialState,
+}
+
+/// Seal of an assignment in the form it is present in the assignment.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SealForm<'a, Seal: ExposedSeal> {
+    /// seal is revealed.
+    Revealed(&'a XChain<Seal>),
+
+    /// seal is concealed.
+    Confidential(XChain<SecretSeal>),
+}
+
+impl<'a, Seal: ExposedSeal> SealForm<'a, Seal> {
+    /// Returns the revealed seal, if present.
+    pub fn revealed(&self) -> Option<&'a XChain<Seal>> {
+        match self {
+            SealForm::Revealed(seal) => Some(seal),
+            SealForm::Confidential(_) => None,
+        }
+    }
+
+    /// Returns the concealed seal, concealing the revealed one if needed.
+    pub fn secret_seal(&self) -> XChain<SecretSeal> {
+        match self {
+            SealForm::Revealed(seal) => seal.conceal(),
+            SealForm::Confidential(secret) => *secret,
+        }
+    }
+}
+
 // Consensus-critical!
 // Assignment indexes are part of the transition ancestor's commitment, so
 // here we use deterministic ordering based on hash values of the concealed
@@ -111,10 +182,7 @@ impl<State: ExposedState, Seal: ExposedSeal> Ord for Assign<State, Seal> {
 }
 
 impl<State: ExposedState, Seal: ExposedSeal> PartialEq for Assign<State, Seal> {
-    fn eq(&self, other: &Self) -> bool {
-        self.to_confidential_seal() == other.to_confidential_seal() &&
-            self.to_confidential_state() == other.to_confidential_state()
-    }
+    fn eq(&self, other: &Self) -> bool { self.commitment_eq(other) }
 }
 
 impl<State: ExposedState, Seal: ExposedSeal> Eq for Assign<State, Seal> {}
@@ -128,6 +196,35 @@ impl<State: ExposedState, Seal: ExposedSeal> Assign<State, Seal> {
         }
     }
 
+    /// Combines two assignments with the same concealed form into a single
+    /// one, which reveals every part revealed by either of them.
+    fn merge_revealed(self, other: Self) -> Self {
+        let seal = self.to_confidential_seal();
+        let state = self.to_confidential_state();
+        let revealed_seal = self.revealed_seal().or_else(|| other.revealed_seal());
+        let revealed_state = self
+            .into_revealed_state()
+            .or_else(|| other.into_revealed_state());
+        match (revealed_seal, revealed_state) {
+            (Some(seal), Some(state)) => Assign::revealed(seal, state),
+            (None, Some(state)) => Assign::ConfidentialSeal {
+                seal,
+                state,
+                lock: default!(),
+            },
+            (Some(seal), None) => Assign::ConfidentialState {
+                seal,
+                state,
+                lock: default!(),
+            },
+            (None, None) => Assign::Confidential {
+                seal,
+                state,
+                lock: default!(),
+            },
+        }
+    }
+
     pub fn with_seal_replaced(assignment: &Self, seal: XChain<Seal>) -> Self {
         match assignment {
             Assign::Confidential {
@@ -161,22 +258,155 @@ impl<State: ExposedState, Seal: ExposedSeal> Assign<State, Seal> {
         }
     }
 
-    pub fn to_confidential_seal(&self) -> XChain<SecretSeal> {
+    /// Returns type of the assigned state, which is known regardless of the
+    /// state being concealed.
+    pub fn state_type(&self) -> StateType {
         match self {
-            Assign::Revealed { seal, .. } | Assign::ConfidentialState { seal, .. } => {
-                seal.conceal()
+            Assign::Revealed { state, .. } | Assign::ConfidentialSeal { state, .. } => {
+                state.state_type()
             }
-            Assign::Confidential { seal, .. } | Assign::ConfidentialSeal { seal, .. } => *seal,
+            Assign::Confidential { state, .. } | Assign::ConfidentialState { state, .. } => {
+                state.state_type()
+            }
+        }
+    }
+
+    pub fn reveal_status(&self) -> RevealStatus {
+        match self {
+            Assign::Confidential { .. } => RevealStatus::Confidential,
+            Assign::Revealed { .. } => RevealStatus::Revealed,
+            Assign::ConfidentialSeal { .. } => RevealStatus::ConfidentialSeal,
+            Assign::ConfidentialState { .. } => RevealStatus::ConfidentialState,
+        }
+    }
+
+    /// Converts assignment into the form with the seal concealed and the state
+    /// left as is. Unlike [`Assign::conceal_seal`], doesn't clone the state.
+    pub fn into_confidential_seal(self) -> Self {
+        match self {
+            Assign::Revealed { seal, state, lock } => Assign::ConfidentialSeal {
+                seal: seal.conceal(),
+                state,
+                lock,
+            },
+            Assign::ConfidentialState { seal, state, lock } => Assign::Confidential {
+                seal: seal.conceal(),
+                state,
+                lock,
+            },
+            Assign::Confidential { .. } | Assign::ConfidentialSeal { .. } => self,
+        }
+    }
+
+    /// Converts assignment into the form with the state concealed and the seal
+    /// left as is.
+    pub fn into_confidential_state(self) -> Self {
+        match self {
+            Assign::Revealed { seal, state, lock } => Assign::ConfidentialState {
+                seal,
+                state: state.conceal(),
+                lock,
+            },
+            Assign::ConfidentialSeal { seal, state, lock } => Assign::Confidential {
+                seal,
+                state: state.conceal(),
+                lock,
+            },
+            Assign::Confidential { .. } | Assign::ConfidentialState { .. } => self,
+        }
+    }
+
+    /// Converts assignment into the form with both the seal and the state
+    /// concealed.
+    pub fn into_fully_confidential(self) -> Self {
+        match self {
+            Assign::Revealed { seal, state, lock } => Assign::Confidential {
+                seal: seal.conceal(),
+                state: state.conceal(),
+                lock,
+            },
+            Assign::ConfidentialSeal { seal, state, lock } => Assign::Confidential {
+                seal,
+                state: state.conceal(),
+                lock,
+            },
+            Assign::ConfidentialState { seal, state, lock } => Assign::Confidential {
+                seal: seal.conceal(),
+                state,
+                lock,
+            },
+            Assign::Confidential { .. } => self,
+        }
+    }
+
+    /// Returns assignment with the seal concealed and the state left as is.
+    pub fn conceal_seal(&self) -> Self {
+        match self {
+            Assign::Revealed { seal, state, lock } => Assign::ConfidentialSeal {
+                seal: seal.conceal(),
+                state: state.clone(),
+                lock: *lock,
+            },
+            Assign::ConfidentialState { seal, state, lock } => Assign::Confidential {
+                seal: seal.conceal(),
+                state: *state,
+                lock: *lock,
+            },
+            Assign::Confidential { .. } | Assign::ConfidentialSeal { .. } => self.clone(),
+        }
+    }
+
+    /// Returns assignment with the state concealed and the seal left as is.
+    pub fn conceal_state(&self) -> Self {
+        match self {
+            Assign::Revealed { seal, state, lock } => Assign::ConfidentialState {
+                seal: *seal,
+                state: state.conceal(),
+                lock: *lock,
+            },
+            Assign::ConfidentialSeal { seal, state, lock } => Assign::Confidential {
+                seal: *seal,
+                state: state.conceal(),
+                lock: *lock,
+            },
+            Assign::Confidential { .. } | Assign::ConfidentialState { .. } => self.clone(),
         }
     }
 
-    pub fn revealed_seal(&self) -> Option<XChain<Seal>> {
+    /// Returns the seal of the assignment in the form it is present in the
+    /// assignment, regardless of the state being revealed or not.
+    pub fn seal(&self) -> SealForm<'_, Seal> {
         match self {
-            Assign::Revealed { seal, .. } | Assign::ConfidentialState { seal, .. } => Some(*seal),
-            Assign::Confidential { .. } | Assign::ConfidentialSeal { .. } => None,
+            Assign::Revealed { seal, .. } | Assign::ConfidentialState { seal, .. } => {
+                SealForm::Revealed(seal)
+            }
+            Assign::Confidential { seal, .. } | Assign::ConfidentialSeal { seal, .. } => {
+                SealForm::Confidential(*seal)
+            }
         }
     }
 
+    /// Returns the concealed seal of the assignment, concealing the seal if
+    /// it is revealed.
+    pub fn secret_seal(&self) -> XChain<SecretSeal> { self.seal().secret_seal() }
+
+    /// Checks whether the assignment is made to the `secret` seal, comparing
+    /// the seals in constant time.
+    pub fn targets_seal(&self, secret: &XChain<SecretSeal>) -> bool {
+        self.secret_seal().ct_eq(secret)
+    }
+
+    /// Checks whether both assignments commit to the same concealed seal and
+    /// concealed state, regardless of which of their parts are revealed.
+    pub fn commitment_eq(&self, other: &Self) -> bool {
+        self.targets_seal(&other.secret_seal()) &&
+            self.to_confidential_state() == other.to_confidential_state()
+    }
+
+    pub fn to_confidential_seal(&self) -> XChain<SecretSeal> { self.secret_seal() }
+
+    pub fn revealed_seal(&self) -> Option<XChain<Seal>> { self.seal().revealed().copied() }
+
     pub fn to_confidential_state(&self) -> State::Confidential {
         match self {
             Assign::Revealed { state, .. } | Assign::ConfidentialSeal { state, .. } => {
@@ -309,6 +539,9 @@ pub enum TypedAssigns<Seal: ExposedSeal> {
     Attachment(SmallVec<AssignAttach<Seal>>),
 }
 
+impl<Seal: ExposedSeal> StrictSerialize for TypedAssigns<Seal> {}
+impl<Seal: ExposedSeal> StrictDeserialize for TypedAssigns<Seal> {}
+
 impl<Seal: ExposedSeal> Conceal for TypedAssigns<Seal> {
     type Concealed = Self;
     fn conceal(&self) -> Self::Concealed {
@@ -338,6 +571,273 @@ impl<Seal: ExposedSeal> Conceal for TypedAssigns<Seal> {
 }
 
 impl<Seal: ExposedSeal> TypedAssigns<Seal> {
+    /// Sorts assignments in the canonical order, defined by the concealed
+    /// seals of the assignments (and thus not depending on whether the seals
+    /// or the state are revealed or not). Assignments with the same concealed
+    /// seal preserve their relative order.
+    ///
+    /// Since assignment indexes are a part of the operation commitment, the
+    /// operation builders must call this method before computing operation id
+    /// to make sure that the same operation produced by different parties has
+    /// the same id.
+    pub fn sort_canonical(&mut self) {
+        fn sort<T: Clone + Ord>(vec: &mut SmallVec<T>) {
+            let mut inner = vec.to_vec();
+            inner.sort();
+            *vec = Confined::try_from(inner).expect("same size");
+        }
+        match self {
+            TypedAssigns::Declarative(s) => sort(s),
+            TypedAssigns::Fungible(s) => sort(s),
+            TypedAssigns::Structured(s) => sort(s),
+            TypedAssigns::Attachment(s) => sort(s),
+        }
+    }
+
+    /// Conceals seals of all assignments, leaving their state as is.
+    pub fn conceal_seals(&mut self) { self.conceal_seals_except(&[]); }
+
+    /// Conceals seals of all assignments, except the ones listed in `except`,
+    /// leaving their state as is.
+    ///
+    /// # Returns
+    ///
+    /// Concealed seals, indexed by the position of their assignment.
+    pub fn conceal_seals_except(
+        &mut self,
+        except: &[XChain<SecretSeal>],
+    ) -> BTreeMap<u16, XChain<SecretSeal>> {
+        fn conceal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            except: &[XChain<SecretSeal>],
+        ) -> BTreeMap<u16, XChain<SecretSeal>> {
+            let positions = seals_concealable(vec, except);
+            conceal_at(vec, &positions, Assign::conceal_seal);
+            positions
+                .into_iter()
+                .map(|pos| (pos, vec[pos as usize].to_confidential_seal()))
+                .collect()
+        }
+        match self {
+            TypedAssigns::Declarative(s) => conceal(s, except),
+            TypedAssigns::Fungible(s) => conceal(s, except),
+            TypedAssigns::Structured(s) => conceal(s, except),
+            TypedAssigns::Attachment(s) => conceal(s, except),
+        }
+    }
+
+    /// Conceals state of all assignments, except the ones which seals are
+    /// listed in `except`, leaving their seals as is.
+    ///
+    /// # Returns
+    ///
+    /// Concealed state, indexed by the position of its assignment.
+    pub fn conceal_state_except(
+        &mut self,
+        except: &[XChain<SecretSeal>],
+    ) -> BTreeMap<u16, ConcealedState> {
+        fn conceal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            except: &[XChain<SecretSeal>],
+        ) -> BTreeMap<u16, ConcealedState> {
+            let positions = state_concealable(vec, except);
+            conceal_at(vec, &positions, Assign::conceal_state);
+            positions
+                .into_iter()
+                .map(|pos| (pos, vec[pos as usize].to_confidential_state().state_commitment()))
+                .collect()
+        }
+        match self {
+            TypedAssigns::Declarative(s) => conceal(s, except),
+            TypedAssigns::Fungible(s) => conceal(s, except),
+            TypedAssigns::Structured(s) => conceal(s, except),
+            TypedAssigns::Attachment(s) => conceal(s, except),
+        }
+    }
+
+    /// Conceals both seals and state of all assignments, except the ones at
+    /// the positions listed in `keep`, which are left as is.
+    ///
+    /// # Returns
+    ///
+    /// Concealed seals and state, indexed by the position of their assignment.
+    #[allow(clippy::type_complexity)]
+    pub fn conceal_except_at(
+        &mut self,
+        keep: &[u16],
+    ) -> (BTreeMap<u16, XChain<SecretSeal>>, BTreeMap<u16, ConcealedState>) {
+        fn conceal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            keep: &[u16],
+        ) -> (BTreeMap<u16, XChain<SecretSeal>>, BTreeMap<u16, ConcealedState>) {
+            let positions = |revealed: fn(&Assign<State, Seal>) -> bool| -> Vec<u16> {
+                vec.iter()
+                    .enumerate()
+                    .map(|(pos, assign)| (pos as u16, assign))
+                    .filter(|(pos, assign)| !keep.contains(pos) && revealed(assign))
+                    .map(|(pos, _)| pos)
+                    .collect()
+            };
+            let seals = positions(|assign| assign.revealed_seal().is_some());
+            let state = positions(|assign| assign.as_revealed_state().is_some());
+            conceal_at(vec, &seals, Assign::conceal_seal);
+            conceal_at(vec, &state, Assign::conceal_state);
+            let seals = seals
+                .into_iter()
+                .map(|pos| (pos, vec[pos as usize].to_confidential_seal()))
+                .collect();
+            let state = state
+                .into_iter()
+                .map(|pos| (pos, vec[pos as usize].to_confidential_state().state_commitment()))
+                .collect();
+            (seals, state)
+        }
+        match self {
+            TypedAssigns::Declarative(s) => conceal(s, keep),
+            TypedAssigns::Fungible(s) => conceal(s, keep),
+            TypedAssigns::Structured(s) => conceal(s, keep),
+            TypedAssigns::Attachment(s) => conceal(s, keep),
+        }
+    }
+
+    /// Reveals seal and/or state of the assignment at `index`, after checking
+    /// that they match the concealed seal and state committed to by the
+    /// assignment. Already revealed data are left as is.
+    ///
+    /// # Errors
+    ///
+    /// If there is no assignment at `index`, or if the provided seal or state
+    /// doesn't match the assignment, in which case it is left unmodified.
+    pub fn reveal_at(
+        &mut self,
+        index: u16,
+        seal: Option<XChain<Seal>>,
+        state: Option<&RevealedState>,
+    ) -> Result<(), RevealError> {
+        fn reveal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            index: u16,
+            seal: Option<XChain<Seal>>,
+            state: Option<State>,
+        ) -> Result<(), RevealError> {
+            let assign = vec
+                .get(index as usize)
+                .ok_or(RevealError::UnknownAssignment)?;
+            if matches!(seal, Some(seal) if seal.conceal() != assign.to_confidential_seal()) {
+                return Err(RevealError::SealMismatch);
+            }
+            if matches!(&state, Some(state) if state.conceal() != assign.to_confidential_state()) {
+                return Err(RevealError::StateMismatch);
+            }
+            let mut revealed = match seal {
+                Some(seal) => Assign::with_seal_replaced(assign, seal),
+                None => assign.clone(),
+            };
+            if let Some(state) = state {
+                revealed = match revealed {
+                    Assign::Confidential { seal, lock, .. } |
+                    Assign::ConfidentialSeal { seal, lock, .. } => {
+                        Assign::ConfidentialSeal { seal, state, lock }
+                    }
+                    Assign::ConfidentialState { seal, lock, .. } |
+                    Assign::Revealed { seal, lock, .. } => Assign::Revealed { seal, state, lock },
+                };
+            }
+            let iter = vec.iter().enumerate().map(|(pos, assign)| {
+                if pos == index as usize {
+                    revealed.clone()
+                } else {
+                    assign.clone()
+                }
+            });
+            *vec = Confined::try_from_iter(iter).expect("same size");
+            Ok(())
+        }
+        if index >= self.len_u16() {
+            return Err(RevealError::UnknownAssignment);
+        }
+        match (self, state) {
+            (TypedAssigns::Declarative(s), None) => reveal(s, index, seal, None),
+            (TypedAssigns::Declarative(s), Some(RevealedState::Void)) => {
+                reveal(s, index, seal, Some(VoidState::default()))
+            }
+            (TypedAssigns::Fungible(s), None) => reveal(s, index, seal, None),
+            (TypedAssigns::Fungible(s), Some(RevealedState::Fungible(value))) => {
+                reveal(s, index, seal, Some(*value))
+            }
+            (TypedAssigns::Structured(s), None) => reveal(s, index, seal, None),
+            (TypedAssigns::Structured(s), Some(RevealedState::Structured(data))) => {
+                reveal(s, index, seal, Some(data.clone()))
+            }
+            (TypedAssigns::Attachment(s), None) => reveal(s, index, seal, None),
+            (TypedAssigns::Attachment(s), Some(RevealedState::Attachment(attach))) => {
+                reveal(s, index, seal, Some(attach.clone()))
+            }
+            _ => Err(RevealError::StateMismatch),
+        }
+    }
+
+    /// Returns positions of the assignments which seals would be concealed by
+    /// [`TypedAssigns::conceal_seals_except`], without modifying them.
+    pub fn concealable_seals(&self, except: &[XChain<SecretSeal>]) -> Vec<u16> {
+        match self {
+            TypedAssigns::Declarative(s) => seals_concealable(s, except),
+            TypedAssigns::Fungible(s) => seals_concealable(s, except),
+            TypedAssigns::Structured(s) => seals_concealable(s, except),
+            TypedAssigns::Attachment(s) => seals_concealable(s, except),
+        }
+    }
+
+    /// Returns positions of the assignments which state would be concealed by
+    /// [`TypedAssigns::conceal_state_except`], without modifying them.
+    pub fn concealable_state(&self, except: &[XChain<SecretSeal>]) -> Vec<u16> {
+        match self {
+            TypedAssigns::Declarative(s) => state_concealable(s, except),
+            TypedAssigns::Fungible(s) => state_concealable(s, except),
+            TypedAssigns::Structured(s) => state_concealable(s, except),
+            TypedAssigns::Attachment(s) => state_concealable(s, except),
+        }
+    }
+
+    /// Reveals concealed seals of the assignments, for which `lookup` returns
+    /// a revealed seal. Seals returned by `lookup` which don't match the
+    /// provided concealed seal are ignored, leaving the assignment concealed.
+    ///
+    /// # Returns
+    ///
+    /// Number of revealed seals.
+    pub fn reveal_seals(
+        &mut self,
+        lookup: impl Fn(XChain<SecretSeal>) -> Option<XChain<Seal>>,
+    ) -> usize {
+        fn reveal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            lookup: impl Fn(XChain<SecretSeal>) -> Option<XChain<Seal>>,
+        ) -> usize {
+            let mut count = 0usize;
+            let iter = vec.iter().map(|assign| {
+                if assign.revealed_seal().is_some() {
+                    return assign.clone();
+                }
+                match lookup(assign.to_confidential_seal()) {
+                    Some(seal) if seal.conceal().ct_eq(&assign.to_confidential_seal()) => {
+                        count += 1;
+                        Assign::with_seal_replaced(assign, seal)
+                    }
+                    _ => assign.clone(),
+                }
+            });
+            *vec = Confined::try_from_iter(iter).expect("same size");
+            count
+        }
+        match self {
+            TypedAssigns::Declarative(s) => reveal(s, lookup),
+            TypedAssigns::Fungible(s) => reveal(s, lookup),
+            TypedAssigns::Structured(s) => reveal(s, lookup),
+            TypedAssigns::Attachment(s) => reveal(s, lookup),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         match self {
             TypedAssigns::Declarative(set) => set.is_empty(),
@@ -366,6 +866,13 @@ impl<Seal: ExposedSeal> TypedAssigns<Seal> {
         }
     }
 
+    /// Checks whether the kind of the assigned state matches the owned state
+    /// `schema`, without looking into the state itself.
+    #[inline]
+    pub fn matches_schema(&self, schema: &OwnedStateSchema) -> bool {
+        self.state_type() == schema.state_type()
+    }
+
     #[inline]
     pub fn is_declarative(&self) -> bool { matches!(self, TypedAssigns::Declarative(_)) }
 
@@ -466,24 +973,160 @@ impl<Seal: ExposedSeal> TypedAssigns<Seal> {
         })
     }
 
-    pub fn to_confidential_seals(&self) -> Vec<XChain<SecretSeal>> {
-        match self {
-            TypedAssigns::Declarative(s) => s
-                .iter()
-                .map(AssignRights::<Seal>::to_confidential_seal)
-                .collect(),
-            TypedAssigns::Fungible(s) => s
+    /// If state definition does not exist, returns [`UnknownDataError`]. If the
+    /// state is confidential, returns `Ok(None)`; otherwise returns revealed
+    /// state data packed as `Ok(Some(`[`RevealedState`]`))`
+    pub fn revealed_state_at(&self, index: u16) -> Result<Option<RevealedState>, UnknownDataError> {
+        let index = index as usize;
+        Ok(match self {
+            TypedAssigns::Declarative(vec) => vec
+                .get(index)
+                .ok_or(UnknownDataError)?
+                .as_revealed_state()
+                .map(|_| RevealedState::Void),
+            TypedAssigns::Fungible(vec) => vec
+                .get(index)
+                .ok_or(UnknownDataError)?
+                .as_revealed_state()
+                .cloned()
+                .map(RevealedState::Fungible),
+            TypedAssigns::Structured(vec) => vec
+                .get(index)
+                .ok_or(UnknownDataError)?
+                .as_revealed_state()
+                .cloned()
+                .map(RevealedState::Structured),
+            TypedAssigns::Attachment(vec) => vec
+                .get(index)
+                .ok_or(UnknownDataError)?
+                .as_revealed_state()
+                .cloned()
+                .map(RevealedState::Attachment),
+        })
+    }
+
+    /// Checks whether both sets of assignments have the same state type and
+    /// commit to the same concealed assignments, regardless of their order
+    /// and of which of their parts are revealed. Identical assignments must
+    /// be present the same number of times on both sides.
+    pub fn commitment_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypedAssigns::Declarative(a), TypedAssigns::Declarative(b)) => {
+                commitment_eq_unordered(a, b)
+            }
+            (TypedAssigns::Fungible(a), TypedAssigns::Fungible(b)) => commitment_eq_unordered(a, b),
+            (TypedAssigns::Structured(a), TypedAssigns::Structured(b)) => {
+                commitment_eq_unordered(a, b)
+            }
+            (TypedAssigns::Attachment(a), TypedAssigns::Attachment(b)) => {
+                commitment_eq_unordered(a, b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks whether any of the assignments is made to the `secret` seal.
+    pub fn targets_seal(&self, secret: &XChain<SecretSeal>) -> bool {
+        self.as_declarative()
+            .iter()
+            .any(|assign| assign.targets_seal(secret)) ||
+            self.as_fungible()
                 .iter()
-                .map(AssignFungible::<Seal>::to_confidential_seal)
-                .collect(),
-            TypedAssigns::Structured(s) => s
+                .any(|assign| assign.targets_seal(secret)) ||
+            self.as_structured()
                 .iter()
-                .map(AssignData::<Seal>::to_confidential_seal)
-                .collect(),
-            TypedAssigns::Attachment(s) => s
+                .any(|assign| assign.targets_seal(secret)) ||
+            self.as_attachment()
                 .iter()
-                .map(AssignAttach::<Seal>::to_confidential_seal)
-                .collect(),
+                .any(|assign| assign.targets_seal(secret))
+    }
+
+    pub fn to_confidential_seals(&self) -> Vec<XChain<SecretSeal>> {
+        self.confidential_seals_iter().collect()
+    }
+
+    /// Iterates over concealed seals of all assignments (concealing the seals
+    /// which are revealed) without allocating an intermediate collection.
+    pub fn confidential_seals_iter(&self) -> impl Iterator<Item = XChain<SecretSeal>> + '_ {
+        // Only one of the slices is non-empty
+        self.as_declarative()
+            .iter()
+            .map(AssignRights::<Seal>::secret_seal)
+            .chain(
+                self.as_fungible()
+                    .iter()
+                    .map(AssignFungible::<Seal>::secret_seal),
+            )
+            .chain(
+                self.as_structured()
+                    .iter()
+                    .map(AssignData::<Seal>::secret_seal),
+            )
+            .chain(
+                self.as_attachment()
+                    .iter()
+                    .map(AssignAttach::<Seal>::secret_seal),
+            )
+    }
+
+    /// Iterates over revealed seals of the assignments, skipping the ones
+    /// which seals are concealed.
+    pub fn revealed_seals_iter(&self) -> impl Iterator<Item = XChain<Seal>> + '_ {
+        self.as_declarative()
+            .iter()
+            .filter_map(AssignRights::<Seal>::revealed_seal)
+            .chain(
+                self.as_fungible()
+                    .iter()
+                    .filter_map(AssignFungible::<Seal>::revealed_seal),
+            )
+            .chain(
+                self.as_structured()
+                    .iter()
+                    .filter_map(AssignData::<Seal>::revealed_seal),
+            )
+            .chain(
+                self.as_attachment()
+                    .iter()
+                    .filter_map(AssignAttach::<Seal>::revealed_seal),
+            )
+    }
+
+    /// Iterates over revealed fungible state, skipping the assignments with
+    /// confidential state. Non-fungible assignments produce no items.
+    pub fn revealed_fungible_iter(&self) -> impl Iterator<Item = &RevealedValue> {
+        self.as_fungible()
+            .iter()
+            .filter_map(AssignFungible::<Seal>::as_revealed_state)
+    }
+
+    /// Iterator version of [`TypedAssigns::fungible_values`].
+    pub fn fungible_values_iter(&self) -> impl Iterator<Item = Option<u64>> + '_ {
+        self.as_fungible().iter().map(|assign| {
+            assign
+                .as_revealed_state()
+                .and_then(|state| state.value.to_u64())
+        })
+    }
+
+    /// Returns indexes of the assignments which concealed form (concealed seal
+    /// and concealed state) differs from the concealed form of the assignment
+    /// with the same index in `other`. Assignments present only in one of the
+    /// sets, as well as all assignments of a different state type, are
+    /// reported as mismatching.
+    pub fn concealed_mismatches(&self, other: &Self) -> Vec<u16> {
+        fn mismatches<T: PartialEq>(a: &[T], b: &[T]) -> Vec<u16> {
+            (0..a.len().max(b.len()))
+                .filter(|i| a.get(*i) != b.get(*i))
+                .map(|i| i as u16)
+                .collect()
+        }
+        match (self, other) {
+            (TypedAssigns::Declarative(a), TypedAssigns::Declarative(b)) => mismatches(a, b),
+            (TypedAssigns::Fungible(a), TypedAssigns::Fungible(b)) => mismatches(a, b),
+            (TypedAssigns::Structured(a), TypedAssigns::Structured(b)) => mismatches(a, b),
+            (TypedAssigns::Attachment(a), TypedAssigns::Attachment(b)) => mismatches(a, b),
+            _ => (0..self.len_u16().max(other.len_u16())).collect(),
         }
     }
 
@@ -513,6 +1156,250 @@ impl<Seal: ExposedSeal> TypedAssigns<Seal> {
         }
     }
 
+    /// Replaces the seal of the assignment at `index` with `seal`. If the seal
+    /// of the assignment is concealed, the new seal gets concealed as well,
+    /// i.e. its secret seal is recomputed; otherwise the new seal is stored
+    /// revealed. State of the assignment is left as is.
+    ///
+    /// NB: This changes the id of the operation containing the assignments,
+    /// thus it must be done before the operation is committed to (for
+    /// instance, added to a bundle or anchored).
+    ///
+    /// # Errors
+    ///
+    /// If the `index` is out of range.
+    pub fn update_seal(&mut self, index: u16, seal: XChain<Seal>) -> Result<(), UpdateSealError> {
+        fn update<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            index: u16,
+            seal: XChain<Seal>,
+        ) -> Result<(), UpdateSealError> {
+            let len = vec.len_u16();
+            if index >= len {
+                return Err(UpdateSealError::OutOfRange { index, len });
+            }
+            replace_at(vec, index, |assign| {
+                let updated = Assign::with_seal_replaced(assign, seal);
+                if assign.revealed_seal().is_some() {
+                    updated
+                } else {
+                    updated.conceal_seal()
+                }
+            });
+            Ok(())
+        }
+        match self {
+            TypedAssigns::Declarative(s) => update(s, index, seal),
+            TypedAssigns::Fungible(s) => update(s, index, seal),
+            TypedAssigns::Structured(s) => update(s, index, seal),
+            TypedAssigns::Attachment(s) => update(s, index, seal),
+        }
+    }
+
+    /// Replaces revealed fungible value of the assignment at `index` with
+    /// `value` blinded with `blinding`, keeping the asset tag and the seal
+    /// (concealed or not) of the assignment.
+    ///
+    /// NB: This changes the id of the operation containing the assignments,
+    /// thus it must be done before the operation is committed to (for
+    /// instance, added to a bundle or anchored). The blinding factors of the
+    /// operation outputs must remain balanced with the inputs.
+    ///
+    /// # Errors
+    ///
+    /// If the assignments are not fungible, if the `index` is out of range, or
+    /// if the state of the assignment is concealed.
+    pub fn update_fungible_value(
+        &mut self,
+        index: u16,
+        value: impl Into<FungibleState>,
+        blinding: BlindingFactor,
+    ) -> Result<(), UpdateValueError> {
+        let state_type = self.state_type();
+        let TypedAssigns::Fungible(vec) = self else {
+            return Err(UpdateValueError::KindMismatch(state_type));
+        };
+        let len = vec.len_u16();
+        let Some(assign) = vec.get(index as usize) else {
+            return Err(UpdateValueError::OutOfRange { index, len });
+        };
+        let Some(state) = assign.as_revealed_state() else {
+            return Err(UpdateValueError::ConcealedState(index));
+        };
+        let state = RevealedValue::with_blinding(value, blinding, state.tag);
+        replace_at(vec, index, |assign| {
+            let mut updated = assign.clone();
+            if let Some(revealed) = updated.as_revealed_state_mut() {
+                *revealed = state;
+            }
+            updated
+        });
+        Ok(())
+    }
+
+    /// Adds decoy assignments to each of the provided `seals`, which makes the
+    /// transaction graph analysis harder by hiding which of the seals are the
+    /// real recipients.
+    ///
+    /// For declarative state, decoys are plain rights assigned to the seals.
+    /// For fungible state, decoys are zero-value assignments of the same asset
+    /// tag with random blinding factors; the blinding factor of the last
+    /// assignment with revealed state is adjusted such that the sum of the
+    /// Pedersen commitments remains unchanged. Decoys are added with revealed
+    /// seals and state to the end of the list; callers may conceal them and
+    /// must call [`TypedAssigns::sort_canonical`] afterwards.
+    ///
+    /// NB: Decoys provide privacy only as long as they are indistinguishable
+    /// from the real assignments: the seals must look like real wallet outputs
+    /// and the state must be concealed when the operation is shared with third
+    /// parties. Decoys count towards the schema occurrence limits for the
+    /// assignment type, thus the schema must allow the resulting number of
+    /// assignments, otherwise the operation will fail validation. Since the
+    /// blinding factor of an existing assignment is changed, the method must
+    /// be called before the operation is committed to, and the updated
+    /// blinding must be used by its recipient.
+    ///
+    /// # Returns
+    ///
+    /// Number of the added decoys.
+    ///
+    /// # Errors
+    ///
+    /// If the state is neither declarative nor fungible, if fungible
+    /// assignments have no revealed state, or if the number of assignments
+    /// exceeds the maximum.
+    pub fn add_decoys<R: Rng + RngCore>(
+        &mut self,
+        seals: impl IntoIterator<Item = XChain<Seal>>,
+        rng: &mut R,
+    ) -> Result<u16, DecoyError> {
+        let state_type = self.state_type();
+        match self {
+            TypedAssigns::Declarative(vec) => {
+                let len = vec.len_u16();
+                let decoys = seals
+                    .into_iter()
+                    .map(|seal| Assign::revealed(seal, VoidState::default()));
+                *vec = Confined::try_from_iter(vec.iter().cloned().chain(decoys))
+                    .map_err(|_| DecoyError::TooManyAssignments)?;
+                Ok(vec.len_u16() - len)
+            }
+            TypedAssigns::Fungible(vec) => {
+                let Some(pos) = vec
+                    .iter()
+                    .rposition(|assign| assign.as_revealed_state().is_some())
+                else {
+                    return Err(DecoyError::NoRevealedState);
+                };
+                let state = *vec[pos].as_revealed_state().expect("checked above");
+                let decoys = seals
+                    .into_iter()
+                    .map(|seal| {
+                        Assign::revealed(seal, RevealedValue::with_rng(0u64, rng, state.tag))
+                    })
+                    .collect::<Vec<_>>();
+                if decoys.is_empty() {
+                    return Ok(0);
+                }
+                let blinding = BlindingFactor::zero_balanced(
+                    [state.blinding],
+                    decoys.iter().map(|assign| {
+                        assign
+                            .as_revealed_state()
+                            .expect("decoys are revealed")
+                            .blinding
+                    }),
+                )
+                .map_err(|_| DecoyError::UnbalancedBlinding)?;
+                let len = vec.len_u16();
+                let mut updated = vec.clone();
+                replace_at(&mut updated, pos as u16, |assign| {
+                    let mut assign = assign.clone();
+                    if let Some(revealed) = assign.as_revealed_state_mut() {
+                        revealed.blinding = blinding;
+                    }
+                    assign
+                });
+                *vec = Confined::try_from_iter(updated.into_iter().chain(decoys))
+                    .map_err(|_| DecoyError::TooManyAssignments)?;
+                Ok(vec.len_u16() - len)
+            }
+            TypedAssigns::Structured(_) | TypedAssigns::Attachment(_) => {
+                Err(DecoyError::UnsupportedState(state_type))
+            }
+        }
+    }
+
+    /// Merges assignments from `other` into `self`, deduplicating identical
+    /// assignments. See [`TypedAssigns::merge_with`] for the details.
+    pub fn merge(self, other: Self) -> Result<Self, MergeError> {
+        self.merge_with(other, MergeDuplicates::Deduplicate)
+    }
+
+    /// Merges assignments from `other` into `self` and sorts the result in
+    /// the canonical order (see [`TypedAssigns::sort_canonical`]).
+    ///
+    /// Assignments are identical if they have the same concealed seal and
+    /// concealed state, regardless of which of their parts are revealed. Such
+    /// assignments, coming from either side, are handled according to the
+    /// `duplicates` argument.
+    ///
+    /// # Errors
+    ///
+    /// If both sides have different state types, if the number of the merged
+    /// assignments exceeds the maximum, or if identical assignments are found
+    /// while `duplicates` is set to [`MergeDuplicates::Reject`].
+    pub fn merge_with(self, other: Self, duplicates: MergeDuplicates) -> Result<Self, MergeError> {
+        match (self, other) {
+            (TypedAssigns::Declarative(a), TypedAssigns::Declarative(b)) => {
+                merge_assigns(a, b, duplicates).map(TypedAssigns::Declarative)
+            }
+            (TypedAssigns::Fungible(a), TypedAssigns::Fungible(b)) => {
+                merge_assigns(a, b, duplicates).map(TypedAssigns::Fungible)
+            }
+            (TypedAssigns::Structured(a), TypedAssigns::Structured(b)) => {
+                merge_assigns(a, b, duplicates).map(TypedAssigns::Structured)
+            }
+            (TypedAssigns::Attachment(a), TypedAssigns::Attachment(b)) => {
+                merge_assigns(a, b, duplicates).map(TypedAssigns::Attachment)
+            }
+            (a, b) => Err(MergeError::KindMismatch {
+                expected: a.state_type(),
+                found: b.state_type(),
+            }),
+        }
+    }
+
+    /// Returns revealed fungible values aligned with the assignment indexes,
+    /// with `None` for the assignments having confidential state (and for the
+    /// values not fitting into `u64`, possible only with the `wide-fungible`
+    /// feature). Non-fungible assignments produce an empty vector.
+    pub fn fungible_values(&self) -> Vec<Option<u64>> { self.fungible_values_iter().collect() }
+
+    /// Computes checked sum of the fungible values.
+    ///
+    /// # Errors
+    ///
+    /// If the assignments are not fungible, if some of them have confidential
+    /// state, or if the sum doesn't fit into `u64`.
+    pub fn fungible_sum(&self) -> Result<u64, FungibleSumError> {
+        if !self.is_fungible() {
+            return Err(FungibleSumError::NotFungible);
+        }
+        let confidential = self.len_u16() as usize - self.revealed_fungible_iter().count();
+        if confidential > 0 {
+            return Err(FungibleSumError::Confidential(confidential as u16));
+        }
+        self.revealed_fungible_iter()
+            .try_fold(0u64, |sum, state| {
+                state
+                    .value
+                    .to_u64()
+                    .and_then(|value| sum.checked_add(value))
+            })
+            .ok_or(FungibleSumError::Overflow)
+    }
+
     pub fn into_structured_state_at(
         self,
         index: u16,
@@ -550,13 +1437,263 @@ impl<Seal: ExposedSeal> TypedAssigns<Seal> {
     }
 }
 
-impl TypedAssigns<GenesisSeal> {
-    pub fn transmutate_seals(&self) -> TypedAssigns<GraphSeal> {
-        match self {
-            TypedAssigns::Declarative(a) => TypedAssigns::Declarative(
-                Confined::try_from_iter(a.iter().map(|a| a.transmutate_seals()))
-                    .expect("same size"),
-            ),
+/// Errors updating seal of an assignment.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum UpdateSealError {
+    /// assignment index {index} is out of range, since there are only {len}
+    /// assignments.
+    OutOfRange { index: u16, len: u16 },
+}
+
+/// Errors updating fungible value of an assignment.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum UpdateValueError {
+    /// assignment index {index} is out of range, since there are only {len}
+    /// assignments.
+    OutOfRange { index: u16, len: u16 },
+
+    /// assignments have {0} state, while fungible state was expected.
+    KindMismatch(StateType),
+
+    /// state of the assignment {0} is concealed, thus its asset tag is unknown.
+    ConcealedState(u16),
+}
+
+/// Errors adding decoy assignments with [`TypedAssigns::add_decoys`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum DecoyError {
+    /// decoy assignments can't be created for {0} state.
+    UnsupportedState(StateType),
+
+    /// fungible assignments have no revealed state, which is required to learn
+    /// the asset tag and to balance blinding factors of the decoys.
+    NoRevealedState,
+
+    /// blinding factors of the decoys can't be balanced.
+    UnbalancedBlinding,
+
+    /// number of assignments with decoys exceeds the maximum.
+    TooManyAssignments,
+}
+
+/// Handling of identical assignments by [`TypedAssigns::merge_with`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum MergeDuplicates {
+    /// Keeps a single assignment out of the identical ones, revealing all the
+    /// parts revealed by any of them.
+    #[default]
+    Deduplicate,
+
+    /// Treats identical assignments as a conflict, failing with
+    /// [`MergeError::Duplicate`].
+    Reject,
+}
+
+/// Errors merging assignments with [`TypedAssigns::merge_with`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MergeError {
+    /// assignments with {expected} state can't be merged with assignments with
+    /// {found} state.
+    KindMismatch {
+        expected: StateType,
+        found: StateType,
+    },
+
+    /// number of the merged assignments exceeds the maximum.
+    TooManyAssignments,
+
+    /// assignment to seal {0} is present more than once.
+    Duplicate(XChain<SecretSeal>),
+}
+
+/// Errors allocating fungible state with [`FungibleAllocator`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AllocationError {
+    /// no outputs are provided for the allocation.
+    NoOutputs,
+
+    /// too many outputs are provided for the allocation.
+    TooManyOutputs,
+
+    /// sum of the input values {inputs} doesn't match the sum of the output
+    /// values {outputs}.
+    Imbalance { inputs: u128, outputs: u128 },
+
+    /// input blinding factors can't be balanced, since they are absent or are
+    /// inverses of each other.
+    UnbalancedBlinding,
+}
+
+/// Errors constructing transfer assignments with
+/// [`build_transfer_assignments`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum TransferError {
+    /// payment amount must be non-zero.
+    ZeroPayment,
+
+    /// inputs of {available} are insufficient for the payment of {required}.
+    InsufficientInputs { available: u128, required: u64 },
+
+    /// change of {0} requires a change seal, which is not provided.
+    NoChangeSeal(u64),
+
+    /// change of {0} exceeds the maximum value of a single assignment.
+    ChangeOverflow(u128),
+
+    #[from]
+    #[display(inner)]
+    Allocation(AllocationError),
+}
+
+/// Allocates fungible state spent from inputs with known values and blinding
+/// factors to a set of outputs, such that Pedersen commitments of the inputs
+/// sum up to the commitments of the produced assignments.
+///
+/// All outputs except the last one receive random blinding factors, while the
+/// blinding factor of the last output balances the inputs.
+#[derive(Clone, Debug)]
+pub struct FungibleAllocator<Seal: ExposedSeal> {
+    tag: AssetTag,
+    inputs: Vec<(u64, BlindingFactor)>,
+    outputs: Vec<(XChain<Seal>, u64)>,
+}
+
+impl<Seal: ExposedSeal> FungibleAllocator<Seal> {
+    /// Constructs allocator of the fungible state with the asset `tag`.
+    pub fn new(tag: AssetTag) -> Self {
+        FungibleAllocator {
+            tag,
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    /// Adds spent input with its revealed value and blinding factor.
+    pub fn add_input(&mut self, value: u64, blinding: BlindingFactor) -> &mut Self {
+        self.inputs.push((value, blinding));
+        self
+    }
+
+    /// Adds output receiving `value` under the `seal`.
+    pub fn add_output(&mut self, seal: XChain<Seal>, value: u64) -> &mut Self {
+        self.outputs.push((seal, value));
+        self
+    }
+
+    /// Produces assignments for the outputs, in the order they were added.
+    ///
+    /// # Errors
+    ///
+    /// If there are no outputs or too many of them, if input and output values
+    /// don't match, or if the input blinding factors can't be balanced.
+    pub fn allocate(&self) -> Result<TypedAssigns<Seal>, AllocationError> {
+        self.allocate_with_rng(&mut thread_rng())
+    }
+
+    /// Produces assignments for the outputs, in the order they were added,
+    /// using custom random number generator for the output blinding factors.
+    ///
+    /// # Errors
+    ///
+    /// If there are no outputs or too many of them, if input and output values
+    /// don't match, or if the input blinding factors can't be balanced.
+    pub fn allocate_with_rng<R: Rng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> Result<TypedAssigns<Seal>, AllocationError> {
+        let Some(((_, last_value), outputs)) = self.outputs.split_last() else {
+            return Err(AllocationError::NoOutputs);
+        };
+        let inputs = self.inputs.iter().map(|(value, _)| *value as u128).sum();
+        let outputs_sum = self.outputs.iter().map(|(_, value)| *value as u128).sum();
+        if inputs != outputs_sum {
+            return Err(AllocationError::Imbalance {
+                inputs,
+                outputs: outputs_sum,
+            });
+        }
+
+        let mut assigns = Vec::with_capacity(self.outputs.len());
+        let mut blindings = Vec::with_capacity(outputs.len());
+        for (seal, value) in outputs {
+            let state = RevealedValue::with_rng(*value, rng, self.tag);
+            blindings.push(state.blinding);
+            assigns.push(Assign::revealed(*seal, state));
+        }
+        let balance = BlindingFactor::zero_balanced(
+            self.inputs.iter().map(|(_, blinding)| *blinding),
+            blindings,
+        )
+        .map_err(|_| AllocationError::UnbalancedBlinding)?;
+        let (seal, _) = self.outputs[self.outputs.len() - 1];
+        assigns.push(Assign::revealed(
+            seal,
+            RevealedValue::with_blinding(*last_value, balance, self.tag),
+        ));
+
+        Confined::try_from(assigns)
+            .map(TypedAssigns::Fungible)
+            .map_err(|_| AllocationError::TooManyOutputs)
+    }
+}
+
+/// Constructs assignments for a transfer of the `payment` amount to its seal
+/// from the `inputs` with known values and blinding factors of the asset with
+/// the given `tag`. The excess of the inputs over the payment (change) is
+/// assigned to the `change_seal` as the second assignment; if the inputs
+/// match the payment exactly, only the payment assignment is produced and the
+/// change seal is ignored. Blinding factors are balanced with the inputs using
+/// [`FungibleAllocator`].
+///
+/// # Errors
+///
+/// If the payment is zero, if the inputs are insufficient for the payment, if
+/// there is change but no change seal is provided, or if the allocation fails.
+pub fn build_transfer_assignments<Seal: ExposedSeal, R: Rng + RngCore>(
+    tag: AssetTag,
+    inputs: &[(u64, BlindingFactor)],
+    payment: (XChain<Seal>, u64),
+    change_seal: Option<XChain<Seal>>,
+    rng: &mut R,
+) -> Result<TypedAssigns<Seal>, TransferError> {
+    let (payment_seal, amount) = payment;
+    if amount == 0 {
+        return Err(TransferError::ZeroPayment);
+    }
+    let available = inputs.iter().map(|(value, _)| *value as u128).sum::<u128>();
+    let Some(change) = available.checked_sub(amount as u128) else {
+        return Err(TransferError::InsufficientInputs {
+            available,
+            required: amount,
+        });
+    };
+
+    let mut allocator = FungibleAllocator::new(tag);
+    for (value, blinding) in inputs {
+        allocator.add_input(*value, *blinding);
+    }
+    allocator.add_output(payment_seal, amount);
+    if change > 0 {
+        let change = u64::try_from(change).map_err(|_| TransferError::ChangeOverflow(change))?;
+        let seal = change_seal.ok_or(TransferError::NoChangeSeal(change))?;
+        allocator.add_output(seal, change);
+    }
+    Ok(allocator.allocate_with_rng(rng)?)
+}
+
+impl TypedAssigns<GenesisSeal> {
+    pub fn transmutate_seals(&self) -> TypedAssigns<GraphSeal> {
+        match self {
+            TypedAssigns::Declarative(a) => TypedAssigns::Declarative(
+                Confined::try_from_iter(a.iter().map(|a| a.transmutate_seals()))
+                    .expect("same size"),
+            ),
             TypedAssigns::Fungible(a) => TypedAssigns::Fungible(
                 Confined::try_from_iter(a.iter().map(|a| a.transmutate_seals()))
                     .expect("same size"),
@@ -590,10 +1727,487 @@ impl TypedAssigns<GenesisSeal> {
 pub struct Assignments<Seal>(TinyOrdMap<AssignmentType, TypedAssigns<Seal>>)
 where Seal: ExposedSeal;
 
+impl<Seal: ExposedSeal> StrictSerialize for Assignments<Seal> {}
+impl<Seal: ExposedSeal> StrictDeserialize for Assignments<Seal> {}
+
 impl<Seal: ExposedSeal> Default for Assignments<Seal> {
     fn default() -> Self { Self(empty!()) }
 }
 
+impl<Seal: ExposedSeal> Assignments<Seal> {
+    /// Sorts assignments of each type in the canonical order. See
+    /// [`TypedAssigns::sort_canonical`] for the details.
+    pub fn sort_canonical(&mut self) {
+        self.keyed_values_mut()
+            .for_each(|(_, a)| a.sort_canonical());
+    }
+
+    /// Conceals seals of all assignments, leaving their state as is.
+    pub fn conceal_seals(&mut self) {
+        self.keyed_values_mut().for_each(|(_, a)| a.conceal_seals());
+    }
+
+    /// Conceals seals of all assignments, except the ones listed in `except`.
+    /// See [`TypedAssigns::conceal_seals_except`] for the details.
+    pub fn conceal_seals_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.conceal_where(|_| true, |a| ConcealedItems::Seals(a.conceal_seals_except(except)))
+    }
+
+    /// Conceals state of all assignments, except the ones which seals are
+    /// listed in `except`. See [`TypedAssigns::conceal_state_except`] for the
+    /// details.
+    pub fn conceal_state_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.conceal_where(|_| true, |a| ConcealedItems::State(a.conceal_state_except(except)))
+    }
+
+    /// Conceals seals of the assignments of type `ty`, except the ones listed
+    /// in `except`, leaving assignments of other types as is.
+    pub fn conceal_seals_by_type(
+        &mut self,
+        ty: AssignmentType,
+        except: &[XChain<SecretSeal>],
+    ) -> ConcealmentReport {
+        self.conceal_where(|t| t == ty, |a| ConcealedItems::Seals(a.conceal_seals_except(except)))
+    }
+
+    /// Conceals state of the assignments of type `ty`, except the ones which
+    /// seals are listed in `except`, leaving assignments of other types as is.
+    pub fn conceal_state_by_type(
+        &mut self,
+        ty: AssignmentType,
+        except: &[XChain<SecretSeal>],
+    ) -> ConcealmentReport {
+        self.conceal_where(|t| t == ty, |a| ConcealedItems::State(a.conceal_state_except(except)))
+    }
+
+    /// Reveals seal and/or state of the assignment at `index`. See
+    /// [`TypedAssigns::reveal_at`] for the details.
+    pub fn reveal_at(
+        &mut self,
+        index: AssignmentIndex,
+        seal: Option<XChain<Seal>>,
+        state: Option<&RevealedState>,
+    ) -> Result<(), RevealError> {
+        self.keyed_values_mut()
+            .find(|(ty, _)| **ty == index.ty)
+            .ok_or(RevealError::UnknownAssignment)?
+            .1
+            .reveal_at(index.pos, seal, state)
+    }
+
+    /// Conceals both seals and state of all assignments, except the ones
+    /// listed in `keep`, which are left as is.
+    pub fn conceal_except(&mut self, keep: &[AssignmentIndex]) -> ConcealmentReport {
+        let mut report = ConcealmentReport::default();
+        for (ty, a) in self.keyed_values_mut() {
+            let positions = keep
+                .iter()
+                .filter(|index| index.ty == *ty)
+                .map(|index| index.pos)
+                .collect::<Vec<_>>();
+            let (seals, state) = a.conceal_except_at(&positions);
+            report.seals.extend(
+                seals
+                    .into_iter()
+                    .map(|(pos, seal)| (AssignmentIndex::new(*ty, pos), seal)),
+            );
+            report.state.extend(
+                state
+                    .into_iter()
+                    .map(|(pos, state)| (AssignmentIndex::new(*ty, pos), state)),
+            );
+        }
+        report
+    }
+
+    fn conceal_where(
+        &mut self,
+        filter: impl Fn(AssignmentType) -> bool,
+        conceal: impl Fn(&mut TypedAssigns<Seal>) -> ConcealedItems,
+    ) -> ConcealmentReport {
+        let mut report = ConcealmentReport::default();
+        for (ty, a) in self.keyed_values_mut().filter(|(ty, _)| filter(**ty)) {
+            match conceal(a) {
+                ConcealedItems::Seals(seals) => report.seals.extend(
+                    seals
+                        .into_iter()
+                        .map(|(pos, seal)| (AssignmentIndex::new(*ty, pos), seal)),
+                ),
+                ConcealedItems::State(state) => report.state.extend(
+                    state
+                        .into_iter()
+                        .map(|(pos, state)| (AssignmentIndex::new(*ty, pos), state)),
+                ),
+            }
+        }
+        report
+    }
+
+    /// Returns indexes of the assignments which seals would be concealed by
+    /// [`Assignments::conceal_seals_except`], without modifying them.
+    pub fn concealable_seals(&self, except: &[XChain<SecretSeal>]) -> Vec<AssignmentIndex> {
+        self.iter()
+            .flat_map(|(ty, a)| {
+                a.concealable_seals(except)
+                    .into_iter()
+                    .map(|pos| AssignmentIndex::new(*ty, pos))
+            })
+            .collect()
+    }
+
+    /// Returns indexes of the assignments which state would be concealed by
+    /// [`Assignments::conceal_state_except`], without modifying them.
+    pub fn concealable_state(&self, except: &[XChain<SecretSeal>]) -> Vec<AssignmentIndex> {
+        self.iter()
+            .flat_map(|(ty, a)| {
+                a.concealable_state(except)
+                    .into_iter()
+                    .map(|pos| AssignmentIndex::new(*ty, pos))
+            })
+            .collect()
+    }
+
+    /// Reveals concealed seals of all assignments, for which `lookup` returns
+    /// a revealed seal. See [`TypedAssigns::reveal_seals`] for the details.
+    ///
+    /// # Returns
+    ///
+    /// Number of revealed seals.
+    pub fn reveal_seals(
+        &mut self,
+        lookup: impl Fn(XChain<SecretSeal>) -> Option<XChain<Seal>>,
+    ) -> usize {
+        self.keyed_values_mut()
+            .map(|(_, a)| a.reveal_seals(&lookup))
+            .sum()
+    }
+}
+
+/// Errors revealing an assignment.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum RevealError {
+    /// assignment is not known.
+    UnknownAssignment,
+
+    /// revealed seal doesn't match the concealed seal of the assignment.
+    SealMismatch,
+
+    /// revealed seal can't be used by the assignment.
+    InvalidSeal,
+
+    /// revealed state doesn't match the concealed state of the assignment.
+    StateMismatch,
+}
+
+/// Report on the assignments modified by a concealment, listing the concealed
+/// form of each of the concealed seals and state.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ConcealmentReport {
+    /// Concealed seals, indexed by their assignment.
+    pub seals: BTreeMap<AssignmentIndex, XChain<SecretSeal>>,
+    /// Concealed state, indexed by its assignment.
+    pub state: BTreeMap<AssignmentIndex, ConcealedState>,
+}
+
+impl ConcealmentReport {
+    /// Number of concealed seals and state items.
+    pub fn len(&self) -> usize { self.seals.len() + self.state.len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+enum ConcealedItems {
+    Seals(BTreeMap<u16, XChain<SecretSeal>>),
+    State(BTreeMap<u16, ConcealedState>),
+}
+
+fn seals_concealable<State: ExposedState, Seal: ExposedSeal>(
+    vec: &SmallVec<Assign<State, Seal>>,
+    except: &[XChain<SecretSeal>],
+) -> Vec<u16> {
+    concealable(vec, except, |assign| assign.revealed_seal().is_some())
+}
+
+fn state_concealable<State: ExposedState, Seal: ExposedSeal>(
+    vec: &SmallVec<Assign<State, Seal>>,
+    except: &[XChain<SecretSeal>],
+) -> Vec<u16> {
+    concealable(vec, except, |assign| assign.as_revealed_state().is_some())
+}
+
+fn concealable<State: ExposedState, Seal: ExposedSeal>(
+    vec: &SmallVec<Assign<State, Seal>>,
+    except: &[XChain<SecretSeal>],
+    revealed: impl Fn(&Assign<State, Seal>) -> bool,
+) -> Vec<u16> {
+    vec.iter()
+        .enumerate()
+        .filter(|(_, assign)| revealed(assign) && !except.contains(&assign.to_confidential_seal()))
+        .map(|(pos, _)| pos as u16)
+        .collect()
+}
+
+fn replace_at<State: ExposedState, Seal: ExposedSeal>(
+    vec: &mut SmallVec<Assign<State, Seal>>,
+    index: u16,
+    replace: impl Fn(&Assign<State, Seal>) -> Assign<State, Seal>,
+) {
+    let iter = vec.iter().enumerate().map(|(pos, assign)| {
+        if pos == index as usize {
+            replace(assign)
+        } else {
+            assign.clone()
+        }
+    });
+    *vec = Confined::try_from_iter(iter).expect("same size");
+}
+
+fn merge_assigns<State: ExposedState, Seal: ExposedSeal>(
+    a: SmallVec<Assign<State, Seal>>,
+    b: SmallVec<Assign<State, Seal>>,
+    duplicates: MergeDuplicates,
+) -> Result<SmallVec<Assign<State, Seal>>, MergeError> {
+    // Stable sort by concealed seals, matching `TypedAssigns::sort_canonical`
+    let mut all = a
+        .into_iter()
+        .chain(b)
+        .map(|assign| (assign.to_confidential_seal(), assign))
+        .collect::<Vec<_>>();
+    all.sort_by_key(|(seal, _)| *seal);
+
+    let mut merged = Vec::<(XChain<SecretSeal>, Assign<State, Seal>)>::with_capacity(all.len());
+    for (seal, assign) in all {
+        // Identical assignments have the same concealed seal and thus follow
+        // each other after the sorting
+        let start = merged.len() -
+            merged
+                .iter()
+                .rev()
+                .take_while(|(prev_seal, _)| *prev_seal == seal)
+                .count();
+        let pos = merged[start..]
+            .iter()
+            .position(|(_, prev)| prev.commitment_eq(&assign))
+            .map(|pos| start + pos);
+        match (pos, duplicates) {
+            (None, _) => merged.push((seal, assign)),
+            (Some(_), MergeDuplicates::Reject) => return Err(MergeError::Duplicate(seal)),
+            (Some(pos), MergeDuplicates::Deduplicate) => {
+                let (_, prev) = &mut merged[pos];
+                *prev = prev.clone().merge_revealed(assign);
+            }
+        }
+    }
+    Confined::try_from_iter(merged.into_iter().map(|(_, assign)| assign))
+        .map_err(|_| MergeError::TooManyAssignments)
+}
+
+fn commitment_eq_unordered<State: ExposedState, Seal: ExposedSeal>(
+    a: &[Assign<State, Seal>],
+    b: &[Assign<State, Seal>],
+) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let concealed = |assigns: &[Assign<State, Seal>]| {
+        let mut concealed = assigns
+            .iter()
+            .map(|assign| (assign.secret_seal(), assign.to_confidential_state()))
+            .collect::<Vec<_>>();
+        concealed.sort_by_key(|(seal, _)| *seal);
+        concealed
+    };
+    let (a, b) = (concealed(a), concealed(b));
+    if a.iter().zip(&b).any(|((x, _), (y, _))| x != y) {
+        return false;
+    }
+    // Both sides have the same seals, thus it remains to match the state of the
+    // assignments to each of the seals
+    let mut start = 0;
+    while start < a.len() {
+        let seal = a[start].0;
+        let end = start +
+            a[start..]
+                .iter()
+                .take_while(|(other, _)| *other == seal)
+                .count();
+        let mut matched = vec![false; end - start];
+        for (_, state) in &a[start..end] {
+            let Some(pos) = b[start..end]
+                .iter()
+                .enumerate()
+                .position(|(pos, (_, other))| !matched[pos] && other == state)
+            else {
+                return false;
+            };
+            matched[pos] = true;
+        }
+        start = end;
+    }
+    true
+}
+
+fn conceal_at<State: ExposedState, Seal: ExposedSeal>(
+    vec: &mut SmallVec<Assign<State, Seal>>,
+    positions: &[u16],
+    conceal: impl Fn(&Assign<State, Seal>) -> Assign<State, Seal>,
+) {
+    let iter = vec.iter().enumerate().map(|(pos, assign)| {
+        if positions.contains(&(pos as u16)) {
+            conceal(assign)
+        } else {
+            assign.clone()
+        }
+    });
+    *vec = Confined::try_from_iter(iter).expect("same size");
+}
+
+/// Data containing concealed seals which can be revealed once the revealed
+/// seal data become known (for instance, from a disclosure by the
+/// counterparty). This is the inverse of concealing the seals.
+pub trait RevealSeals {
+    /// Reveals concealed seals which revealed form is present in `known`,
+    /// indexed by the concealed seal. Revealed seals which don't hash to the
+    /// concealed seal they are indexed by, or which can't be used in the given
+    /// context (like witness-based seals in genesis), are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Number of revealed seals.
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize;
+}
+
+fn lookup_seal<Seal: ExposedSeal>(
+    known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>,
+    secret: XChain<SecretSeal>,
+) -> Option<XChain<Seal>>
+where
+    XChain<Seal>: TryFrom<XGraphSeal>,
+{
+    known
+        .get(&secret)
+        .and_then(|seal| XChain::<Seal>::try_from(*seal).ok())
+}
+
+impl<State: ExposedState, Seal: ExposedSeal> RevealSeals for Assign<State, Seal>
+where XChain<Seal>: TryFrom<XGraphSeal>
+{
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        if self.revealed_seal().is_some() {
+            return 0;
+        }
+        let secret = self.secret_seal();
+        match lookup_seal(known, secret) {
+            Some(seal) if seal.conceal().ct_eq(&secret) => {
+                *self = Assign::with_seal_replaced(self, seal);
+                1
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl<Seal: ExposedSeal> RevealSeals for TypedAssigns<Seal>
+where XChain<Seal>: TryFrom<XGraphSeal>
+{
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        TypedAssigns::reveal_seals(self, |secret| lookup_seal(known, secret))
+    }
+}
+
+impl<Seal: ExposedSeal> RevealSeals for Assignments<Seal>
+where XChain<Seal>: TryFrom<XGraphSeal>
+{
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        Assignments::reveal_seals(self, |secret| lookup_seal(known, secret))
+    }
+}
+
+/// Provider of the revealed state data for their concealed form, which is
+/// known to the wallet, for instance, from a disclosure by the counterparty.
+pub trait StateProvider {
+    /// Returns fungible state opening the Pedersen commitment `concealed`.
+    fn revealed_value(&self, concealed: &ConcealedValue) -> Option<RevealedValue>;
+
+    /// Returns structured state data matching the `concealed` data hash.
+    fn revealed_data(&self, concealed: &ConcealedData) -> Option<RevealedData>;
+
+    /// Returns attachment state matching the `concealed` attachment id.
+    fn revealed_attach(&self, concealed: &ConcealedAttach) -> Option<RevealedAttach>;
+}
+
+/// Data containing concealed state which can be revealed once the state
+/// openings become known. This is the inverse of concealing the state.
+pub trait RevealState {
+    /// Reveals concealed state which revealed form is returned by `provider`.
+    /// Revealed state which doesn't conceal to the concealed state it was
+    /// returned for is ignored, leaving the assignment concealed.
+    ///
+    /// # Returns
+    ///
+    /// Number of assignments which state was revealed.
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize;
+}
+
+impl<Seal: ExposedSeal> RevealState for TypedAssigns<Seal> {
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize {
+        fn reveal<State: ExposedState, Seal: ExposedSeal>(
+            vec: &mut SmallVec<Assign<State, Seal>>,
+            lookup: impl Fn(&State::Confidential) -> Option<State>,
+        ) -> usize {
+            let mut count = 0usize;
+            let iter = vec.iter().map(|assign| {
+                let revealed = match assign {
+                    Assign::Confidential { state, .. } |
+                    Assign::ConfidentialState { state, .. } => {
+                        lookup(state).filter(|revealed| revealed.conceal() == *state)
+                    }
+                    Assign::Revealed { .. } | Assign::ConfidentialSeal { .. } => None,
+                };
+                match (assign, revealed) {
+                    (Assign::Confidential { seal, lock, .. }, Some(state)) => {
+                        count += 1;
+                        Assign::ConfidentialSeal {
+                            seal: *seal,
+                            state,
+                            lock: *lock,
+                        }
+                    }
+                    (Assign::ConfidentialState { seal, lock, .. }, Some(state)) => {
+                        count += 1;
+                        Assign::Revealed {
+                            seal: *seal,
+                            state,
+                            lock: *lock,
+                        }
+                    }
+                    _ => assign.clone(),
+                }
+            });
+            *vec = Confined::try_from_iter(iter).expect("same size");
+            count
+        }
+        match self {
+            // Declarative state has no data to conceal
+            TypedAssigns::Declarative(s) => reveal(s, |_| Some(VoidState::default())),
+            TypedAssigns::Fungible(s) => reveal(s, |concealed| provider.revealed_value(concealed)),
+            TypedAssigns::Structured(s) => reveal(s, |concealed| provider.revealed_data(concealed)),
+            TypedAssigns::Attachment(s) => {
+                reveal(s, |concealed| provider.revealed_attach(concealed))
+            }
+        }
+    }
+}
+
+impl<Seal: ExposedSeal> RevealState for Assignments<Seal> {
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize {
+        self.keyed_values_mut()
+            .map(|(_, a)| a.reveal_state(provider))
+            .sum()
+    }
+}
+
 impl Assignments<GenesisSeal> {
     pub fn transmutate_seals(&self) -> Assignments<GraphSeal> {
         Assignments(
@@ -629,6 +2243,14 @@ impl AssignmentsRef<'_> {
 
     pub fn is_empty(&self) -> bool { self.len() == 0 }
 
+    /// Returns total number of assignments (i.e. seals) of all types.
+    pub fn seals_count(&self) -> usize {
+        match self {
+            AssignmentsRef::Genesis(a) => a.values().map(|t| t.len_u16() as usize).sum(),
+            AssignmentsRef::Graph(a) => a.values().map(|t| t.len_u16() as usize).sum(),
+        }
+    }
+
     pub fn flat(&self) -> Assignments<GraphSeal> {
         match *self {
             AssignmentsRef::Genesis(a) => a.transmutate_seals(),
@@ -657,3 +2279,757 @@ impl AssignmentsRef<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallBlob;
+    use amplify::hex::FromHex;
+    use bp::seals::txout::{BlindSeal, CloseMethod, TxPtr};
+    use bp::{Txid, Vout};
+    use commit_verify::CommitVerify;
+
+    use super::*;
+    use crate::{
+        AttachId, FungibleType, MediaType, Operation, PedersenCommitment, Transition, ValueBounds,
+    };
+
+    fn seal(vout: u32) -> XChain<GraphSeal> {
+        XChain::Bitcoin(BlindSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0x5eed + vout as u64,
+            txid: TxPtr::Txid(
+                Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839")
+                    .unwrap(),
+            ),
+            vout: Vout::from(vout),
+        })
+    }
+
+    fn transition(assigns: Vec<AssignRights<GraphSeal>>) -> Transition {
+        let mut typed = TypedAssigns::Declarative(Confined::try_from(assigns).unwrap());
+        typed.sort_canonical();
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! { AssignmentType::with(1) => typed }).unwrap(),
+        );
+        transition
+    }
+
+    #[test]
+    fn canonical_sort() {
+        let assigns = (0..6u32)
+            .map(|vout| {
+                let assign = Assign::revealed(seal(vout), VoidState::default());
+                if vout % 2 == 0 {
+                    assign.conceal()
+                } else {
+                    assign
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut shuffled = assigns.clone();
+        shuffled.reverse();
+        shuffled.swap(1, 4);
+        assert_ne!(assigns, shuffled);
+
+        let ordered = transition(assigns);
+        let shuffled = transition(shuffled);
+        assert_eq!(ordered.id(), shuffled.id());
+
+        let secrets = ordered.assignments[&AssignmentType::with(1)].to_confidential_seals();
+        let mut sorted = secrets.clone();
+        sorted.sort();
+        assert_eq!(secrets, sorted);
+    }
+
+    #[test]
+    fn conceal_by_type() {
+        let rights = |vouts: [u32; 2]| {
+            TypedAssigns::Declarative(
+                Confined::try_from_iter(
+                    vouts.map(|vout| AssignRights::revealed(seal(vout), VoidState::default())),
+                )
+                .unwrap(),
+            )
+        };
+        let mut assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => rights([0, 1]),
+                AssignmentType::with(2) => rights([2, 3]),
+            })
+            .unwrap(),
+        );
+        let except = [seal(1).to_secret_seal()];
+        let untouched = assignments.get(&AssignmentType::with(2)).unwrap().clone();
+
+        let report = assignments.conceal_seals_by_type(AssignmentType::with(1), &except);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.seals, bmap! {
+            AssignmentIndex::new(AssignmentType::with(1), 0) => seal(0).to_secret_seal()
+        });
+        let report = assignments.conceal_state_by_type(AssignmentType::with(1), &except);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.state, bmap! {
+            AssignmentIndex::new(AssignmentType::with(1), 0) => ConcealedState::Void
+        });
+        assert!(assignments
+            .conceal_seals_by_type(AssignmentType::with(3), &[])
+            .is_empty());
+        let concealed = assignments.get(&AssignmentType::with(1)).unwrap();
+        assert_eq!(concealed.concealable_seals(&[]), vec![1]);
+        assert_eq!(concealed.concealable_state(&[]), vec![1]);
+
+        // Assignments of other types remain fully revealed
+        let other = assignments.get(&AssignmentType::with(2)).unwrap();
+        assert_eq!(other.concealable_seals(&[]), vec![0, 1]);
+        assert_eq!(other.concealable_state(&[]), vec![0, 1]);
+        assert_eq!(other, &untouched);
+    }
+
+    #[test]
+    fn fungible_sum() {
+        let tag = AssetTag::from([0x55; 32]);
+        let fungible = |values: &[u64]| {
+            let assigns = values.iter().enumerate().map(|(vout, value)| {
+                let state = RevealedValue::with_blinding(*value, BlindingFactor::EMPTY, tag);
+                Assign::revealed(seal(vout as u32), state)
+            });
+            TypedAssigns::Fungible(Confined::try_from_iter(assigns).unwrap())
+        };
+
+        let assigns = fungible(&[10, 20, 30]);
+        assert_eq!(assigns.fungible_sum(), Ok(60));
+        assert_eq!(assigns.fungible_values(), vec![Some(10), Some(20), Some(30)]);
+        assert_eq!(fungible(&[]).fungible_sum(), Ok(0));
+
+        let overflow = fungible(&[u64::MAX, 1]);
+        assert_eq!(overflow.fungible_sum(), Err(FungibleSumError::Overflow));
+        assert_eq!(overflow.fungible_values(), vec![Some(u64::MAX), Some(1)]);
+        assert_eq!(fungible(&[u64::MAX, 0]).fungible_sum(), Ok(u64::MAX));
+
+        let mut partial = fungible(&[10, 20, 30]);
+        partial.conceal_state_except(&[partial.to_confidential_seals()[1]]);
+        assert_eq!(partial.fungible_values(), vec![None, Some(20), None]);
+        assert_eq!(partial.fungible_sum(), Err(FungibleSumError::Confidential(2)));
+
+        let rights = TypedAssigns::Declarative(
+            Confined::try_from(vec![Assign::revealed(seal(0), VoidState::default())]).unwrap(),
+        );
+        assert_eq!(rights.fungible_sum(), Err(FungibleSumError::NotFungible));
+        assert_eq!(rights.fungible_values(), vec![]);
+    }
+
+    #[test]
+    fn fungible_allocation() {
+        let tag = AssetTag::from([0x55; 32]);
+        let inputs = [(70u64, BlindingFactor::random()), (30, BlindingFactor::random())];
+        let commitments = |values: &mut dyn Iterator<Item = RevealedValue>| {
+            values
+                .map(|state| PedersenCommitment::commit(&state))
+                .collect::<Vec<_>>()
+        };
+        let input_commitments = commitments(
+            &mut inputs
+                .iter()
+                .map(|(value, blinding)| RevealedValue::with_blinding(*value, *blinding, tag)),
+        );
+
+        let mut allocator = FungibleAllocator::new(tag);
+        for (value, blinding) in inputs {
+            allocator.add_input(value, blinding);
+        }
+        allocator
+            .add_output(seal(1), 10)
+            .add_output(seal(2), 55)
+            .add_output(seal(3), 35);
+        let assigns = allocator.allocate().unwrap();
+        assert_eq!(assigns.fungible_values(), vec![Some(10), Some(55), Some(35)]);
+        assert_eq!(assigns.revealed_seal_at(2), Ok(Some(seal(3))));
+        let output_commitments = commitments(
+            &mut assigns
+                .as_fungible()
+                .iter()
+                .map(|assign| *assign.as_revealed_state().unwrap()),
+        );
+        assert!(PedersenCommitment::verify_sum(&input_commitments, &output_commitments));
+
+        // Output blindings are random, but remain balanced
+        let other = allocator.allocate().unwrap();
+        assert_ne!(other, assigns);
+        let output_commitments = commitments(
+            &mut other
+                .as_fungible()
+                .iter()
+                .map(|assign| *assign.as_revealed_state().unwrap()),
+        );
+        assert!(PedersenCommitment::verify_sum(&input_commitments, &output_commitments));
+
+        // Single output gets the balancing blinding factor
+        let mut single = FungibleAllocator::new(tag);
+        single.add_input(100, inputs[0].1).add_output(seal(1), 100);
+        let assigns = single.allocate().unwrap();
+        assert_eq!(
+            assigns.as_fungible()[0]
+                .as_revealed_state()
+                .unwrap()
+                .blinding,
+            inputs[0].1
+        );
+    }
+
+    #[test]
+    fn fungible_allocation_errors() {
+        let tag = AssetTag::from([0x55; 32]);
+        let mut allocator = FungibleAllocator::<GraphSeal>::new(tag);
+        allocator.add_input(100, BlindingFactor::random());
+        assert_eq!(allocator.allocate(), Err(AllocationError::NoOutputs));
+
+        allocator.add_output(seal(1), 60).add_output(seal(2), 50);
+        assert_eq!(
+            allocator.allocate(),
+            Err(AllocationError::Imbalance {
+                inputs: 100,
+                outputs: 110
+            })
+        );
+
+        let mut allocator = FungibleAllocator::new(tag);
+        allocator
+            .add_input(u64::MAX, BlindingFactor::random())
+            .add_input(u64::MAX, BlindingFactor::random())
+            .add_output(seal(1), u64::MAX);
+        assert_eq!(
+            allocator.allocate(),
+            Err(AllocationError::Imbalance {
+                inputs: u64::MAX as u128 * 2,
+                outputs: u64::MAX as u128
+            })
+        );
+
+        let mut allocator = FungibleAllocator::new(tag);
+        allocator.add_output(seal(1), 0);
+        assert_eq!(allocator.allocate(), Err(AllocationError::UnbalancedBlinding));
+    }
+
+    #[test]
+    fn seal_update() {
+        let mut assigns = TypedAssigns::Declarative(
+            Confined::try_from(vec![
+                AssignRights::revealed(seal(0), VoidState::default()),
+                AssignRights::revealed(seal(1), VoidState::default()).conceal_seal(),
+            ])
+            .unwrap(),
+        );
+        let before = transition(assigns.as_declarative().to_vec()).id();
+
+        assigns.update_seal(0, seal(2)).unwrap();
+        assigns.update_seal(1, seal(3)).unwrap();
+        assert_eq!(assigns.revealed_seal_at(0), Ok(Some(seal(2))));
+        assert_eq!(assigns.revealed_seal_at(1), Ok(None));
+        assert_eq!(assigns.to_confidential_seals(), vec![seal(2).conceal(), seal(3).conceal()]);
+        assert_ne!(transition(assigns.as_declarative().to_vec()).id(), before);
+
+        assert_eq!(
+            assigns.update_seal(2, seal(4)),
+            Err(UpdateSealError::OutOfRange { index: 2, len: 2 })
+        );
+    }
+
+    #[test]
+    fn fungible_value_update() {
+        let tag = AssetTag::from([0x55; 32]);
+        let blinding = BlindingFactor::random();
+        let mut assigns = TypedAssigns::Fungible(
+            Confined::try_from(vec![
+                Assign::revealed(seal(0), RevealedValue::new_random_blinding(10u64, tag)),
+                Assign::revealed(seal(1), RevealedValue::new_random_blinding(20u64, tag))
+                    .conceal_seal(),
+                Assign::revealed(seal(2), RevealedValue::new_random_blinding(30u64, tag))
+                    .conceal_state(),
+            ])
+            .unwrap(),
+        );
+
+        assigns.update_fungible_value(0, 15u64, blinding).unwrap();
+        assigns.update_fungible_value(1, 25u64, blinding).unwrap();
+        assert_eq!(assigns.fungible_values(), vec![Some(15), Some(25), None]);
+        assert_eq!(
+            assigns.as_fungible()[1].as_revealed_state(),
+            Some(&RevealedValue::with_blinding(25u64, blinding, tag))
+        );
+        assert_eq!(assigns.revealed_seal_at(0), Ok(Some(seal(0))));
+        assert_eq!(assigns.revealed_seal_at(1), Ok(None));
+
+        assert_eq!(
+            assigns.update_fungible_value(2, 35u64, blinding),
+            Err(UpdateValueError::ConcealedState(2))
+        );
+        assert_eq!(
+            assigns.update_fungible_value(3, 35u64, blinding),
+            Err(UpdateValueError::OutOfRange { index: 3, len: 3 })
+        );
+
+        let mut rights = TypedAssigns::Declarative(
+            Confined::try_from(vec![AssignRights::revealed(seal(0), VoidState::default())])
+                .unwrap(),
+        );
+        assert_eq!(
+            rights.update_fungible_value(0, 1u64, blinding),
+            Err(UpdateValueError::KindMismatch(StateType::Void))
+        );
+    }
+
+    #[test]
+    fn decoys() {
+        let mut rng = thread_rng();
+        let mut rights = TypedAssigns::Declarative(
+            Confined::try_from(vec![AssignRights::revealed(seal(0), VoidState::default())])
+                .unwrap(),
+        );
+        assert_eq!(rights.add_decoys([seal(1), seal(2)], &mut rng), Ok(2));
+        assert_eq!(rights.to_confidential_seals(), vec![
+            seal(0).conceal(),
+            seal(1).conceal(),
+            seal(2).conceal()
+        ]);
+
+        let tag = AssetTag::from([0x55; 32]);
+        let mut assigns = TypedAssigns::Fungible(
+            Confined::try_from(vec![
+                Assign::revealed(seal(0), RevealedValue::new_random_blinding(60u64, tag)),
+                Assign::revealed(seal(1), RevealedValue::new_random_blinding(40u64, tag)),
+                Assign::revealed(seal(2), RevealedValue::new_random_blinding(0u64, tag))
+                    .conceal_state(),
+            ])
+            .unwrap(),
+        );
+        let commitments = |assigns: &TypedAssigns<GraphSeal>| {
+            assigns
+                .as_fungible()
+                .iter()
+                .map(|assign| assign.to_confidential_state().commitment)
+                .collect::<Vec<_>>()
+        };
+        let before = commitments(&assigns);
+        assert_eq!(assigns.add_decoys([], &mut rng), Ok(0));
+        assert_eq!(commitments(&assigns), before);
+
+        assert_eq!(assigns.add_decoys([seal(3), seal(4)], &mut rng), Ok(2));
+        assert_eq!(assigns.fungible_values(), vec![Some(60), Some(40), None, Some(0), Some(0)]);
+        assert_ne!(commitments(&assigns), before);
+        assert!(PedersenCommitment::verify_sum(&before, &commitments(&assigns)));
+        assert_eq!(assigns.as_fungible()[3].as_revealed_state().unwrap().tag, tag);
+
+        let mut concealed = TypedAssigns::Fungible(
+            Confined::try_from(vec![Assign::revealed(
+                seal(0),
+                RevealedValue::new_random_blinding(60u64, tag),
+            )
+            .conceal_state()])
+            .unwrap(),
+        );
+        assert_eq!(concealed.add_decoys([seal(1)], &mut rng), Err(DecoyError::NoRevealedState));
+        assert_eq!(concealed.len_u16(), 1);
+
+        let mut data = TypedAssigns::<GraphSeal>::Structured(empty!());
+        assert_eq!(
+            data.add_decoys([seal(1)], &mut rng),
+            Err(DecoyError::UnsupportedState(StateType::Structured))
+        );
+    }
+
+    #[test]
+    fn iterators() {
+        let tag = AssetTag::from([0x55; 32]);
+        let assigns = TypedAssigns::Fungible(
+            Confined::try_from(vec![
+                Assign::revealed(seal(0), RevealedValue::new_random_blinding(60u64, tag)),
+                Assign::revealed(seal(1), RevealedValue::new_random_blinding(40u64, tag))
+                    .conceal_seal(),
+                Assign::revealed(seal(2), RevealedValue::new_random_blinding(0u64, tag))
+                    .conceal_state(),
+            ])
+            .unwrap(),
+        );
+        assert_eq!(
+            assigns.confidential_seals_iter().collect::<Vec<_>>(),
+            assigns.to_confidential_seals()
+        );
+        assert_eq!(assigns.revealed_seals_iter().collect::<Vec<_>>(), vec![seal(0), seal(2)]);
+        assert_eq!(
+            assigns
+                .revealed_fungible_iter()
+                .map(|state| state.value.to_u64())
+                .collect::<Vec<_>>(),
+            vec![Some(60), Some(40)]
+        );
+        assert_eq!(assigns.fungible_values_iter().collect::<Vec<_>>(), assigns.fungible_values());
+
+        // Counting matches doesn't require collecting seals
+        let secret = seal(1).conceal();
+        assert_eq!(
+            assigns
+                .confidential_seals_iter()
+                .filter(|seal| *seal == secret)
+                .count(),
+            1
+        );
+
+        let rights = TypedAssigns::Declarative(
+            Confined::try_from(vec![AssignRights::revealed(seal(3), VoidState::default())])
+                .unwrap(),
+        );
+        assert_eq!(rights.confidential_seals_iter().collect::<Vec<_>>(), vec![seal(3).conceal()]);
+        assert_eq!(rights.revealed_fungible_iter().count(), 0);
+        assert_eq!(rights.fungible_values_iter().count(), 0);
+    }
+
+    #[test]
+    fn downgrade_conversions() {
+        fn check<State: ExposedState>(assign: Assign<State, GraphSeal>) {
+            assert_eq!(assign.reveal_status(), RevealStatus::Revealed);
+            let secret_seal = assign.to_confidential_seal();
+            let concealed_state = assign.to_confidential_state();
+
+            let confidential_seal = assign.clone().into_confidential_seal();
+            assert_eq!(confidential_seal.reveal_status(), RevealStatus::ConfidentialSeal);
+            assert_eq!(confidential_seal.revealed_seal(), None);
+            assert_eq!(confidential_seal.to_confidential_seal(), secret_seal);
+            assert_eq!(confidential_seal.as_revealed_state(), assign.as_revealed_state());
+            assert_eq!(confidential_seal.reveal_status(), assign.conceal_seal().reveal_status());
+
+            let confidential_state = assign.clone().into_confidential_state();
+            assert_eq!(confidential_state.reveal_status(), RevealStatus::ConfidentialState);
+            assert_eq!(confidential_state.revealed_seal(), assign.revealed_seal());
+            assert_eq!(confidential_state.as_revealed_state(), None);
+            assert_eq!(confidential_state.to_confidential_state(), concealed_state);
+            assert_eq!(confidential_state.reveal_status(), assign.conceal_state().reveal_status());
+
+            let concealed = assign.conceal();
+            for confidential in [
+                assign.clone().into_fully_confidential(),
+                confidential_seal.into_fully_confidential(),
+                confidential_state.into_fully_confidential(),
+            ] {
+                assert_eq!(confidential.reveal_status(), RevealStatus::Confidential);
+                assert_eq!(confidential.to_confidential_seal(), concealed.to_confidential_seal());
+                assert_eq!(confidential.to_confidential_state(), concealed.to_confidential_state());
+                assert_eq!(
+                    confidential
+                        .clone()
+                        .into_confidential_seal()
+                        .reveal_status(),
+                    RevealStatus::Confidential
+                );
+                assert_eq!(
+                    confidential.into_confidential_state().reveal_status(),
+                    RevealStatus::Confidential
+                );
+            }
+        }
+
+        check(AssignRights::revealed(seal(0), VoidState::default()));
+        check(AssignFungible::revealed(
+            seal(1),
+            RevealedValue::new_random_blinding(100u64, AssetTag::from([0x55; 32])),
+        ));
+        check(AssignData::revealed(
+            seal(2),
+            RevealedData::with_salt(SmallBlob::try_from(vec![1, 2, 3]).unwrap(), 0),
+        ));
+        check(AssignAttach::revealed(
+            seal(3),
+            RevealedAttach::with_salt(AttachId::from([0x6c; 32]), MediaType::Image, 1024, 0),
+        ));
+    }
+
+    #[test]
+    fn transfer_assignments() {
+        let mut rng = thread_rng();
+        let tag = AssetTag::from([0x55; 32]);
+        let inputs = [(70u64, BlindingFactor::random()), (30, BlindingFactor::random())];
+        let input_commitments = inputs
+            .iter()
+            .map(|(value, blinding)| {
+                PedersenCommitment::commit(&RevealedValue::with_blinding(*value, *blinding, tag))
+            })
+            .collect::<Vec<_>>();
+        let balanced = |assigns: &TypedAssigns<GraphSeal>| {
+            let outputs = assigns
+                .as_fungible()
+                .iter()
+                .map(|assign| assign.to_confidential_state().commitment)
+                .collect::<Vec<_>>();
+            PedersenCommitment::verify_sum(&input_commitments, &outputs)
+        };
+
+        // Payment with change
+        let assigns =
+            build_transfer_assignments(tag, &inputs, (seal(1), 80), Some(seal(2)), &mut rng)
+                .unwrap();
+        assert_eq!(assigns.fungible_values(), vec![Some(80), Some(20)]);
+        assert_eq!(assigns.revealed_seal_at(0), Ok(Some(seal(1))));
+        assert_eq!(assigns.revealed_seal_at(1), Ok(Some(seal(2))));
+        assert!(balanced(&assigns));
+
+        // Exact amount, change seal is not needed
+        for change_seal in [None, Some(seal(2))] {
+            let assigns =
+                build_transfer_assignments(tag, &inputs, (seal(1), 100), change_seal, &mut rng)
+                    .unwrap();
+            assert_eq!(assigns.fungible_values(), vec![Some(100)]);
+            assert!(balanced(&assigns));
+        }
+
+        // Zero payment
+        assert_eq!(
+            build_transfer_assignments(tag, &inputs, (seal(1), 0), Some(seal(2)), &mut rng),
+            Err(TransferError::ZeroPayment)
+        );
+        assert_eq!(
+            build_transfer_assignments(tag, &inputs, (seal(1), 101), Some(seal(2)), &mut rng),
+            Err(TransferError::InsufficientInputs {
+                available: 100,
+                required: 101
+            })
+        );
+        assert_eq!(
+            build_transfer_assignments(tag, &inputs, (seal(1), 99), None, &mut rng),
+            Err(TransferError::NoChangeSeal(1))
+        );
+    }
+
+    #[test]
+    fn state_kind() {
+        let tag = AssetTag::from([0x55; 32]);
+        let rights = TypedAssigns::Declarative(
+            Confined::try_from(vec![AssignRights::revealed(seal(0), VoidState::default())])
+                .unwrap(),
+        );
+        let fungible = TypedAssigns::Fungible(
+            Confined::try_from(vec![AssignFungible::revealed(
+                seal(1),
+                RevealedValue::new_random_blinding(10u64, tag),
+            )
+            .conceal()])
+            .unwrap(),
+        );
+        let data = TypedAssigns::Structured(
+            Confined::try_from(vec![AssignData::revealed(
+                seal(2),
+                RevealedData::with_salt(SmallBlob::default(), 0),
+            )])
+            .unwrap(),
+        );
+        let attach = TypedAssigns::Attachment(
+            Confined::try_from(vec![AssignAttach::revealed(
+                seal(3),
+                RevealedAttach::with_salt(AttachId::from([0x6c; 32]), MediaType::Image, 1024, 0),
+            )
+            .conceal_state()])
+            .unwrap(),
+        );
+
+        let schemata = [
+            OwnedStateSchema::Declarative,
+            OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OwnedStateSchema::Structured(strict_dumb!()),
+            OwnedStateSchema::Attachment(MediaType::Any),
+        ];
+        let kinds =
+            [StateType::Void, StateType::Fungible, StateType::Structured, StateType::Attachment];
+        for (index, assigns) in [&rights, &fungible, &data, &attach].into_iter().enumerate() {
+            assert_eq!(assigns.state_type(), kinds[index]);
+            assert_eq!(assigns.is_declarative(), index == 0);
+            assert_eq!(assigns.is_fungible(), index == 1);
+            assert_eq!(assigns.is_structured(), index == 2);
+            assert_eq!(assigns.is_attachment(), index == 3);
+            for (pos, schema) in schemata.iter().enumerate() {
+                assert_eq!(assigns.matches_schema(schema), pos == index);
+            }
+        }
+        // State type is known for concealed state as well
+        assert_eq!(fungible.as_fungible()[0].state_type(), StateType::Fungible);
+        assert_eq!(attach.as_attachment()[0].state_type(), StateType::Attachment);
+        assert!(TypedAssigns::<GraphSeal>::Fungible(empty!()).matches_schema(
+            &OwnedStateSchema::BoundedFungible(FungibleType::Unsigned64Bit, ValueBounds::FULL)
+        ));
+    }
+
+    #[test]
+    fn merge() {
+        let rights = |assigns: Vec<AssignRights<GraphSeal>>| {
+            TypedAssigns::Declarative(Confined::try_from(assigns).unwrap())
+        };
+        let a = rights(vec![
+            AssignRights::revealed(seal(2), VoidState::default()),
+            AssignRights::revealed(seal(1), VoidState::default()).conceal_seal(),
+        ]);
+        let b = rights(vec![
+            AssignRights::revealed(seal(1), VoidState::default()),
+            AssignRights::revealed(seal(0), VoidState::default()),
+        ]);
+        let mut expected = rights(vec![
+            AssignRights::revealed(seal(0), VoidState::default()),
+            AssignRights::revealed(seal(1), VoidState::default()),
+            AssignRights::revealed(seal(2), VoidState::default()),
+        ]);
+        expected.sort_canonical();
+
+        let merged = a.clone().merge(b.clone()).unwrap();
+        assert_eq!(merged, expected);
+        assert_eq!(merged.to_confidential_seals(), expected.to_confidential_seals());
+        // Duplicates reveal the union of their revealed parts
+        assert_eq!(merged.revealed_seals_iter().count(), 3);
+        assert_eq!(
+            a.clone().merge_with(b, MergeDuplicates::Reject),
+            Err(MergeError::Duplicate(seal(1).conceal()))
+        );
+        assert_eq!(
+            a.clone().merge(rights(vec![])),
+            Ok({
+                let mut sorted = a.clone();
+                sorted.sort_canonical();
+                sorted
+            })
+        );
+
+        // Assignments of different state to the same seal are not duplicates
+        let tag = AssetTag::from([0x55; 32]);
+        let fungible = |value: u64| {
+            TypedAssigns::Fungible(
+                Confined::try_from(vec![Assign::revealed(
+                    seal(0),
+                    RevealedValue::new_random_blinding(value, tag),
+                )])
+                .unwrap(),
+            )
+        };
+        let merged = fungible(10)
+            .merge_with(fungible(20), MergeDuplicates::Reject)
+            .unwrap();
+        let mut values = merged.fungible_values();
+        values.sort();
+        assert_eq!(values, vec![Some(10), Some(20)]);
+
+        assert_eq!(
+            a.merge(fungible(10)),
+            Err(MergeError::KindMismatch {
+                expected: StateType::Void,
+                found: StateType::Fungible
+            })
+        );
+    }
+
+    #[test]
+    fn merge_overflow() {
+        let rights = |range: core::ops::Range<u32>| {
+            TypedAssigns::Declarative(
+                Confined::try_from_iter(
+                    range.map(|vout| AssignRights::revealed(seal(vout), VoidState::default())),
+                )
+                .unwrap(),
+            )
+        };
+        let max = u16::MAX as u32;
+        // Overlapping assignments are deduplicated before the size check
+        let merged = rights(0..40000).merge(rights(20000..max)).unwrap();
+        assert_eq!(merged.len_u16(), u16::MAX);
+        assert_eq!(
+            rights(0..40000).merge(rights(20000..max + 1)),
+            Err(MergeError::TooManyAssignments)
+        );
+    }
+
+    #[test]
+    fn seal_form() {
+        let tag = AssetTag::from([0x55; 32]);
+        let revealed =
+            AssignFungible::revealed(seal(0), RevealedValue::new_random_blinding(10u64, tag));
+        let secret = seal(0).conceal();
+        let variants = [
+            revealed.clone(),
+            revealed.conceal_seal(),
+            revealed.conceal_state(),
+            revealed.conceal(),
+        ];
+        let statuses = [
+            RevealStatus::Revealed,
+            RevealStatus::ConfidentialSeal,
+            RevealStatus::ConfidentialState,
+            RevealStatus::Confidential,
+        ];
+        for (assign, status) in variants.iter().zip(statuses) {
+            assert_eq!(assign.reveal_status(), status);
+            let seal_revealed =
+                matches!(status, RevealStatus::Revealed | RevealStatus::ConfidentialState);
+            match assign.seal() {
+                SealForm::Revealed(revealed) => {
+                    assert!(seal_revealed);
+                    assert_eq!(*revealed, seal(0));
+                }
+                SealForm::Confidential(concealed) => {
+                    assert!(!seal_revealed);
+                    assert_eq!(concealed, secret);
+                }
+            }
+            assert_eq!(assign.seal().revealed().is_some(), seal_revealed);
+            assert_eq!(assign.seal().secret_seal(), secret);
+            assert_eq!(assign.secret_seal(), secret);
+            assert_eq!(assign.to_confidential_seal(), secret);
+            assert!(assign.targets_seal(&secret));
+            assert!(!assign.targets_seal(&seal(1).conceal()));
+        }
+
+        let assigns = TypedAssigns::Fungible(Confined::try_from(variants.to_vec()).unwrap());
+        assert!(assigns.targets_seal(&secret));
+        assert!(!assigns.targets_seal(&seal(1).conceal()));
+        assert!(!TypedAssigns::<GraphSeal>::Declarative(empty!()).targets_seal(&secret));
+    }
+
+    #[test]
+    fn commitment_equality() {
+        let tag = AssetTag::from([0x55; 32]);
+        let variants = |assign: AssignFungible<GraphSeal>| {
+            [assign.clone(), assign.conceal_seal(), assign.conceal_state(), assign.conceal()]
+        };
+        let state = RevealedValue::new_random_blinding(10u64, tag);
+        let same = variants(Assign::revealed(seal(0), state));
+        let other_seal = variants(Assign::revealed(seal(1), state));
+        let other_state =
+            variants(Assign::revealed(seal(0), RevealedValue::new_random_blinding(10u64, tag)));
+        for a in &same {
+            for b in &same {
+                assert!(a.commitment_eq(b));
+                assert_eq!(a, b);
+            }
+            for b in other_seal.iter().chain(&other_state) {
+                assert!(!a.commitment_eq(b));
+                assert!(!b.commitment_eq(a));
+            }
+        }
+
+        let typed = |assigns: Vec<AssignFungible<GraphSeal>>| {
+            TypedAssigns::Fungible(Confined::try_from(assigns).unwrap())
+        };
+        let a = typed(vec![same[0].clone(), other_seal[1].clone(), other_state[2].clone()]);
+        // Different order and reveal variants
+        let b = typed(vec![other_state[3].clone(), same[3].clone(), other_seal[0].clone()]);
+        assert!(a.commitment_eq(&b));
+        assert!(b.commitment_eq(&a));
+        assert_ne!(a, b);
+        // Same seal with a different state
+        let c = typed(vec![same[0].clone(), other_seal[1].clone(), same[2].clone()]);
+        assert!(!a.commitment_eq(&c));
+        // Multiplicity matters
+        let d = typed(vec![same[0].clone(), same[1].clone(), other_seal[2].clone()]);
+        let e = typed(vec![same[0].clone(), other_seal[1].clone(), other_seal[2].clone()]);
+        assert!(!d.commitment_eq(&e));
+        assert!(!a.commitment_eq(&typed(vec![same[0].clone(), other_seal[1].clone()])));
+        // Different state types never match
+        assert!(!TypedAssigns::<GraphSeal>::Declarative(empty!())
+            .commitment_eq(&TypedAssigns::Fungible(empty!())));
+        assert!(TypedAssigns::<GraphSeal>::Fungible(empty!())
+            .commitment_eq(&TypedAssigns::Fungible(empty!())));
+    }
+}
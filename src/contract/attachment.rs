@@ -20,10 +20,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::fmt::{Display, Formatter};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read};
 use std::str::FromStr;
 
+use amplify::hex::FromHex;
 use amplify::{ByteArray, Bytes32};
 use baid64::{Baid64ParseError, DisplayBaid64, FromBaid64Str};
 use bp::secp256k1::rand::{random, Rng, RngCore};
@@ -31,7 +33,10 @@ use commit_verify::{CommitId, CommitmentId, Conceal, DigestExt, Sha256};
 use strict_encoding::StrictEncode;
 
 use super::{ConfidentialState, ExposedState};
-use crate::{impl_serde_baid64, ConcealedState, MediaType, RevealedState, StateType, LIB_NAME_RGB};
+use crate::{
+    impl_serde_baid64, ConcealedData, ConcealedState, ConcealedValue, MediaType, MediaTypeError,
+    RevealedData, RevealedState, RevealedValue, StateProvider, StateType, LIB_NAME_RGB,
+};
 
 /// Unique data attachment identifier
 #[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
@@ -55,7 +60,15 @@ impl DisplayBaid64 for AttachId {
 impl FromBaid64Str for AttachId {}
 impl FromStr for AttachId {
     type Err = Baid64ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid64_str(s) }
+    /// Parses attachment id either from Baid64 or from hexadecimal string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 64 {
+            if let Ok(id) = Self::from_hex(s) {
+                return Ok(id);
+            }
+        }
+        Self::from_baid64_str(s)
+    }
 }
 impl Display for AttachId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { self.fmt_baid64(f) }
@@ -63,6 +76,185 @@ impl Display for AttachId {
 
 impl_serde_baid64!(AttachId);
 
+impl AttachId {
+    /// Computes attachment id as SHA256 digest of the attachment content read
+    /// from `reader` till its end, without loading the whole content into
+    /// memory.
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut hasher = Sha256::default();
+        let mut buf = [0u8; 8192];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(len) => hasher.input_raw(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(AttachId::from(hasher.finish()))
+    }
+}
+
+/// Attachment content doesn't match the expected id {expected}; the actual id
+/// is {found}.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct AttachMismatch {
+    pub expected: AttachId,
+    pub found: AttachId,
+}
+
+/// Verifier of attachment content against a known [`AttachId`], digesting the
+/// content incrementally as it gets written via [`io::Write`] interface.
+#[derive(Clone)]
+pub struct AttachVerifier {
+    expected: AttachId,
+    hasher: Sha256,
+}
+
+impl AttachVerifier {
+    pub fn new(expected: AttachId) -> Self {
+        Self {
+            expected,
+            hasher: Sha256::default(),
+        }
+    }
+
+    pub fn expected(&self) -> AttachId { self.expected }
+
+    /// Completes the verification of all the data written so far.
+    ///
+    /// # Errors
+    ///
+    /// If the written data doesn't match the expected attachment id.
+    pub fn finalize(self) -> Result<(), AttachMismatch> {
+        let expected = self.expected;
+        let found = AttachId::from(self.hasher.finish());
+        if found != expected {
+            return Err(AttachMismatch { expected, found });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for AttachVerifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttachVerifier")
+            .field("expected", &self.expected)
+            .finish_non_exhaustive()
+    }
+}
+
+impl io::Write for AttachVerifier {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.input_raw(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+/// Errors retrieving attachment data from [`AttachStore`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum AttachStoreError {
+    /// data for attachment {0} are absent in the store.
+    Absent(AttachId),
+
+    /// I/O error reading attachment {0} data: {1:?}.
+    Io(AttachId, io::ErrorKind),
+
+    #[from]
+    #[display(inner)]
+    Mismatch(AttachMismatch),
+}
+
+/// Store of attachment data, which are committed to in the contract state by
+/// their [`AttachId`] only.
+pub trait AttachStore {
+    /// Returns reader for the data of the attachment with the given `id`, if
+    /// known to the store. The data are not verified to match the id; use
+    /// [`AttachStore::verify`] for that.
+    fn get(&self, id: AttachId) -> Option<Box<dyn Read + '_>>;
+
+    /// Adds attachment data read from `data` till its end to the store.
+    ///
+    /// # Returns
+    ///
+    /// Id of the added attachment.
+    fn put(&mut self, data: impl Read) -> io::Result<AttachId>;
+
+    /// Checks that the store has data for the attachment with the given `id`
+    /// and that the data match the id. The data are verified while streaming
+    /// them from the store.
+    fn verify(&self, id: AttachId) -> Result<(), AttachStoreError> {
+        let mut reader = self.get(id).ok_or(AttachStoreError::Absent(id))?;
+        let mut verifier = AttachVerifier::new(id);
+        io::copy(&mut reader, &mut verifier).map_err(|err| AttachStoreError::Io(id, err.kind()))?;
+        verifier.finalize()?;
+        Ok(())
+    }
+}
+
+/// In-memory store of attachment data.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MemAttachStore(BTreeMap<AttachId, Vec<u8>>);
+
+impl MemAttachStore {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn len(&self) -> usize { self.0.len() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    pub fn contains(&self, id: AttachId) -> bool { self.0.contains_key(&id) }
+}
+
+impl AttachStore for MemAttachStore {
+    fn get(&self, id: AttachId) -> Option<Box<dyn Read + '_>> {
+        self.0
+            .get(&id)
+            .map(|data| Box::new(data.as_slice()) as Box<dyn Read + '_>)
+    }
+
+    fn put(&mut self, mut data: impl Read) -> io::Result<AttachId> {
+        let mut buf = vec![];
+        data.read_to_end(&mut buf)?;
+        let id = AttachId::from_reader(buf.as_slice())?;
+        self.0.insert(id, buf);
+        Ok(id)
+    }
+}
+
+/// State provider revealing attachment state only if the attachment data are
+/// present in the attachment store and match the attachment id. All other
+/// state is provided by the wrapped provider as is.
+#[derive(Debug)]
+pub struct VerifiedAttachProvider<'a, P: StateProvider, S: AttachStore> {
+    provider: &'a P,
+    store: &'a S,
+}
+
+impl<'a, P: StateProvider, S: AttachStore> VerifiedAttachProvider<'a, P, S> {
+    pub fn new(provider: &'a P, store: &'a S) -> Self { Self { provider, store } }
+}
+
+impl<P: StateProvider, S: AttachStore> StateProvider for VerifiedAttachProvider<'_, P, S> {
+    fn revealed_value(&self, concealed: &ConcealedValue) -> Option<RevealedValue> {
+        self.provider.revealed_value(concealed)
+    }
+
+    fn revealed_data(&self, concealed: &ConcealedData) -> Option<RevealedData> {
+        self.provider.revealed_data(concealed)
+    }
+
+    fn revealed_attach(&self, concealed: &ConcealedAttach) -> Option<RevealedAttach> {
+        self.provider
+            .revealed_attach(concealed)
+            .filter(|attach| self.store.verify(attach.id).is_ok())
+    }
+}
+
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
@@ -78,14 +270,23 @@ pub struct RevealedAttach {
     /// We do not enforce a MIME standard since non-standard types can be also
     /// used
     pub media_type: MediaType,
+    /// Size of the attached data in bytes, as advertised by the state creator.
+    /// Schemata may limit the size with
+    /// [`OwnedStateSchema::BoundedAttachment`].
+    ///
+    /// [`OwnedStateSchema::BoundedAttachment`]: crate::OwnedStateSchema::BoundedAttachment
+    pub size: u64,
+    /// Salt which is committed to by the concealed form of the state, such
+    /// that a party guessing the attached file can't confirm the guess by
+    /// concealing its id. Must be random and kept by the state owner.
     pub salt: u64,
 }
 
 impl RevealedAttach {
     /// Constructs new state using the provided value using random blinding
     /// factor.
-    pub fn new_random_salt(id: AttachId, media_type: impl Into<MediaType>) -> Self {
-        Self::with_salt(id, media_type, random())
+    pub fn new_random_salt(id: AttachId, media_type: impl Into<MediaType>, size: u64) -> Self {
+        Self::with_salt(id, media_type, size, random())
     }
 
     /// Constructs new state using the provided value and random generator for
@@ -93,19 +294,37 @@ impl RevealedAttach {
     pub fn with_rng<R: Rng + RngCore>(
         id: AttachId,
         media_type: impl Into<MediaType>,
+        size: u64,
         rng: &mut R,
     ) -> Self {
-        Self::with_salt(id, media_type, rng.next_u64())
+        Self::with_salt(id, media_type, size, rng.next_u64())
     }
 
     /// Convenience constructor.
-    pub fn with_salt(id: AttachId, media_type: impl Into<MediaType>, salt: u64) -> Self {
+    pub fn with_salt(id: AttachId, media_type: impl Into<MediaType>, size: u64, salt: u64) -> Self {
         Self {
             id,
             media_type: media_type.into(),
+            size,
             salt,
         }
     }
+
+    /// Constructs new state parsing the media type from a `type/subtype`
+    /// string, like `image/png`.
+    ///
+    /// # Errors
+    ///
+    /// If the media type string is invalid or has unsupported top-level type.
+    pub fn with_media_str(
+        id: AttachId,
+        media_type: &str,
+        size: u64,
+        salt: u64,
+    ) -> Result<Self, MediaTypeError> {
+        MediaType::from_str(media_type)
+            .map(|media_type| Self::with_salt(id, media_type, size, salt))
+    }
 }
 
 impl ExposedState for RevealedAttach {
@@ -122,6 +341,10 @@ impl Conceal for RevealedAttach {
 
 /// Confidential version of an attachment information.
 ///
+/// Commits to the attachment id, media type and salt, thus concealed forms of
+/// the same attachment with different salts are unlinkable to each other and to
+/// the attachment id.
+///
 /// See also revealed version [`RevealedAttach`].
 #[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
 #[wrapper(Deref, BorrowSlice, Hex, Index, RangeOps)]
@@ -153,7 +376,14 @@ impl CommitmentId for ConcealedAttach {
 
 #[cfg(test)]
 mod test {
+    use std::io::Write;
+
+    use amplify::confinement::Confined;
+    use amplify::hex::ToHex;
+    use strict_encoding::StrictDumb;
+
     use super::*;
+    use crate::{AssignAttach, GraphSeal, RevealState, TypedAssigns, XChain};
 
     #[test]
     fn attach_id_display() {
@@ -180,4 +410,180 @@ mod test {
             AttachId::from_str("rgb:fs:bGxsbGxs-bGxsbGx-sbGxsbG-xsbGxsb-GxsbGxs-bGxsbGw").unwrap()
         );
     }
+
+    const ABC_ID: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn attach_id_from_reader() {
+        let id = AttachId::from_hex(ABC_ID).unwrap();
+        assert_eq!(AttachId::from_reader(&b"abc"[..]).unwrap(), id);
+        assert_ne!(AttachId::from_reader(&b"abd"[..]).unwrap(), id);
+    }
+
+    #[test]
+    fn attach_verifier() {
+        let data = (0..100_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let id = AttachId::from_reader(data.as_slice()).unwrap();
+
+        let mut verifier = AttachVerifier::new(id);
+        for chunk in data.chunks(4096) {
+            verifier.write_all(chunk).unwrap();
+        }
+        assert_eq!(verifier.finalize(), Ok(()));
+
+        let mut corrupted = data.clone();
+        *corrupted.last_mut().unwrap() ^= 0x01;
+        let mut verifier = AttachVerifier::new(id);
+        for chunk in corrupted.chunks(1000) {
+            verifier.write_all(chunk).unwrap();
+        }
+        assert_eq!(
+            verifier.finalize(),
+            Err(AttachMismatch {
+                expected: id,
+                found: AttachId::from_reader(corrupted.as_slice()).unwrap(),
+            })
+        );
+
+        let mut verifier = AttachVerifier::new(AttachId::from_hex(ABC_ID).unwrap());
+        verifier.write_all(b"ab").unwrap();
+        verifier.write_all(b"c").unwrap();
+        assert_eq!(verifier.finalize(), Ok(()));
+    }
+
+    #[test]
+    fn attach_id_hex() {
+        let id = AttachId::from_byte_array([0x6c; 32]);
+        assert_eq!(AttachId::from_str(&id.to_hex()).unwrap(), id);
+        assert_eq!(AttachId::from_str(&id.to_string()).unwrap(), id);
+        assert!(AttachId::from_str(&id.to_hex()[1..]).is_err());
+        assert!(AttachId::from_str(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn media_type_parse() {
+        for (s, media_type) in [
+            ("image/png", MediaType::Image),
+            ("application/pdf", MediaType::Application),
+            ("text/plain; charset=utf-8", MediaType::Text),
+            ("video/*", MediaType::Video),
+            ("*/*", MediaType::Any),
+        ] {
+            assert_eq!(MediaType::from_str(s), Ok(media_type));
+        }
+        assert_eq!(MediaType::from_str("audio/mpeg"), Ok(MediaType::Audio));
+        for s in ["", "image", "image/", "/png", "image/png/x", "ima ge/png"] {
+            assert_eq!(MediaType::from_str(s), Err(MediaTypeError::Invalid(s.to_owned())));
+        }
+        assert_eq!(
+            MediaType::from_str("chemical/x-pdb"),
+            Err(MediaTypeError::Unsupported("chemical/x-pdb".to_owned()))
+        );
+        assert_eq!(
+            MediaType::from(&mime::Mime::from_str("chemical/x-pdb").unwrap()),
+            MediaType::Any
+        );
+        assert_eq!(MediaType::Image.to_string(), "image/*");
+
+        let id = AttachId::from_byte_array([0x6c; 32]);
+        assert_eq!(
+            RevealedAttach::with_media_str(id, "image/png", 1024, 0),
+            Ok(RevealedAttach::with_salt(id, MediaType::Image, 1024, 0))
+        );
+        assert_eq!(
+            RevealedAttach::with_media_str(id, "png", 1024, 0),
+            Err(MediaTypeError::Invalid("png".to_owned()))
+        );
+    }
+
+    struct Provider(RevealedAttach);
+
+    impl StateProvider for Provider {
+        fn revealed_value(&self, _: &ConcealedValue) -> Option<RevealedValue> { None }
+        fn revealed_data(&self, _: &ConcealedData) -> Option<RevealedData> { None }
+        fn revealed_attach(&self, concealed: &ConcealedAttach) -> Option<RevealedAttach> {
+            Some(self.0.clone()).filter(|attach| attach.conceal() == *concealed)
+        }
+    }
+
+    #[test]
+    fn attach_store() {
+        let data = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let mut store = MemAttachStore::new();
+        let id = store.put(data.as_slice()).unwrap();
+        assert_eq!(id, AttachId::from_reader(data.as_slice()).unwrap());
+        assert!(store.contains(id));
+        assert_eq!(store.verify(id), Ok(()));
+
+        let mut read = vec![];
+        store.get(id).unwrap().read_to_end(&mut read).unwrap();
+        assert_eq!(read, data);
+
+        let missing = AttachId::from_byte_array([0x6c; 32]);
+        assert!(store.get(missing).is_none());
+        assert_eq!(store.verify(missing), Err(AttachStoreError::Absent(missing)));
+
+        let mut corrupted = data.clone();
+        *corrupted.last_mut().unwrap() ^= 0x01;
+        store.0.insert(id, corrupted.clone());
+        assert_eq!(
+            store.verify(id),
+            Err(AttachStoreError::Mismatch(AttachMismatch {
+                expected: id,
+                found: AttachId::from_reader(corrupted.as_slice()).unwrap(),
+            }))
+        );
+    }
+
+    #[test]
+    fn attach_reveal_verified() {
+        let data = b"attachment data";
+        let mut store = MemAttachStore::new();
+        let id = AttachId::from_reader(&data[..]).unwrap();
+        let attach = RevealedAttach::with_salt(id, MediaType::Text, data.len() as u64, 0);
+        let provider = Provider(attach.clone());
+        let mut assigns = TypedAssigns::Attachment(
+            Confined::try_from(vec![AssignAttach::revealed(
+                XChain::Bitcoin(GraphSeal::strict_dumb()),
+                attach,
+            )
+            .conceal_state()])
+            .unwrap(),
+        );
+
+        // Missing data
+        assert_eq!(assigns.reveal_state(&VerifiedAttachProvider::new(&provider, &store)), 0);
+        assert_eq!(assigns.as_attachment()[0].as_revealed_state(), None);
+
+        // Data not matching the id
+        store.0.insert(id, b"other data".to_vec());
+        assert_eq!(assigns.reveal_state(&VerifiedAttachProvider::new(&provider, &store)), 0);
+
+        assert_eq!(store.put(&data[..]).unwrap(), id);
+        assert_eq!(assigns.reveal_state(&VerifiedAttachProvider::new(&provider, &store)), 1);
+        assert_eq!(assigns.as_attachment()[0].as_revealed_state(), Some(&provider.0));
+    }
+
+    #[test]
+    fn attach_conceal_salted() {
+        let id = AttachId::from_byte_array([0x6c; 32]);
+        let attach = RevealedAttach::with_salt(id, MediaType::Image, 1024, 1);
+        assert_eq!(
+            attach.conceal(),
+            RevealedAttach::with_salt(id, MediaType::Image, 1024, 1).conceal()
+        );
+        assert_ne!(
+            attach.conceal(),
+            RevealedAttach::with_salt(id, MediaType::Image, 1024, 2).conceal()
+        );
+        assert_ne!(
+            attach.conceal(),
+            RevealedAttach::with_salt(id, MediaType::Image, 2048, 1).conceal()
+        );
+        assert_ne!(
+            RevealedAttach::new_random_salt(id, MediaType::Image, 1024).conceal(),
+            RevealedAttach::new_random_salt(id, MediaType::Image, 1024).conceal()
+        );
+        assert_ne!(attach.conceal().to_byte_array(), id.to_byte_array());
+    }
 }
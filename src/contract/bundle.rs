@@ -29,7 +29,7 @@ use bp::Vout;
 use commit_verify::{mpc, CommitEncode, CommitEngine, CommitId, CommitmentId, DigestExt, Sha256};
 use strict_encoding::{StrictDumb, StrictEncode};
 
-use crate::{OpId, Transition, LIB_NAME_RGB};
+use crate::{OpId, Operation, Transition, LIB_NAME_RGB};
 
 pub type Vin = Vout;
 
@@ -133,6 +133,151 @@ impl StrictDumb for TransitionBundle {
     }
 }
 
+/// Errors concealing or revealing state transitions of a bundle.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum BundleError {
+    /// state transition {0} is not a part of the bundle.
+    UnknownTransition(OpId),
+
+    /// state transition {actual} is provided in place of {expected}.
+    IdMismatch { expected: OpId, actual: OpId },
+
+    /// bundle must keep at least one of its state transitions known.
+    NoKnownTransitions,
+}
+
 impl TransitionBundle {
     pub fn bundle_id(&self) -> BundleId { self.commit_id() }
+
+    /// Conceals all known state transitions except the ones listed in `keep`,
+    /// leaving just their ids in the input map. Doesn't change the bundle id.
+    ///
+    /// # Returns
+    ///
+    /// Ids of the concealed state transitions.
+    ///
+    /// # Errors
+    ///
+    /// If any of the transitions in `keep` is not a part of the bundle, or if
+    /// none of them is known; in both cases the bundle is left unmodified.
+    pub fn conceal_all_except(&mut self, keep: &[OpId]) -> Result<Vec<OpId>, BundleError> {
+        if let Some(opid) = keep
+            .iter()
+            .find(|opid| !self.input_map.values().any(|id| id == *opid))
+        {
+            return Err(BundleError::UnknownTransition(*opid));
+        }
+        let (known, concealed): (BTreeMap<_, _>, BTreeMap<_, _>) = self
+            .known_transitions
+            .iter()
+            .map(|(opid, transition)| (*opid, transition.clone()))
+            .partition(|(opid, _)| keep.contains(opid));
+        self.known_transitions =
+            Confined::try_from(known).map_err(|_| BundleError::NoKnownTransitions)?;
+        Ok(concealed.into_keys().collect())
+    }
+
+    /// Reveals state transition `opid` which was concealed in the bundle,
+    /// after checking that the provided `transition` has this id.
+    ///
+    /// # Returns
+    ///
+    /// Whether the transition was previously concealed.
+    ///
+    /// # Errors
+    ///
+    /// If the transition id doesn't match `opid`, or if it is not a part of
+    /// the bundle.
+    pub fn reveal(&mut self, opid: OpId, transition: Transition) -> Result<bool, BundleError> {
+        let actual = transition.id();
+        if actual != opid {
+            return Err(BundleError::IdMismatch {
+                expected: opid,
+                actual,
+            });
+        }
+        if !self.input_map.values().any(|id| *id == opid) {
+            return Err(BundleError::UnknownTransition(opid));
+        }
+        let prev = self
+            .known_transitions
+            .insert(opid, transition)
+            .expect("known transitions are limited by the input map size");
+        Ok(prev.is_none())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TransitionType;
+
+    fn transition(ty: u16) -> Transition {
+        let mut transition = Transition::strict_dumb();
+        transition.transition_type = TransitionType::with(ty);
+        transition
+    }
+
+    fn bundle() -> TransitionBundle {
+        let transitions = [transition(1), transition(2)];
+        TransitionBundle {
+            close_method: CloseMethod::TapretFirst,
+            input_map: InputMap::from(
+                Confined::try_from_iter(
+                    transitions
+                        .iter()
+                        .enumerate()
+                        .map(|(vin, t)| (Vin::from(vin as u32), t.id())),
+                )
+                .unwrap(),
+            ),
+            known_transitions: Confined::try_from_iter(transitions.map(|t| (t.id(), t))).unwrap(),
+        }
+    }
+
+    #[test]
+    fn conceal_reveal_round_trip() {
+        let original = bundle();
+        let id = original.bundle_id();
+        let (kept, concealed) = (transition(1), transition(2));
+
+        let mut bundle = original.clone();
+        assert_eq!(bundle.conceal_all_except(&[kept.id()]), Ok(vec![concealed.id()]));
+        assert_eq!(bundle.bundle_id(), id);
+        assert_eq!(bundle.known_transitions.len(), 1);
+        assert!(bundle.known_transitions.contains_key(&kept.id()));
+        assert_eq!(bundle.conceal_all_except(&[kept.id()]), Ok(vec![]));
+
+        assert_eq!(bundle.reveal(concealed.id(), concealed.clone()), Ok(true));
+        assert_eq!(bundle, original);
+        assert_eq!(bundle.bundle_id(), id);
+        assert_eq!(bundle.reveal(concealed.id(), concealed), Ok(false));
+        assert_eq!(bundle, original);
+    }
+
+    #[test]
+    fn conceal_reveal_mismatch() {
+        let original = bundle();
+        let foreign = transition(3);
+
+        let mut bundle = original.clone();
+        assert_eq!(
+            bundle.reveal(transition(1).id(), transition(2)),
+            Err(BundleError::IdMismatch {
+                expected: transition(1).id(),
+                actual: transition(2).id()
+            })
+        );
+        assert_eq!(
+            bundle.reveal(foreign.id(), foreign.clone()),
+            Err(BundleError::UnknownTransition(foreign.id()))
+        );
+        assert_eq!(
+            bundle.conceal_all_except(&[transition(1).id(), foreign.id()]),
+            Err(BundleError::UnknownTransition(foreign.id()))
+        );
+        assert_eq!(bundle.conceal_all_except(&[]), Err(BundleError::NoKnownTransitions));
+        assert_eq!(bundle, original);
+    }
 }
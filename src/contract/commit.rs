@@ -23,9 +23,9 @@
 use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::{fmt, vec};
+use std::{fmt, iter, slice, vec};
 
-use amplify::confinement::{Confined, MediumOrdMap, U16 as U16MAX};
+use amplify::confinement::{Confined, MediumOrdMap, MediumVec, U16 as U16MAX};
 use amplify::hex::{FromHex, ToHex};
 use amplify::num::u256;
 use amplify::{hex, ByteArray, Bytes32, FromSliceError, Wrapper};
@@ -34,14 +34,14 @@ use commit_verify::{
     mpc, CommitEncode, CommitEngine, CommitId, CommitmentId, Conceal, DigestExt, MerkleHash,
     MerkleLeaves, ReservedBytes, Sha256, StrictHash,
 };
-use strict_encoding::StrictDumb;
+use strict_encoding::{StrictDeserialize, StrictDumb, StrictSerialize};
 
 use crate::{
     impl_serde_baid64, Assign, AssignmentType, Assignments, BundleId, ConcealedAttach,
     ConcealedData, ConcealedState, ConfidentialState, DataState, ExposedSeal, ExposedState,
-    Extension, ExtensionType, Ffv, Genesis, GlobalState, GlobalStateType, Operation,
-    PedersenCommitment, Redeemed, SchemaId, SecretSeal, Transition, TransitionBundle,
-    TransitionType, TypedAssigns, XChain, LIB_NAME_RGB,
+    Extension, ExtensionType, Ffv, Genesis, GlobalState, GlobalStateType, MetaType, MetaValue,
+    Metadata, Operation, Opout, PedersenCommitment, Redeemed, SchemaId, SecretSeal, Transition,
+    TransitionBundle, TransitionType, TypedAssigns, XChain, LIB_NAME_RGB,
 };
 
 /// Unique contract identifier equivalent to the contract genesis commitment
@@ -118,7 +118,57 @@ impl From<Sha256> for OpId {
 }
 
 impl CommitmentId for OpId {
-    const TAG: &'static str = "urn:lnp-bp:rgb:operation#2024-02-03";
+    const TAG: &'static str = CommitmentVersion::LATEST.tag();
+}
+
+/// Version of the operation commitment, defined by the tag used in hashing
+/// the operation data into [`OpId`].
+///
+/// Each consensus upgrade rotating the operation tag must add a new variant,
+/// such that ids produced under previous tags remain verifiable.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+pub enum CommitmentVersion {
+    /// Operations tagged with `urn:lnp-bp:rgb:operation#2024-02-03`.
+    #[display("operation#2024-02-03")]
+    V2024_02_03,
+}
+
+impl CommitmentVersion {
+    /// All known commitment versions, from the most recent one.
+    pub const ALL: [CommitmentVersion; 1] = [CommitmentVersion::V2024_02_03];
+
+    /// Version used for computing operation ids by this library.
+    pub const LATEST: CommitmentVersion = CommitmentVersion::V2024_02_03;
+
+    /// Tag used for the commitment hashing under this version.
+    pub const fn tag(self) -> &'static str {
+        match self {
+            CommitmentVersion::V2024_02_03 => "urn:lnp-bp:rgb:operation#2024-02-03",
+        }
+    }
+
+    /// Computes operation id under this commitment version.
+    pub fn op_id(self, op: &impl CommitEncode<CommitmentId = OpId>) -> OpId {
+        op_id_tagged(op, self.tag())
+    }
+}
+
+fn op_id_tagged(op: &impl CommitEncode<CommitmentId = OpId>, tag: &'static str) -> OpId {
+    let mut engine = CommitEngine::new(tag);
+    op.commit_encode(&mut engine);
+    engine.set_finished();
+    engine.finish().into()
+}
+
+/// Recomputes id of the operation under each of the known commitment
+/// versions, returning the version producing the `expected` id, if any.
+pub fn verify_commitment_any_version(
+    op: &impl CommitEncode<CommitmentId = OpId>,
+    expected: OpId,
+) -> Option<CommitmentVersion> {
+    CommitmentVersion::ALL
+        .into_iter()
+        .find(|version| version.op_id(op) == expected)
 }
 
 impl FromStr for OpId {
@@ -231,6 +281,11 @@ impl TransitionBundle {
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 pub struct BaseCommitment {
     pub flags: ReservedBytes<1, 0>,
     pub schema_id: SchemaId,
@@ -244,6 +299,11 @@ pub struct BaseCommitment {
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB, tags = custom, dumb = Self::Transition(strict_dumb!(), strict_dumb!()))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 pub enum TypeCommitment {
     #[strict_type(tag = 0)]
     Genesis(BaseCommitment),
@@ -253,6 +313,12 @@ pub enum TypeCommitment {
 
     #[strict_type(tag = 2)]
     Extension(ContractId, ExtensionType),
+
+    /// State transition committing to the schema of the contract. Used only
+    /// for the transitions having [`Transition::schema_id`] set, such that ids
+    /// of the other transitions are not affected.
+    #[strict_type(tag = 3)]
+    BoundTransition(ContractId, TransitionType, SchemaId),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -260,6 +326,11 @@ pub enum TypeCommitment {
 #[strict_type(lib = LIB_NAME_RGB)]
 #[derive(CommitEncode)]
 #[commit_encode(strategy = strict, id = OpId)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 pub struct OpCommitment {
     pub ffv: Ffv,
     pub op_type: TypeCommitment,
@@ -273,6 +344,8 @@ pub struct OpCommitment {
     pub validator: StrictHash,
 }
 
+impl StrictSerialize for OpCommitment {}
+
 impl Genesis {
     pub fn commit(&self) -> OpCommitment {
         let base = BaseCommitment {
@@ -302,10 +375,21 @@ impl Genesis {
 }
 
 impl Transition {
+    fn type_commitment(&self) -> TypeCommitment {
+        if let Some(schema_id) = self.schema_id {
+            return TypeCommitment::BoundTransition(
+                self.contract_id,
+                self.transition_type,
+                schema_id,
+            );
+        }
+        TypeCommitment::Transition(self.contract_id, self.transition_type)
+    }
+
     pub fn commit(&self) -> OpCommitment {
         OpCommitment {
-            ffv: self.ffv,
-            op_type: TypeCommitment::Transition(self.contract_id, self.transition_type),
+            ffv: self.encoding_ffv(),
+            op_type: self.type_commitment(),
             metadata: self.metadata.commit_id(),
             globals: MerkleHash::merklize(&self.globals),
             inputs: MerkleHash::merklize(&self.inputs),
@@ -347,6 +431,13 @@ impl ConcealedState {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 pub struct AssignmentCommitment {
     pub ty: AssignmentType,
     pub state: ConcealedState,
@@ -408,6 +499,171 @@ impl<Seal: ExposedSeal> MerkleLeaves for Assignments<Seal> {
     }
 }
 
+/// Errors constructing [`AssignmentProof`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AssignmentProofError {
+    /// output {0} doesn't belong to the operation {1}.
+    OpidMismatch(Opout, OpId),
+
+    /// operation has no assignment {0}.
+    UnknownAssignment(Opout),
+}
+
+/// Proof that an assignment is committed to by an operation id, which allows
+/// to prove the assignment to a third party without disclosing the rest of
+/// the operation. The proof contains just hashes of the other operation
+/// data, and only concealed form of the other operation assignments.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct AssignmentProof {
+    /// Operation commitment, which id is the operation id.
+    pub commitment: OpCommitment,
+    /// Concealed assignments of the operation in the order of their
+    /// merklization.
+    pub leaves: MediumVec<AssignmentCommitment>,
+    /// Position of the proven assignment within the `leaves`.
+    pub pos: u32,
+}
+
+impl StrictSerialize for AssignmentProof {}
+impl StrictDeserialize for AssignmentProof {}
+
+impl AssignmentProof {
+    fn with<Seal: ExposedSeal>(
+        id: OpId,
+        commitment: OpCommitment,
+        assignments: &Assignments<Seal>,
+        opout: Opout,
+    ) -> Result<Self, AssignmentProofError> {
+        if opout.op != id {
+            return Err(AssignmentProofError::OpidMismatch(opout, id));
+        }
+        let known = assignments
+            .get(&opout.ty)
+            .map(|a| opout.no < a.len_u16())
+            .unwrap_or_default();
+        if !known {
+            return Err(AssignmentProofError::UnknownAssignment(opout));
+        }
+        let pos = assignments
+            .iter()
+            .take_while(|(ty, _)| **ty < opout.ty)
+            .map(|(_, a)| a.len_u16() as u32)
+            .sum::<u32>() +
+            opout.no as u32;
+        Ok(AssignmentProof {
+            commitment,
+            leaves: Confined::from_iter_unsafe(assignments.merkle_leaves()),
+            pos,
+        })
+    }
+
+    /// Verifies that the `expected` assignment is committed to by the
+    /// operation with id `opid`.
+    pub fn verify(&self, opid: OpId, expected: &AssignmentCommitment) -> bool {
+        self.leaves.get(self.pos as usize) == Some(expected) &&
+            MerkleHash::merklize(self) == self.commitment.assignments &&
+            self.commitment.commit_id() == opid
+    }
+}
+
+impl MerkleLeaves for AssignmentProof {
+    type Leaf = AssignmentCommitment;
+    type LeafIter<'tmp> = iter::Copied<slice::Iter<'tmp, AssignmentCommitment>>;
+
+    fn merkle_leaves(&self) -> Self::LeafIter<'_> { self.leaves.iter().copied() }
+}
+
+impl Genesis {
+    /// Constructs proof that the assignment `opout` is committed to by the
+    /// genesis id.
+    pub fn prove_assignment(&self, opout: Opout) -> Result<AssignmentProof, AssignmentProofError> {
+        AssignmentProof::with(self.id(), self.commit(), &self.assignments, opout)
+    }
+}
+
+impl Transition {
+    /// Constructs proof that the assignment `opout` is committed to by the
+    /// state transition id.
+    pub fn prove_assignment(&self, opout: Opout) -> Result<AssignmentProof, AssignmentProofError> {
+        AssignmentProof::with(self.id(), self.commit(), &self.assignments, opout)
+    }
+}
+
+impl Extension {
+    /// Constructs proof that the assignment `opout` is committed to by the
+    /// state extension id.
+    pub fn prove_assignment(&self, opout: Opout) -> Result<AssignmentProof, AssignmentProofError> {
+        AssignmentProof::with(self.id(), self.commit(), &self.assignments, opout)
+    }
+}
+
+/// Errors constructing [`MetadataProof`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MetadataProofError {
+    /// genesis has no metadata field of type {0}.
+    UnknownField(MetaType),
+}
+
+/// Proof that a metadata field is committed to by a contract genesis, which
+/// allows to prove the field value to a third party without disclosing the
+/// genesis assignments and global state. The proof relies on the metadata
+/// sub-commitment of the operation and thus discloses all metadata fields of
+/// the genesis, not just the proven one.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct MetadataProof {
+    /// Genesis commitment, which id is the contract id.
+    pub commitment: OpCommitment,
+    /// Genesis metadata matching the `commitment`.
+    pub metadata: Metadata,
+}
+
+impl StrictSerialize for MetadataProof {}
+impl StrictDeserialize for MetadataProof {}
+
+impl MetadataProof {
+    /// Verifies that the metadata field of `field_type` with the given `value`
+    /// is committed to by the genesis of the contract `contract_id`.
+    pub fn verify(&self, contract_id: ContractId, field_type: MetaType, value: &MetaValue) -> bool {
+        self.metadata.get(&field_type) == Some(value) &&
+            self.metadata.commit_id() == self.commitment.metadata &&
+            matches!(self.commitment.op_type, TypeCommitment::Genesis(_)) &&
+            ContractId::from_inner(self.commitment.commit_id().into_inner()) == contract_id
+    }
+}
+
+impl Genesis {
+    /// Constructs proof that the metadata field of `field_type` is committed
+    /// to by the contract id.
+    pub fn prove_metadata_field(
+        &self,
+        field_type: MetaType,
+    ) -> Result<MetadataProof, MetadataProofError> {
+        if !self.metadata.contains_key(&field_type) {
+            return Err(MetadataProofError::UnknownField(field_type));
+        }
+        Ok(MetadataProof {
+            commitment: self.commit(),
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct GlobalCommitment {
     pub ty: GlobalStateType,
@@ -440,3 +696,339 @@ impl MerkleLeaves for GlobalState {
             .into_iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallBlob;
+    use bp::seals::txout::{BlindSeal, CloseMethod, TxPtr};
+    use bp::{Txid, Vout};
+
+    use super::*;
+    use crate::{AssignRights, GraphSeal, VoidState};
+
+    fn seal(vout: u32) -> XChain<GraphSeal> {
+        XChain::Bitcoin(BlindSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0x5eed + vout as u64,
+            txid: TxPtr::Txid(Txid::from([0x11; 32])),
+            vout: Vout::from(vout),
+        })
+    }
+
+    fn transition() -> Transition {
+        let rights = |vouts: &[u32]| {
+            TypedAssigns::Declarative(
+                Confined::try_from_iter(
+                    vouts
+                        .iter()
+                        .map(|vout| AssignRights::revealed(seal(*vout), VoidState::default())),
+                )
+                .unwrap(),
+            )
+        };
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => rights(&[0, 1, 2]),
+                AssignmentType::with(2) => rights(&[3, 4]),
+            })
+            .unwrap(),
+        );
+        transition
+    }
+
+    fn commitment(transition: &Transition, ty: u16, no: u16) -> AssignmentCommitment {
+        let ty = AssignmentType::with(ty);
+        transition.assignments.get(&ty).unwrap().as_declarative()[no as usize].commitment(ty)
+    }
+
+    #[test]
+    fn commitment_tags() {
+        let tags = [
+            <SchemaId as CommitmentId>::TAG,
+            <OpId as CommitmentId>::TAG,
+            <DiscloseHash as CommitmentId>::TAG,
+            <BundleId as CommitmentId>::TAG,
+            <ConcealedData as CommitmentId>::TAG,
+            <ConcealedAttach as CommitmentId>::TAG,
+        ];
+        let unique = tags.iter().collect::<BTreeSet<_>>();
+        assert_eq!(unique.len(), tags.len(), "commitment tags must be domain-separated");
+        for tag in tags {
+            assert!(tag.starts_with("urn:lnp-bp:rgb:"), "{tag} is not an RGB URN");
+            assert!(tag.contains('#'), "{tag} is not versioned");
+        }
+    }
+
+    #[test]
+    fn commitment_versions() {
+        let transition = transition();
+        let id = transition.id();
+        assert_eq!(CommitmentVersion::LATEST.op_id(&transition), id);
+        assert_eq!(CommitmentVersion::LATEST.op_id(&transition.commit()), id);
+        assert_eq!(verify_commitment_any_version(&transition, id), Some(CommitmentVersion::LATEST));
+        assert_eq!(
+            verify_commitment_any_version(&transition.commit(), id),
+            Some(CommitmentVersion::LATEST)
+        );
+
+        // Ids produced under a rotated tag are distinct and not recognized until
+        // the tag is added as a new version
+        let rotated = op_id_tagged(&transition, "urn:lnp-bp:rgb:operation#2099-01-01");
+        assert_ne!(rotated, id);
+        assert_eq!(verify_commitment_any_version(&transition, rotated), None);
+        assert_eq!(verify_commitment_any_version(&Transition::strict_dumb(), id), None);
+    }
+
+    #[test]
+    fn commitment_streaming() {
+        // Operation ids are computed by streaming the commitment into the hasher;
+        // they must match hashing of the fully serialized commitment.
+        for op in [Transition::strict_dumb(), transition()] {
+            let data = op
+                .commit()
+                .to_strict_serialized::<{ usize::MAX }>()
+                .unwrap();
+            let mut hasher = Sha256::from_tag(<OpId as CommitmentId>::TAG);
+            hasher.input_raw(&data);
+            assert_eq!(OpId::from(hasher), op.id());
+        }
+    }
+
+    #[test]
+    fn assignment_proof() {
+        let transition = transition();
+        let id = transition.id();
+        for (ty, count) in [(1u16, 3u16), (2, 2)] {
+            for no in 0..count {
+                let opout = Opout::new(id, AssignmentType::with(ty), no);
+                let proof = transition.prove_assignment(opout).unwrap();
+                assert!(proof.verify(id, &commitment(&transition, ty, no)));
+                assert!(!proof.verify(id, &commitment(&transition, 1, (no + 1) % 3)));
+            }
+        }
+
+        let opout = Opout::new(id, AssignmentType::with(2), 2);
+        assert_eq!(
+            transition.prove_assignment(opout),
+            Err(AssignmentProofError::UnknownAssignment(opout))
+        );
+        let opout = Opout::new(id, AssignmentType::with(3), 0);
+        assert_eq!(
+            transition.prove_assignment(opout),
+            Err(AssignmentProofError::UnknownAssignment(opout))
+        );
+        let opout = Opout::new(OpId::from([0x11; 32]), AssignmentType::with(1), 0);
+        assert_eq!(
+            transition.prove_assignment(opout),
+            Err(AssignmentProofError::OpidMismatch(opout, id))
+        );
+    }
+
+    #[test]
+    fn assignment_proof_tampered() {
+        let transition = transition();
+        let id = transition.id();
+        let expected = commitment(&transition, 2, 1);
+        let proof = transition
+            .prove_assignment(Opout::new(id, AssignmentType::with(2), 1))
+            .unwrap();
+        assert!(proof.verify(id, &expected));
+        assert!(!proof.verify(OpId::from([0x11; 32]), &expected));
+
+        let mut tampered = proof.clone();
+        tampered.pos -= 1;
+        assert!(!tampered.verify(id, &expected));
+
+        // Substitution of other assignments changes the merkle root
+        let mut tampered = proof.clone();
+        let mut leaves = tampered.leaves.into_inner();
+        leaves[0].seal = seal(5).to_secret_seal();
+        tampered.leaves = Confined::try_from(leaves).unwrap();
+        assert!(!tampered.verify(id, &expected));
+
+        // Substitution of the operation data changes the operation id
+        let mut tampered = proof.clone();
+        tampered.commitment.op_type =
+            TypeCommitment::Transition(ContractId::from([0x11; 32]), transition.transition_type);
+        assert!(!tampered.verify(id, &expected));
+
+        // Substitution of the proven assignment
+        let mut tampered = proof;
+        let mut leaves = tampered.leaves.into_inner();
+        leaves[tampered.pos as usize].seal = seal(5).to_secret_seal();
+        tampered.leaves = Confined::try_from(leaves).unwrap();
+        let substituted = tampered.leaves[tampered.pos as usize];
+        assert!(!tampered.verify(id, &substituted));
+    }
+
+    #[test]
+    fn assignment_proof_encoding() {
+        let transition = transition();
+        let id = transition.id();
+        let expected = commitment(&transition, 1, 2);
+        let proof = transition
+            .prove_assignment(Opout::new(id, AssignmentType::with(1), 2))
+            .unwrap();
+
+        let data = proof.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let decoded = AssignmentProof::from_strict_serialized::<{ usize::MAX }>(data).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(id, &expected));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&proof).unwrap();
+            let decoded: AssignmentProof = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, proof);
+        }
+    }
+
+    #[test]
+    fn schema_binding() {
+        let unbound = transition();
+        assert_eq!(
+            unbound.commit().op_type,
+            TypeCommitment::Transition(unbound.contract_id, unbound.transition_type)
+        );
+        assert_eq!(unbound.commit().ffv, Ffv::default());
+
+        let mut bound = unbound.clone();
+        bound.bind_schema(SchemaId::from([0x44; 32]));
+        assert_eq!(bound.ffv, Ffv::V1);
+        assert_eq!(bound.commit().ffv, Ffv::V1);
+        assert_eq!(
+            bound.commit().op_type,
+            TypeCommitment::BoundTransition(
+                bound.contract_id,
+                bound.transition_type,
+                SchemaId::from([0x44; 32])
+            )
+        );
+        assert_ne!(bound.id(), unbound.id());
+
+        let mut other = bound.clone();
+        other.schema_id = Some(SchemaId::from([0x55; 32]));
+        assert_ne!(other.id(), bound.id());
+
+        // Unbound transitions keep the original layout, while the bound ones
+        // add the schema id to it
+        let unbound_data = unbound.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let bound_data = bound.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(&unbound_data[..2], &[0, 0]);
+        assert_eq!(&bound_data[..2], &[1, 0]);
+        assert_eq!(bound_data.len(), unbound_data.len() + 33);
+        let decoded = Transition::from_strict_serialized::<{ usize::MAX }>(unbound_data).unwrap();
+        assert_eq!(decoded, unbound);
+        let decoded = Transition::from_strict_serialized::<{ usize::MAX }>(bound_data).unwrap();
+        assert_eq!(decoded, bound);
+
+        // Setting the field directly keeps the ids consistent with the encoding
+        let mut direct = unbound.clone();
+        direct.schema_id = Some(SchemaId::from([0x44; 32]));
+        let data = direct.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let decoded = Transition::from_strict_serialized::<{ usize::MAX }>(data).unwrap();
+        assert_eq!(decoded.id(), direct.id());
+        assert_eq!(direct.id(), bound.id());
+    }
+
+    fn genesis() -> Genesis {
+        let mut genesis = Genesis::strict_dumb();
+        genesis.metadata = Metadata::try_from_iter([
+            (MetaType::with(1), &b"ticker"[..]),
+            (MetaType::with(2), &[0xaa, 0xbb][..]),
+        ])
+        .unwrap();
+        genesis
+    }
+
+    fn meta_value(value: &[u8]) -> MetaValue {
+        MetaValue::from(SmallBlob::try_from(value.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn metadata_proof() {
+        let genesis = genesis();
+        let contract_id = genesis.contract_id();
+        let proof = genesis.prove_metadata_field(MetaType::with(1)).unwrap();
+        assert!(proof.verify(contract_id, MetaType::with(1), &meta_value(b"ticker")));
+        assert!(proof.verify(contract_id, MetaType::with(2), &meta_value(&[0xaa, 0xbb])));
+
+        assert_eq!(
+            genesis.prove_metadata_field(MetaType::with(3)),
+            Err(MetadataProofError::UnknownField(MetaType::with(3)))
+        );
+    }
+
+    #[test]
+    fn metadata_proof_tampered() {
+        let genesis = genesis();
+        let contract_id = genesis.contract_id();
+        let proof = genesis.prove_metadata_field(MetaType::with(1)).unwrap();
+
+        assert!(!proof.verify(contract_id, MetaType::with(1), &meta_value(b"tIcker")));
+        assert!(!proof.verify(contract_id, MetaType::with(3), &meta_value(b"ticker")));
+        assert!(!proof.verify(
+            ContractId::from([0x33; 32]),
+            MetaType::with(1),
+            &meta_value(b"ticker")
+        ));
+
+        let forged_metadata = || {
+            let mut metadata = proof.metadata.clone();
+            metadata.remove(&MetaType::with(1)).unwrap();
+            metadata
+                .add_value(MetaType::with(1), meta_value(b"forged"))
+                .unwrap();
+            metadata
+        };
+
+        // Forges the disclosed metadata value, keeping the commitment intact
+        let mut forged = proof.clone();
+        forged.metadata = forged_metadata();
+        assert!(!forged.verify(contract_id, MetaType::with(1), &meta_value(b"forged")));
+
+        // Forges the metadata value together with the metadata hash in the
+        // commitment, which changes the contract id
+        let mut forged = proof.clone();
+        forged.metadata = forged_metadata();
+        forged.commitment.metadata = forged.metadata.commit_id();
+        assert!(!forged.verify(contract_id, MetaType::with(1), &meta_value(b"forged")));
+
+        // Forges the genesis timestamp, which changes the contract id
+        let mut forged = proof.clone();
+        let TypeCommitment::Genesis(base) = &mut forged.commitment.op_type else {
+            unreachable!("metadata proofs commit to genesis")
+        };
+        base.timestamp += 1;
+        assert!(!forged.verify(contract_id, MetaType::with(1), &meta_value(b"ticker")));
+
+        // Forges the operation type, presenting a state transition as genesis
+        let mut forged = proof;
+        forged.commitment.op_type =
+            TypeCommitment::Transition(contract_id, TransitionType::with(1));
+        assert!(!forged.verify(contract_id, MetaType::with(1), &meta_value(b"ticker")));
+    }
+
+    #[test]
+    fn metadata_proof_encoding() {
+        let genesis = genesis();
+        let proof = genesis.prove_metadata_field(MetaType::with(2)).unwrap();
+
+        let data = proof.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let decoded = MetadataProof::from_strict_serialized::<{ usize::MAX }>(data).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(
+            genesis.contract_id(),
+            MetaType::with(2),
+            &meta_value(&[0xaa, 0xbb])
+        ));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&proof).unwrap();
+            let decoded: MetadataProof = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, proof);
+        }
+    }
+}
@@ -0,0 +1,486 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test vectors pinning the commit encoding of consensus-critical data.
+//!
+//! [`Metadata`], [`Assignments`] and whole operations (genesis, state
+//! transitions and extensions) participate in the operation id through their
+//! strict encoding; an accidental change of the map
+//! ordering or of a value encoding silently forks consensus. Each vector
+//! consists of the strict-serialized sample (`<name>.hex`) and of the digest
+//! by which the sample enters the operation commitment (`<name>.id`).
+//!
+//! Seal types are covered as well, since they are persisted by wallets as a
+//! part of consignments and stashes; their vectors have no commitment file.
+//!
+//! Vectors may be (re)generated only with the `rgbcore-vectors` binary, which
+//! refuses to change existing vectors unless the `--force` flag is given.
+
+use std::fmt::Debug;
+use std::path::Path;
+use std::{fs, io};
+
+use amplify::confinement::Confined;
+use amplify::hex::{FromHex, ToHex};
+use bp::seals::txout::{BlindSeal, CloseMethod, TxPtr};
+use bp::{Txid, Vout};
+use commit_verify::{CommitId, MerkleHash};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::{
+    AltLayer1, AltLayer1Set, AssetTag, AssetTags, Assign, AssignFungible, AssignRights,
+    AssignmentType, Assignments, BlindingFactor, ContractId, Extension, ExtensionType, Ffv,
+    FungibleState, Genesis, GenesisSeal, GraphSeal, Identity, MetaType, Metadata, OpId, Operation,
+    Redeemed, RevealedValue, SchemaId, SecretSeal, Transition, TransitionType, TypedAssigns,
+    Valencies, ValencyType, VoidState, WitnessOrd, WitnessPos, XChain, XGenesisSeal, XGraphSeal,
+    XWitnessId,
+};
+
+/// Path to the test vectors directory, relative to the crate root.
+pub const VECTORS_DIR: &str = "fixtures/commit";
+
+const BLINDING: u64 = 0x0102030405060708;
+
+/// Error detected by [`verify_all_vectors`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum VectorError {
+    /// test vector '{0}' is absent or incomplete (both `.hex` and `.id` files
+    /// are required for data having a commitment).
+    Absent(&'static str),
+
+    /// test vector '{0}' is not a valid hex string.
+    Hex(&'static str),
+
+    /// test vector '{0}' can't be decoded by the current version of the
+    /// library. Details: {1}
+    Decode(&'static str, String),
+
+    /// test vector '{name}' decodes into {found} instead of {expected}.
+    Value {
+        name: &'static str,
+        expected: String,
+        found: String,
+    },
+
+    /// strict encoding of test vector '{name}' has changed: it was {expected},
+    /// but now it is {found}.
+    Layout {
+        name: &'static str,
+        expected: String,
+        found: String,
+    },
+
+    /// commitment to test vector '{name}' has changed: it was {expected}, but
+    /// now it is {found}.
+    Commitment {
+        name: &'static str,
+        expected: String,
+        found: String,
+    },
+}
+
+/// Consensus-critical data covered by the test vectors.
+pub trait CommitVector: StrictSerialize + StrictDeserialize + Eq + Debug {
+    /// Returns hex-encoded digest under which the data enter the operation
+    /// commitment, or `None` if the data are committed only as a part of other
+    /// data.
+    fn commitment(&self) -> Option<String> { None }
+}
+
+impl CommitVector for Metadata {
+    fn commitment(&self) -> Option<String> { Some(self.commit_id().to_hex()) }
+}
+
+impl CommitVector for Assignments<GraphSeal> {
+    fn commitment(&self) -> Option<String> { Some(MerkleHash::merklize(self).to_hex()) }
+}
+
+impl CommitVector for Genesis {
+    fn commitment(&self) -> Option<String> { Some(self.id().to_hex()) }
+}
+
+impl CommitVector for Transition {
+    fn commitment(&self) -> Option<String> { Some(self.id().to_hex()) }
+}
+
+impl CommitVector for Extension {
+    fn commitment(&self) -> Option<String> { Some(self.id().to_hex()) }
+}
+
+impl CommitVector for XGraphSeal {}
+
+impl CommitVector for XGenesisSeal {}
+
+impl CommitVector for XChain<SecretSeal> {}
+
+impl CommitVector for WitnessOrd {}
+
+impl CommitVector for XWitnessId {}
+
+/// Test vector produced by the current version of the library.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Sample {
+    /// Name of the vector, used as the file stem.
+    pub name: &'static str,
+    /// Hex-encoded strict serialization of the sample.
+    pub data: String,
+    /// Hex-encoded commitment to the sample, if the sample has one.
+    pub commitment: Option<String>,
+}
+
+fn txid() -> Txid {
+    Txid::from_hex("1111111111111111111111111111111111111111111111111111111111111111")
+        .expect("hardcoded txid")
+}
+
+fn metadata(values: &[(u16, &[u8])]) -> Metadata {
+    Metadata::try_from_iter(
+        values
+            .iter()
+            .map(|(ty, value)| (MetaType::with(*ty), value)),
+    )
+    .expect("valid metadata")
+}
+
+fn assignments() -> Assignments<GraphSeal> {
+    let revealed = AssignRights::revealed(
+        XChain::Bitcoin(BlindSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+            blinding: BLINDING,
+        }),
+        VoidState::default(),
+    );
+    let concealed = Assign::ConfidentialSeal {
+        seal: XChain::Bitcoin(SecretSeal::from([0x22; 32])),
+        state: VoidState::default(),
+        lock: default!(),
+    };
+    let rights = TypedAssigns::Declarative(
+        Confined::try_from(vec![revealed, concealed]).expect("two assignments"),
+    );
+    Assignments::from(
+        Confined::try_from(bmap! { AssignmentType::with(1) => rights }).expect("one type"),
+    )
+}
+
+/// Fungible assignments: the first one is revealed, while the rest have
+/// concealed seals.
+fn fungible_assignments(values: &[FungibleState]) -> Assignments<GraphSeal> {
+    let state = |value: FungibleState| {
+        let blinding = BlindingFactor::try_from([0x01; 32]).expect("hardcoded blinding");
+        RevealedValue::with_blinding(value, blinding, AssetTag::from([0x55; 32]))
+    };
+    let assigns = values.iter().enumerate().map(|(no, value)| {
+        if no == 0 {
+            return AssignFungible::revealed(
+                XChain::Bitcoin(BlindSeal {
+                    method: CloseMethod::TapretFirst,
+                    txid: TxPtr::Txid(txid()),
+                    vout: Vout::from(2),
+                    blinding: BLINDING,
+                }),
+                state(*value),
+            );
+        }
+        Assign::ConfidentialSeal {
+            seal: XChain::Bitcoin(SecretSeal::from([0x11 * (no as u8 + 1); 32])),
+            state: state(*value),
+            lock: default!(),
+        }
+    });
+    let fungible =
+        TypedAssigns::Fungible(Confined::try_from_iter(assigns).expect("few assignments"));
+    Assignments::from(
+        Confined::try_from(bmap! { AssignmentType::with(2) => fungible }).expect("one type"),
+    )
+}
+
+fn transition() -> Transition {
+    Transition {
+        ffv: Ffv::default(),
+        contract_id: ContractId::from([0x33; 32]),
+        transition_type: TransitionType::with(1),
+        schema_id: None,
+        metadata: metadata(&[(1, &[1, 2, 3])]),
+        globals: default!(),
+        inputs: default!(),
+        assignments: assignments(),
+        valencies: default!(),
+        validator: default!(),
+        witness: default!(),
+    }
+}
+
+fn bound_transition() -> Transition {
+    let mut transition = transition();
+    transition.bind_schema(SchemaId::from([0x44; 32]));
+    transition
+}
+
+fn genesis_assignments() -> Assignments<GenesisSeal> {
+    let rights = AssignRights::revealed(
+        XChain::Bitcoin(BlindSeal {
+            method: CloseMethod::TapretFirst,
+            txid: txid(),
+            vout: Vout::from(2),
+            blinding: BLINDING,
+        }),
+        VoidState::default(),
+    );
+    let rights =
+        TypedAssigns::Declarative(Confined::try_from(vec![rights]).expect("one assignment"));
+    Assignments::from(
+        Confined::try_from(bmap! { AssignmentType::with(1) => rights }).expect("one type"),
+    )
+}
+
+fn valencies() -> Valencies {
+    Valencies::from(Confined::try_from(bset! { ValencyType::with(1) }).expect("one valency"))
+}
+
+fn genesis() -> Genesis {
+    Genesis {
+        ffv: Ffv::default(),
+        schema_id: SchemaId::from([0x44; 32]),
+        flags: default!(),
+        timestamp: 1713571767,
+        issuer: Identity::default(),
+        testnet: true,
+        alt_layers1: AltLayer1Set::from(
+            Confined::try_from(bset! { AltLayer1::Liquid }).expect("one layer"),
+        ),
+        asset_tags: AssetTags::from(
+            Confined::try_from(bmap! { AssignmentType::with(2) => AssetTag::from([0x55; 32]) })
+                .expect("one asset tag"),
+        ),
+        metadata: metadata(&[(1, &[1, 2, 3])]),
+        globals: default!(),
+        assignments: genesis_assignments(),
+        valencies: valencies(),
+        validator: default!(),
+    }
+}
+
+fn extension() -> Extension {
+    Extension {
+        ffv: Ffv::default(),
+        contract_id: ContractId::from([0x33; 32]),
+        extension_type: ExtensionType::with(1),
+        metadata: metadata(&[(1, &[1, 2, 3])]),
+        globals: default!(),
+        assignments: genesis_assignments(),
+        redeemed: Redeemed::from(
+            Confined::try_from(bmap! { ValencyType::with(1) => OpId::from([0x66; 32]) })
+                .expect("one redeemed valency"),
+        ),
+        valencies: valencies(),
+        validator: default!(),
+        witness: default!(),
+    }
+}
+
+fn sample<T: CommitVector>(name: &'static str, sample: &T) -> Sample {
+    let data = sample
+        .to_strict_serialized::<{ usize::MAX }>()
+        .expect("test vector sample must be serializable")
+        .to_hex();
+    Sample {
+        name,
+        data,
+        commitment: sample.commitment(),
+    }
+}
+
+fn read(path: &Path) -> Result<Option<String>, io::Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(Some(s.trim().to_owned())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn check<T: CommitVector>(dir: &Path, name: &'static str, sample: T) -> Result<(), VectorError> {
+    let data = read(&dir.join(format!("{name}.hex")))
+        .ok()
+        .flatten()
+        .ok_or(VectorError::Absent(name))?;
+    let bytes = Vec::<u8>::from_hex(&data).map_err(|_| VectorError::Hex(name))?;
+    let bytes = Confined::try_from(bytes).expect("test vector exceeds usize::MAX");
+    let decoded = T::from_strict_serialized::<{ usize::MAX }>(bytes)
+        .map_err(|err| VectorError::Decode(name, err.to_string()))?;
+    if decoded != sample {
+        return Err(VectorError::Value {
+            name,
+            expected: format!("{sample:?}"),
+            found: format!("{decoded:?}"),
+        });
+    }
+    let current = self::sample(name, &sample);
+    if current.data != data {
+        return Err(VectorError::Layout {
+            name,
+            expected: data,
+            found: current.data,
+        });
+    }
+    let Some(found) = current.commitment else {
+        return Ok(());
+    };
+    let commitment = read(&dir.join(format!("{name}.id")))
+        .ok()
+        .flatten()
+        .ok_or(VectorError::Absent(name))?;
+    if found != commitment {
+        return Err(VectorError::Commitment {
+            name,
+            expected: commitment,
+            found,
+        });
+    }
+    Ok(())
+}
+
+macro_rules! vectors {
+    ($($(#[$attr:meta])* $name:ident => $sample:expr),+ $(,)?) => {
+        /// Returns all test vectors as produced by the current version of the
+        /// library.
+        // Samples may be feature-gated, so they can't be listed in `vec![]`
+        #[allow(clippy::vec_init_then_push)]
+        pub fn samples() -> Vec<Sample> {
+            let mut samples = vec![];
+            $(
+                $(#[$attr])*
+                samples.push(sample(stringify!($name), &$sample));
+            )+
+            samples
+        }
+
+        /// Verifies all test vectors stored in the `dir`, checking that they
+        /// decode into the samples, and that the samples are still encoded and
+        /// committed to in the same way.
+        ///
+        /// # Errors
+        ///
+        /// If any of the vectors is absent, can't be decoded, decodes into a
+        /// different value, or if the sample encoding or commitment has
+        /// changed.
+        pub fn verify_all_vectors(dir: impl AsRef<Path>) -> Result<(), VectorError> {
+            let dir = dir.as_ref();
+            $(
+                $(#[$attr])*
+                check(dir, stringify!($name), $sample)?;
+            )+
+            Ok(())
+        }
+    };
+}
+
+vectors! {
+    metadata_empty => Metadata::default(),
+    metadata_single => metadata(&[(1, &[1, 2, 3])]),
+    metadata_unordered => metadata(&[(16, b"rgb"), (2, &[0xaa])]),
+    assignments_empty => Assignments::<GraphSeal>::default(),
+    assignments_declarative => assignments(),
+    // Wide fungible types must not change encoding and commitments of 64-bit
+    // values.
+    assignments_fungible => fungible_assignments(&[
+        FungibleState::Bits64(1_000_000),
+        FungibleState::Bits64(u64::MAX),
+    ]),
+    #[cfg(feature = "wide-fungible")]
+    assignments_fungible_wide => fungible_assignments(&[
+        FungibleState::Bits128(u64::MAX as u128 + 1),
+        FungibleState::SignedBits64(-2),
+        FungibleState::SignedBits128(-1),
+    ]),
+    genesis => genesis(),
+    // Transitions which are not bound to a schema keep the original encoding
+    // and ids.
+    transition => transition(),
+    transition_bound => bound_transition(),
+    extension => extension(),
+    // Seals are persisted by wallets, thus their encoding must not change.
+    xgraph_seal_bitcoin => XGraphSeal::Bitcoin(BlindSeal {
+        method: CloseMethod::TapretFirst,
+        txid: TxPtr::Txid(txid()),
+        vout: Vout::from(2),
+        blinding: BLINDING,
+    }),
+    xgraph_seal_liquid_witness => XGraphSeal::Liquid(BlindSeal {
+        method: CloseMethod::OpretFirst,
+        txid: TxPtr::WitnessTx,
+        vout: Vout::from(1),
+        blinding: BLINDING,
+    }),
+    xgenesis_seal_bitcoin => XGenesisSeal::Bitcoin(BlindSeal {
+        method: CloseMethod::TapretFirst,
+        txid: txid(),
+        vout: Vout::from(2),
+        blinding: BLINDING,
+    }),
+    xsecret_seal_bitcoin => XChain::Bitcoin(SecretSeal::from([0x22; 32])),
+    witness_ord_on_chain => WitnessOrd::OnChain(
+        WitnessPos::new(840000, 1713571767).expect("valid witness position"),
+    ),
+    witness_ord_off_chain => WitnessOrd::OffChain,
+    xwitness_id_bitcoin => XWitnessId::Bitcoin(txid()),
+    xwitness_id_liquid => XWitnessId::Liquid(txid()),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTORS_DIR);
+        if let Err(err) = verify_all_vectors(dir) {
+            panic!("consensus encoding is broken: {err}");
+        }
+    }
+
+    #[test]
+    fn metadata_order() {
+        let direct = metadata(&[(2, &[0xaa]), (16, b"rgb")]);
+        let reversed = metadata(&[(16, b"rgb"), (2, &[0xaa])]);
+        assert_eq!(sample("direct", &direct).data, sample("reversed", &reversed).data);
+        assert_eq!(direct.commitment(), reversed.commitment());
+    }
+
+    #[test]
+    fn absent_commitment() {
+        let dir = std::env::temp_dir().join(format!("rgbcore-vectors-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let sample = sample("metadata_single", &metadata(&[(1, &[1, 2, 3])]));
+        fs::write(dir.join("metadata_single.hex"), &sample.data).unwrap();
+        let res = check(&dir, "metadata_single", metadata(&[(1, &[1, 2, 3])]));
+        assert_eq!(res, Err(VectorError::Absent("metadata_single")));
+
+        fs::write(dir.join("metadata_single.id"), sample.commitment.unwrap()).unwrap();
+        let res = check(&dir, "metadata_single", metadata(&[(1, &[1, 2, 3])]));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(res, Ok(()));
+    }
+}
@@ -0,0 +1,493 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Harness checking that concealment of operations and their assignments is
+//! reversible and doesn't affect their commitments.
+//!
+//! Each new kind of state risks breaking these invariants; the hand-rolled
+//! deterministic generator ([`Gen`]) produces random assignments, typed
+//! assignments and small state transitions, which can be checked with
+//! [`assert_conceal_roundtrip`] and [`assert_commitment_invariance`]. The
+//! harness is exported under the `test-utils` feature such that schema
+//! implementors may reuse it.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use amplify::confinement::{Confined, SmallBlob, SmallVec};
+use amplify::ByteArray;
+use bp::seals::txout::{CloseMethod, TxPtr};
+use bp::{Txid, Vout};
+use commit_verify::{Conceal, MerkleHash};
+use secp256k1_zkp::rand::{Error, RngCore};
+use strict_encoding::{StrictDumb, StrictSerialize};
+
+use crate::{
+    AssetTag, Assign, AssignmentType, Assignments, AttachId, ConcealedAttach, ConcealedData,
+    ConcealedValue, ContractId, ExposedSeal, ExposedState, Extension, Genesis, GenesisSeal,
+    GraphSeal, MediaType, OpId, Operation, RevealSeals, RevealState, RevealedAttach, RevealedData,
+    RevealedValue, SecretSeal, StateProvider, StateType, Transition, TransitionType, TypedAssigns,
+    VoidState, XChain, XGraphSeal,
+};
+
+/// Deterministic pseudo-random generator of test data (SplitMix64), such that
+/// a failing case can be reproduced from its seed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Gen(u64);
+
+impl RngCore for Gen {
+    fn next_u32(&mut self) -> u32 { (self.next_u64() >> 32) as u32 }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl Gen {
+    pub fn new(seed: u64) -> Self { Gen(seed) }
+
+    fn below(&mut self, max: u64) -> u64 { self.next_u64() % max }
+
+    fn bytes32(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    fn method(&mut self) -> CloseMethod {
+        match self.below(2) {
+            0 => CloseMethod::TapretFirst,
+            _ => CloseMethod::OpretFirst,
+        }
+    }
+
+    fn layer1<Seal>(&mut self, seal: Seal) -> XChain<Seal> {
+        match self.below(4) {
+            0 => XChain::Liquid(seal),
+            _ => XChain::Bitcoin(seal),
+        }
+    }
+
+    /// Generates graph seal, which may be defined relative to the witness
+    /// transaction.
+    pub fn graph_seal(&mut self) -> XChain<GraphSeal> {
+        let txid = match self.below(4) {
+            0 => TxPtr::WitnessTx,
+            _ => TxPtr::Txid(Txid::from(self.bytes32())),
+        };
+        let seal = GraphSeal {
+            method: self.method(),
+            txid,
+            vout: Vout::from(self.below(16) as u32),
+            blinding: self.next_u64(),
+        };
+        self.layer1(seal)
+    }
+
+    /// Generates genesis seal.
+    pub fn genesis_seal(&mut self) -> XChain<GenesisSeal> {
+        let seal = GenesisSeal {
+            method: self.method(),
+            txid: Txid::from(self.bytes32()),
+            vout: Vout::from(self.below(16) as u32),
+            blinding: self.next_u64(),
+        };
+        self.layer1(seal)
+    }
+
+    /// Generates fungible state with a random blinding factor.
+    pub fn value(&mut self) -> RevealedValue {
+        let value = self.next_u64();
+        let tag = AssetTag::from_byte_array(self.bytes32());
+        RevealedValue::with_rng(value, self, tag)
+    }
+
+    /// Generates structured state of up to 8 bytes with a random salt.
+    pub fn data(&mut self) -> RevealedData {
+        let len = self.below(9) as usize;
+        let mut data = vec![0u8; len];
+        self.fill_bytes(&mut data);
+        let data = SmallBlob::try_from(data).expect("small data");
+        RevealedData::with_rng(data, self)
+    }
+
+    /// Generates attachment state of a random size with a random salt.
+    pub fn attach(&mut self) -> RevealedAttach {
+        const MEDIA: [MediaType; 4] =
+            [MediaType::Text, MediaType::Image, MediaType::Application, MediaType::Any];
+        let id = AttachId::from(self.bytes32());
+        let media_type = MEDIA[self.below(4) as usize];
+        let size = self.next_u64();
+        RevealedAttach::with_rng(id, media_type, size, self)
+    }
+
+    /// Generates assignment of the `state` to a random seal, concealing either
+    /// the seal, the state, both or none of them.
+    pub fn assign<State: ExposedState, Seal: GenSeal>(
+        &mut self,
+        state: State,
+    ) -> Assign<State, Seal> {
+        let assign = Assign::revealed(Seal::generate(self), state);
+        match self.below(4) {
+            0 => assign,
+            1 => assign.conceal_seal(),
+            2 => assign.conceal_state(),
+            _ => assign.conceal(),
+        }
+    }
+
+    fn assigns<State: ExposedState, Seal: GenSeal>(
+        &mut self,
+        state: impl Fn(&mut Self) -> State,
+    ) -> SmallVec<Assign<State, Seal>> {
+        let len = 1 + self.below(4) as usize;
+        let assigns = (0..len)
+            .map(|_| {
+                let state = state(self);
+                self.assign(state)
+            })
+            .collect::<Vec<_>>();
+        Confined::try_from(assigns).expect("small number of assignments")
+    }
+
+    /// Generates from one to four assignments of the given state type.
+    pub fn typed_assigns<Seal: GenSeal>(&mut self, state_type: StateType) -> TypedAssigns<Seal> {
+        match state_type {
+            StateType::Void => TypedAssigns::Declarative(self.assigns(|_| VoidState::default())),
+            StateType::Fungible => TypedAssigns::Fungible(self.assigns(Self::value)),
+            StateType::Structured => TypedAssigns::Structured(self.assigns(Self::data)),
+            StateType::Attachment => TypedAssigns::Attachment(self.assigns(Self::attach)),
+        }
+    }
+
+    /// Generates from one to four assignment types, each having state of a
+    /// random type.
+    pub fn assignments<Seal: GenSeal>(&mut self) -> Assignments<Seal> {
+        const STATE_TYPES: [StateType; 4] =
+            [StateType::Void, StateType::Fungible, StateType::Structured, StateType::Attachment];
+        let len = 1 + self.below(4) as u16;
+        let assignments = (0..len)
+            .map(|ty| {
+                let state_type = STATE_TYPES[self.below(4) as usize];
+                (AssignmentType::with(ty), self.typed_assigns(state_type))
+            })
+            .collect::<BTreeMap<_, _>>();
+        Assignments::from(Confined::try_from(assignments).expect("tiny number of types"))
+    }
+
+    /// Generates state transition with random assignments.
+    pub fn transition(&mut self) -> Transition {
+        Transition {
+            contract_id: ContractId::from(self.bytes32()),
+            transition_type: TransitionType::with(self.below(4) as u16),
+            assignments: self.assignments(),
+            ..Transition::strict_dumb()
+        }
+    }
+}
+
+/// Seals which can be produced by [`Gen`].
+pub trait GenSeal: ExposedSeal {
+    fn generate(gen: &mut Gen) -> XChain<Self>;
+}
+
+impl GenSeal for GraphSeal {
+    fn generate(gen: &mut Gen) -> XChain<Self> { gen.graph_seal() }
+}
+
+impl GenSeal for GenesisSeal {
+    fn generate(gen: &mut Gen) -> XChain<Self> { gen.genesis_seal() }
+}
+
+/// Openings of the revealed seals and state of a node, which are known before
+/// the node is concealed.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Openings {
+    pub seals: BTreeMap<XChain<SecretSeal>, XGraphSeal>,
+    pub values: Vec<RevealedValue>,
+    pub data: Vec<RevealedData>,
+    pub attach: Vec<RevealedAttach>,
+}
+
+impl StateProvider for Openings {
+    fn revealed_value(&self, concealed: &ConcealedValue) -> Option<RevealedValue> {
+        self.values
+            .iter()
+            .find(|value| value.conceal() == *concealed)
+            .cloned()
+    }
+
+    fn revealed_data(&self, concealed: &ConcealedData) -> Option<RevealedData> {
+        self.data
+            .iter()
+            .find(|data| data.conceal() == *concealed)
+            .cloned()
+    }
+
+    fn revealed_attach(&self, concealed: &ConcealedAttach) -> Option<RevealedAttach> {
+        self.attach
+            .iter()
+            .find(|attach| attach.conceal() == *concealed)
+            .cloned()
+    }
+}
+
+impl Openings {
+    fn collect<State: ExposedState, Seal: ExposedSeal>(
+        &mut self,
+        assigns: &[Assign<State, Seal>],
+        mut push_state: impl FnMut(&mut Self, &State),
+    ) where
+        XChain<Seal>: Into<XGraphSeal>,
+    {
+        for assign in assigns {
+            if let Some(seal) = assign.revealed_seal() {
+                self.seals
+                    .insert(assign.to_confidential_seal(), seal.into());
+            }
+            if let Some(state) = assign.as_revealed_state() {
+                push_state(self, state);
+            }
+        }
+    }
+
+    fn extend<Seal: ExposedSeal>(&mut self, assigns: &TypedAssigns<Seal>)
+    where XChain<Seal>: Into<XGraphSeal> {
+        match assigns {
+            TypedAssigns::Declarative(a) => self.collect(a, |_, _| {}),
+            TypedAssigns::Fungible(a) => self.collect(a, |o, s| o.values.push(*s)),
+            TypedAssigns::Structured(a) => self.collect(a, |o, s| o.data.push(s.clone())),
+            TypedAssigns::Attachment(a) => self.collect(a, |o, s| o.attach.push(s.clone())),
+        }
+    }
+
+    fn with_assignments<Seal: ExposedSeal>(assignments: &Assignments<Seal>) -> Self
+    where XChain<Seal>: Into<XGraphSeal> {
+        let mut openings = Openings::default();
+        for assigns in assignments.values() {
+            openings.extend(assigns);
+        }
+        openings
+    }
+}
+
+/// Data which can be concealed and revealed back, checked by the harness.
+pub trait ConcealNode: Clone + Debug + StrictSerialize + RevealSeals + RevealState {
+    /// Commitment which must not be affected by concealment.
+    type Commitment: Eq + Debug;
+
+    /// Returns openings for all revealed seals and state.
+    fn openings(&self) -> Openings;
+
+    /// Conceals all seals and state.
+    fn conceal_all(&mut self);
+
+    fn commitment(&self) -> Self::Commitment;
+}
+
+impl<Seal: ExposedSeal> ConcealNode for TypedAssigns<Seal>
+where XChain<Seal>: Into<XGraphSeal> + TryFrom<XGraphSeal>
+{
+    type Commitment = MerkleHash;
+
+    fn openings(&self) -> Openings {
+        let mut openings = Openings::default();
+        openings.extend(self);
+        openings
+    }
+
+    fn conceal_all(&mut self) { *self = self.conceal(); }
+
+    fn commitment(&self) -> MerkleHash {
+        let assignments = bmap! { AssignmentType::with(0) => self.clone() };
+        MerkleHash::merklize(&Assignments::from(
+            Confined::try_from(assignments).expect("single assignment type"),
+        ))
+    }
+}
+
+impl<Seal: ExposedSeal> ConcealNode for Assignments<Seal>
+where XChain<Seal>: Into<XGraphSeal> + TryFrom<XGraphSeal>
+{
+    type Commitment = MerkleHash;
+
+    fn openings(&self) -> Openings { Openings::with_assignments(self) }
+
+    fn conceal_all(&mut self) { self.keyed_values_mut().for_each(|(_, a)| *a = a.conceal()); }
+
+    fn commitment(&self) -> MerkleHash { MerkleHash::merklize(self) }
+}
+
+macro_rules! impl_conceal_node {
+    ($($ty:ty),+) => {
+        $(
+            impl ConcealNode for $ty {
+                type Commitment = OpId;
+
+                fn openings(&self) -> Openings { Openings::with_assignments(&self.assignments) }
+
+                fn conceal_all(&mut self) { *self = self.conceal(); }
+
+                fn commitment(&self) -> OpId { self.id() }
+            }
+        )+
+    };
+}
+
+impl_conceal_node!(Genesis, Transition, Extension);
+
+fn serialize(node: &impl StrictSerialize) -> Vec<u8> {
+    node.to_strict_serialized::<{ usize::MAX }>()
+        .expect("node must be serializable")
+        .into_inner()
+}
+
+fn reveal<N: ConcealNode>(node: &mut N, openings: &Openings) {
+    node.reveal_seals(&openings.seals);
+    node.reveal_state(openings);
+}
+
+/// Asserts that concealing all seals and state of the `node` and revealing
+/// them back from the openings known before the concealment restores the
+/// original node.
+///
+/// Void state carries no data, thus [`RevealState`] always reveals it; the
+/// original node is normalized in the same way before the comparison.
+///
+/// # Panics
+///
+/// If the restored node differs from the original.
+pub fn assert_conceal_roundtrip<N: ConcealNode>(node: &N) {
+    let openings = node.openings();
+    let mut original = node.clone();
+    original.reveal_state(&openings);
+
+    let mut restored = node.clone();
+    restored.conceal_all();
+    reveal(&mut restored, &openings);
+    assert_eq!(
+        serialize(&restored),
+        serialize(&original),
+        "concealed node {node:?} is not restored by revealing it back: {restored:?}"
+    );
+}
+
+/// Asserts that neither concealment nor revealing of the `node` affects its
+/// commitment.
+///
+/// # Panics
+///
+/// If the commitment of the concealed or revealed node differs from the
+/// commitment of the original.
+pub fn assert_commitment_invariance<N: ConcealNode>(node: &N) {
+    let commitment = node.commitment();
+    let openings = node.openings();
+
+    let mut concealed = node.clone();
+    concealed.conceal_all();
+    assert_eq!(concealed.commitment(), commitment, "concealment of {node:?} changes commitment");
+
+    reveal(&mut concealed, &openings);
+    assert_eq!(concealed.commitment(), commitment, "revealing of {node:?} changes commitment");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEEDS: u64 = 64;
+    const STATE_TYPES: [StateType; 4] =
+        [StateType::Void, StateType::Fungible, StateType::Structured, StateType::Attachment];
+
+    #[test]
+    fn typed_assigns() {
+        for seed in 0..SEEDS {
+            let mut gen = Gen::new(seed);
+            for state_type in STATE_TYPES {
+                let assigns = gen.typed_assigns::<GraphSeal>(state_type);
+                assert_eq!(assigns.state_type(), state_type);
+                assert_conceal_roundtrip(&assigns);
+                assert_commitment_invariance(&assigns);
+
+                let assigns = gen.typed_assigns::<GenesisSeal>(state_type);
+                assert_conceal_roundtrip(&assigns);
+                assert_commitment_invariance(&assigns);
+            }
+        }
+    }
+
+    #[test]
+    fn assignments() {
+        for seed in 0..SEEDS {
+            let assignments = Gen::new(seed).assignments::<GraphSeal>();
+            assert_conceal_roundtrip(&assignments);
+            assert_commitment_invariance(&assignments);
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        for seed in 0..SEEDS {
+            let transition = Gen::new(seed).transition();
+            assert_conceal_roundtrip(&transition);
+            assert_commitment_invariance(&transition);
+        }
+    }
+
+    #[test]
+    fn genesis_and_extensions() {
+        for seed in 0..SEEDS {
+            let mut gen = Gen::new(seed);
+            let mut genesis = Genesis::strict_dumb();
+            genesis.assignments = gen.assignments();
+            assert_conceal_roundtrip(&genesis);
+            assert_commitment_invariance(&genesis);
+
+            let mut extension = Extension::strict_dumb();
+            extension.assignments = gen.assignments();
+            assert_conceal_roundtrip(&extension);
+            assert_commitment_invariance(&extension);
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(Gen::new(7).transition(), Gen::new(7).transition());
+        assert_ne!(Gen::new(7).transition().id(), Gen::new(8).transition().id());
+    }
+}
@@ -23,21 +23,24 @@
 //! Extraction of contract state.
 
 use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::Hash;
 use std::num::ParseIntError;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
 use amplify::confinement::{LargeOrdMap, LargeOrdSet, SmallVec, TinyOrdMap};
-use amplify::hex;
-use strict_encoding::{StrictDecode, StrictDumb, StrictEncode};
+use amplify::{hex, ByteArray, Bytes32, Wrapper};
+use baid64::{Baid64ParseError, DisplayBaid64, FromBaid64Str};
+use bp::secp256k1::rand::{thread_rng, RngCore};
+use commit_verify::{DigestExt, Sha256};
+use strict_encoding::{StrictDecode, StrictDeserialize, StrictDumb, StrictEncode, StrictSerialize};
 
 use crate::{
-    Assign, AssignmentType, Assignments, AssignmentsRef, ContractId, DataState, ExposedSeal,
-    ExposedState, Extension, Genesis, GlobalStateType, OpId, Operation, RevealedAttach,
-    RevealedData, RevealedValue, Schema, SchemaId, Transition, TypedAssigns, VoidState,
-    WitnessAnchor, XChain, XOutputSeal, XWitnessId, LIB_NAME_RGB,
+    impl_serde_baid64, Assign, AssignmentType, Assignments, AssignmentsRef, ContractId, DataState,
+    ExposedSeal, ExposedState, Extension, Genesis, GlobalStateType, OpId, Operation,
+    RevealedAttach, RevealedData, RevealedValue, Schema, SchemaId, Transition, TypedAssigns,
+    VoidState, WitnessAnchor, XChain, XOutputSeal, XWitnessId, LIB_NAME_RGB,
 };
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
@@ -92,6 +95,75 @@ impl FromStr for Opout {
     }
 }
 
+/// Blinded reference to an operation output, which allows a receiver to refer
+/// to an existing allocation (for instance, in an invoice) without disclosing
+/// which contract output it is.
+///
+/// This is a companion structure which doesn't participate in any of the
+/// consensus commitments.
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref, BorrowSlice, Hex, Index, RangeOps)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+pub struct BlindedOpout(
+    #[from]
+    #[from([u8; 32])]
+    Bytes32,
+);
+
+impl StrictSerialize for BlindedOpout {}
+impl StrictDeserialize for BlindedOpout {}
+
+impl BlindedOpout {
+    pub const TAG: &'static str = "urn:lnp-bp:rgb:opout-blinded#2024-07-10";
+
+    /// Blinds the operation output with the provided `blinding` factor.
+    pub fn new(opout: Opout, blinding: u64) -> Self {
+        let mut hasher = Sha256::from_tag(Self::TAG);
+        hasher.input_raw(&opout.op.to_byte_array());
+        hasher.input_raw(&opout.ty.to_le_bytes());
+        hasher.input_raw(&opout.no.to_le_bytes());
+        hasher.input_raw(&blinding.to_le_bytes());
+        BlindedOpout(Bytes32::from_byte_array(hasher.finish()))
+    }
+
+    /// Blinds the operation output with a random blinding factor.
+    ///
+    /// # Returns
+    ///
+    /// Blinded output together with the blinding factor, which must be kept
+    /// by the receiver for the later verification.
+    pub fn with_random_blinding(opout: Opout) -> (Self, u64) {
+        let blinding = thread_rng().next_u64();
+        (Self::new(opout, blinding), blinding)
+    }
+
+    /// Verifies that the blinded output references `opout` under the given
+    /// `blinding` factor.
+    pub fn verify(&self, opout: Opout, blinding: u64) -> bool {
+        *self == Self::new(opout, blinding)
+    }
+}
+
+impl DisplayBaid64 for BlindedOpout {
+    const HRI: &'static str = "rgb:opout";
+    const CHUNKING: bool = true;
+    const PREFIX: bool = true;
+    const EMBED_CHECKSUM: bool = false;
+    const MNEMONIC: bool = false;
+    fn to_baid64_payload(&self) -> [u8; 32] { self.to_byte_array() }
+}
+impl FromBaid64Str for BlindedOpout {}
+impl FromStr for BlindedOpout {
+    type Err = Baid64ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid64_str(s) }
+}
+impl Display for BlindedOpout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { self.fmt_baid64(f) }
+}
+
+impl_serde_baid64!(BlindedOpout);
+
 /// Trait used by contract state. Unlike [`ExposedState`] it doesn't allow
 /// concealment of the state, i.e. may contain incomplete data without blinding
 /// factors, asset tags etc.
@@ -181,7 +253,8 @@ impl<State: KnownState> OutputAssignment<State> {
     /// # Panics
     ///
     /// If the processing is done on invalid stash data, the seal is
-    /// witness-based and the anchor chain doesn't match the seal chain.
+    /// witness-based and the anchor chain doesn't match the seal chain, or the
+    /// seal is defined on a layer 1 which doesn't provide a closing method.
     pub fn with_witness<Seal: ExposedSeal>(
         seal: XChain<Seal>,
         witness_id: XWitnessId,
@@ -194,7 +267,7 @@ impl<State: KnownState> OutputAssignment<State> {
             opout: Opout::new(opid, ty, no),
             seal: seal.try_to_output_seal(witness_id).expect(
                 "processing contract from unverified/invalid stash: witness seal chain doesn't \
-                 match anchor's chain",
+                 match anchor's chain or seal has no closing method",
             ),
             state,
             witness: witness_id.into(),
@@ -204,7 +277,8 @@ impl<State: KnownState> OutputAssignment<State> {
     /// # Panics
     ///
     /// If the processing is done on invalid stash data, the seal is
-    /// witness-based and the anchor chain doesn't match the seal chain.
+    /// witness-based, or the seal is defined on a layer 1 which doesn't provide
+    /// a closing method.
     pub fn with_no_witness<Seal: ExposedSeal>(
         seal: XChain<Seal>,
         state: State,
@@ -216,15 +290,14 @@ impl<State: KnownState> OutputAssignment<State> {
             opout: Opout::new(opid, ty, no),
             seal: seal.to_output_seal().expect(
                 "processing contract from unverified/invalid stash: seal must have txid \
-                 information since it comes from genesis or extension",
+                 information since it comes from genesis or extension, and a closing method",
             ),
             state,
             witness: AssignmentWitness::Absent,
         }
     }
 
-    pub fn transmute<S: KnownState>(self) -> OutputAssignment<S>
-    where S: From<State> {
+    pub fn transmute<S: KnownState + From<State>>(self) -> OutputAssignment<S> {
         OutputAssignment {
             opout: self.opout,
             seal: self.seal,
@@ -509,3 +582,71 @@ impl ContractState {
         SmallVec::try_from_iter(iter).expect("same size as previous confined collection")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn opouts() -> Vec<Opout> {
+        let ops = [OpId::from([0x11; 32]), OpId::from([0x12; 32])];
+        ops.iter()
+            .flat_map(|op| {
+                (1..3u16).flat_map(move |ty| {
+                    (0..3u16).map(move |no| Opout::new(*op, AssignmentType::with(ty), no))
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blinded_opout_verify() {
+        let opouts = opouts();
+        for opout in &opouts {
+            let (blinded, blinding) = BlindedOpout::with_random_blinding(*opout);
+            assert!(blinded.verify(*opout, blinding));
+            assert!(!blinded.verify(*opout, blinding.wrapping_add(1)));
+            // Different outputs never verify under the same blinding
+            for other in opouts.iter().filter(|other| *other != opout) {
+                assert!(!blinded.verify(*other, blinding));
+            }
+        }
+    }
+
+    #[test]
+    fn blinded_opout_collisions() {
+        let opouts = opouts();
+        let blindings = [0u64, 1, 0xba5e, u64::MAX];
+        let blinded = opouts
+            .iter()
+            .flat_map(|opout| blindings.iter().map(|b| BlindedOpout::new(*opout, *b)))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(blinded.len(), opouts.len() * blindings.len());
+
+        // Output type and number are not interchangeable
+        let op = OpId::from([0x11; 32]);
+        assert_ne!(
+            BlindedOpout::new(Opout::new(op, AssignmentType::with(1), 2), 0),
+            BlindedOpout::new(Opout::new(op, AssignmentType::with(2), 1), 0)
+        );
+    }
+
+    #[test]
+    fn blinded_opout_encoding() {
+        let blinded = BlindedOpout::new(opouts()[0], 0xba5e);
+        let s = blinded.to_string();
+        assert!(s.starts_with("rgb:opout:"));
+        assert_eq!(BlindedOpout::from_str(&s).unwrap(), blinded);
+
+        let data = blinded.to_strict_serialized::<32>().unwrap();
+        assert_eq!(BlindedOpout::from_strict_serialized::<32>(data).unwrap(), blinded);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&blinded).unwrap();
+            assert_eq!(json, format!("\"{s}\""));
+            assert_eq!(serde_json::from_str::<BlindedOpout>(&json).unwrap(), blinded);
+        }
+    }
+}
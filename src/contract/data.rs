@@ -23,12 +23,13 @@
 use core::fmt::{self, Debug, Formatter};
 use std::cmp::Ordering;
 
-use amplify::confinement::SmallBlob;
+use amplify::confinement::{SmallBlob, U16};
 use amplify::hex::ToHex;
 use amplify::{Bytes32, Wrapper};
 use bp::secp256k1::rand::{random, Rng, RngCore};
 use commit_verify::{CommitId, CommitmentId, Conceal, DigestExt, Sha256};
-use strict_encoding::{StrictSerialize, StrictType};
+use strict_encoding::{DeserializeError, StrictDeserialize, StrictSerialize, StrictType};
+use strict_types::{SemId, TypeSystem};
 
 use super::{ConfidentialState, ExposedState};
 use crate::{ConcealedState, RevealedState, StateType, LIB_NAME_RGB};
@@ -69,6 +70,51 @@ impl From<RevealedData> for DataState {
     fn from(data: RevealedData) -> Self { data.value }
 }
 
+/// structured state data of {0} bytes exceeds the maximum of 65535 bytes.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct DataTooLarge(pub usize);
+
+impl DataState {
+    /// Constructs state data from the provided bytes, checking that they fit
+    /// the consensus size limit.
+    pub fn try_from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, DataTooLarge> {
+        let bytes = bytes.as_ref();
+        SmallBlob::try_from_iter(bytes.iter().copied())
+            .map(Self)
+            .map_err(|_| DataTooLarge(bytes.len()))
+    }
+}
+
+impl TryFrom<Vec<u8>> for DataState {
+    type Error = DataTooLarge;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        let len = bytes.len();
+        SmallBlob::try_from(bytes)
+            .map(Self)
+            .map_err(|_| DataTooLarge(len))
+    }
+}
+
+impl TryFrom<&[u8]> for DataState {
+    type Error = DataTooLarge;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> { Self::try_from_bytes(bytes) }
+}
+
+/// Errors decoding structured state data into a typed value.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum TypedDataError {
+    /// structured state data don't match semantic type {0}: {1}
+    TypeMismatch(SemId, String),
+
+    /// unable to decode structured state data: {0}
+    #[from]
+    Decode(DeserializeError),
+}
+
 #[cfg(feature = "serde")]
 mod _serde {
     use amplify::hex::FromHex;
@@ -122,6 +168,62 @@ impl RevealedData {
             salt,
         }
     }
+
+    /// Constructs new state from the provided bytes using random blinding
+    /// factor.
+    ///
+    /// # Errors
+    ///
+    /// If the data exceed the consensus size limit of 65535 bytes.
+    pub fn try_from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, DataTooLarge> {
+        DataState::try_from_bytes(bytes).map(Self::new_random_salt)
+    }
+
+    /// Constructs new state from the strict serialization of `value` using
+    /// random blinding factor.
+    ///
+    /// # Errors
+    ///
+    /// If the serialized data exceed the consensus size limit of 65535 bytes.
+    pub fn from_typed<T: StrictSerialize>(value: &T) -> Result<Self, DataTooLarge> {
+        let data = value
+            .to_strict_serialized::<{ usize::MAX }>()
+            .expect("in-memory serialization without size limit can't fail");
+        DataState::try_from(data.unbox()).map(Self::new_random_salt)
+    }
+
+    /// Decodes the state data into a value of type `T` using strict encoding.
+    ///
+    /// The data are not checked against the semantic type of `T`; use
+    /// [`RevealedData::checked_to_typed`] to do that.
+    ///
+    /// # Errors
+    ///
+    /// If the data can't be decoded into `T`, or are not entirely consumed by
+    /// the decoding.
+    pub fn to_typed<T: StrictDeserialize>(&self) -> Result<T, DeserializeError> {
+        T::from_strict_serialized::<U16>(self.value.to_inner())
+    }
+
+    /// Decodes the state data into a value of type `T` after checking that
+    /// the data are a valid strict serialization of the semantic type
+    /// `sem_id` from the type system `types`, matching the check performed
+    /// by the validator for the structured state declared by the schema.
+    ///
+    /// # Errors
+    ///
+    /// If the data don't match the semantic type or can't be decoded into
+    /// `T`.
+    pub fn checked_to_typed<T: StrictDeserialize>(
+        &self,
+        sem_id: SemId,
+        types: &TypeSystem,
+    ) -> Result<T, TypedDataError> {
+        types
+            .strict_deserialize_type(sem_id, self.value.as_ref())
+            .map_err(|err| TypedDataError::TypeMismatch(sem_id, err.to_string()))?;
+        self.to_typed().map_err(TypedDataError::from)
+    }
 }
 
 impl ExposedState for RevealedData {
@@ -190,3 +292,107 @@ impl From<Sha256> for ConcealedData {
 impl CommitmentId for ConcealedData {
     const TAG: &'static str = "urn:lnp-bp:rgb:state-data#2024-02-12";
 }
+
+#[cfg(test)]
+mod test {
+    use strict_encoding::StrictDumb;
+    use strict_types::typelib::LibBuilder;
+    use strict_types::SystemBuilder;
+
+    use super::*;
+
+    const LIB_NAME_TEST: &str = "DataTest";
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_TEST)]
+    struct Amount {
+        value: u64,
+        precision: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_TEST)]
+    struct Entry {
+        amount: Amount,
+        flags: u16,
+    }
+    impl StrictSerialize for Entry {}
+    impl StrictDeserialize for Entry {}
+
+    fn entry() -> Entry {
+        Entry {
+            amount: Amount {
+                value: 100_000_000,
+                precision: 8,
+            },
+            flags: 0x0301,
+        }
+    }
+
+    #[test]
+    fn data_size_limit() {
+        let max = vec![0xA5u8; u16::MAX as usize];
+        let data = RevealedData::try_from_bytes(&max).unwrap();
+        assert_eq!(data.value.as_slice(), max.as_slice());
+        assert_eq!(DataState::try_from(max.clone()).unwrap(), data.value);
+        assert_eq!(DataState::try_from(max.as_slice()).unwrap(), data.value);
+
+        let over = vec![0xA5u8; u16::MAX as usize + 1];
+        assert_eq!(RevealedData::try_from_bytes(&over), Err(DataTooLarge(over.len())));
+        assert_eq!(DataState::try_from(over.clone()), Err(DataTooLarge(over.len())));
+        assert_eq!(DataState::try_from_bytes(&over), Err(DataTooLarge(u16::MAX as usize + 1)));
+        assert_eq!(
+            DataTooLarge(over.len()).to_string(),
+            "structured state data of 65536 bytes exceeds the maximum of 65535 bytes."
+        );
+
+        assert!(DataState::try_from_bytes(b"").unwrap().is_empty());
+    }
+
+    #[test]
+    fn typed_roundtrip() {
+        let data = RevealedData::from_typed(&entry()).unwrap();
+        assert_eq!(data.value.len(), 8 + 1 + 2);
+        assert_eq!(data.to_typed::<Entry>().unwrap(), entry());
+
+        let lib = LibBuilder::new(libname!(LIB_NAME_TEST), None)
+            .transpile::<Entry>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("DataTest.Entry").unwrap();
+        assert_eq!(
+            data.checked_to_typed::<Entry>(sem_id, sys.as_types())
+                .unwrap(),
+            entry()
+        );
+
+        let too_large = DataState::try_from(vec![0u8; u16::MAX as usize]).unwrap();
+        assert_eq!(RevealedData::from_typed(&too_large), Err(DataTooLarge(u16::MAX as usize + 2)));
+    }
+
+    #[test]
+    fn typed_decode_failure() {
+        let mut bytes = RevealedData::from_typed(&entry()).unwrap().value.to_vec();
+        let short = RevealedData::try_from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(matches!(short.to_typed::<Entry>(), Err(DeserializeError::Decode(_))));
+
+        bytes.push(0);
+        let long = RevealedData::try_from_bytes(&bytes).unwrap();
+        assert!(matches!(long.to_typed::<Entry>(), Err(DeserializeError::DataNotEntirelyConsumed)));
+
+        // Data aren't decoded when the semantic type is not known
+        let data = RevealedData::from_typed(&entry()).unwrap();
+        let sem_id = SemId::strict_dumb();
+        assert!(matches!(
+            data.checked_to_typed::<Entry>(sem_id, &TypeSystem::default()),
+            Err(TypedDataError::TypeMismatch(id, _)) if id == sem_id
+        ));
+    }
+}
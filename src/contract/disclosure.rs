@@ -0,0 +1,361 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Disclosure of concealed assignments to a third party.
+//!
+//! Wallets exchange [`Disclosure`]s out-of-band to reveal seals and state of
+//! the assignments which are concealed in the operations known to the
+//! counterparty. Each [`Opening`] is checked to match the concealed data
+//! committed to by the operation before being applied.
+
+use std::collections::BTreeMap;
+
+use amplify::confinement::{self, MediumVec};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::{
+    AssignmentIndex, Assignments, ContractId, ExposedSeal, Extension, Genesis, OpId, Operation,
+    Opout, RevealError, RevealedState, Transition, XChain, XGraphSeal, LIB_NAME_RGB,
+};
+
+/// Opening of a concealed assignment, disclosing its seal, its state, or both.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct Opening {
+    pub opout: Opout,
+    pub seal: Option<XGraphSeal>,
+    pub state: Option<RevealedState>,
+}
+
+/// Container of the openings for the concealed assignments of a contract.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct Disclosure {
+    pub contract_id: ContractId,
+    pub openings: MediumVec<Opening>,
+}
+
+impl StrictSerialize for Disclosure {}
+impl StrictDeserialize for Disclosure {}
+
+/// Errors applying a [`Disclosure`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum DisclosureError {
+    /// operation {0} doesn't belong to the disclosed contract.
+    ContractMismatch(OpId),
+
+    /// openings {invalid:?} don't match the operation data; {applied} other
+    /// openings were applied.
+    InvalidOpenings {
+        applied: usize,
+        invalid: BTreeMap<Opout, RevealError>,
+    },
+}
+
+impl Disclosure {
+    pub fn new(contract_id: ContractId) -> Self {
+        Disclosure {
+            contract_id,
+            openings: empty!(),
+        }
+    }
+
+    /// Adds an opening to the disclosure.
+    pub fn push(&mut self, opening: Opening) -> Result<(), confinement::Error> {
+        self.openings.push(opening)
+    }
+
+    /// Reveals the disclosed seals and state in the operations from `ops`,
+    /// indexed by their ids. Openings for operations absent from `ops` are
+    /// ignored.
+    ///
+    /// # Returns
+    ///
+    /// Number of applied openings.
+    ///
+    /// # Errors
+    ///
+    /// If any of the operations belongs to a different contract, nothing is
+    /// applied. Openings which don't match the concealed data of the
+    /// operation are skipped and reported in
+    /// [`DisclosureError::InvalidOpenings`], while the rest of the openings
+    /// are still applied.
+    pub fn apply<Op: RevealOpening>(
+        &self,
+        ops: &mut BTreeMap<OpId, Op>,
+    ) -> Result<usize, DisclosureError> {
+        for opening in &self.openings {
+            let id = opening.opout.op;
+            if matches!(ops.get(&id), Some(op) if op.contract_id() != self.contract_id) {
+                return Err(DisclosureError::ContractMismatch(id));
+            }
+        }
+        let mut applied = 0usize;
+        let mut invalid = BTreeMap::new();
+        for opening in &self.openings {
+            let Some(op) = ops.get_mut(&opening.opout.op) else {
+                continue;
+            };
+            match op.reveal_opening(opening) {
+                Ok(()) => applied += 1,
+                Err(err) => {
+                    invalid.insert(opening.opout, err);
+                }
+            }
+        }
+        if invalid.is_empty() {
+            Ok(applied)
+        } else {
+            Err(DisclosureError::InvalidOpenings { applied, invalid })
+        }
+    }
+}
+
+/// Operation which concealed assignments can be revealed with an [`Opening`].
+pub trait RevealOpening: Operation {
+    /// Reveals the assignment referenced by the `opening`. The opening must
+    /// reference an assignment of this operation.
+    ///
+    /// # Errors
+    ///
+    /// If the assignment is absent, or its concealed data don't match the
+    /// opening, in which case the operation is left unmodified.
+    fn reveal_opening(&mut self, opening: &Opening) -> Result<(), RevealError>;
+}
+
+fn reveal_opening<Seal: ExposedSeal>(
+    assignments: &mut Assignments<Seal>,
+    opening: &Opening,
+) -> Result<(), RevealError>
+where
+    XChain<Seal>: TryFrom<XGraphSeal>,
+{
+    let seal = opening
+        .seal
+        .map(|seal| XChain::<Seal>::try_from(seal).map_err(|_| RevealError::InvalidSeal))
+        .transpose()?;
+    let index = AssignmentIndex::new(opening.opout.ty, opening.opout.no);
+    assignments.reveal_at(index, seal, opening.state.as_ref())
+}
+
+impl RevealOpening for Genesis {
+    fn reveal_opening(&mut self, opening: &Opening) -> Result<(), RevealError> {
+        if opening.opout.op != self.id() {
+            return Err(RevealError::UnknownAssignment);
+        }
+        reveal_opening(&mut self.assignments, opening)
+    }
+}
+
+impl RevealOpening for Transition {
+    fn reveal_opening(&mut self, opening: &Opening) -> Result<(), RevealError> {
+        if opening.opout.op != self.id() {
+            return Err(RevealError::UnknownAssignment);
+        }
+        reveal_opening(&mut self.assignments, opening)
+    }
+}
+
+impl RevealOpening for Extension {
+    fn reveal_opening(&mut self, opening: &Opening) -> Result<(), RevealError> {
+        if opening.opout.op != self.id() {
+            return Err(RevealError::UnknownAssignment);
+        }
+        reveal_opening(&mut self.assignments, opening)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::Confined;
+    use amplify::ByteArray;
+    use bp::seals::txout::{CloseMethod, TxPtr};
+    use bp::{Txid, Vout};
+    use commit_verify::Conceal;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::{AssetTag, AssignFungible, AssignmentType, GraphSeal, RevealedValue, TypedAssigns};
+
+    const TY: AssignmentType = AssignmentType::with(2);
+
+    fn tag() -> AssetTag { AssetTag::from_byte_array([1u8; 32]) }
+
+    fn seal(vout: u32) -> XGraphSeal {
+        XChain::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0xba5e + vout as u64,
+            txid: TxPtr::Txid(Txid::from([0x11; 32])),
+            vout: Vout::from(vout),
+        })
+    }
+
+    fn setup() -> (Transition, Disclosure) {
+        let mut transition = Transition::strict_dumb();
+        transition.contract_id = ContractId::from([0x33; 32]);
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                TY => TypedAssigns::Fungible(
+                    Confined::try_from_iter((0..3u32).map(|vout| {
+                        let value = RevealedValue::new_random_blinding(10u64 + vout as u64, tag());
+                        AssignFungible::revealed(seal(vout), value)
+                    }))
+                    .unwrap(),
+                )
+            })
+            .unwrap(),
+        );
+        let id = transition.id();
+        let assigns = transition.assignments.get(&TY).unwrap();
+        let mut disclosure = Disclosure::new(transition.contract_id);
+        for no in 0..3u16 {
+            disclosure
+                .push(Opening {
+                    opout: Opout::new(id, TY, no),
+                    seal: assigns.revealed_seal_at(no).unwrap(),
+                    state: assigns.revealed_state_at(no).unwrap(),
+                })
+                .unwrap();
+        }
+        (transition, disclosure)
+    }
+
+    #[test]
+    fn disclosure_apply() {
+        let (transition, disclosure) = setup();
+        let id = transition.id();
+        let mut ops = bmap! { id => transition.conceal() };
+        // Assignments compare by their commitments, so revealed data are checked
+        // explicitly
+        let revealed = |op: &Transition| {
+            let assigns = op.assignments.get(&TY).unwrap();
+            (0..3u16).all(|no| {
+                assigns.revealed_seal_at(no).unwrap().is_some() &&
+                    assigns.revealed_state_at(no).unwrap().is_some()
+            })
+        };
+        assert!(!revealed(&ops[&id]));
+        assert_eq!(disclosure.apply(&mut ops), Ok(3));
+        assert!(revealed(&ops[&id]));
+        assert_eq!(ops[&id], transition);
+        assert_eq!(ops[&id].id(), id);
+        // Already revealed data are left as is
+        assert_eq!(disclosure.apply(&mut ops), Ok(3));
+        assert_eq!(ops[&id], transition);
+
+        // Openings for unknown operations are ignored
+        assert_eq!(disclosure.apply(&mut BTreeMap::<OpId, Transition>::new()), Ok(0));
+
+        let mut other = transition.conceal();
+        other.contract_id = ContractId::from([0x44; 32]);
+        let other_id = other.id();
+        let mut foreign = disclosure.clone();
+        foreign.openings = Confined::try_from_iter(disclosure.openings.iter().map(|opening| {
+            let mut opening = opening.clone();
+            opening.opout.op = other_id;
+            opening
+        }))
+        .unwrap();
+        let mut ops = bmap! { other_id => other.clone() };
+        assert_eq!(foreign.apply(&mut ops), Err(DisclosureError::ContractMismatch(other_id)));
+        assert_eq!(ops[&other_id], other);
+    }
+
+    #[test]
+    fn disclosure_tampered() {
+        let (transition, disclosure) = setup();
+        let id = transition.id();
+
+        let mut tampered = disclosure.clone();
+        let mut openings = tampered.openings.into_inner();
+        openings[1].state =
+            Some(RevealedState::Fungible(RevealedValue::new_random_blinding(11u64, tag())));
+        openings[2].seal = Some(seal(7));
+        tampered.openings = Confined::try_from(openings).unwrap();
+
+        let mut ops = bmap! { id => transition.conceal() };
+        assert_eq!(
+            tampered.apply(&mut ops),
+            Err(DisclosureError::InvalidOpenings {
+                applied: 1,
+                invalid: bmap! {
+                    Opout::new(id, TY, 1) => RevealError::StateMismatch,
+                    Opout::new(id, TY, 2) => RevealError::SealMismatch,
+                },
+            })
+        );
+        // Only the valid opening is applied, and tampered ones are not applied
+        // even partially
+        let assigns = ops[&id].assignments.get(&TY).unwrap();
+        let original = transition.assignments.get(&TY).unwrap();
+        assert_eq!(assigns.as_fungible()[0], original.as_fungible()[0]);
+        for no in 1..3 {
+            assert_eq!(assigns.revealed_seal_at(no).unwrap(), None);
+            assert_eq!(assigns.revealed_state_at(no).unwrap(), None);
+        }
+        assert_eq!(ops[&id].id(), id);
+
+        let mut tampered = disclosure.clone();
+        let mut openings = tampered.openings.into_inner();
+        openings[0].state = Some(RevealedState::Void);
+        openings[1].opout.no = 5;
+        tampered.openings = Confined::try_from(openings).unwrap();
+        let mut ops = bmap! { id => transition.conceal() };
+        assert_eq!(
+            tampered.apply(&mut ops),
+            Err(DisclosureError::InvalidOpenings {
+                applied: 1,
+                invalid: bmap! {
+                    Opout::new(id, TY, 0) => RevealError::StateMismatch,
+                    Opout::new(id, TY, 5) => RevealError::UnknownAssignment,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn disclosure_encoding() {
+        let (_, disclosure) = setup();
+        let data = disclosure.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(Disclosure::from_strict_serialized::<{ usize::MAX }>(data).unwrap(), disclosure);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&disclosure).unwrap();
+            let decoded: Disclosure = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, disclosure);
+        }
+    }
+}
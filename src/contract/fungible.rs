@@ -114,6 +114,21 @@ pub enum FungibleState {
     #[from]
     #[strict_type(tag = 8)] // Matches strict types U64 primitive value
     Bits64(u64),
+
+    /// 128-bit value.
+    #[cfg(feature = "wide-fungible")]
+    #[strict_type(tag = 0x10)] // Matches strict types U128 primitive value
+    Bits128(u128),
+
+    /// Signed 64-bit value.
+    #[cfg(feature = "wide-fungible")]
+    #[strict_type(tag = 0x48)] // Matches strict types I64 primitive value
+    SignedBits64(i64),
+
+    /// Signed 128-bit value.
+    #[cfg(feature = "wide-fungible")]
+    #[strict_type(tag = 0x50)] // Matches strict types I128 primitive value
+    SignedBits128(i128),
     // When/if adding more variants do not forget to re-write FromStr impl
 }
 
@@ -127,14 +142,17 @@ impl From<RevealedValue> for FungibleState {
 
 impl FromStr for FungibleState {
     type Err = ParseIntError;
+
+    /// Parses 64-bit unsigned value. Use [`FungibleState::from_str_typed`] to
+    /// parse values of other types defined by a schema.
     fn from_str(s: &str) -> Result<Self, Self::Err> { s.parse().map(FungibleState::Bits64) }
 }
 
-impl From<FungibleState> for u64 {
-    fn from(value: FungibleState) -> Self {
-        match value {
-            FungibleState::Bits64(val) => val,
-        }
+impl TryFrom<FungibleState> for u64 {
+    type Error = OutOfU64Range;
+
+    fn try_from(value: FungibleState) -> Result<Self, Self::Error> {
+        value.to_u64().ok_or(OutOfU64Range)
     }
 }
 
@@ -142,10 +160,63 @@ impl FungibleState {
     pub fn fungible_type(&self) -> schema::FungibleType {
         match self {
             FungibleState::Bits64(_) => schema::FungibleType::Unsigned64Bit,
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::Bits128(_) => schema::FungibleType::Unsigned128Bit,
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::SignedBits64(_) => schema::FungibleType::Signed64Bit,
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::SignedBits128(_) => schema::FungibleType::Signed128Bit,
+        }
+    }
+
+    /// Parses the value of the given fungible type.
+    #[cfg(feature = "wide-fungible")]
+    pub fn from_str_typed(s: &str, ty: schema::FungibleType) -> Result<Self, ParseIntError> {
+        match ty {
+            schema::FungibleType::Unsigned64Bit => s.parse().map(FungibleState::Bits64),
+            schema::FungibleType::Unsigned128Bit => s.parse().map(FungibleState::Bits128),
+            schema::FungibleType::Signed64Bit => s.parse().map(FungibleState::SignedBits64),
+            schema::FungibleType::Signed128Bit => s.parse().map(FungibleState::SignedBits128),
+        }
+    }
+
+    /// Returns the value as a 64-bit unsigned integer.
+    ///
+    /// # Panics
+    ///
+    /// If the value is negative or exceeds `u64::MAX`, which is possible only
+    /// for the types provided by the `wide-fungible` feature.
+    #[deprecated(since = "0.11.0", note = "use FungibleState::to_u64, which doesn't panic")]
+    pub fn as_u64(&self) -> u64 {
+        self.to_u64()
+            .expect("fungible value doesn't fit into 64-bit unsigned integer")
+    }
+
+    pub fn is_zero(&self) -> bool { self.to_u64() == Some(0) }
+
+    /// Returns the value as a 64-bit unsigned integer, if it fits into it.
+    pub fn to_u64(&self) -> Option<u64> {
+        match *self {
+            FungibleState::Bits64(val) => Some(val),
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::Bits128(val) => u64::try_from(val).ok(),
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::SignedBits64(val) => u64::try_from(val).ok(),
+            #[cfg(feature = "wide-fungible")]
+            FungibleState::SignedBits128(val) => u64::try_from(val).ok(),
         }
     }
 
-    pub fn as_u64(&self) -> u64 { (*self).into() }
+    /// Returns the absolute value and whether the value is negative.
+    #[cfg(feature = "wide-fungible")]
+    fn magnitude(&self) -> (u128, bool) {
+        match *self {
+            FungibleState::Bits64(val) => (val as u128, false),
+            FungibleState::Bits128(val) => (val, false),
+            FungibleState::SignedBits64(val) => (val.unsigned_abs() as u128, val < 0),
+            FungibleState::SignedBits128(val) => (val.unsigned_abs(), val < 0),
+        }
+    }
 }
 
 /// value provided for a blinding factor overflows prime field order for
@@ -155,6 +226,67 @@ impl FungibleState {
 #[from(secp256k1_zkp::UpstreamError)]
 pub struct InvalidFieldElement;
 
+/// fungible value doesn't fit into 64-bit unsigned integer.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct OutOfU64Range;
+
+/// zero value is not allowed for the fungible state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct ZeroValue;
+
+/// total supply of the fungible state overflows 64-bit unsigned integer.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct SupplyOverflow;
+
+/// Overflow-checked accumulator of fungible values, used to compute total
+/// supply of the fungible state issued by a contract.
+///
+/// Once an addition overflows, the accumulator remains overflown: all further
+/// additions fail, and the total saturates at `u64::MAX`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct FungibleAccumulator {
+    total: u64,
+    overflown: bool,
+}
+
+impl FungibleAccumulator {
+    pub fn new() -> Self { Self::default() }
+
+    /// Adds `value` to the total.
+    ///
+    /// # Errors
+    ///
+    /// If the total overflows 64-bit unsigned integer now or has overflown
+    /// before.
+    pub fn add(&mut self, value: u64) -> Result<(), SupplyOverflow> {
+        if self.overflown {
+            return Err(SupplyOverflow);
+        }
+        match self.total.checked_add(value) {
+            Some(total) => {
+                self.total = total;
+                Ok(())
+            }
+            None => {
+                self.total = u64::MAX;
+                self.overflown = true;
+                Err(SupplyOverflow)
+            }
+        }
+    }
+
+    /// Returns the total of the added values, or `None` if it has overflown.
+    pub fn total(&self) -> Option<u64> { (!self.overflown).then_some(self.total) }
+
+    /// Returns the total of the added values, saturating at `u64::MAX`.
+    pub fn saturating_total(&self) -> u64 { self.total }
+
+    pub fn is_overflown(&self) -> bool { self.overflown }
+}
+
 /// Errors parsing string representation of a blinding factor.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
@@ -193,6 +325,13 @@ impl Deref for BlindingFactor {
     fn deref(&self) -> &Self::Target { self.0.as_inner() }
 }
 
+// Blinding factors are `Copy` and can't be zeroized on drop, thus they have to
+// be wiped explicitly once not needed.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for BlindingFactor {
+    fn zeroize(&mut self) { amplify::WrapperMut::as_inner_mut(&mut self.0).zeroize(); }
+}
+
 impl ToHex for BlindingFactor {
     fn to_hex(&self) -> String { self.0.to_hex() }
 }
@@ -323,6 +462,29 @@ impl RevealedValue {
             tag,
         }
     }
+
+    /// Constructs new state for schemata which don't allow zero values (see
+    /// [`schema::ValueBounds::allows_zero`]).
+    ///
+    /// # Errors
+    ///
+    /// If the value is zero.
+    pub fn non_zero(
+        value: impl Into<FungibleState>,
+        blinding: BlindingFactor,
+        tag: AssetTag,
+    ) -> Result<Self, ZeroValue> {
+        let value = value.into();
+        if value.is_zero() {
+            return Err(ZeroValue);
+        }
+        Ok(Self::with_blinding(value, blinding, tag))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for RevealedValue {
+    fn zeroize(&mut self) { self.blinding.zeroize(); }
 }
 
 impl ExposedState for RevealedValue {
@@ -389,18 +551,140 @@ impl StrictDecode for PedersenCommitment {
     }
 }
 
+impl PedersenCommitment {
+    /// Verifies that the sum of the `inputs` commitments is equal to the sum of
+    /// the `outputs` commitments, i.e. that the committed values are conserved.
+    pub fn verify_sum(inputs: &[PedersenCommitment], outputs: &[PedersenCommitment]) -> bool {
+        let inputs = inputs.iter().map(|c| c.0).collect::<Vec<_>>();
+        let outputs = outputs.iter().map(|c| c.0).collect::<Vec<_>>();
+        secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &inputs, &outputs)
+    }
+}
+
 impl CommitVerify<RevealedValue, UntaggedProtocol> for PedersenCommitment {
     fn commit(revealed: &RevealedValue) -> Self {
         use secp256k1_zkp::{Generator, Tag, Tweak};
 
-        let blinding = Tweak::from_inner(revealed.blinding.0.into_inner())
-            .expect("type guarantees of BlindingFactor are broken");
-        let FungibleState::Bits64(value) = revealed.value;
-
         let tag = Tag::from(revealed.tag.to_byte_array());
         let generator = Generator::new_unblinded(SECP256K1, tag);
 
-        secp256k1_zkp::PedersenCommitment::new(SECP256K1, value, blinding, generator).into()
+        match revealed.value {
+            FungibleState::Bits64(value) => {
+                let blinding = Tweak::from_inner(revealed.blinding.0.into_inner())
+                    .expect("type guarantees of BlindingFactor are broken");
+                secp256k1_zkp::PedersenCommitment::new(SECP256K1, value, blinding, generator).into()
+            }
+            #[cfg(feature = "wide-fungible")]
+            value => wide::commit(value, revealed.blinding, generator).into(),
+        }
+    }
+}
+
+/// Pedersen commitments to the values which are negative or exceed 64 bits.
+///
+/// Secp256k1-zkp commits only to 64-bit unsigned values, thus the magnitude
+/// of a wide value is split into 64-bit limbs `lo + hi * 2^64`, committed to
+/// with the asset generator `A` and with `A * 2^64`. The limb commitments are
+/// then added into a single commitment `r * G + v * A`, which keeps the
+/// commitments to wide values homomorphic with the commitments to 64-bit
+/// values of the same asset, and leaves the encoding of the concealed state
+/// unchanged.
+///
+/// The library doesn't expose group operations on the commitments, so the
+/// points are added as public keys. Commitment and generator encodings store
+/// in their first bit whether the y coordinate is a quadratic residue, which
+/// is flipped by the point negation, while public keys store the y parity
+/// instead. Candidate sums are thus checked with the commitment sum
+/// verification.
+#[cfg(feature = "wide-fungible")]
+mod wide {
+    use secp256k1_zkp::{
+        Generator, PedersenCommitment, PublicKey, SecretKey, Tweak, SECP256K1, ZERO_TWEAK,
+    };
+
+    use super::{BlindingFactor, FungibleState};
+
+    pub fn commit(
+        value: FungibleState,
+        blinding: BlindingFactor,
+        generator: Generator,
+    ) -> PedersenCommitment {
+        let (magnitude, negative) = value.magnitude();
+        // Negative value is committed as the negation of the commitment to its
+        // magnitude with the negated blinding factor:
+        // -(-r * G + |v| * A) = r * G + v * A
+        let mut blinding = SecretKey::from(blinding);
+        if negative {
+            blinding = blinding.negate();
+        }
+        let blinding =
+            Tweak::from_inner(blinding.secret_bytes()).expect("secret key is always a valid tweak");
+
+        let lo = magnitude as u64;
+        let hi = (magnitude >> 64) as u64;
+        let mut commitment = PedersenCommitment::new(SECP256K1, lo, blinding, generator);
+        if hi != 0 {
+            let high = PedersenCommitment::new(SECP256K1, hi, ZERO_TWEAK, shifted(generator));
+            commitment = add(commitment, high);
+        }
+        if negative {
+            negate(commitment)
+        } else {
+            commitment
+        }
+    }
+
+    /// Computes generator `A * 2^64` for the high limbs.
+    fn shifted(generator: Generator) -> Generator {
+        let half = PedersenCommitment::new(SECP256K1, 1 << 63, ZERO_TWEAK, generator);
+        let point = add(half, half);
+        let mut data = point.serialize();
+        // Commitments use 0x08/0x09 prefixes, while generators - 0x0A/0x0B
+        data[0] += 2;
+        for _ in 0..2 {
+            let shifted = Generator::from_slice(&data).expect("valid curve point");
+            if PedersenCommitment::new(SECP256K1, 1, ZERO_TWEAK, shifted) == point {
+                return shifted;
+            }
+            data[0] ^= 1;
+        }
+        unreachable!("either the point or its negation is the shifted generator")
+    }
+
+    fn negate(commitment: PedersenCommitment) -> PedersenCommitment {
+        let mut data = commitment.serialize();
+        data[0] ^= 1;
+        PedersenCommitment::from_slice(&data).expect("negation of a valid commitment")
+    }
+
+    fn add(a: PedersenCommitment, b: PedersenCommitment) -> PedersenCommitment {
+        let key = |commitment: PedersenCommitment, parity: u8| {
+            let mut data = commitment.serialize();
+            data[0] = 0x02 | parity;
+            PublicKey::from_slice(&data).expect("commitment is a valid curve point")
+        };
+        let a_key = key(a, 0);
+        for parity in 0..2 {
+            // Fails if the points are negations of each other, in which case the
+            // other parity gives the sum
+            let Ok(sum) = a_key.combine(&key(b, parity)) else {
+                continue;
+            };
+            let mut data = sum.serialize();
+            for prefix in [0x08, 0x09] {
+                data[0] = prefix;
+                let Ok(candidate) = PedersenCommitment::from_slice(&data) else {
+                    continue;
+                };
+                if secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &[a, b], &[candidate])
+                {
+                    return candidate;
+                }
+            }
+        }
+        unreachable!(
+            "commitments may sum up to the point at infinity only with negligible probability"
+        )
     }
 }
 
@@ -505,23 +789,60 @@ impl CommitVerify<RevealedValue, PedersenProtocol> for ConcealedValue {
 
 /// Errors verifying range proofs.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 #[display(doc_comments)]
 pub enum RangeProofError {
     /// invalid blinding factor {0}.
     InvalidBlinding(BlindingFactor),
 
+    /// range proof is malformed and can't be parsed.
+    Malformed,
+
+    /// Pedersen commitment for the range proof is invalid.
+    InvalidCommitment,
+
+    /// range proof is well-formed, but its verification has failed.
+    VerificationFailed,
+
     /// bulletproofs verification is not implemented in RGB Core v0.10. Please
     /// update your software and try again, or ask your software producer to use
     /// latest RGB release.
     BulletproofsAbsent,
 }
 
+impl From<secp256k1_zkp::Error> for RangeProofError {
+    fn from(err: secp256k1_zkp::Error) -> Self {
+        match err {
+            secp256k1_zkp::Error::InvalidRangeProof => RangeProofError::Malformed,
+            secp256k1_zkp::Error::InvalidPedersenCommitment => RangeProofError::InvalidCommitment,
+            _ => RangeProofError::VerificationFailed,
+        }
+    }
+}
+
 impl ConcealedValue {
     /// Verifies validity of the range proof.
     pub fn verify_range_proof(&self) -> Result<bool, RangeProofError> {
         // We always fail here
         Err(RangeProofError::BulletproofsAbsent)
     }
+
+    /// Verifies range proofs of multiple concealed values at once, which is
+    /// faster than verifying each of them individually. Fails if any of the
+    /// proofs is invalid, without indicating which one.
+    pub fn verify_range_proofs<'a>(
+        values: impl IntoIterator<Item = &'a ConcealedValue>,
+    ) -> Result<bool, RangeProofError> {
+        match values.into_iter().next() {
+            None => Ok(true),
+            // We always fail here, until the bulletproofs library will be integrated
+            Some(_) => Err(RangeProofError::BulletproofsAbsent),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -633,4 +954,161 @@ mod test {
 
         assert!(secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &[a, b], &[c, d]))
     }
+
+    #[test]
+    fn range_proof_error_kinds() {
+        assert_eq!(
+            RangeProofError::from(secp256k1_zkp::Error::InvalidRangeProof),
+            RangeProofError::Malformed
+        );
+        assert_eq!(
+            RangeProofError::from(secp256k1_zkp::Error::InvalidPedersenCommitment),
+            RangeProofError::InvalidCommitment
+        );
+        assert_eq!(
+            RangeProofError::from(secp256k1_zkp::Error::CannotMakeRangeProof),
+            RangeProofError::VerificationFailed
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "wide-fungible")]
+    fn wide_types() {
+        use schema::FungibleType;
+
+        // Tags of the values must match the codes of their fungible types
+        assert_eq!(FungibleType::Unsigned64Bit as u8, 0x08);
+        assert_eq!(FungibleType::Unsigned128Bit as u8, 0x10);
+        assert_eq!(FungibleType::Signed64Bit as u8, 0x48);
+        assert_eq!(FungibleType::Signed128Bit as u8, 0x50);
+
+        // Parsing from string doesn't depend on the feature
+        assert_eq!(FungibleState::from_str("5"), Ok(FungibleState::Bits64(5)));
+        assert!(FungibleState::from_str("18446744073709551616").is_err());
+        assert!(FungibleState::from_str("-5").is_err());
+        assert_eq!(
+            FungibleState::from_str_typed("18446744073709551616", FungibleType::Unsigned128Bit),
+            Ok(FungibleState::Bits128(1 << 64))
+        );
+        assert_eq!(
+            FungibleState::from_str_typed("-5", FungibleType::Signed64Bit),
+            Ok(FungibleState::SignedBits64(-5))
+        );
+        assert_eq!(
+            FungibleState::from_str_typed("5", FungibleType::Signed128Bit),
+            Ok(FungibleState::SignedBits128(5))
+        );
+        assert!(FungibleState::from_str_typed("-5", FungibleType::Unsigned128Bit).is_err());
+        assert_eq!(FungibleState::SignedBits128(-7).to_string(), "-7");
+
+        assert_eq!(FungibleState::Bits128(5).to_u64(), Some(5));
+        assert_eq!(FungibleState::Bits128(1 << 64).to_u64(), None);
+        assert_eq!(FungibleState::SignedBits64(-1).to_u64(), None);
+        assert_eq!(u64::try_from(FungibleState::Bits128(5)), Ok(5));
+        assert_eq!(u64::try_from(FungibleState::SignedBits64(-1)), Err(OutOfU64Range));
+    }
+
+    #[test]
+    #[cfg(feature = "wide-fungible")]
+    fn wide_commitments() {
+        let blinding = BlindingFactor::random();
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let commit =
+            |value| PedersenCommitment::commit(&RevealedValue::with_blinding(value, blinding, tag));
+
+        // Values fitting into the low limb are committed as 64-bit values
+        let narrow = commit(FungibleState::Bits64(5));
+        assert_eq!(commit(FungibleState::Bits128(5)), narrow);
+        assert_eq!(commit(FungibleState::SignedBits64(5)), narrow);
+        assert_eq!(commit(FungibleState::SignedBits128(5)), narrow);
+
+        assert_ne!(commit(FungibleState::Bits128(1 << 64)), commit(FungibleState::Bits64(0)));
+        assert_ne!(commit(FungibleState::SignedBits64(-5)), narrow);
+    }
+
+    #[test]
+    #[cfg(feature = "wide-fungible")]
+    fn wide_commitment_sums() {
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let check = |inputs: &[FungibleState], outputs: &[FungibleState]| {
+            let in_blindings = inputs
+                .iter()
+                .map(|_| BlindingFactor::random())
+                .collect::<Vec<_>>();
+            let mut out_blindings = outputs
+                .iter()
+                .skip(1)
+                .map(|_| BlindingFactor::random())
+                .collect::<Vec<_>>();
+            let balance =
+                BlindingFactor::zero_balanced(in_blindings.clone(), out_blindings.clone()).unwrap();
+            out_blindings.insert(0, balance);
+            let commitments = |values: &[FungibleState], blindings: &[BlindingFactor]| {
+                values
+                    .iter()
+                    .zip(blindings)
+                    .map(|(value, blinding)| {
+                        PedersenCommitment::commit(&RevealedValue::with_blinding(
+                            *value, *blinding, tag,
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            PedersenCommitment::verify_sum(
+                &commitments(inputs, &in_blindings),
+                &commitments(outputs, &out_blindings),
+            )
+        };
+
+        // Carry between the limbs
+        let wide = FungibleState::Bits128(u64::MAX as u128 + 10);
+        assert!(check(&[wide], &[FungibleState::Bits64(u64::MAX), FungibleState::Bits64(10)]));
+        assert!(check(&[FungibleState::Bits64(u64::MAX), FungibleState::Bits64(10)], &[wide]));
+        assert!(!check(&[wide], &[FungibleState::Bits64(u64::MAX), FungibleState::Bits64(9)]));
+        let huge = FungibleState::Bits128(u128::MAX);
+        assert!(check(&[huge], &[
+            FungibleState::Bits128(u128::MAX - (1 << 100)),
+            FungibleState::Bits128(1 << 100)
+        ]));
+
+        // Signed adjustments
+        assert!(check(&[FungibleState::Bits64(100)], &[
+            FungibleState::SignedBits64(-20),
+            FungibleState::Bits64(120)
+        ]));
+        assert!(check(&[FungibleState::Bits64(1)], &[
+            FungibleState::SignedBits128(-(1 << 70)),
+            FungibleState::Bits128((1 << 70) + 1)
+        ]));
+        assert!(check(
+            &[FungibleState::SignedBits128(i128::MIN), FungibleState::Bits128(1 << 127)],
+            &[FungibleState::Bits64(0)]
+        ));
+        assert!(!check(&[FungibleState::Bits64(100)], &[
+            FungibleState::SignedBits64(20),
+            FungibleState::Bits64(120)
+        ]));
+    }
+
+    #[test]
+    fn supply_accumulation() {
+        let mut accumulator = FungibleAccumulator::new();
+        assert_eq!(accumulator.total(), Some(0));
+        accumulator.add(u64::MAX - 10).unwrap();
+        accumulator.add(10).unwrap();
+        assert_eq!(accumulator.total(), Some(u64::MAX));
+        assert_eq!(accumulator.saturating_total(), u64::MAX);
+        assert!(!accumulator.is_overflown());
+        // Adding zero to the maximum doesn't overflow
+        accumulator.add(0).unwrap();
+        assert_eq!(accumulator.total(), Some(u64::MAX));
+
+        assert_eq!(accumulator.add(1), Err(SupplyOverflow));
+        assert_eq!(accumulator.total(), None);
+        assert_eq!(accumulator.saturating_total(), u64::MAX);
+        assert!(accumulator.is_overflown());
+        // Overflown accumulator remains overflown
+        assert_eq!(accumulator.add(0), Err(SupplyOverflow));
+        assert_eq!(accumulator.total(), None);
+    }
 }
@@ -21,12 +21,22 @@
 // limitations under the License.
 
 use std::collections::btree_map;
+use std::fmt::{self, Display, Formatter};
+use std::str;
 
 use amplify::confinement::{SmallBlob, TinyOrdMap};
+use amplify::hex::ToHex;
 use amplify::{confinement, Wrapper};
 use commit_verify::StrictHash;
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
 
-use crate::{schema, LIB_NAME_RGB};
+use crate::{schema, Schema, LIB_NAME_RGB};
+
+/// Maximal number of value bytes shown when metadata are displayed as hex.
+const DISPLAY_MAX_BYTES: usize = 32;
+/// Maximal number of characters shown for decoded metadata values.
+const DISPLAY_MAX_CHARS: usize = 64;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error, From)]
 #[display(doc_comments)]
@@ -37,6 +47,28 @@ pub enum MetadataError {
     /// too many metadata values.
     #[from(confinement::Error)]
     TooManyValues,
+
+    /// metadata type #{0} is not present.
+    Absent(schema::MetaType),
+
+    /// value of metadata type #{ty} is {len} bytes long, while {expected} bytes
+    /// were expected.
+    InvalidLength {
+        ty: schema::MetaType,
+        expected: usize,
+        len: usize,
+    },
+
+    /// value of metadata type #{ty} is {len} bytes long, exceeding the limit
+    /// of {max_len} bytes.
+    TooLong {
+        ty: schema::MetaType,
+        max_len: usize,
+        len: usize,
+    },
+
+    /// value of metadata type #{0} is not a valid UTF-8 string.
+    InvalidUtf8(schema::MetaType),
 }
 
 #[derive(
@@ -87,6 +119,9 @@ mod _serde {
 )]
 pub struct Metadata(TinyOrdMap<schema::MetaType, MetaValue>);
 
+impl StrictSerialize for Metadata {}
+impl StrictDeserialize for Metadata {}
+
 impl Metadata {
     pub fn add_value(
         &mut self,
@@ -99,6 +134,179 @@ impl Metadata {
         self.0.insert(ty, meta)?;
         Ok(())
     }
+
+    /// Adds value of the metadata type `ty` from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the value of the type is already present, if the maximal number of
+    /// metadata types is reached, or if the value exceeds the size limit.
+    pub fn try_insert(
+        &mut self,
+        ty: schema::MetaType,
+        value: impl AsRef<[u8]>,
+    ) -> Result<(), MetadataError> {
+        let value = value.as_ref();
+        let blob = SmallBlob::try_from_iter(value.iter().copied()).map_err(|_| {
+            MetadataError::TooLong {
+                ty,
+                max_len: u16::MAX as usize,
+                len: value.len(),
+            }
+        })?;
+        self.add_value(ty, MetaValue::from(blob))
+    }
+
+    /// Constructs metadata from the raw values of the metadata types.
+    ///
+    /// # Errors
+    ///
+    /// Same as for [`Metadata::try_insert`].
+    pub fn try_from_iter<V: AsRef<[u8]>>(
+        iter: impl IntoIterator<Item = (schema::MetaType, V)>,
+    ) -> Result<Self, MetadataError> {
+        let mut metadata = Metadata::default();
+        for (ty, value) in iter {
+            metadata.try_insert(ty, value)?;
+        }
+        Ok(metadata)
+    }
+
+    /// Iterates over the values of the metadata type `ty`.
+    ///
+    /// Metadata keep a single value per type (adding a second one fails with
+    /// [`MetadataError::AlreadyExists`]), thus the iterator yields at most one
+    /// value.
+    pub fn values_of(&self, ty: schema::MetaType) -> impl Iterator<Item = &MetaValue> {
+        self.0.get(&ty).into_iter()
+    }
+
+    /// Returns raw bytes of the metadata type `ty`.
+    pub fn bytes(&self, ty: schema::MetaType) -> Result<&[u8], MetadataError> {
+        self.0
+            .get(&ty)
+            .map(|value| value.as_ref())
+            .ok_or(MetadataError::Absent(ty))
+    }
+
+    /// Returns the metadata type `ty` as a UTF-8 string no longer than
+    /// `max_len` bytes.
+    pub fn string(&self, ty: schema::MetaType, max_len: usize) -> Result<&str, MetadataError> {
+        let bytes = self.bytes(ty)?;
+        if bytes.len() > max_len {
+            return Err(MetadataError::TooLong {
+                ty,
+                max_len,
+                len: bytes.len(),
+            });
+        }
+        str::from_utf8(bytes).map_err(|_| MetadataError::InvalidUtf8(ty))
+    }
+
+    pub fn u8(&self, ty: schema::MetaType) -> Result<u8, MetadataError> {
+        self.array(ty).map(u8::from_le_bytes)
+    }
+
+    pub fn u16(&self, ty: schema::MetaType) -> Result<u16, MetadataError> {
+        self.array(ty).map(u16::from_le_bytes)
+    }
+
+    pub fn u32(&self, ty: schema::MetaType) -> Result<u32, MetadataError> {
+        self.array(ty).map(u32::from_le_bytes)
+    }
+
+    pub fn u64(&self, ty: schema::MetaType) -> Result<u64, MetadataError> {
+        self.array(ty).map(u64::from_le_bytes)
+    }
+
+    pub fn i64(&self, ty: schema::MetaType) -> Result<i64, MetadataError> {
+        self.array(ty).map(i64::from_le_bytes)
+    }
+
+    /// Returns the value of the metadata type `ty`, which must be exactly
+    /// `LEN` bytes long (strict encoding of integers is little-endian).
+    fn array<const LEN: usize>(&self, ty: schema::MetaType) -> Result<[u8; LEN], MetadataError> {
+        let bytes = self.bytes(ty)?;
+        <[u8; LEN]>::try_from(bytes).map_err(|_| MetadataError::InvalidLength {
+            ty,
+            expected: LEN,
+            len: bytes.len(),
+        })
+    }
+}
+
+impl Metadata {
+    /// Returns displayable form of the metadata, which uses the `schema` to
+    /// resolve the metadata types and to decode the values with `types`.
+    /// Values of the types unknown to the schema, as well as values failing
+    /// to decode, are displayed as hex. Long values are truncated.
+    pub fn display_with<'a>(
+        &'a self,
+        schema: &'a Schema,
+        types: &'a TypeSystem,
+    ) -> MetadataDisplay<'a> {
+        MetadataDisplay {
+            metadata: self,
+            schema,
+            types,
+        }
+    }
+}
+
+/// Displays metadata as `type => hex` lines.
+impl Display for Metadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (ty, value) in self {
+            writeln!(f, "{ty} => {}", TruncatedHex(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Schema-aware display of [`Metadata`], see [`Metadata::display_with`].
+#[derive(Copy, Clone, Debug)]
+pub struct MetadataDisplay<'a> {
+    metadata: &'a Metadata,
+    schema: &'a Schema,
+    types: &'a TypeSystem,
+}
+
+impl Display for MetadataDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (ty, value) in self.metadata {
+            let Some(sem_id) = self.schema.meta_types.get(ty) else {
+                writeln!(f, "{ty} (unknown) => {}", TruncatedHex(value))?;
+                continue;
+            };
+            write!(f, "{ty} ({}) => ", self.schema.meta_kind(*ty))?;
+            match self
+                .types
+                .strict_deserialize_type(*sem_id, value.as_slice())
+            {
+                Ok(val) => {
+                    let val = val.unbox().to_string();
+                    match val.char_indices().nth(DISPLAY_MAX_CHARS) {
+                        Some((pos, _)) => writeln!(f, "{}…", &val[..pos])?,
+                        None => writeln!(f, "{val}")?,
+                    }
+                }
+                Err(_) => writeln!(f, "{}", TruncatedHex(value))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct TruncatedHex<'a>(&'a MetaValue);
+
+impl Display for TruncatedHex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.as_slice();
+        if bytes.len() <= DISPLAY_MAX_BYTES {
+            return f.write_str(&bytes.to_hex());
+        }
+        write!(f, "{}… ({} bytes)", bytes[..DISPLAY_MAX_BYTES].to_hex(), bytes.len())
+    }
 }
 
 impl<'a> IntoIterator for &'a Metadata {
@@ -107,3 +315,188 @@ impl<'a> IntoIterator for &'a Metadata {
 
     fn into_iter(self) -> Self::IntoIter { self.0.iter() }
 }
+
+#[cfg(test)]
+mod test {
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::{MetaKind, MetaType};
+
+    fn metadata(values: &[(u16, &[u8])]) -> Metadata {
+        Metadata::try_from_iter(
+            values
+                .iter()
+                .map(|(ty, value)| (MetaType::with(*ty), value)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn integers() {
+        let metadata = metadata(&[
+            (1, &[0xFE]),
+            (2, &0xBEEFu16.to_le_bytes()),
+            (3, &0xDEADBEEFu32.to_le_bytes()),
+            (4, &u64::MAX.to_le_bytes()),
+            (5, &(-1_700_000_000i64).to_le_bytes()),
+        ]);
+        assert_eq!(metadata.u8(MetaType::with(1)), Ok(0xFE));
+        assert_eq!(metadata.u16(MetaType::with(2)), Ok(0xBEEF));
+        assert_eq!(metadata.u32(MetaType::with(3)), Ok(0xDEADBEEF));
+        assert_eq!(metadata.u64(MetaType::with(4)), Ok(u64::MAX));
+        assert_eq!(metadata.i64(MetaType::with(5)), Ok(-1_700_000_000));
+        assert_eq!(metadata.i64(MetaType::with(4)), Ok(-1));
+    }
+
+    #[test]
+    fn integer_errors() {
+        let metadata = metadata(&[(1, &[0xFE]), (4, &[0; 8])]);
+        let ty = MetaType::with(1);
+        let invalid = |expected| MetadataError::InvalidLength {
+            ty,
+            expected,
+            len: 1,
+        };
+        assert_eq!(metadata.u16(ty), Err(invalid(2)));
+        assert_eq!(metadata.u32(ty), Err(invalid(4)));
+        assert_eq!(metadata.u64(ty), Err(invalid(8)));
+        assert_eq!(metadata.i64(ty), Err(invalid(8)));
+        assert_eq!(
+            metadata.u8(MetaType::with(4)),
+            Err(MetadataError::InvalidLength {
+                ty: MetaType::with(4),
+                expected: 1,
+                len: 8
+            })
+        );
+
+        let absent = MetaType::with(2);
+        assert_eq!(metadata.u8(absent), Err(MetadataError::Absent(absent)));
+        assert_eq!(metadata.u16(absent), Err(MetadataError::Absent(absent)));
+        assert_eq!(metadata.u32(absent), Err(MetadataError::Absent(absent)));
+        assert_eq!(metadata.u64(absent), Err(MetadataError::Absent(absent)));
+        assert_eq!(metadata.i64(absent), Err(MetadataError::Absent(absent)));
+    }
+
+    #[test]
+    fn bytes_and_strings() {
+        let metadata = metadata(&[(1, b"RGB20"), (2, &[0xFF, 0xFE]), (3, b"")]);
+        let (ticker, binary, empty) = (MetaType::with(1), MetaType::with(2), MetaType::with(3));
+
+        assert_eq!(metadata.bytes(ticker), Ok(&b"RGB20"[..]));
+        assert_eq!(metadata.bytes(empty), Ok(&[][..]));
+        assert_eq!(metadata.string(ticker, 8), Ok("RGB20"));
+        assert_eq!(metadata.string(ticker, 5), Ok("RGB20"));
+        assert_eq!(metadata.string(empty, 0), Ok(""));
+        assert_eq!(
+            metadata.string(ticker, 4),
+            Err(MetadataError::TooLong {
+                ty: ticker,
+                max_len: 4,
+                len: 5
+            })
+        );
+        assert_eq!(metadata.string(binary, 8), Err(MetadataError::InvalidUtf8(binary)));
+
+        let absent = MetaType::with(4);
+        assert_eq!(metadata.bytes(absent), Err(MetadataError::Absent(absent)));
+        assert_eq!(metadata.string(absent, 8), Err(MetadataError::Absent(absent)));
+    }
+
+    #[test]
+    fn repeated_values() {
+        let mut metadata = metadata(&[(1, b"one")]);
+        let ty = MetaType::with(1);
+        let values = metadata.values_of(ty).collect::<Vec<_>>();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].as_slice(), b"one");
+        assert_eq!(metadata.values_of(MetaType::with(2)).count(), 0);
+
+        let second = MetaValue::from(SmallBlob::try_from(b"two".to_vec()).unwrap());
+        assert_eq!(metadata.add_value(ty, second), Err(MetadataError::AlreadyExists(ty)));
+        assert_eq!(metadata.values_of(ty).count(), 1);
+    }
+
+    #[test]
+    fn display() {
+        let long = [0xA5u8; 40];
+        let metadata = metadata(&[
+            (1, b"text"),
+            (2, &1_700_000_000i64.to_le_bytes()),
+            (3, &long),
+            (0x100, &[]),
+        ]);
+        assert_eq!(
+            metadata.to_string(),
+            format!(
+                "0x0001 => 74657874\n0x0002 => 00f1536500000000\n0x0003 => {}… (40 bytes)\n0x0100 \
+                 => \n",
+                "a5".repeat(32)
+            )
+        );
+
+        let mut schema = Schema::strict_dumb();
+        schema
+            .meta_types
+            .insert(MetaType::with(1), strict_dumb!())
+            .unwrap();
+        schema
+            .meta_types
+            .insert(MetaType::with(2), strict_dumb!())
+            .unwrap();
+        schema
+            .meta_kinds
+            .insert(MetaType::with(2), MetaKind::Timestamp)
+            .unwrap();
+        // Values which types are absent in the type system are shown as hex
+        let types = TypeSystem::default();
+        assert_eq!(
+            metadata.display_with(&schema, &types).to_string(),
+            format!(
+                "0x0001 (generic) => 74657874\n0x0002 (timestamp) => 00f1536500000000\n0x0003 \
+                 (unknown) => {}… (40 bytes)\n0x0100 (unknown) => \n",
+                "a5".repeat(32)
+            )
+        );
+        assert_eq!(
+            Metadata::default()
+                .display_with(&schema, &types)
+                .to_string(),
+            ""
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let mut metadata = Metadata::default();
+        metadata
+            .try_insert(MetaType::with(1), vec![0u8; u16::MAX as usize])
+            .unwrap();
+        assert_eq!(
+            metadata.try_insert(MetaType::with(2), vec![0u8; u16::MAX as usize + 1]),
+            Err(MetadataError::TooLong {
+                ty: MetaType::with(2),
+                max_len: u16::MAX as usize,
+                len: u16::MAX as usize + 1
+            })
+        );
+        assert_eq!(
+            metadata.try_insert(MetaType::with(1), b"other"),
+            Err(MetadataError::AlreadyExists(MetaType::with(1)))
+        );
+        assert_eq!(metadata.len(), 1);
+
+        let values = (0..u8::MAX as u16).map(|ty| (MetaType::with(ty), [ty as u8]));
+        let mut metadata = Metadata::try_from_iter(values.clone()).unwrap();
+        assert_eq!(metadata.len(), u8::MAX as usize);
+        assert_eq!(
+            metadata.try_insert(MetaType::with(u16::MAX), b""),
+            Err(MetadataError::TooManyValues)
+        );
+        assert_eq!(
+            Metadata::try_from_iter(values.chain([(MetaType::with(u16::MAX), [0])])),
+            Err(MetadataError::TooManyValues)
+        );
+    }
+}
@@ -35,39 +35,64 @@ mod bundle;
 mod contract;
 mod xchain;
 mod commit;
+mod disclosure;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod commit_vectors;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod conceal_harness;
 
-pub use anchor::{DbcError, DbcProof, EAnchor, Layer1, WitnessAnchor};
+pub use anchor::{
+    build_mpc_messages, verify_anchor, verify_contract_in_anchor, AnchorContractError, AnchorError,
+    DbcError, DbcProof, EAnchor, Layer1, MpcAssemblyError, WitnessAnchor,
+};
 pub use assignments::{
-    Assign, AssignAttach, AssignData, AssignFungible, AssignRights, Assignments, AssignmentsRef,
-    TypedAssigns,
+    build_transfer_assignments, AllocationError, Assign, AssignAttach, AssignData, AssignFungible,
+    AssignRights, Assignments, AssignmentsRef, ConcealmentReport, DecoyError, FungibleAllocator,
+    FungibleSumError, MergeDuplicates, MergeError, RevealError, RevealSeals, RevealState,
+    RevealStatus, SealForm, StateProvider, TransferError, TypedAssigns, UpdateSealError,
+    UpdateValueError,
+};
+pub use attachment::{
+    AttachId, AttachMismatch, AttachStore, AttachStoreError, AttachVerifier, ConcealedAttach,
+    MemAttachStore, RevealedAttach, VerifiedAttachProvider,
 };
-pub use attachment::{AttachId, ConcealedAttach, RevealedAttach};
-pub use bundle::{BundleId, InputMap, TransitionBundle, Vin};
+pub use bundle::{BundleError, BundleId, InputMap, TransitionBundle, Vin};
 pub use commit::{
-    AssignmentCommitment, AssignmentIndex, BaseCommitment, BundleDisclosure, ContractId,
-    DiscloseHash, GlobalCommitment, OpCommitment, OpDisclose, OpId, TypeCommitment,
+    verify_commitment_any_version, AssignmentCommitment, AssignmentIndex, AssignmentProof,
+    AssignmentProofError, BaseCommitment, BundleDisclosure, CommitmentVersion, ContractId,
+    DiscloseHash, GlobalCommitment, MetadataProof, MetadataProofError, OpCommitment, OpDisclose,
+    OpId, TypeCommitment,
 };
 pub use contract::{
-    AssignmentWitness, ContractHistory, ContractState, GlobalOrd, KnownState, Opout,
+    AssignmentWitness, BlindedOpout, ContractHistory, ContractState, GlobalOrd, KnownState, Opout,
     OpoutParseError, OutputAssignment,
 };
-pub use data::{ConcealedData, DataState, RevealedData, VoidState};
+pub use data::{ConcealedData, DataState, DataTooLarge, RevealedData, TypedDataError, VoidState};
+pub use disclosure::{Disclosure, DisclosureError, Opening, RevealOpening};
 pub use fungible::{
-    AssetTag, BlindingFactor, BlindingParseError, ConcealedValue, FungibleState,
-    InvalidFieldElement, NoiseDumb, PedersenCommitment, RangeProof, RangeProofError, RevealedValue,
+    AssetTag, BlindingFactor, BlindingParseError, ConcealedValue, FungibleAccumulator,
+    FungibleState, InvalidFieldElement, NoiseDumb, OutOfU64Range, PedersenCommitment, RangeProof,
+    RangeProofError, RevealedValue, SupplyOverflow, ZeroValue,
 };
 pub use global::{GlobalState, GlobalValues};
-pub use meta::{MetaValue, Metadata, MetadataError};
+pub use meta::{MetaValue, Metadata, MetadataDisplay, MetadataError};
 pub use operations::{
-    AssetTags, Extension, Genesis, Identity, Input, Inputs, OpRef, Operation, Redeemed, Transition,
-    Valencies,
+    conceal_collection, AssetTags, BulkConcealmentReport, ConcealOutputsError, ConcealableNode,
+    Extension, Genesis, Identity, Input, Inputs, OpRef, Operation, Redeemed, Transition, Valencies,
 };
+#[cfg(feature = "prime-seals")]
+pub use seal::SealPreimage;
 pub use seal::{
-    ExposedSeal, GenesisSeal, GraphSeal, OutputSeal, SecretSeal, TxoSeal, WitnessOrd, WitnessPos,
-    XGenesisSeal, XGraphSeal, XOutputSeal, XWitnessId, XWitnessTx,
+    BlindingDeriver, CtEq, ExposedSeal, GenesisSeal, GraphSeal, LayerMismatch, OutpointBlindProof,
+    OutputSeal, OutputSealDraft, SealRegistry, SecretBlindingDeriver, SecretSeal, SecretSealExt,
+    SecretSealParseError, SemiRevealedSeal, TxoSeal, UnresolvedWitness, WitnessOrd,
+    WitnessOrdParseError, WitnessPos, WitnessSeal, XGenesisSeal, XGraphSeal, XOutputSeal,
+    XOutputSealDraft, XWitnessId, XWitnessTx,
 };
 pub use state::{ConcealedState, ConfidentialState, ExposedState, RevealedState, StateType};
 pub use xchain::{
-    AltLayer1, AltLayer1Set, XChain, XChainParseError, XOutpoint, XCHAIN_BITCOIN_PREFIX,
-    XCHAIN_LIQUID_PREFIX, Impossible
+    AltLayer1, AltLayer1Set, Impossible, XChain, XChainParseError, XOutpoint,
+    XCHAIN_BITCOIN_PREFIX, XCHAIN_LIQUID_PREFIX,
 };
+#[cfg(feature = "prime-seals")]
+pub use xchain::{XCHAIN_ABRAXAS_PREFIX, XCHAIN_PRIME_PREFIX};
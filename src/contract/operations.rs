@@ -22,7 +22,7 @@
 
 use std::cmp::Ordering;
 use std::collections::{btree_map, btree_set, BTreeMap};
-use std::iter;
+use std::{io, iter};
 
 use amplify::confinement::{Confined, SmallOrdSet, TinyOrdMap, TinyOrdSet};
 use amplify::Wrapper;
@@ -31,14 +31,18 @@ use commit_verify::{
     StrictHash,
 };
 use strict_encoding::stl::AsciiPrintable;
-use strict_encoding::{RString, StrictDeserialize, StrictEncode, StrictSerialize};
+use strict_encoding::{
+    DecodeError, RString, StrictDecode, StrictDeserialize, StrictEncode, StrictProduct,
+    StrictSerialize, StrictStruct, StrictType, TypeName, TypedRead, TypedWrite, WriteStruct,
+};
 
 use crate::schema::{self, ExtensionType, OpFullType, OpType, SchemaId, TransitionType};
 use crate::{
     AltLayer1Set, AssetTag, Assign, AssignmentIndex, AssignmentType, Assignments, AssignmentsRef,
-    ConcealedAttach, ConcealedData, ConcealedValue, ContractId, DiscloseHash, ExposedState, Ffv,
-    GenesisSeal, GlobalState, GraphSeal, Metadata, OpDisclose, OpId, Opout, SecretSeal,
-    TypedAssigns, VoidState, XChain, LIB_NAME_RGB,
+    ConcealedAttach, ConcealedData, ConcealedValue, ConcealmentReport, ContractId, DiscloseHash,
+    ExposedSeal, ExposedState, Ffv, GenesisSeal, GlobalState, GraphSeal, Metadata, OpDisclose,
+    OpId, Opout, RevealSeals, RevealState, SecretSeal, StateProvider, TypedAssigns, VoidState,
+    XChain, XGraphSeal, LIB_NAME_RGB,
 };
 
 #[derive(Wrapper, WrapperMut, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, From)]
@@ -188,7 +192,7 @@ pub trait Operation {
     fn globals(&self) -> &GlobalState;
     fn valencies(&self) -> &Valencies;
 
-    fn assignments(&self) -> AssignmentsRef;
+    fn assignments(&self) -> AssignmentsRef<'_>;
 
     fn assignments_by_type(&self, t: AssignmentType) -> Option<TypedAssigns<GraphSeal>>;
 
@@ -249,6 +253,32 @@ pub trait Operation {
     }
 
     fn disclose_hash(&self) -> DiscloseHash { self.disclose().commit_id() }
+
+    /// Returns outputs of the operation which seals would be concealed when
+    /// concealing all seals except the ones listed in `except`, without
+    /// modifying the operation.
+    fn concealable_seals(&self, except: &[XChain<SecretSeal>]) -> Vec<Opout> {
+        let id = self.id();
+        self.assignments()
+            .flat()
+            .concealable_seals(except)
+            .into_iter()
+            .map(|index| Opout::new(id, index.ty, index.pos))
+            .collect()
+    }
+
+    /// Returns outputs of the operation which state would be concealed when
+    /// concealing state of all outputs except the ones which seals are listed
+    /// in `except`, without modifying the operation.
+    fn concealable_state(&self, except: &[XChain<SecretSeal>]) -> Vec<Opout> {
+        let id = self.id();
+        self.assignments()
+            .flat()
+            .concealable_state(except)
+            .into_iter()
+            .map(|index| Opout::new(id, index.ty, index.pos))
+            .collect()
+    }
 }
 
 /// An ASCII printable string up to 4096 chars representing identity of the
@@ -345,8 +375,15 @@ impl PartialOrd for Extension {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
+/// State transition.
+///
+/// The strict encoding of the transition depends on its fast-forward version:
+/// [`Transition::schema_id`] is encoded only starting from [`Ffv::V1`], which
+/// is used automatically for the transitions bound to a schema. Transitions
+/// which are not bound keep the original layout and ids, and remain readable
+/// by the software unaware of the schema binding.
 #[derive(Clone, PartialEq, Eq, Debug)]
-#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[derive(StrictDumb)]
 #[strict_type(lib = LIB_NAME_RGB)]
 #[cfg_attr(
     feature = "serde",
@@ -357,6 +394,10 @@ pub struct Transition {
     pub ffv: Ffv,
     pub contract_id: ContractId,
     pub transition_type: TransitionType,
+    /// Optional commitment to the schema of the contract, which must match the
+    /// contract schema during the validation. See [`Transition::bind_schema`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub schema_id: Option<SchemaId>,
     pub metadata: Metadata,
     pub globals: GlobalState,
     pub inputs: Inputs,
@@ -366,6 +407,76 @@ pub struct Transition {
     pub witness: ReservedBytes<2, 0>,
 }
 
+impl StrictType for Transition {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_RGB;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Transition")) }
+}
+impl StrictProduct for Transition {}
+impl StrictStruct for Transition {
+    const ALL_FIELDS: &'static [&'static str] = &[
+        "ffv",
+        "contractId",
+        "transitionType",
+        "schemaId",
+        "metadata",
+        "globals",
+        "inputs",
+        "assignments",
+        "valencies",
+        "validator",
+        "witness",
+    ];
+}
+impl StrictEncode for Transition {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let ffv = self.encoding_ffv();
+        writer.write_struct::<Self>(|w| {
+            let mut w = w
+                .write_field(fname!("ffv"), &ffv)?
+                .write_field(fname!("contractId"), &self.contract_id)?
+                .write_field(fname!("transitionType"), &self.transition_type)?;
+            if ffv >= Ffv::V1 {
+                w = w.write_field(fname!("schemaId"), &self.schema_id)?;
+            }
+            Ok(w.write_field(fname!("metadata"), &self.metadata)?
+                .write_field(fname!("globals"), &self.globals)?
+                .write_field(fname!("inputs"), &self.inputs)?
+                .write_field(fname!("assignments"), &self.assignments)?
+                .write_field(fname!("valencies"), &self.valencies)?
+                .write_field(fname!("validator"), &self.validator)?
+                .write_field(fname!("witness"), &self.witness)?
+                .complete())
+        })
+    }
+}
+impl StrictDecode for Transition {
+    // Fields are decoded one by one since the struct reader insists on all of
+    // them being present, while `schemaId` is absent before `Ffv::V1`.
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let ffv = Ffv::strict_decode(reader)?;
+        let contract_id = StrictDecode::strict_decode(reader)?;
+        let transition_type = StrictDecode::strict_decode(reader)?;
+        let schema_id = if ffv >= Ffv::V1 {
+            StrictDecode::strict_decode(reader)?
+        } else {
+            None
+        };
+        Ok(Transition {
+            ffv,
+            contract_id,
+            transition_type,
+            schema_id,
+            metadata: StrictDecode::strict_decode(reader)?,
+            globals: StrictDecode::strict_decode(reader)?,
+            inputs: StrictDecode::strict_decode(reader)?,
+            assignments: StrictDecode::strict_decode(reader)?,
+            valencies: StrictDecode::strict_decode(reader)?,
+            validator: StrictDecode::strict_decode(reader)?,
+            witness: StrictDecode::strict_decode(reader)?,
+        })
+    }
+}
+
 impl StrictSerialize for Transition {}
 impl StrictDeserialize for Transition {}
 
@@ -413,6 +524,42 @@ impl Conceal for Extension {
     }
 }
 
+impl RevealSeals for Genesis {
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        RevealSeals::reveal_seals(&mut self.assignments, known)
+    }
+}
+
+impl RevealSeals for Transition {
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        RevealSeals::reveal_seals(&mut self.assignments, known)
+    }
+}
+
+impl RevealSeals for Extension {
+    fn reveal_seals(&mut self, known: &BTreeMap<XChain<SecretSeal>, XGraphSeal>) -> usize {
+        RevealSeals::reveal_seals(&mut self.assignments, known)
+    }
+}
+
+impl RevealState for Genesis {
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize {
+        self.assignments.reveal_state(provider)
+    }
+}
+
+impl RevealState for Transition {
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize {
+        self.assignments.reveal_state(provider)
+    }
+}
+
+impl RevealState for Extension {
+    fn reveal_state(&mut self, provider: &impl StateProvider) -> usize {
+        self.assignments.reveal_state(provider)
+    }
+}
+
 impl CommitEncode for Genesis {
     type CommitmentId = OpId;
     fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(&self.commit()) }
@@ -428,11 +575,89 @@ impl CommitEncode for Extension {
     fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(&self.commit()) }
 }
 
+/// Errors concealing operation outputs.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ConcealOutputsError {
+    /// output {0} doesn't belong to the operation {1}.
+    OpidMismatch(Opout, OpId),
+
+    /// operation has no assignment {0}.
+    UnknownAssignment(Opout),
+}
+
+fn conceal_except_outputs<Seal: ExposedSeal>(
+    id: OpId,
+    assignments: &mut Assignments<Seal>,
+    keep: &[Opout],
+) -> Result<ConcealmentReport, ConcealOutputsError> {
+    let mut indexes = Vec::with_capacity(keep.len());
+    for opout in keep {
+        if opout.op != id {
+            return Err(ConcealOutputsError::OpidMismatch(*opout, id));
+        }
+        let known = assignments
+            .get(&opout.ty)
+            .map(|a| opout.no < a.len_u16())
+            .unwrap_or_default();
+        if !known {
+            return Err(ConcealOutputsError::UnknownAssignment(*opout));
+        }
+        indexes.push(AssignmentIndex::new(opout.ty, opout.no));
+    }
+    Ok(assignments.conceal_except(&indexes))
+}
+
+impl Genesis {
+    /// Conceals seals and state of all operation outputs, except the ones
+    /// listed in `keep`, which are left as is. Doesn't change the operation
+    /// id.
+    ///
+    /// # Errors
+    ///
+    /// If any of the outputs in `keep` doesn't belong to the operation, in
+    /// which case the operation is left unmodified.
+    pub fn conceal_except_outputs(
+        &mut self,
+        keep: &[Opout],
+    ) -> Result<ConcealmentReport, ConcealOutputsError> {
+        conceal_except_outputs(self.id(), &mut self.assignments, keep)
+    }
+}
+
 impl Transition {
+    /// Binds the transition to the schema of the contract, upgrading its
+    /// fast-forward version to [`Ffv::V1`] if needed. The binding is
+    /// committed to by the transition id and checked during the validation.
+    pub fn bind_schema(&mut self, schema_id: SchemaId) {
+        self.schema_id = Some(schema_id);
+        self.ffv = self.ffv.max(Ffv::V1);
+    }
+
+    /// Fast-forward version used in the transition encoding and commitment,
+    /// which is at least [`Ffv::V1`] if the transition is bound to a schema.
+    pub(crate) fn encoding_ffv(&self) -> Ffv {
+        if self.schema_id.is_none() {
+            self.ffv
+        } else {
+            self.ffv.max(Ffv::V1)
+        }
+    }
+
     /// Returns reference to information about the owned rights in form of
     /// [`Inputs`] wrapper structure which this operation updates with
     /// state transition ("parent owned rights").
     pub fn prev_state(&self) -> &Inputs { &self.inputs }
+
+    /// Conceals seals and state of all operation outputs, except the ones
+    /// listed in `keep`. See [`Genesis::conceal_except_outputs`] for the
+    /// details.
+    pub fn conceal_except_outputs(
+        &mut self,
+        keep: &[Opout],
+    ) -> Result<ConcealmentReport, ConcealOutputsError> {
+        conceal_except_outputs(self.id(), &mut self.assignments, keep)
+    }
 }
 
 impl Extension {
@@ -442,6 +667,89 @@ impl Extension {
     /// referenced by another state extension, which this operation updates
     /// ("parent public rights").
     pub fn redeemed(&self) -> &Redeemed { &self.redeemed }
+
+    /// Conceals seals and state of all operation outputs, except the ones
+    /// listed in `keep`. See [`Genesis::conceal_except_outputs`] for the
+    /// details.
+    pub fn conceal_except_outputs(
+        &mut self,
+        keep: &[Opout],
+    ) -> Result<ConcealmentReport, ConcealOutputsError> {
+        conceal_except_outputs(self.id(), &mut self.assignments, keep)
+    }
+}
+
+/// Operation which seals and state can be concealed via a trait object, such
+/// that collections mixing different types of operations can be concealed in
+/// bulk with [`conceal_collection`].
+pub trait ConcealableNode: Operation {
+    /// Conceals seals of all assignments, except the ones listed in `except`.
+    fn conceal_seals_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport;
+
+    /// Conceals state of all assignments, except the ones which seals are
+    /// listed in `except`.
+    fn conceal_state_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport;
+}
+
+impl ConcealableNode for Genesis {
+    fn conceal_seals_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_seals_except(except)
+    }
+
+    fn conceal_state_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_state_except(except)
+    }
+}
+
+impl ConcealableNode for Transition {
+    fn conceal_seals_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_seals_except(except)
+    }
+
+    fn conceal_state_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_state_except(except)
+    }
+}
+
+impl ConcealableNode for Extension {
+    fn conceal_seals_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_seals_except(except)
+    }
+
+    fn conceal_state_except(&mut self, except: &[XChain<SecretSeal>]) -> ConcealmentReport {
+        self.assignments.conceal_state_except(except)
+    }
+}
+
+/// Report on the concealment of a collection of operations, listing
+/// concealment reports for each of the operations.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct BulkConcealmentReport {
+    pub nodes: BTreeMap<OpId, ConcealmentReport>,
+}
+
+impl BulkConcealmentReport {
+    /// Total number of concealed seals and state items.
+    pub fn len(&self) -> usize { self.nodes.values().map(ConcealmentReport::len).sum() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+/// Conceals seals and state of all operations in the collection, except
+/// assignments to the seals listed in `keep`. Doesn't change operation ids.
+pub fn conceal_collection(
+    nodes: &mut [&mut dyn ConcealableNode],
+    keep: &[XChain<SecretSeal>],
+) -> BulkConcealmentReport {
+    let mut bulk = BulkConcealmentReport::default();
+    for node in nodes {
+        let state = node.conceal_state_except(keep);
+        let seals = node.conceal_seals_except(keep);
+        let report = bulk.nodes.entry(node.id()).or_default();
+        report.state.extend(state.state);
+        report.seals.extend(seals.seals);
+    }
+    bulk
 }
 
 impl Operation for Genesis {
@@ -473,7 +781,7 @@ impl Operation for Genesis {
     fn valencies(&self) -> &Valencies { &self.valencies }
 
     #[inline]
-    fn assignments(&self) -> AssignmentsRef { (&self.assignments).into() }
+    fn assignments(&self) -> AssignmentsRef<'_> { (&self.assignments).into() }
 
     #[inline]
     fn assignments_by_type(&self, t: AssignmentType) -> Option<TypedAssigns<GraphSeal>> {
@@ -515,7 +823,7 @@ impl Operation for Extension {
     fn valencies(&self) -> &Valencies { &self.valencies }
 
     #[inline]
-    fn assignments(&self) -> AssignmentsRef { (&self.assignments).into() }
+    fn assignments(&self) -> AssignmentsRef<'_> { (&self.assignments).into() }
 
     #[inline]
     fn assignments_by_type(&self, t: AssignmentType) -> Option<TypedAssigns<GraphSeal>> {
@@ -557,7 +865,7 @@ impl Operation for Transition {
     fn valencies(&self) -> &Valencies { &self.valencies }
 
     #[inline]
-    fn assignments(&self) -> AssignmentsRef { (&self.assignments).into() }
+    fn assignments(&self) -> AssignmentsRef<'_> { (&self.assignments).into() }
 
     #[inline]
     fn assignments_by_type(&self, t: AssignmentType) -> Option<TypedAssigns<GraphSeal>> {
@@ -681,8 +989,15 @@ mod test {
 
     use amplify::ByteArray;
     use baid64::DisplayBaid64;
+    use bp::seals::txout::{CloseMethod, TxPtr};
+    use bp::{Txid, Vout};
+    use strict_encoding::StrictDumb;
 
     use super::*;
+    use crate::{
+        AssignFungible, AssignRights, ConcealedState, RevealedAttach, RevealedData, RevealedValue,
+        SealRegistry,
+    };
 
     #[test]
     fn contract_id_display() {
@@ -722,4 +1037,333 @@ mod test {
             ContractId::from_str("rgb:bGxs-bGxs-bGxsbGx-sbGxsbG-xsbGxsb-GxsbGxs-bGxsbGw").is_ok()
         );
     }
+
+    fn graph_seal(vout: u32, txid: TxPtr) -> XGraphSeal {
+        XChain::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0xba5e + vout as u64,
+            txid,
+            vout: Vout::from(vout),
+        })
+    }
+
+    fn rights<Seal: ExposedSeal>(seals: &[XChain<Seal>]) -> Assignments<Seal> {
+        let assigns = seals
+            .iter()
+            .map(|seal| AssignRights::revealed(*seal, VoidState::default()))
+            .collect::<Vec<_>>();
+        Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => TypedAssigns::Declarative(Confined::try_from(assigns).unwrap())
+            })
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn reveal_seals_round_trip() {
+        let txid = Txid::from([0x11; 32]);
+        let seals = [graph_seal(0, TxPtr::Txid(txid)), graph_seal(1, TxPtr::WitnessTx)];
+        let known = SealRegistry::from_iter(seals);
+
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = rights(&seals);
+        let original = transition.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        transition.assignments.conceal_seals();
+        assert_ne!(transition.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+        assert_eq!(known.reveal_in(&mut transition), 2);
+        assert_eq!(transition.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+        // Already revealed seals are left as is
+        assert_eq!(known.reveal_in(&mut transition), 0);
+
+        let genesis_seal = XChain::Bitcoin(GenesisSeal {
+            method: CloseMethod::OpretFirst,
+            txid,
+            vout: Vout::from(2),
+            blinding: 0xba5e,
+        });
+        let mut genesis = Genesis::strict_dumb();
+        genesis.assignments = rights(&[genesis_seal]);
+        let original = genesis.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        genesis.assignments.conceal_seals();
+        let mut extension = Extension::strict_dumb();
+        extension.assignments = genesis.assignments.clone();
+        let known = SealRegistry::from_iter([XGraphSeal::from(genesis_seal)]);
+        assert_eq!(known.reveal_in(&mut genesis), 1);
+        assert_eq!(genesis.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+        assert_eq!(known.reveal_in(&mut extension), 1);
+        assert_eq!(extension.assignments, genesis.assignments);
+    }
+
+    #[test]
+    fn reveal_seals_mismatch() {
+        let txid = Txid::from([0x11; 32]);
+        let seals = [graph_seal(0, TxPtr::Txid(txid)), graph_seal(1, TxPtr::Txid(txid))];
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = rights(&seals[..1]);
+        transition.assignments.conceal_seals();
+        let concealed = transition.to_strict_serialized::<{ usize::MAX }>().unwrap();
+
+        // Seal which doesn't hash to the concealed seal is not substituted
+        let known = bmap! { seals[0].to_secret_seal() => seals[1] };
+        assert_eq!(transition.reveal_seals(&known), 0);
+        assert_eq!(transition.to_strict_serialized::<{ usize::MAX }>().unwrap(), concealed);
+
+        // Witness-based seals can't be revealed in genesis
+        let witness_seal = graph_seal(2, TxPtr::WitnessTx);
+        let mut genesis = Genesis::strict_dumb();
+        genesis.assignments = rights(&[XChain::Bitcoin(GenesisSeal {
+            method: CloseMethod::TapretFirst,
+            txid,
+            vout: Vout::from(2),
+            blinding: 0xba5e + 2,
+        })]);
+        genesis.assignments.conceal_seals();
+        let concealed = genesis.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let secret = genesis
+            .assignments
+            .get(&AssignmentType::with(1))
+            .unwrap()
+            .to_confidential_seals()[0];
+        let known = bmap! { secret => witness_seal };
+        assert_eq!(genesis.reveal_seals(&known), 0);
+        assert_eq!(genesis.to_strict_serialized::<{ usize::MAX }>().unwrap(), concealed);
+    }
+
+    struct Openings(Vec<RevealedValue>);
+
+    impl StateProvider for Openings {
+        fn revealed_value(&self, concealed: &ConcealedValue) -> Option<RevealedValue> {
+            self.0
+                .iter()
+                .find(|value| value.conceal() == *concealed)
+                .cloned()
+        }
+
+        fn revealed_data(&self, _concealed: &ConcealedData) -> Option<RevealedData> { None }
+
+        fn revealed_attach(&self, _concealed: &ConcealedAttach) -> Option<RevealedAttach> { None }
+    }
+
+    #[test]
+    fn reveal_state_round_trip() {
+        let txid = Txid::from([0x11; 32]);
+        let seals = [graph_seal(0, TxPtr::Txid(txid)), graph_seal(1, TxPtr::WitnessTx)];
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let values = vec![
+            RevealedValue::new_random_blinding(100u64, tag),
+            RevealedValue::new_random_blinding(200u64, tag),
+        ];
+        let mut transition = Transition::strict_dumb();
+        let mut assignments = rights(&seals[..1]).into_inner().into_inner();
+        assignments.insert(
+            AssignmentType::with(2),
+            TypedAssigns::Fungible(
+                Confined::try_from_iter(
+                    values
+                        .iter()
+                        .zip(seals)
+                        .map(|(value, seal)| AssignFungible::revealed(seal, *value)),
+                )
+                .unwrap(),
+            ),
+        );
+        transition.assignments = Assignments::from(Confined::try_from(assignments).unwrap());
+        let original = transition.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let id = transition.id();
+
+        let mut concealed = transition.conceal();
+        assert_eq!(concealed.id(), id);
+        // Provider not knowing the openings reveals only the declarative state
+        assert_eq!(concealed.clone().reveal_state(&Openings(vec![])), 1);
+        assert_eq!(
+            concealed
+                .clone()
+                .reveal_state(&Openings(vec![RevealedValue::new_random_blinding(100u64, tag)])),
+            1
+        );
+
+        assert_eq!(concealed.reveal_state(&Openings(values)), 3);
+        assert_eq!(concealed.id(), id);
+        assert_eq!(concealed.reveal_state(&Openings(vec![])), 0);
+        // Seals are left concealed until revealed separately
+        assert_eq!(SealRegistry::from_iter(seals).reveal_in(&mut concealed), 3);
+        assert_eq!(concealed.id(), id);
+        assert_eq!(concealed.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+    }
+
+    #[test]
+    fn conceal_preview() {
+        let txid = Txid::from([0x11; 32]);
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let seals = (0..3)
+            .map(|vout| graph_seal(vout, TxPtr::Txid(txid)))
+            .collect::<Vec<_>>();
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(2) => TypedAssigns::Fungible(
+                    Confined::try_from_iter(seals.iter().map(|seal| {
+                        AssignFungible::revealed(*seal, RevealedValue::new_random_blinding(10u64, tag))
+                    }))
+                    .unwrap()
+                )
+            })
+            .unwrap(),
+        );
+        let id = transition.id();
+        let changed = |before: Vec<AssignmentIndex>, after: Vec<AssignmentIndex>| {
+            before
+                .into_iter()
+                .filter(|index| !after.contains(index))
+                .map(|index| Opout::new(id, index.ty, index.pos))
+                .collect::<Vec<_>>()
+        };
+        let except = [seals[1].to_secret_seal()];
+
+        let preview = transition.concealable_seals(&except);
+        assert_eq!(preview.len(), 2);
+        let before = transition.disclose();
+        let mut concealed = transition.clone();
+        let report = concealed.assignments.conceal_seals_except(&except);
+        assert_eq!(report.len(), preview.len());
+        assert!(report.state.is_empty());
+        let after = concealed.disclose();
+        assert_eq!(
+            changed(before.seals.keys().copied().collect(), after.seals.keys().copied().collect()),
+            preview
+        );
+        assert!(concealed.concealable_seals(&except).is_empty());
+
+        let preview = transition.concealable_state(&except);
+        assert_eq!(preview.len(), 2);
+        let mut concealed = transition.clone();
+        let report = concealed.assignments.conceal_state_except(&except);
+        assert_eq!(report.len(), preview.len());
+        for (index, state) in &report.state {
+            let ConcealedState::Fungible(value) = state else {
+                panic!("fungible state expected");
+            };
+            assert_eq!(before.fungible.get(index), Some(&value.commitment));
+        }
+        let after = concealed.disclose();
+        assert_eq!(
+            changed(
+                before.fungible.keys().copied().collect(),
+                after.fungible.keys().copied().collect()
+            ),
+            preview
+        );
+        assert!(concealed.concealable_state(&except).is_empty());
+        // Concealing state leaves the seals as is
+        assert_eq!(concealed.concealable_seals(&[]).len(), 3);
+    }
+
+    #[test]
+    fn conceal_outputs() {
+        let txid = Txid::from([0x11; 32]);
+        let seals = [
+            graph_seal(0, TxPtr::Txid(txid)),
+            graph_seal(1, TxPtr::Txid(txid)),
+            graph_seal(2, TxPtr::WitnessTx),
+        ];
+        let ty = AssignmentType::with(1);
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = rights(&seals);
+        let id = transition.id();
+        let original = transition.clone();
+
+        let keep = [Opout::new(id, ty, 0), Opout::new(id, ty, 2)];
+        assert_eq!(
+            transition.conceal_except_outputs(&[Opout::new(id, ty, 0), Opout::new(id, ty, 3)]),
+            Err(ConcealOutputsError::UnknownAssignment(Opout::new(id, ty, 3)))
+        );
+        let other = Opout::new(OpId::from([0x11; 32]), ty, 1);
+        assert_eq!(
+            transition.conceal_except_outputs(&[other]),
+            Err(ConcealOutputsError::OpidMismatch(other, id))
+        );
+        assert_eq!(
+            transition.conceal_except_outputs(&[Opout::new(id, AssignmentType::with(2), 0)]),
+            Err(ConcealOutputsError::UnknownAssignment(Opout::new(id, AssignmentType::with(2), 0)))
+        );
+        assert_eq!(transition, original);
+
+        let report = transition.conceal_except_outputs(&keep).unwrap();
+        assert_eq!(transition.id(), id);
+        assert_eq!(report.seals.keys().copied().collect::<Vec<_>>(), vec![AssignmentIndex::new(
+            ty, 1
+        )]);
+        assert_eq!(report.seals[&AssignmentIndex::new(ty, 1)], seals[1].to_secret_seal());
+        assert!(report.state.keys().all(|index| index.pos == 1));
+
+        let assigns = transition.assignments.get(&ty).unwrap().as_declarative();
+        let before = original.assignments.get(&ty).unwrap().as_declarative();
+        for no in [0, 2] {
+            assert_eq!(assigns[no], before[no]);
+            assert_eq!(assigns[no].revealed_seal(), Some(seals[no]));
+        }
+        assert_eq!(assigns[1].revealed_seal(), None);
+
+        // Repeated concealment has nothing to conceal
+        assert!(transition.conceal_except_outputs(&keep).unwrap().is_empty());
+        assert_eq!(transition.conceal_except_outputs(&[]).unwrap().seals.len(), 2);
+    }
+
+    #[test]
+    fn conceal_mixed_collection() {
+        use std::collections::BTreeSet;
+
+        let txid = Txid::from([0x11; 32]);
+        let seals = (0..6)
+            .map(|vout| graph_seal(vout, TxPtr::Txid(txid)))
+            .collect::<Vec<_>>();
+        let genesis_seals = seals
+            .iter()
+            .map(|seal| XChain::<GenesisSeal>::try_from(*seal).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut genesis = Genesis::strict_dumb();
+        genesis.assignments = rights(&genesis_seals[..2]);
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = rights(&seals[2..5]);
+        let mut extension = Extension::strict_dumb();
+        extension.assignments = rights(&genesis_seals[5..]);
+        let ids = [genesis.id(), transition.id(), extension.id()];
+
+        // Genesis seals commit to the txid differently from the graph seals, so
+        // they are concealed on their own
+        let keep = [genesis_seals[0].conceal(), seals[3].to_secret_seal()];
+        let mut nodes: Vec<&mut dyn ConcealableNode> =
+            vec![&mut genesis, &mut transition, &mut extension];
+        let bulk = conceal_collection(&mut nodes, &keep);
+
+        assert_eq!(bulk.nodes.keys().copied().collect::<BTreeSet<_>>(), BTreeSet::from(ids));
+        assert_eq!(
+            bulk.len(),
+            bulk.nodes
+                .values()
+                .map(ConcealmentReport::len)
+                .sum::<usize>()
+        );
+        // Seal and state of each of the assignments, except the kept ones
+        assert_eq!(bulk.nodes[&ids[0]].len(), 2);
+        assert_eq!(bulk.nodes[&ids[1]].len(), 4);
+        assert_eq!(bulk.nodes[&ids[2]].len(), 2);
+        assert_eq!(bulk.len(), 8);
+        assert_eq!([genesis.id(), transition.id(), extension.id()], ids);
+
+        let ty = AssignmentType::with(1);
+        let assigns = genesis.assignments.get(&ty).unwrap().as_declarative();
+        assert_eq!(assigns[0].revealed_seal(), Some(genesis_seals[0]));
+        assert_eq!(assigns[1].revealed_seal(), None);
+        let assigns = transition.assignments.get(&ty).unwrap().as_declarative();
+        assert_eq!(assigns[1].revealed_seal(), Some(seals[3]));
+        assert_eq!(assigns[0].revealed_seal(), None);
+
+        // Repeated concealment has nothing to conceal
+        let mut nodes: Vec<&mut dyn ConcealableNode> =
+            vec![&mut genesis, &mut transition, &mut extension];
+        assert!(conceal_collection(&mut nodes, &keep).is_empty());
+    }
 }
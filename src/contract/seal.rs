@@ -20,34 +20,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::fmt::Debug;
+use core::fmt::{self, Debug};
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::hash::Hash;
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, ParseIntError};
+use std::str::FromStr;
 
+use amplify::hex::FromHex;
+use amplify::{hex, ByteArray, Bytes32};
+use baid64::{Baid64ParseError, DisplayBaid64, FromBaid64Str};
 use bp::dbc::Method;
 pub use bp::seals::txout::blind::{ChainBlindSeal, ParseError, SingleBlindSeal};
 pub use bp::seals::txout::TxoSeal;
-use bp::seals::txout::{BlindSeal, CloseMethod, ExplicitSeal, SealTxid, VerifyError, Witness};
+use bp::seals::txout::{
+    BlindSeal, CloseMethod, ExplicitSeal, SealTxid, TxPtr, VerifyError, Witness,
+};
 pub use bp::seals::SecretSeal;
 use bp::{dbc, Outpoint, Tx, Txid, Vout};
-use commit_verify::{mpc, Conceal};
+use commit_verify::{mpc, Conceal, DigestExt, Sha256};
 use single_use_seals::SealWitness;
-use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+use strict_encoding::{
+    StrictDecode, StrictDeserialize, StrictDumb, StrictEncode, StrictSerialize, StrictType,
+};
 
 use crate::contract::xchain::Impossible;
-use crate::{XChain, XOutpoint, LIB_NAME_RGB};
+use crate::{Layer1, RevealSeals, XChain, XOutpoint, LIB_NAME_RGB};
 
 pub type GenesisSeal = SingleBlindSeal<Method>;
 pub type GraphSeal = ChainBlindSeal<Method>;
 
 pub type OutputSeal = ExplicitSeal<Txid, Method>;
+/// Output seal which may reference a witness transaction not known yet (for
+/// instance, not signed or not constructed).
+pub type OutputSealDraft = ExplicitSeal<TxPtr, Method>;
 
 pub type XWitnessId = XChain<Txid>;
 
 pub type XGenesisSeal = XChain<GenesisSeal>;
 pub type XGraphSeal = XChain<GraphSeal>;
 pub type XOutputSeal = XChain<OutputSeal>;
+pub type XOutputSealDraft = XChain<OutputSealDraft>;
 
 pub trait ExposedSeal:
     Debug
@@ -67,31 +80,30 @@ impl ExposedSeal for GraphSeal {}
 
 impl ExposedSeal for GenesisSeal {}
 
+/// Seals defined on layers 1 which are not based on transaction outputs
+/// (available with `prime-seals` feature) have no transaction outpoint: for
+/// them [`TxoSeal::txid`] and [`TxoSeal::outpoint`] return `None`, while
+/// [`TxoSeal::txid_or`] and [`TxoSeal::outpoint_or`] use the provided
+/// transaction id, as for witness-based seals.
 impl<Seal: TxoSeal> TxoSeal for XChain<Seal> {
-    fn method(&self) -> CloseMethod {
-        match self {
-            XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.method(),
-            XChain::Other(_) => unreachable!(),
-        }
-    }
+    fn method(&self) -> CloseMethod { self.as_reduced_unsafe().method() }
 
     fn txid(&self) -> Option<Txid> {
         match self {
             XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.txid(),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => None,
             XChain::Other(_) => unreachable!(),
         }
     }
 
-    fn vout(&self) -> Vout {
-        match self {
-            XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.vout(),
-            XChain::Other(_) => unreachable!(),
-        }
-    }
+    fn vout(&self) -> Vout { self.as_reduced_unsafe().vout() }
 
     fn outpoint(&self) -> Option<Outpoint> {
         match self {
             XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.outpoint(),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => None,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -99,6 +111,8 @@ impl<Seal: TxoSeal> TxoSeal for XChain<Seal> {
     fn txid_or(&self, default_txid: Txid) -> Txid {
         match self {
             XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.txid_or(default_txid),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => default_txid,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -106,13 +120,61 @@ impl<Seal: TxoSeal> TxoSeal for XChain<Seal> {
     fn outpoint_or(&self, default_txid: Txid) -> Outpoint {
         match self {
             XChain::Bitcoin(seal) | XChain::Liquid(seal) => seal.outpoint_or(default_txid),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(seal) | XChain::Prime(seal) => Outpoint::new(default_txid, seal.vout()),
             XChain::Other(_) => unreachable!(),
         }
     }
 }
 
-/*
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Error indicating that a witness-based seal can't be resolved into an
+/// outpoint since the witness belongs to a different layer 1.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display("seal defined on {seal} can't be resolved using witness from {witness}")]
+pub struct LayerMismatch {
+    pub seal: Layer1,
+    pub witness: Layer1,
+}
+
+impl<Seal: TxoSeal> XChain<Seal> {
+    /// Returns transaction outpoint of the seal together with the layer 1 it
+    /// is defined on. If the seal is witness-based (i.e. doesn't contain an
+    /// explicit txid) or is defined on a layer 1 not based on transaction
+    /// outputs returns `None`.
+    ///
+    /// Unlike [`TxoSeal::outpoint`] the method doesn't lose information about
+    /// the seal layer 1.
+    pub fn outpoint(&self) -> Option<(Layer1, Outpoint)> {
+        TxoSeal::outpoint(self).map(|outpoint| (self.layer1(), outpoint))
+    }
+
+    /// Returns transaction outpoint of the seal together with the layer 1 it
+    /// is defined on, using witness id to resolve witness-based seals.
+    ///
+    /// # Errors
+    ///
+    /// If the seal is witness-based and the witness belongs to a different
+    /// layer 1.
+    pub fn outpoint_or(&self, witness_id: XWitnessId) -> Result<(Layer1, Outpoint), LayerMismatch> {
+        let layer1 = self.layer1();
+        if let Some(outpoint) = TxoSeal::outpoint(self) {
+            return Ok((layer1, outpoint));
+        }
+        if witness_id.layer1() != layer1 {
+            return Err(LayerMismatch {
+                seal: layer1,
+                witness: witness_id.layer1(),
+            });
+        }
+        Ok((layer1, TxoSeal::outpoint_or(self, *witness_id.as_reduced_unsafe())))
+    }
+}
+
+/// Seal preimage, used by the layer 1s which do not define seals as
+/// bitcoin-style transaction outputs.
+#[cfg(feature = "prime-seals")]
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref, BorrowSlice, Hex, Index, RangeOps)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
 #[cfg_attr(
@@ -120,8 +182,79 @@ impl<Seal: TxoSeal> TxoSeal for XChain<Seal> {
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", transparent)
 )]
-pub struct SealPreimage(Bytes32);
- */
+pub struct SealPreimage(
+    #[from]
+    #[from([u8; 32])]
+    Bytes32,
+);
+
+/// Source of deterministic blinding factors, allowing to re-create seals
+/// (for instance, from a wallet seed) without keeping them in a database.
+pub trait BlindingDeriver {
+    /// Derives blinding factor for the seal with a given index.
+    fn blinding(&self, index: u64) -> u64;
+}
+
+/// Blinding deriver computing blinding factors as a tagged hash of a
+/// caller-supplied 32-byte secret and the seal index.
+#[derive(Clone, Eq, PartialEq)]
+pub struct SecretBlindingDeriver([u8; 32]);
+
+impl Debug for SecretBlindingDeriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBlindingDeriver(..)")
+    }
+}
+
+impl SecretBlindingDeriver {
+    pub const TAG: &'static str = "urn:lnp-bp:rgb:seal-blinding#2024-06-20";
+
+    pub fn new(secret: [u8; 32]) -> Self { Self(secret) }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretBlindingDeriver {
+    fn zeroize(&mut self) { self.0.zeroize(); }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretBlindingDeriver {
+    fn drop(&mut self) { zeroize::Zeroize::zeroize(self) }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretBlindingDeriver {}
+
+impl BlindingDeriver for SecretBlindingDeriver {
+    fn blinding(&self, index: u64) -> u64 {
+        let mut hasher = Sha256::from_tag(Self::TAG);
+        hasher.input_raw(&self.0);
+        hasher.input_raw(&index.to_le_bytes());
+        let hash = hasher.finish();
+        let mut blinding = [0u8; 8];
+        blinding.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(blinding)
+    }
+}
+
+impl XGraphSeal {
+    /// Constructs seal for a given outpoint, using blinding factor derived
+    /// with the `deriver` for the seal `index`.
+    pub fn with_derived_blinding(
+        layer1: Layer1,
+        method: CloseMethod,
+        outpoint: Outpoint,
+        deriver: &impl BlindingDeriver,
+        index: u64,
+    ) -> Self {
+        XChain::with(layer1, GraphSeal {
+            method,
+            txid: TxPtr::Txid(outpoint.txid),
+            vout: outpoint.vout,
+            blinding: deriver.blinding(index),
+        })
+    }
+}
 
 impl From<XChain<GenesisSeal>> for XOutpoint {
     #[inline]
@@ -136,12 +269,49 @@ impl XChain<GenesisSeal> {
     pub fn to_outpoint(&self) -> XOutpoint { self.map_ref(GenesisSeal::to_outpoint).into() }
 }
 
+/// Error converting graph seal into a genesis seal, since the graph seal
+/// references the witness transaction, which doesn't exist for genesis.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display("seal {0} references witness transaction and can't be used in genesis")]
+pub struct WitnessSeal(pub XGraphSeal);
+
+impl XGraphSeal {
+    /// Detects whether the seal is defined relative to the witness
+    /// transaction, i.e. doesn't contain an explicit txid.
+    pub fn requires_witness(&self) -> bool { self.as_reduced_unsafe().txid == TxPtr::WitnessTx }
+}
+
+impl From<XGenesisSeal> for XGraphSeal {
+    #[inline]
+    fn from(seal: XGenesisSeal) -> Self { seal.transmutate() }
+}
+
+impl TryFrom<XGraphSeal> for XGenesisSeal {
+    type Error = WitnessSeal;
+
+    fn try_from(seal: XGraphSeal) -> Result<Self, Self::Error> {
+        seal.try_map_ref(|s| match s.txid {
+            TxPtr::WitnessTx => Err(WitnessSeal(seal)),
+            TxPtr::Txid(txid) => Ok(GenesisSeal {
+                method: s.method,
+                txid,
+                vout: s.vout,
+                blinding: s.blinding,
+            }),
+        })
+    }
+}
+
 impl<U: ExposedSeal> XChain<U> {
-    pub fn method(self) -> CloseMethod
+    /// Returns seal closing method, or `None` if the seal is defined on a
+    /// layer 1 which doesn't use bitcoin-style transaction outputs.
+    pub fn method(self) -> Option<CloseMethod>
     where U: TxoSeal {
         match self {
-            XChain::Bitcoin(seal) => seal.method(),
-            XChain::Liquid(seal) => seal.method(),
+            XChain::Bitcoin(seal) => Some(seal.method()),
+            XChain::Liquid(seal) => Some(seal.method()),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => None,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -149,36 +319,30 @@ impl<U: ExposedSeal> XChain<U> {
     #[inline]
     pub fn to_output_seal(self) -> Option<XOutputSeal>
     where U: TxoSeal {
-        Some(match self {
-            XChain::Bitcoin(seal) => {
-                let outpoint = seal.outpoint()?;
-                XChain::Bitcoin(ExplicitSeal::new(seal.method(), outpoint))
-            }
-            XChain::Liquid(seal) => {
-                let outpoint = seal.outpoint()?;
-                XChain::Liquid(ExplicitSeal::new(seal.method(), outpoint))
-            }
-            XChain::Other(_) => unreachable!(),
-        })
+        let method = self.method()?;
+        let (layer1, outpoint) = self.outpoint()?;
+        Some(XChain::with(layer1, ExplicitSeal::new(method, outpoint)))
     }
 
     pub fn try_to_output_seal(self, witness_id: XWitnessId) -> Result<XOutputSeal, Self>
     where U: TxoSeal {
-        self.to_output_seal()
-            .or(match (self, witness_id) {
-                (XChain::Bitcoin(seal), XWitnessId::Bitcoin(txid)) => {
-                    Some(XChain::Bitcoin(ExplicitSeal::new(seal.method(), seal.outpoint_or(txid))))
-                }
-                (XChain::Liquid(seal), XWitnessId::Liquid(txid)) => {
-                    Some(XChain::Liquid(ExplicitSeal::new(seal.method(), seal.outpoint_or(txid))))
-                }
-                _ => None,
-            })
-            .ok_or(self)
+        let method = self.method().ok_or(self)?;
+        let (layer1, outpoint) = self.outpoint_or(witness_id).map_err(|_| self)?;
+        Ok(XChain::with(layer1, ExplicitSeal::new(method, outpoint)))
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+/// Seal with a revealed outpoint, but with the blinding factor replaced by
+/// its hash.
+///
+/// Allows to prove which transaction output carries the state without
+/// disclosing the blinding factor, and thus without making it possible to
+/// link the seal to its concealed form ([`SecretSeal`]).
+///
+/// NB: This is not a consensus object and it doesn't participate in any
+/// operation or node commitments; it is a companion structure used for
+/// selective disclosure only.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
 #[cfg_attr(
@@ -186,6 +350,170 @@ impl<U: ExposedSeal> XChain<U> {
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", rename_all = "camelCase")
 )]
+pub struct SemiRevealedSeal {
+    pub layer1: Layer1,
+    pub method: CloseMethod,
+    pub txid: TxPtr,
+    pub vout: Vout,
+    pub blinding_hash: Bytes32,
+}
+
+impl SemiRevealedSeal {
+    pub const TAG: &'static str = "urn:lnp-bp:rgb:seal-blinding-hash#2024-06-24";
+
+    /// Computes hash of the seal blinding factor.
+    pub fn blinding_hash(blinding: u64) -> Bytes32 {
+        let mut hasher = Sha256::from_tag(Self::TAG);
+        hasher.input_raw(&blinding.to_le_bytes());
+        Bytes32::from_byte_array(hasher.finish())
+    }
+
+    /// Verifies that the semi-revealed seal matches the fully revealed one.
+    pub fn verify(&self, full: &XGraphSeal) -> bool { *self == Self::from(*full) }
+}
+
+impl StrictSerialize for SemiRevealedSeal {}
+impl StrictDeserialize for SemiRevealedSeal {}
+
+impl From<XGraphSeal> for SemiRevealedSeal {
+    fn from(seal: XGraphSeal) -> Self {
+        let layer1 = seal.layer1();
+        let seal = seal.as_reduced_unsafe();
+        SemiRevealedSeal {
+            layer1,
+            method: seal.method,
+            txid: seal.txid,
+            vout: seal.vout,
+            blinding_hash: Self::blinding_hash(seal.blinding),
+        }
+    }
+}
+
+/// Proof that a concealed seal (for instance, the one provided in an invoice)
+/// hides a specific transaction outpoint.
+///
+/// The proof contains the seal outpoint and closing method together with the
+/// blinding factor, which is the opening of the seal commitment. Like
+/// [`SemiRevealedSeal`], it is not a consensus object.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct OutpointBlindProof {
+    pub layer1: Layer1,
+    pub method: CloseMethod,
+    pub outpoint: Outpoint,
+    pub blinding: u64,
+}
+
+impl StrictSerialize for OutpointBlindProof {}
+impl StrictDeserialize for OutpointBlindProof {}
+
+// The proof is `Copy`, thus it can't be zeroized on drop; the blinding has to
+// be wiped explicitly.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for OutpointBlindProof {
+    fn zeroize(&mut self) { self.blinding.zeroize(); }
+}
+
+impl OutpointBlindProof {
+    /// Reconstructs the seal from the proof data.
+    pub fn to_genesis_seal(&self) -> XGenesisSeal {
+        XChain::with(self.layer1, GenesisSeal {
+            method: self.method,
+            txid: self.outpoint.txid,
+            vout: self.outpoint.vout,
+            blinding: self.blinding,
+        })
+    }
+
+    /// Reconstructs the graph seal from the proof data.
+    pub fn to_graph_seal(&self) -> XGraphSeal {
+        XChain::with(self.layer1, GraphSeal {
+            method: self.method,
+            txid: TxPtr::Txid(self.outpoint.txid),
+            vout: self.outpoint.vout,
+            blinding: self.blinding,
+        })
+    }
+
+    /// Verifies that the concealed seal commits to the outpoint from the
+    /// proof, either as a genesis or as a graph seal, which are concealed
+    /// differently. Both comparisons are performed in constant time.
+    pub fn verify(&self, secret: &XChain<SecretSeal>) -> bool {
+        let genesis = self.to_genesis_seal().to_secret_seal().ct_eq(secret);
+        let graph = self.to_graph_seal().to_secret_seal().ct_eq(secret);
+        genesis | graph
+    }
+}
+
+impl XGraphSeal {
+    /// Produces proof of the outpoint hidden by the concealed form of this
+    /// seal. Returns `None` if the seal is defined relative to the witness
+    /// transaction, and thus doesn't have an explicit outpoint.
+    pub fn blind_proof(&self) -> Option<OutpointBlindProof> {
+        let seal = self.as_reduced_unsafe();
+        let TxPtr::Txid(txid) = seal.txid else {
+            return None;
+        };
+        Some(OutpointBlindProof {
+            layer1: self.layer1(),
+            method: seal.method,
+            outpoint: Outpoint::new(txid, seal.vout),
+            blinding: seal.blinding,
+        })
+    }
+}
+
+/// Error converting output seal draft into an output seal, since the draft
+/// still references an unresolved witness transaction.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display("output seal draft references witness transaction which is not resolved yet")]
+pub struct UnresolvedWitness(pub XOutputSealDraft);
+
+impl XOutputSealDraft {
+    /// Resolves the draft into an output seal, using the provided txid in
+    /// place of the witness transaction reference. If the draft already has
+    /// an explicit txid, it is kept and the provided one is ignored.
+    pub fn resolve(self, witness_txid: Txid) -> XOutputSeal {
+        self.map(|seal| {
+            let txid = match seal.txid {
+                TxPtr::WitnessTx => witness_txid,
+                TxPtr::Txid(txid) => txid,
+            };
+            ExplicitSeal::new(seal.method, Outpoint::new(txid, seal.vout))
+        })
+    }
+}
+
+impl From<XOutputSeal> for XOutputSealDraft {
+    fn from(seal: XOutputSeal) -> Self {
+        seal.map(|seal| OutputSealDraft {
+            method: seal.method,
+            txid: TxPtr::Txid(seal.txid),
+            vout: seal.vout,
+        })
+    }
+}
+
+impl TryFrom<XOutputSealDraft> for XOutputSeal {
+    type Error = UnresolvedWitness;
+
+    fn try_from(draft: XOutputSealDraft) -> Result<Self, Self::Error> {
+        draft.try_map_ref(|seal| match seal.txid {
+            TxPtr::WitnessTx => Err(UnresolvedWitness(draft)),
+            TxPtr::Txid(txid) => Ok(ExplicitSeal::new(seal.method, Outpoint::new(txid, seal.vout))),
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
 #[display("{height}@{timestamp}")]
 pub struct WitnessPos {
     height: u32,
@@ -201,6 +529,41 @@ impl WitnessPos {
     }
 
     pub fn height(&self) -> NonZeroU32 { NonZeroU32::new(self.height).expect("invariant") }
+
+    pub fn timestamp(&self) -> i64 { self.timestamp }
+}
+
+/// Error parsing witness position or witness ordering string.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum WitnessOrdParseError {
+    /// witness position '{0}' must have 'height@timestamp' format.
+    InvalidFormat(String),
+
+    /// witness position '{0}' contains invalid number. Details: {1}
+    InvalidNumber(String, ParseIntError),
+
+    /// witness position '{0}' has zero height or a timestamp preceding bitcoin
+    /// genesis.
+    InvalidPos(String),
+}
+
+impl FromStr for WitnessPos {
+    type Err = WitnessOrdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (height, timestamp) = s
+            .split_once('@')
+            .ok_or_else(|| WitnessOrdParseError::InvalidFormat(s.to_owned()))?;
+        let height = height
+            .parse()
+            .map_err(|err| WitnessOrdParseError::InvalidNumber(s.to_owned(), err))?;
+        let timestamp = timestamp
+            .parse()
+            .map_err(|err| WitnessOrdParseError::InvalidNumber(s.to_owned(), err))?;
+        WitnessPos::new(height, timestamp)
+            .ok_or_else(|| WitnessOrdParseError::InvalidPos(s.to_owned()))
+    }
 }
 
 impl PartialOrd for WitnessPos {
@@ -216,11 +579,6 @@ impl Ord for WitnessPos {
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display, From)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB, tags = order)]
-#[cfg_attr(
-    feature = "serde",
-    derive(Serialize, Deserialize),
-    serde(crate = "serde_crate", rename_all = "camelCase", untagged)
-)]
 pub enum WitnessOrd {
     #[from]
     #[display(inner)]
@@ -231,12 +589,196 @@ pub enum WitnessOrd {
     OffChain,
 }
 
+impl StrictSerialize for WitnessOrd {}
+impl StrictDeserialize for WitnessOrd {}
+
 impl WitnessOrd {
     pub fn with_mempool_or_height(height: u32, timestamp: i64) -> Self {
         WitnessPos::new(height, timestamp)
             .map(WitnessOrd::OnChain)
             .unwrap_or(WitnessOrd::OffChain)
     }
+
+    /// Returns sort key for the witness ordering, which is monotone with the
+    /// [`Ord`] implementation and thus can be used as a database index.
+    /// Off-chain witnesses have priority greater than any of on-chain ones.
+    pub fn to_priority(&self) -> u64 {
+        match self {
+            // Timestamp is guaranteed to be positive by the `WitnessPos` constructor
+            WitnessOrd::OnChain(pos) => pos.timestamp as u64,
+            WitnessOrd::OffChain => u64::MAX,
+        }
+    }
+}
+
+impl FromStr for WitnessOrd {
+    type Err = WitnessOrdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "offchain" => Ok(WitnessOrd::OffChain),
+            s => WitnessPos::from_str(s).map(WitnessOrd::OnChain),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod _serde {
+    use serde_crate::de::Error;
+    use serde_crate::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "serde_crate", rename = "WitnessPos")]
+    struct WitnessPosRepr {
+        height: u32,
+        timestamp: i64,
+    }
+
+    // Binary serializers keep the untagged representation of the earlier
+    // releases, such that the already serialized data remain readable.
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "serde_crate", rename = "WitnessOrd", untagged)]
+    enum WitnessOrdRepr {
+        OnChain(WitnessPosRepr),
+        OffChain,
+    }
+
+    impl From<WitnessOrd> for WitnessOrdRepr {
+        fn from(ord: WitnessOrd) -> Self {
+            match ord {
+                WitnessOrd::OnChain(pos) => WitnessOrdRepr::OnChain(pos.into()),
+                WitnessOrd::OffChain => WitnessOrdRepr::OffChain,
+            }
+        }
+    }
+
+    impl WitnessOrdRepr {
+        fn validate<E: Error>(self) -> Result<WitnessOrd, E> {
+            match self {
+                WitnessOrdRepr::OnChain(pos) => pos.validate().map(WitnessOrd::OnChain),
+                WitnessOrdRepr::OffChain => Ok(WitnessOrd::OffChain),
+            }
+        }
+    }
+
+    impl From<WitnessPos> for WitnessPosRepr {
+        fn from(pos: WitnessPos) -> Self {
+            WitnessPosRepr {
+                height: pos.height,
+                timestamp: pos.timestamp,
+            }
+        }
+    }
+
+    impl WitnessPosRepr {
+        fn validate<E: Error>(self) -> Result<WitnessPos, E> {
+            WitnessPos::new(self.height, self.timestamp).ok_or_else(|| {
+                E::custom(WitnessOrdParseError::InvalidPos(format!(
+                    "{}@{}",
+                    self.height, self.timestamp
+                )))
+            })
+        }
+    }
+
+    impl Serialize for WitnessPos {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+            if serializer.is_human_readable() {
+                serializer.serialize_str(&self.to_string())
+            } else {
+                WitnessPosRepr::from(*self).serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for WitnessPos {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+            if deserializer.is_human_readable() {
+                let s = String::deserialize(deserializer)?;
+                Self::from_str(&s).map_err(D::Error::custom)
+            } else {
+                WitnessPosRepr::deserialize(deserializer)?.validate()
+            }
+        }
+    }
+
+    impl Serialize for WitnessOrd {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+            if serializer.is_human_readable() {
+                serializer.serialize_str(&self.to_string())
+            } else {
+                WitnessOrdRepr::from(*self).serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for WitnessOrd {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+            if deserializer.is_human_readable() {
+                let s = String::deserialize(deserializer)?;
+                Self::from_str(&s).map_err(D::Error::custom)
+            } else {
+                WitnessOrdRepr::deserialize(deserializer)?.validate()
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        // Serde derivations used by the releases before the string-based
+        // human-readable representation was introduced
+        #[derive(Serialize, Deserialize)]
+        #[serde(crate = "serde_crate", rename_all = "camelCase")]
+        struct LegacyWitnessPos {
+            height: u32,
+            timestamp: i64,
+        }
+
+        #[derive(Serialize, Deserialize)]
+        #[serde(crate = "serde_crate", rename_all = "camelCase", untagged)]
+        enum LegacyWitnessOrd {
+            OnChain(LegacyWitnessPos),
+            OffChain,
+        }
+
+        #[test]
+        fn witness_ord_binary_repr() {
+            let legacy = [
+                LegacyWitnessOrd::OnChain(LegacyWitnessPos {
+                    height: 840000,
+                    timestamp: 1713571767,
+                }),
+                LegacyWitnessOrd::OffChain,
+            ];
+            let ords = [
+                WitnessOrd::OnChain(WitnessPos::new(840000, 1713571767).unwrap()),
+                WitnessOrd::OffChain,
+            ];
+            for (legacy, ord) in legacy.iter().zip(ords) {
+                let data = serde_json::to_value(legacy).unwrap();
+                assert_eq!(serde_json::to_value(WitnessOrdRepr::from(ord)).unwrap(), data);
+                let repr = serde_json::from_value::<WitnessOrdRepr>(data).unwrap();
+                assert_eq!(repr.validate::<serde_json::Error>().unwrap(), ord);
+            }
+
+            let invalid = LegacyWitnessOrd::OnChain(LegacyWitnessPos {
+                height: 0,
+                timestamp: 1713571767,
+            });
+            let repr =
+                serde_json::from_value::<WitnessOrdRepr>(serde_json::to_value(invalid).unwrap())
+                    .unwrap();
+            assert!(repr.validate::<serde_json::Error>().is_err());
+        }
+    }
 }
 
 pub type XWitnessTx<X = Impossible> = XChain<Tx, X>;
@@ -246,6 +788,10 @@ impl XWitnessTx {
         match self {
             Self::Bitcoin(tx) => XWitnessId::Bitcoin(tx.txid()),
             Self::Liquid(tx) => XWitnessId::Liquid(tx.txid()),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(tx) => XWitnessId::Abraxas(tx.txid()),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(tx) => XWitnessId::Prime(tx.txid()),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -256,6 +802,10 @@ impl<Dbc: dbc::Proof> XChain<Witness<Dbc>> {
         match self {
             Self::Bitcoin(w) => XWitnessId::Bitcoin(w.txid),
             Self::Liquid(w) => XWitnessId::Liquid(w.txid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(w) => XWitnessId::Abraxas(w.txid),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(w) => XWitnessId::Prime(w.txid),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -265,9 +815,16 @@ impl<Dbc: dbc::Proof, Seal: TxoSeal> SealWitness<Seal> for XChain<Witness<Dbc>>
     type Message = mpc::Commitment;
     type Error = VerifyError<Dbc::Error>;
 
+    /// Witnesses of layers 1 which are not based on transaction outputs can't
+    /// close any seal, and the verification fails with
+    /// [`VerifyError::WitnessNotClosingSeal`].
     fn verify_seal(&self, seal: &Seal, msg: &Self::Message) -> Result<(), Self::Error> {
         match self {
             Self::Bitcoin(witness) | Self::Liquid(witness) => witness.verify_seal(seal, msg),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(witness) | Self::Prime(witness) => {
+                Err(VerifyError::WitnessNotClosingSeal(seal.outpoint_or(witness.txid)))
+            }
             Self::Other(_) => unreachable!(),
         }
     }
@@ -282,6 +839,13 @@ impl<Dbc: dbc::Proof, Seal: TxoSeal> SealWitness<Seal> for XChain<Witness<Dbc>>
     {
         match self {
             Self::Bitcoin(witness) | Self::Liquid(witness) => witness.verify_many_seals(seals, msg),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(witness) | Self::Prime(witness) => match seals.into_iter().next() {
+                Some(seal) => {
+                    Err(VerifyError::WitnessNotClosingSeal(seal.outpoint_or(witness.txid)))
+                }
+                None => Ok(()),
+            },
             Self::Other(_) => unreachable!(),
         }
     }
@@ -293,10 +857,151 @@ impl<Id: SealTxid> XChain<BlindSeal<Id>> {
     pub fn to_secret_seal(&self) -> XChain<SecretSeal> { self.conceal() }
 }
 
+/// Equality check which takes the same time independently of the position of
+/// the first differing byte, such that comparison with a secret value doesn't
+/// leak it through a timing side channel.
+pub trait CtEq {
+    /// Checks equality in constant time.
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+// Tests substitute the comparison with a wrapper counting its calls
+#[cfg(not(test))]
+use ct_eq_bytes as secret_seal_eq;
+#[cfg(test)]
+use test::counted_ct_eq_bytes as secret_seal_eq;
+
+fn ct_eq_bytes<const LEN: usize>(a: &[u8; LEN], b: &[u8; LEN]) -> bool {
+    let diff = a.iter().zip(b).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    core::hint::black_box(diff) == 0
+}
+
+impl CtEq for SecretSeal {
+    fn ct_eq(&self, other: &Self) -> bool {
+        secret_seal_eq(&self.to_byte_array(), &other.to_byte_array())
+    }
+}
+
+impl CtEq for XChain<SecretSeal> {
+    fn ct_eq(&self, other: &Self) -> bool {
+        // Layer 1 is not secret, thus it may be compared directly
+        self.layer1() == other.layer1() && self.as_reduced_unsafe().ct_eq(other.as_reduced_unsafe())
+    }
+}
+
+/// Registry of revealed graph seals, indexed by their concealed form, which
+/// allows to reveal seals in operations constructed by other parties.
+///
+/// Seals pointing to a known transaction id are also indexed by the concealed
+/// form of the matching genesis seal, which commits to the txid differently.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", from = "Vec<XGraphSeal>", into = "Vec<XGraphSeal>")
+)]
+pub struct SealRegistry(BTreeMap<XChain<SecretSeal>, XGraphSeal>);
+
+impl SealRegistry {
+    pub fn new() -> Self { default!() }
+
+    pub fn len(&self) -> usize { self.iter().count() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Adds revealed seal to the registry, returning its concealed form.
+    pub fn insert(&mut self, reveal: XGraphSeal) -> XChain<SecretSeal> {
+        let secret = reveal.to_secret_seal();
+        self.0.insert(secret, reveal);
+        if let Ok(genesis_seal) = XGenesisSeal::try_from(reveal) {
+            self.0.insert(genesis_seal.conceal(), reveal);
+        }
+        secret
+    }
+
+    pub fn get(&self, secret: &XChain<SecretSeal>) -> Option<&XGraphSeal> { self.0.get(secret) }
+
+    pub fn contains(&self, secret: &XChain<SecretSeal>) -> bool { self.0.contains_key(secret) }
+
+    pub fn iter(&self) -> impl Iterator<Item = &XGraphSeal> {
+        self.0
+            .iter()
+            .filter(|(secret, seal)| **secret == seal.to_secret_seal())
+            .map(|(_, seal)| seal)
+    }
+
+    /// Reveals all concealed seals in the `target` known to the registry.
+    ///
+    /// # Returns
+    ///
+    /// Number of revealed seals.
+    pub fn reveal_in(&self, target: &mut impl RevealSeals) -> usize { target.reveal_seals(&self.0) }
+}
+
+impl Extend<XGraphSeal> for SealRegistry {
+    fn extend<T: IntoIterator<Item = XGraphSeal>>(&mut self, iter: T) {
+        for reveal in iter {
+            self.insert(reveal);
+        }
+    }
+}
+
+impl FromIterator<XGraphSeal> for SealRegistry {
+    fn from_iter<T: IntoIterator<Item = XGraphSeal>>(iter: T) -> Self {
+        let mut registry = SealRegistry::new();
+        registry.extend(iter);
+        registry
+    }
+}
+
+impl From<Vec<XGraphSeal>> for SealRegistry {
+    fn from(seals: Vec<XGraphSeal>) -> Self { seals.into_iter().collect() }
+}
+
+impl From<SealRegistry> for Vec<XGraphSeal> {
+    fn from(registry: SealRegistry) -> Self { registry.iter().copied().collect() }
+}
+
+/// Error parsing [`SecretSeal`] string representation.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum SecretSealParseError {
+    /// invalid secret seal baid64 representation. Details: {0}
+    #[from]
+    Baid64(Baid64ParseError),
+
+    /// invalid secret seal hex representation. Details: {0}
+    #[from]
+    Hex(hex::Error),
+}
+
+/// Helpers for the text representation of [`SecretSeal`]s embedded into
+/// invoices.
+pub trait SecretSealExt: Sized {
+    /// Parses secret seal from its baid64 representation, falling back to the
+    /// legacy hex representation for compatibility.
+    fn from_baid64_or_hex(s: &str) -> Result<Self, SecretSealParseError>;
+
+    /// Returns checksum mnemonic of the secret seal, which can be used by users
+    /// to visually verify the seal.
+    fn mnemonic(&self) -> String;
+}
+
+impl SecretSealExt for SecretSeal {
+    fn from_baid64_or_hex(s: &str) -> Result<Self, SecretSealParseError> {
+        if !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit()) {
+            let bytes = Bytes32::from_hex(s)?;
+            return Ok(SecretSeal::from(bytes.to_byte_array()));
+        }
+        SecretSeal::from_baid64_str(s).map_err(SecretSealParseError::from)
+    }
+
+    fn mnemonic(&self) -> String { self.to_baid64_mnemonic() }
+}
+
 #[cfg(test)]
 mod test {
     use amplify::hex::FromHex;
-    use bp::seals::txout::TxPtr;
 
     use super::*;
 
@@ -318,4 +1023,495 @@ mod test {
         );
         assert_eq!(reveal.to_secret_seal(), reveal.conceal())
     }
+
+    fn txid() -> Txid {
+        Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839").unwrap()
+    }
+
+    #[test]
+    fn outpoint_explicit_txid() {
+        let seal = XChain::Liquid(BlindSeal {
+            method: CloseMethod::OpretFirst,
+            blinding: 54683213134637,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+        });
+        let outpoint = Outpoint::new(txid(), Vout::from(2));
+        assert_eq!(seal.outpoint(), Some((Layer1::Liquid, outpoint)));
+        // Explicit txid takes precedence over the witness
+        let witness_id = XWitnessId::Bitcoin(
+            Txid::from_hex("1e9a1c0e1ec4a4c5a0ba0c9a0b8f56e3ad7a5dc1f2e3b7c7d4e1a0b2c3d4e5f6")
+                .unwrap(),
+        );
+        assert_eq!(seal.outpoint_or(witness_id), Ok((Layer1::Liquid, outpoint)));
+    }
+
+    #[test]
+    fn outpoint_witness_vout() {
+        let seal = XChain::Bitcoin(BlindSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 54683213134637,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(1),
+        });
+        assert_eq!(seal.outpoint(), None);
+        assert_eq!(
+            seal.outpoint_or(XWitnessId::Bitcoin(txid())),
+            Ok((Layer1::Bitcoin, Outpoint::new(txid(), Vout::from(1))))
+        );
+        assert_eq!(
+            seal.outpoint_or(XWitnessId::Liquid(txid())),
+            Err(LayerMismatch {
+                seal: Layer1::Bitcoin,
+                witness: Layer1::Liquid,
+            })
+        );
+    }
+
+    #[test]
+    fn output_seal_draft_resolve() {
+        let witness_txid =
+            Txid::from_hex("1e9a1c0e1ec4a4c5a0ba0c9a0b8f56e3ad7a5dc1f2e3b7c7d4e1a0b2c3d4e5f6")
+                .unwrap();
+        let draft = XOutputSealDraft::Liquid(OutputSealDraft {
+            method: CloseMethod::OpretFirst,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(3),
+        });
+        let seal = draft.resolve(witness_txid);
+        assert_eq!(
+            seal,
+            XOutputSeal::Liquid(ExplicitSeal::new(
+                CloseMethod::OpretFirst,
+                Outpoint::new(witness_txid, Vout::from(3))
+            ))
+        );
+        assert_eq!(XOutputSeal::try_from(XOutputSealDraft::from(seal)), Ok(seal));
+
+        let explicit = XOutputSealDraft::Bitcoin(OutputSealDraft {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(1),
+        });
+        let expected = XOutputSeal::Bitcoin(ExplicitSeal::new(
+            CloseMethod::TapretFirst,
+            Outpoint::new(txid(), Vout::from(1)),
+        ));
+        assert_eq!(explicit.resolve(witness_txid), expected);
+        assert_eq!(XOutputSeal::try_from(explicit), Ok(expected));
+    }
+
+    #[test]
+    fn output_seal_draft_unresolved() {
+        let draft = XOutputSealDraft::Bitcoin(OutputSealDraft {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(0),
+        });
+        assert_eq!(XOutputSeal::try_from(draft), Err(UnresolvedWitness(draft)));
+    }
+
+    #[test]
+    fn derived_blinding_is_deterministic() {
+        let outpoint = Outpoint::new(txid(), Vout::from(4));
+        let deriver = SecretBlindingDeriver::new([0xA5; 32]);
+        let seal = |deriver: &SecretBlindingDeriver, index| {
+            XGraphSeal::with_derived_blinding(
+                Layer1::Bitcoin,
+                CloseMethod::TapretFirst,
+                outpoint,
+                deriver,
+                index,
+            )
+        };
+
+        let secret = seal(&deriver, 7).to_secret_seal();
+        assert_eq!(secret, seal(&SecretBlindingDeriver::new([0xA5; 32]), 7).to_secret_seal());
+        assert_ne!(secret, seal(&deriver, 8).to_secret_seal());
+        assert_ne!(secret, seal(&SecretBlindingDeriver::new([0x5A; 32]), 7).to_secret_seal());
+        assert_eq!(seal(&deriver, 7).outpoint(), Some((Layer1::Bitcoin, outpoint)));
+    }
+
+    #[test]
+    fn secret_seal_parse() {
+        const BAID64: &str = "utxob:lD72u61i-sxCEKth-vqjH0mI-kcEwa1Q-fbnPLon-tDtXveO-keHh0";
+        let secret = SecretSeal::from_baid64_or_hex(BAID64).unwrap();
+        assert_eq!(secret.to_string(), BAID64);
+        assert_eq!(secret.mnemonic(), secret.to_baid64_mnemonic());
+
+        let hex = format!("{:x}", Bytes32::from(secret.to_baid64_payload()));
+        assert_eq!(SecretSeal::from_baid64_or_hex(&hex).unwrap(), secret);
+        assert_eq!(SecretSeal::from_baid64_or_hex(&hex.to_uppercase()).unwrap(), secret);
+
+        assert!(matches!(
+            SecretSeal::from_baid64_or_hex(&hex[2..]),
+            Err(SecretSealParseError::Hex(_))
+        ));
+        assert!(matches!(
+            SecretSeal::from_baid64_or_hex("utxob:lD72u61i-sxCEKth-vqjH0mI-kcEwa1Q-fbnPLon"),
+            Err(SecretSealParseError::Baid64(_))
+        ));
+        assert!(matches!(
+            SecretSeal::from_baid64_or_hex("not a seal"),
+            Err(SecretSealParseError::Baid64(_))
+        ));
+        assert!(matches!(SecretSeal::from_baid64_or_hex(""), Err(SecretSealParseError::Baid64(_))));
+    }
+
+    #[test]
+    fn method_txo() {
+        let seal = XChain::Liquid(GraphSeal {
+            method: CloseMethod::OpretFirst,
+            blinding: 54683213134637,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(0),
+        });
+        assert_eq!(seal.method(), Some(CloseMethod::OpretFirst));
+        assert_eq!(seal.to_output_seal(), None);
+        assert_eq!(
+            seal.try_to_output_seal(XWitnessId::Liquid(txid())),
+            Ok(XOutputSeal::Liquid(ExplicitSeal::new(
+                CloseMethod::OpretFirst,
+                Outpoint::new(txid(), Vout::from(0))
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "prime-seals")]
+    fn method_non_txo() {
+        use bp::dbc::opret::OpretProof;
+
+        for seal in [
+            XChain::Abraxas(GraphSeal {
+                method: CloseMethod::TapretFirst,
+                blinding: 54683213134637,
+                txid: TxPtr::Txid(txid()),
+                vout: Vout::from(1),
+            }),
+            XChain::Prime(GraphSeal {
+                method: CloseMethod::OpretFirst,
+                blinding: 54683213134637,
+                txid: TxPtr::WitnessTx,
+                vout: Vout::from(1),
+            }),
+        ] {
+            assert_eq!(seal.method(), None);
+            assert_eq!(seal.to_output_seal(), None);
+            let witness_id = XWitnessId::with(seal.layer1(), txid());
+            assert_eq!(seal.try_to_output_seal(witness_id), Err(seal));
+
+            // Non-TXO seals have no outpoint, but don't panic
+            assert_eq!(TxoSeal::txid(&seal), None);
+            assert_eq!(TxoSeal::outpoint(&seal), None);
+            assert_eq!(seal.outpoint(), None);
+            assert_eq!(
+                seal.outpoint_or(witness_id),
+                Ok((seal.layer1(), Outpoint::new(txid(), Vout::from(1))))
+            );
+            assert_eq!(seal.try_as_bp(), None);
+
+            // Witnesses from non-TXO layers can't close seals
+            let tx = Tx::strict_dumb();
+            let outpoint = Outpoint::new(tx.txid(), Vout::from(1));
+            let witness: XChain<Witness<OpretProof>> =
+                XChain::with(seal.layer1(), Witness::with(tx, OpretProof::default()));
+            let msg = mpc::Commitment::strict_dumb();
+            assert!(matches!(
+                witness.verify_seal(&seal, &msg),
+                Err(VerifyError::WitnessNotClosingSeal(found)) if found == outpoint
+            ));
+            assert!(matches!(
+                witness.verify_many_seals([&seal], &msg),
+                Err(VerifyError::WitnessNotClosingSeal(found)) if found == outpoint
+            ));
+        }
+    }
+
+    #[test]
+    fn witness_ord_str_round_trip() {
+        let pos = WitnessPos::new(840000, 1713571767).unwrap();
+        for ord in [WitnessOrd::OnChain(pos), WitnessOrd::OffChain] {
+            assert_eq!(WitnessOrd::from_str(&ord.to_string()), Ok(ord));
+        }
+        assert_eq!(WitnessOrd::OnChain(pos).to_string(), "840000@1713571767");
+        assert_eq!(WitnessOrd::OffChain.to_string(), "offchain");
+        assert_eq!(WitnessPos::from_str("840000@1713571767"), Ok(pos));
+
+        assert!(matches!(
+            WitnessOrd::from_str("840000"),
+            Err(WitnessOrdParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            WitnessOrd::from_str("840000@x"),
+            Err(WitnessOrdParseError::InvalidNumber(..))
+        ));
+        assert!(matches!(
+            WitnessOrd::from_str("0@1713571767"),
+            Err(WitnessOrdParseError::InvalidPos(_))
+        ));
+        assert!(matches!(
+            WitnessOrd::from_str("840000@100"),
+            Err(WitnessOrdParseError::InvalidPos(_))
+        ));
+    }
+
+    #[test]
+    fn witness_ord_priority() {
+        let early = WitnessOrd::OnChain(WitnessPos::new(1, 1231006505).unwrap());
+        let late = WitnessOrd::OnChain(WitnessPos::new(840000, 1713571767).unwrap());
+        let ords = [early, late, WitnessOrd::OffChain];
+        for a in ords {
+            for b in ords {
+                assert_eq!(a.cmp(&b), a.to_priority().cmp(&b.to_priority()));
+            }
+        }
+        assert_eq!(WitnessOrd::OffChain.to_priority(), u64::MAX);
+    }
+
+    #[test]
+    fn seal_registry_round_trip() {
+        use amplify::confinement::Confined;
+
+        use crate::{
+            AssignRights, AssignmentType, Assignments, Transition, TypedAssigns, VoidState,
+        };
+
+        let seals = (0..4u32)
+            .map(|vout| {
+                XChain::Bitcoin(GraphSeal {
+                    method: CloseMethod::TapretFirst,
+                    blinding: 54683213134637 + vout as u64,
+                    txid: TxPtr::Txid(txid()),
+                    vout: Vout::from(vout),
+                })
+            })
+            .collect::<Vec<_>>();
+        let assigns = seals
+            .iter()
+            .map(|seal| AssignRights::revealed(*seal, VoidState::default()))
+            .collect::<Vec<_>>();
+        let mut transition = Transition::strict_dumb();
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => TypedAssigns::Declarative(Confined::try_from(assigns).unwrap())
+            })
+            .unwrap(),
+        );
+        let original = transition.to_strict_serialized::<{ usize::MAX }>().unwrap();
+
+        transition.assignments.conceal_seals();
+        assert_ne!(transition.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+
+        // Registry knowing only a part of the seals reveals just them
+        let partial = SealRegistry::from_iter(seals[..2].iter().copied());
+        assert_eq!(partial.get(&seals[0].to_secret_seal()), Some(&seals[0]));
+        assert_eq!(partial.get(&seals[3].to_secret_seal()), None);
+        let mut partially_revealed = transition.clone();
+        assert_eq!(partial.reveal_in(&mut partially_revealed.assignments), 2);
+        assert_ne!(
+            partially_revealed
+                .to_strict_serialized::<{ usize::MAX }>()
+                .unwrap(),
+            original
+        );
+
+        let registry = SealRegistry::from(seals.clone());
+        assert_eq!(registry.len(), 4);
+        assert_eq!(registry.reveal_in(&mut transition.assignments), 4);
+        assert_eq!(transition.to_strict_serialized::<{ usize::MAX }>().unwrap(), original);
+        // Nothing is left to reveal
+        assert_eq!(registry.reveal_in(&mut transition.assignments), 0);
+        assert_eq!(SealRegistry::from(Vec::from(registry.clone())), registry);
+    }
+
+    #[test]
+    fn genesis_graph_round_trip() {
+        let genesis = XGenesisSeal::Liquid(GenesisSeal {
+            method: CloseMethod::OpretFirst,
+            txid: txid(),
+            vout: Vout::from(3),
+            blinding: 54683213134637,
+        });
+        let graph = XGraphSeal::from(genesis);
+        assert!(!graph.requires_witness());
+        assert_eq!(graph.layer1(), Layer1::Liquid);
+        assert_eq!(graph.as_reduced_unsafe().method, CloseMethod::OpretFirst);
+        assert_eq!(graph.as_reduced_unsafe().blinding, 54683213134637);
+        assert_eq!(graph.as_reduced_unsafe().txid, TxPtr::Txid(txid()));
+        assert_eq!(XGenesisSeal::try_from(graph), Ok(genesis));
+        assert_eq!(graph.to_secret_seal(), genesis.transmutate().to_secret_seal());
+    }
+
+    #[test]
+    fn graph_witness_to_genesis() {
+        let graph = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(1),
+            blinding: 54683213134637,
+        });
+        assert!(graph.requires_witness());
+        assert_eq!(XGenesisSeal::try_from(graph), Err(WitnessSeal(graph)));
+    }
+
+    #[test]
+    fn semi_revealed_verify() {
+        let seal = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+            blinding: 54683213134637,
+        });
+        let semi = SemiRevealedSeal::from(seal);
+        assert!(semi.verify(&seal));
+
+        let mut tampered = semi;
+        tampered.blinding_hash = SemiRevealedSeal::blinding_hash(54683213134638);
+        assert!(!tampered.verify(&seal));
+
+        let other = seal.map(|mut s| {
+            s.blinding += 1;
+            s
+        });
+        assert!(!semi.verify(&other));
+        let other = XGraphSeal::Liquid(*seal.as_reduced_unsafe());
+        assert!(!semi.verify(&other));
+
+        let data = semi.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(SemiRevealedSeal::from_strict_serialized::<{ usize::MAX }>(data).unwrap(), semi);
+    }
+
+    #[test]
+    fn outpoint_blind_proof() {
+        let seal = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+            blinding: 54683213134637,
+        });
+        let secret = seal.to_secret_seal();
+        let proof = seal.blind_proof().unwrap();
+        assert!(proof.verify(&secret));
+        // The same outpoint concealed as a genesis seal
+        let genesis_seal = XGenesisSeal::try_from(seal).unwrap();
+        assert!(proof.verify(&genesis_seal.to_secret_seal()));
+
+        let mut tampered = proof;
+        tampered.layer1 = Layer1::Liquid;
+        assert!(!tampered.verify(&secret));
+        let mut tampered = proof;
+        tampered.method = CloseMethod::OpretFirst;
+        assert!(!tampered.verify(&secret));
+        let mut tampered = proof;
+        tampered.outpoint.vout = Vout::from(3);
+        assert!(!tampered.verify(&secret));
+        let mut tampered = proof;
+        tampered.outpoint.txid =
+            Txid::from_hex("1e9a1c0e1ec4a4c5a0ba0c9a0b8f56e3ad7a5dc1f2e3b7c7d4e1a0b2c3d4e5f6")
+                .unwrap();
+        assert!(!tampered.verify(&secret));
+        let mut tampered = proof;
+        tampered.blinding += 1;
+        assert!(!tampered.verify(&secret));
+
+        let data = proof.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(
+            OutpointBlindProof::from_strict_serialized::<{ usize::MAX }>(data).unwrap(),
+            proof
+        );
+
+        let witness_seal = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(2),
+            blinding: 54683213134637,
+        });
+        assert_eq!(witness_seal.blind_proof(), None);
+    }
+
+    thread_local! {
+        static CT_EQ_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    pub(super) fn counted_ct_eq_bytes<const LEN: usize>(a: &[u8; LEN], b: &[u8; LEN]) -> bool {
+        CT_EQ_CALLS.with(|calls| calls.set(calls.get() + 1));
+        ct_eq_bytes(a, b)
+    }
+
+    fn ct_eq_calls() -> usize { CT_EQ_CALLS.with(|calls| calls.get()) }
+
+    #[test]
+    fn secret_seal_ct_eq() {
+        let seal = |vout: u32, blinding: u64| {
+            XGraphSeal::Bitcoin(GraphSeal {
+                method: CloseMethod::TapretFirst,
+                txid: TxPtr::Txid(txid()),
+                vout: Vout::from(vout),
+                blinding,
+            })
+        };
+        let secret = seal(2, 1).to_secret_seal();
+        assert!(secret.ct_eq(&seal(2, 1).to_secret_seal()));
+        assert!(!secret.ct_eq(&seal(2, 2).to_secret_seal()));
+        assert!(!secret.ct_eq(&seal(3, 1).to_secret_seal()));
+        let liquid = XChain::Liquid(*secret.as_reduced_unsafe());
+        assert!(!secret.ct_eq(&liquid));
+        for other in [seal(2, 1), seal(2, 2), seal(3, 1)] {
+            let other = other.to_secret_seal();
+            assert_eq!(secret.ct_eq(&other), secret == other);
+        }
+    }
+
+    #[test]
+    fn ct_eq_in_verification() {
+        use crate::{AssignRights, VoidState};
+
+        let seal = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+            blinding: 54683213134637,
+        });
+        let secret = seal.to_secret_seal();
+
+        let calls = ct_eq_calls();
+        assert!(seal.blind_proof().unwrap().verify(&secret));
+        assert!(ct_eq_calls() > calls);
+
+        let revealed = AssignRights::revealed(seal, VoidState::default());
+        let mut concealed = AssignRights::ConfidentialSeal {
+            seal: secret,
+            state: VoidState::default(),
+            lock: default!(),
+        };
+        let calls = ct_eq_calls();
+        assert_eq!(concealed, revealed);
+        assert!(ct_eq_calls() > calls);
+
+        let calls = ct_eq_calls();
+        assert_eq!(concealed.reveal_seals(&bmap! { secret => seal }), 1);
+        assert!(ct_eq_calls() > calls);
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize_blinding() {
+        use zeroize::Zeroize;
+
+        let mut deriver = SecretBlindingDeriver::new([0xA5; 32]);
+        deriver.zeroize();
+        assert_eq!(deriver, SecretBlindingDeriver::new([0; 32]));
+
+        let seal = XGraphSeal::Bitcoin(GraphSeal {
+            method: CloseMethod::TapretFirst,
+            txid: TxPtr::Txid(txid()),
+            vout: Vout::from(2),
+            blinding: 54683213134637,
+        });
+        let mut proof = seal.blind_proof().unwrap();
+        proof.zeroize();
+        assert_eq!(proof.blinding, 0);
+        assert_eq!(proof.outpoint.vout, Vout::from(2));
+    }
 }
@@ -28,6 +28,7 @@ use strict_encoding::{StrictDecode, StrictDumb, StrictEncode};
 
 use crate::{
     ConcealedAttach, ConcealedData, ConcealedValue, RevealedAttach, RevealedData, RevealedValue,
+    LIB_NAME_RGB,
 };
 
 /// Marker trait for types of state which are just a commitment to the actual
@@ -78,18 +79,37 @@ pub enum StateType {
 
 /// Categories of the state
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB, tags = custom, dumb = Self::Void)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", rename_all = "camelCase", tag = "type")
 )]
 pub enum RevealedState {
+    #[strict_type(tag = 0)]
     Void,
+    #[strict_type(tag = 1)]
     Fungible(RevealedValue),
+    #[strict_type(tag = 2)]
     Structured(RevealedData),
+    #[strict_type(tag = 3)]
     Attachment(RevealedAttach),
 }
 
+impl Conceal for RevealedState {
+    type Concealed = ConcealedState;
+
+    fn conceal(&self) -> Self::Concealed {
+        match self {
+            RevealedState::Void => ConcealedState::Void,
+            RevealedState::Fungible(value) => ConcealedState::Fungible(value.conceal()),
+            RevealedState::Structured(data) => ConcealedState::Structured(data.conceal()),
+            RevealedState::Attachment(attach) => ConcealedState::Attachment(attach.conceal()),
+        }
+    }
+}
+
 impl RevealedState {
     pub fn state_type(&self) -> StateType {
         match self {
@@ -102,6 +122,8 @@ impl RevealedState {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB, tags = custom, dumb = Self::Void)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -109,9 +131,13 @@ impl RevealedState {
 )]
 #[allow(clippy::large_enum_variant)]
 pub enum ConcealedState {
+    #[strict_type(tag = 0)]
     Void,
+    #[strict_type(tag = 1)]
     Fungible(ConcealedValue),
+    #[strict_type(tag = 2)]
     Structured(ConcealedData),
+    #[strict_type(tag = 3)]
     Attachment(ConcealedAttach),
 }
 
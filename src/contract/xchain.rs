@@ -30,15 +30,19 @@ use amplify::confinement::TinyOrdSet;
 use bp::{Bp, Outpoint};
 use commit_verify::{Conceal, StrictHash};
 use strict_encoding::{
-    DecodeError, DefineUnion, ReadTuple, ReadUnion, StrictDecode, StrictDumb, StrictEncode,
-    StrictEnum, StrictSum, StrictType, StrictUnion, TypedRead, TypedWrite, VariantError,
-    WriteUnion,
+    DecodeError, DefineUnion, ReadTuple, ReadUnion, StrictDecode, StrictDeserialize, StrictDumb,
+    StrictEncode, StrictEnum, StrictSerialize, StrictSum, StrictType, StrictUnion, TypedRead,
+    TypedWrite, VariantError, WriteUnion,
 };
 
 use crate::{Layer1, OutputSeal, XOutputSeal, LIB_NAME_RGB};
 
 pub const XCHAIN_BITCOIN_PREFIX: &str = "bc";
 pub const XCHAIN_LIQUID_PREFIX: &str = "lq";
+#[cfg(feature = "prime-seals")]
+pub const XCHAIN_ABRAXAS_PREFIX: &str = "ab";
+#[cfg(feature = "prime-seals")]
+pub const XCHAIN_PRIME_PREFIX: &str = "pr";
 
 #[derive(Wrapper, WrapperMut, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, From)]
 #[wrapper(Deref, FromStr, Display)]
@@ -102,14 +106,20 @@ mod _serde {
 pub enum AltLayer1 {
     #[strict_type(dumb)]
     Liquid = 1,
-    // Abraxas = 0x10,
-    // Prime = 0x11,
+    #[cfg(feature = "prime-seals")]
+    Abraxas = 0x10,
+    #[cfg(feature = "prime-seals")]
+    Prime = 0x11,
 }
 
 impl AltLayer1 {
     pub fn layer1(&self) -> Layer1 {
         match self {
             AltLayer1::Liquid => Layer1::Liquid,
+            #[cfg(feature = "prime-seals")]
+            AltLayer1::Abraxas => Layer1::Abraxas,
+            #[cfg(feature = "prime-seals")]
+            AltLayer1::Prime => Layer1::Prime,
         }
     }
 }
@@ -189,6 +199,12 @@ pub enum XChain<T, X = Impossible> {
 
     Liquid(T),
 
+    #[cfg(feature = "prime-seals")]
+    Abraxas(T),
+
+    #[cfg(feature = "prime-seals")]
+    Prime(T),
+
     Other(X),
 }
 
@@ -205,6 +221,18 @@ impl<T: Ord, X: Ord> Ord for XChain<T, X> {
             (_, Self::Bitcoin(_)) => Ordering::Less,
             (Self::Liquid(_), _) => Ordering::Greater,
             (_, Self::Liquid(_)) => Ordering::Less,
+            #[cfg(feature = "prime-seals")]
+            (Self::Abraxas(t1), Self::Abraxas(t2)) => t1.cmp(t2),
+            #[cfg(feature = "prime-seals")]
+            (Self::Prime(t1), Self::Prime(t2)) => t1.cmp(t2),
+            #[cfg(feature = "prime-seals")]
+            (Self::Abraxas(_), _) => Ordering::Greater,
+            #[cfg(feature = "prime-seals")]
+            (_, Self::Abraxas(_)) => Ordering::Less,
+            #[cfg(feature = "prime-seals")]
+            (Self::Prime(_), _) => Ordering::Greater,
+            #[cfg(feature = "prime-seals")]
+            (_, Self::Prime(_)) => Ordering::Less,
             (Self::Other(x1), Self::Other(x2)) => x1.cmp(x2),
         }
     }
@@ -225,12 +253,20 @@ where T: StrictDumb + StrictType
 impl<T> StrictSum for XChain<T>
 where T: StrictDumb + StrictType
 {
+    #[cfg(not(feature = "prime-seals"))]
     const ALL_VARIANTS: &'static [(u8, &'static str)] = &[(0x00, "bitcoin"), (0x01, "liquid")];
+    #[cfg(feature = "prime-seals")]
+    const ALL_VARIANTS: &'static [(u8, &'static str)] =
+        &[(0x00, "bitcoin"), (0x01, "liquid"), (0x10, "abraxas"), (0x11, "prime")];
 
     fn variant_name(&self) -> &'static str {
         match self {
             XChain::Bitcoin(_) => Self::ALL_VARIANTS[0].1,
             XChain::Liquid(_) => Self::ALL_VARIANTS[1].1,
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) => Self::ALL_VARIANTS[2].1,
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(_) => Self::ALL_VARIANTS[3].1,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -248,11 +284,19 @@ where T: StrictDumb + StrictEncode
         writer.write_union::<Self>(|w| {
             let w = w
                 .define_newtype::<T>(vname!(Self::ALL_VARIANTS[0].1))
-                .define_newtype::<T>(vname!(Self::ALL_VARIANTS[1].1))
-                .complete();
+                .define_newtype::<T>(vname!(Self::ALL_VARIANTS[1].1));
+            #[cfg(feature = "prime-seals")]
+            let w = w
+                .define_newtype::<T>(vname!(Self::ALL_VARIANTS[2].1))
+                .define_newtype::<T>(vname!(Self::ALL_VARIANTS[3].1));
+            let w = w.complete();
             Ok(match self {
                 XChain::Bitcoin(t) => w.write_newtype(vname!(Self::ALL_VARIANTS[0].1), t)?,
                 XChain::Liquid(t) => w.write_newtype(vname!(Self::ALL_VARIANTS[1].1), t)?,
+                #[cfg(feature = "prime-seals")]
+                XChain::Abraxas(t) => w.write_newtype(vname!(Self::ALL_VARIANTS[2].1), t)?,
+                #[cfg(feature = "prime-seals")]
+                XChain::Prime(t) => w.write_newtype(vname!(Self::ALL_VARIANTS[3].1), t)?,
                 XChain::Other(_) => unreachable!(),
             }
             .complete())
@@ -268,34 +312,78 @@ where T: StrictDumb + StrictDecode
                 r.read_tuple(|r| r.read_field().map(Self::Bitcoin))
             }
             x if x == Self::ALL_VARIANTS[1].1 => r.read_tuple(|r| r.read_field().map(Self::Liquid)),
+            #[cfg(feature = "prime-seals")]
+            x if x == Self::ALL_VARIANTS[2].1 => {
+                r.read_tuple(|r| r.read_field().map(Self::Abraxas))
+            }
+            #[cfg(feature = "prime-seals")]
+            x if x == Self::ALL_VARIANTS[3].1 => r.read_tuple(|r| r.read_field().map(Self::Prime)),
             _ => unreachable!(),
         })
     }
 }
+impl<T> StrictSerialize for XChain<T> where T: StrictDumb + StrictEncode {}
+impl<T> StrictDeserialize for XChain<T> where T: StrictDumb + StrictDecode {}
 
 impl<T> XChain<T, Impossible> {
     pub fn layer1(&self) -> Layer1 {
         match self {
             XChain::Bitcoin(_) => Layer1::Bitcoin,
             XChain::Liquid(_) => Layer1::Liquid,
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) => Layer1::Abraxas,
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(_) => Layer1::Prime,
             XChain::Other(_) => unreachable!(),
         }
     }
 
+    /// # Panics
+    ///
+    /// If the value doesn't belong to a bitcoin protocol layer 1 (see
+    /// [`XChain::try_as_bp`] for a non-panicking version).
     pub fn as_bp(&self) -> Bp<&T>
+    where for<'a> &'a T: StrictDumb + StrictEncode + StrictDecode {
+        self.try_as_bp().expect("non-bitcoin protocol layer 1")
+    }
+
+    /// Returns reference to the value in bitcoin protocol representation, or
+    /// `None` if it belongs to some other layer 1.
+    pub fn try_as_bp(&self) -> Option<Bp<&T>>
     where for<'a> &'a T: StrictDumb + StrictEncode + StrictDecode {
         match self {
-            XChain::Bitcoin(t) => Bp::Bitcoin(t),
-            XChain::Liquid(t) => Bp::Liquid(t),
+            XChain::Bitcoin(t) => Some(Bp::Bitcoin(t)),
+            XChain::Liquid(t) => Some(Bp::Liquid(t)),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => None,
             XChain::Other(_) => unreachable!(),
         }
     }
 
+    /// # Panics
+    ///
+    /// If the value doesn't belong to a bitcoin protocol layer 1 (see
+    /// [`XChain::reduce_to_bp`] for a non-panicking version).
     pub fn into_bp(self) -> Bp<T>
     where T: StrictDumb + StrictEncode + StrictDecode {
         match self {
             XChain::Bitcoin(t) => Bp::Bitcoin(t),
             XChain::Liquid(t) => Bp::Liquid(t),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => panic!("non-bitcoin protocol layer 1"),
+            XChain::Other(_) => unreachable!(),
+        }
+    }
+
+    /// Converts the value into bitcoin protocol representation, returning
+    /// `None` if it belongs to some other layer 1.
+    pub fn reduce_to_bp(self) -> Option<Bp<T>>
+    where T: StrictDumb + StrictEncode + StrictDecode {
+        match self {
+            XChain::Bitcoin(t) => Some(Bp::Bitcoin(t)),
+            XChain::Liquid(t) => Some(Bp::Liquid(t)),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => None,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -303,6 +391,8 @@ impl<T> XChain<T, Impossible> {
     pub fn as_reduced_unsafe(&self) -> &T {
         match self {
             XChain::Bitcoin(t) | XChain::Liquid(t) => t,
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(t) | XChain::Prime(t) => t,
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -312,6 +402,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => XChain::Bitcoin(f(t)),
             Self::Liquid(t) => XChain::Liquid(f(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => XChain::Abraxas(f(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => XChain::Prime(f(t)),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -321,6 +415,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => XChain::Bitcoin(f(t)),
             Self::Liquid(t) => XChain::Liquid(f(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => XChain::Abraxas(f(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => XChain::Prime(f(t)),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -331,6 +429,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => f(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f(t).map(XChain::Prime),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -341,6 +443,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => f(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f(t).map(XChain::Prime),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -351,6 +457,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => f(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f(t).map(XChain::Prime),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -361,6 +471,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             Self::Bitcoin(t) => f(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f(t).map(XChain::Prime),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -373,6 +487,10 @@ impl<T> XChain<T, Impossible> {
         match self {
             XChain::Bitcoin(t) => Box::new(t.into_iter().map(XChain::Bitcoin)),
             XChain::Liquid(t) => Box::new(t.into_iter().map(XChain::Liquid)),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(t) => Box::new(t.into_iter().map(XChain::Abraxas)),
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(t) => Box::new(t.into_iter().map(XChain::Prime)),
             Self::Other(_) => unreachable!(),
         }
     }
@@ -383,14 +501,24 @@ impl<T, X> XChain<T, X> {
         match layer1 {
             Layer1::Bitcoin => XChain::Bitcoin(data.into()),
             Layer1::Liquid => XChain::Liquid(data.into()),
+            #[cfg(feature = "prime-seals")]
+            Layer1::Abraxas => XChain::Abraxas(data.into()),
+            #[cfg(feature = "prime-seals")]
+            Layer1::Prime => XChain::Prime(data.into()),
         }
     }
 
     pub fn is_bitcoin(&self) -> bool { matches!(self, XChain::Bitcoin(_)) }
     pub fn is_liquid(&self) -> bool { matches!(self, XChain::Liquid(_)) }
+    #[cfg(feature = "prime-seals")]
+    pub fn is_abraxas(&self) -> bool { matches!(self, XChain::Abraxas(_)) }
+    #[cfg(feature = "prime-seals")]
+    pub fn is_prime(&self) -> bool { matches!(self, XChain::Prime(_)) }
     pub fn is_bp(&self) -> bool {
         match self {
             XChain::Bitcoin(_) | XChain::Liquid(_) => true,
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(_) | XChain::Prime(_) => false,
             XChain::Other(_) => false,
         }
     }
@@ -400,6 +528,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => XChain::Bitcoin(f1(t)),
             Self::Liquid(t) => XChain::Liquid(f1(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => XChain::Abraxas(f1(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => XChain::Prime(f1(t)),
             Self::Other(x) => XChain::Other(f2(x)),
         }
     }
@@ -413,6 +545,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => XChain::Bitcoin(f1(t)),
             Self::Liquid(t) => XChain::Liquid(f1(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => XChain::Abraxas(f1(t)),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => XChain::Prime(f1(t)),
             Self::Other(x) => XChain::Other(f2(x)),
         }
     }
@@ -427,6 +563,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => f1(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f1(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f1(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f1(t).map(XChain::Prime),
             Self::Other(x) => f2(x).map(XChain::Other),
         }
     }
@@ -441,6 +581,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => f1(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f1(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f1(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f1(t).map(XChain::Prime),
             Self::Other(x) => f2(x).map(XChain::Other),
         }
     }
@@ -455,6 +599,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => f1(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f1(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f1(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f1(t).map(XChain::Prime),
             Self::Other(x) => f2(x).map(XChain::Other),
         }
     }
@@ -469,6 +617,10 @@ impl<T, X> XChain<T, X> {
         match self {
             Self::Bitcoin(t) => f1(t).map(XChain::Bitcoin),
             Self::Liquid(t) => f1(t).map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            Self::Abraxas(t) => f1(t).map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            Self::Prime(t) => f1(t).map(XChain::Prime),
             Self::Other(x) => f2(x).map(XChain::Other),
         }
     }
@@ -487,6 +639,10 @@ impl<T> XChain<Option<T>, Impossible> {
         match self {
             XChain::Bitcoin(inner) => inner.map(XChain::Bitcoin),
             XChain::Liquid(inner) => inner.map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(inner) => inner.map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(inner) => inner.map(XChain::Prime),
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -499,6 +655,10 @@ impl<I: Iterator> Iterator for XChain<I, Impossible> {
         match self {
             XChain::Bitcoin(t) => t.next().map(XChain::Bitcoin),
             XChain::Liquid(t) => t.next().map(XChain::Liquid),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(t) => t.next().map(XChain::Abraxas),
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(t) => t.next().map(XChain::Prime),
             XChain::Other(_) => unreachable!(),
         }
     }
@@ -506,7 +666,17 @@ impl<I: Iterator> Iterator for XChain<I, Impossible> {
 
 #[derive(Clone, Debug, Display, Error, From)]
 pub enum XChainParseError<E: Debug + Display> {
-    #[display("unknown chain prefix '{0}'; only 'bc:' and 'lq:' are currently supported")]
+    #[cfg_attr(
+        not(feature = "prime-seals"),
+        display("unknown chain prefix '{0}'; only 'bc:' and 'lq:' are currently supported")
+    )]
+    #[cfg_attr(
+        feature = "prime-seals",
+        display(
+            "unknown chain prefix '{0}'; only 'bc:', 'lq:', 'ab:' and 'pr:' are currently \
+             supported"
+        )
+    )]
     UnknownPrefix(String),
 
     #[from]
@@ -534,6 +704,13 @@ where
                     .parse()
                     .map(XChain::Liquid)
                     .map_err(XChainParseError::from),
+                #[cfg(feature = "prime-seals")]
+                XCHAIN_ABRAXAS_PREFIX => s
+                    .parse()
+                    .map(XChain::Abraxas)
+                    .map_err(XChainParseError::from),
+                #[cfg(feature = "prime-seals")]
+                XCHAIN_PRIME_PREFIX => s.parse().map(XChain::Prime).map_err(XChainParseError::from),
                 unknown => Err(XChainParseError::UnknownPrefix(unknown.to_owned())),
             }
         } else {
@@ -553,7 +730,70 @@ where
         match self {
             XChain::Bitcoin(t) => write!(f, "{XCHAIN_BITCOIN_PREFIX}:{t}"),
             XChain::Liquid(t) => write!(f, "{XCHAIN_LIQUID_PREFIX}:{t}"),
+            #[cfg(feature = "prime-seals")]
+            XChain::Abraxas(t) => write!(f, "{XCHAIN_ABRAXAS_PREFIX}:{t}"),
+            #[cfg(feature = "prime-seals")]
+            XChain::Prime(t) => write!(f, "{XCHAIN_PRIME_PREFIX}:{t}"),
             XChain::Other(x) => Display::fmt(x, f),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::Confined;
+    use amplify::hex::FromHex;
+    use bp::{Txid, Vout};
+
+    use super::*;
+
+    fn outpoint() -> Outpoint {
+        let txid =
+            Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839")
+                .unwrap();
+        Outpoint::new(txid, Vout::from(2))
+    }
+
+    fn tag(chain: XChain<Outpoint>) -> u8 {
+        chain.to_strict_serialized::<{ usize::MAX }>().unwrap()[0]
+    }
+
+    #[test]
+    fn bp_tags() {
+        assert_eq!(tag(XChain::Bitcoin(outpoint())), 0x00);
+        assert_eq!(tag(XChain::Liquid(outpoint())), 0x01);
+    }
+
+    #[test]
+    #[cfg(not(feature = "prime-seals"))]
+    fn prime_tags_rejected() {
+        for tag in [0x10u8, 0x11] {
+            let mut data = XChain::Bitcoin(outpoint())
+                .to_strict_serialized::<{ usize::MAX }>()
+                .unwrap()
+                .into_inner();
+            data[0] = tag;
+            let data = Confined::try_from(data).unwrap();
+            assert!(XChain::<Outpoint>::from_strict_serialized::<{ usize::MAX }>(data).is_err());
+        }
+        assert!(XChain::<Outpoint>::from_str(&format!("ab:{}", outpoint())).is_err());
+        assert!(XChain::<Outpoint>::from_str(&format!("pr:{}", outpoint())).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "prime-seals")]
+    fn prime_tags() {
+        assert_eq!(tag(XChain::Abraxas(outpoint())), 0x10);
+        assert_eq!(tag(XChain::Prime(outpoint())), 0x11);
+
+        for chain in [XChain::Abraxas(outpoint()), XChain::Prime(outpoint())] {
+            let data = chain.to_strict_serialized::<{ usize::MAX }>().unwrap();
+            let data = Confined::try_from(data.into_inner()).unwrap();
+            assert_eq!(XChain::from_strict_serialized::<{ usize::MAX }>(data).unwrap(), chain);
+            assert_eq!(XChain::from_str(&chain.to_string()).unwrap(), chain);
+            assert_eq!(chain.reduce_to_bp(), None);
+        }
+        assert_eq!(XChain::Abraxas(outpoint()).to_string(), format!("ab:{}", outpoint()));
+        assert_eq!(XChain::Prime(outpoint()).to_string(), format!("pr:{}", outpoint()));
+    }
+}
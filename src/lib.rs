@@ -79,6 +79,16 @@ pub const LIB_NAME_RGB: &str = "RGB";
 )]
 pub struct Ffv(u16);
 
+impl Ffv {
+    /// Version adding explicit metadata kinds to schemas (see
+    /// [`schema::MetaKind`]) and schema binding to state transitions (see
+    /// [`Transition::schema_id`]).
+    pub const V1: Ffv = Ffv(1);
+
+    /// The most recent version supported by this library.
+    pub const LATEST: Ffv = Ffv::V1;
+}
+
 mod _ffv {
     use strict_encoding::{DecodeError, ReadTuple, StrictDecode, TypedRead};
 
@@ -87,7 +97,7 @@ mod _ffv {
     impl StrictDecode for Ffv {
         fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
             let ffv = reader.read_tuple(|r| r.read_field().map(Self))?;
-            if ffv != Ffv::default() {
+            if ffv > Ffv::LATEST {
                 Err(DecodeError::DataIntegrityError(format!(
                     "unsupported fast-forward version code belonging to a future RGB version. \
                      Please update your software, or, if the problem persists, contact your \
@@ -32,4 +32,7 @@ pub use operations::{
     OpFullType, OpSchema, OpType, TransitionSchema, ValencySchema, ValencyType,
 };
 pub use schema::{ExtensionType, GlobalStateType, MetaType, Schema, SchemaId, TransitionType};
-pub use state::{FungibleType, GlobalStateSchema, MediaType, OwnedStateSchema};
+pub use state::{
+    FungibleType, GlobalStateSchema, MediaType, MediaTypeError, MetaKind, OwnedStateSchema,
+    ValueBounds, TIMESTAMP_MIN,
+};
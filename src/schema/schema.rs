@@ -22,6 +22,7 @@
 
 use std::cmp::Ordering;
 use std::fmt::{self, Display, Formatter};
+use std::io;
 use std::str::FromStr;
 
 use aluvm::library::LibId;
@@ -32,12 +33,14 @@ use commit_verify::{
     CommitEncode, CommitEngine, CommitId, CommitmentId, DigestExt, ReservedBytes, Sha256,
 };
 use strict_encoding::{
-    StrictDecode, StrictDeserialize, StrictEncode, StrictSerialize, StrictType, TypeName,
+    DecodeError, StrictDecode, StrictDeserialize, StrictEncode, StrictProduct, StrictSerialize,
+    StrictStruct, StrictType, TypeName, TypedRead, TypedWrite, WriteStruct,
 };
 use strict_types::SemId;
 
 use super::{
-    AssignmentType, ExtensionSchema, GenesisSchema, OwnedStateSchema, TransitionSchema, ValencyType,
+    AssignmentType, ExtensionSchema, GenesisSchema, MetaKind, OwnedStateSchema, TransitionSchema,
+    ValencyType,
 };
 use crate::{impl_serde_baid64, Ffv, GlobalStateSchema, Identity, Occurrences, LIB_NAME_RGB};
 
@@ -147,8 +150,15 @@ impl Display for SchemaId {
 
 impl_serde_baid64!(SchemaId);
 
+/// RGB contract schema.
+///
+/// The strict encoding of the schema depends on its fast-forward version:
+/// [`Schema::meta_kinds`] are encoded (and committed to) only starting from
+/// [`Ffv::V1`], which is used automatically once any metadata kind is
+/// declared. Schemas without metadata kinds keep the original layout and
+/// schema id, and remain readable by the software unaware of metadata kinds.
 #[derive(Clone, Eq, Debug)]
-#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[derive(StrictDumb)]
 #[strict_type(lib = LIB_NAME_RGB)]
 #[cfg_attr(
     feature = "serde",
@@ -164,6 +174,10 @@ pub struct Schema {
     pub developer: Identity,
 
     pub meta_types: TinyOrdMap<MetaType, SemId>,
+    /// Kinds of the metadata types requiring additional validation; metadata
+    /// types absent from this map are [`MetaKind::Generic`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_empty"))]
+    pub meta_kinds: TinyOrdMap<MetaType, MetaKind>,
     pub global_types: TinyOrdMap<GlobalStateType, GlobalStateSchema>,
     pub owned_types: TinyOrdMap<AssignmentType, OwnedStateSchema>,
     pub valency_types: TinyOrdSet<ValencyType>,
@@ -174,11 +188,15 @@ pub struct Schema {
     pub reserved: ReservedBytes<8, 0>,
 }
 
+#[cfg(feature = "serde")]
+fn is_empty(meta_kinds: &TinyOrdMap<MetaType, MetaKind>) -> bool { meta_kinds.is_empty() }
+
 impl CommitEncode for Schema {
     type CommitmentId = SchemaId;
 
     fn commit_encode(&self, e: &mut CommitEngine) {
-        e.commit_to_serialized(&self.ffv);
+        let ffv = self.encoding_ffv();
+        e.commit_to_serialized(&ffv);
         e.commit_to_serialized(&self.flags);
 
         e.commit_to_serialized(&self.name);
@@ -186,6 +204,9 @@ impl CommitEncode for Schema {
         e.commit_to_serialized(&self.developer);
 
         e.commit_to_map(&self.meta_types);
+        if ffv >= Ffv::V1 {
+            e.commit_to_map(&self.meta_kinds);
+        }
         e.commit_to_map(&self.global_types);
         e.commit_to_map(&self.owned_types);
         e.commit_to_set(&self.valency_types);
@@ -209,6 +230,88 @@ impl PartialOrd for Schema {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
+impl StrictType for Schema {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_RGB;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Schema")) }
+}
+impl StrictProduct for Schema {}
+impl StrictStruct for Schema {
+    const ALL_FIELDS: &'static [&'static str] = &[
+        "ffv",
+        "flags",
+        "name",
+        "timestamp",
+        "developer",
+        "metaTypes",
+        "metaKinds",
+        "globalTypes",
+        "ownedTypes",
+        "valencyTypes",
+        "genesis",
+        "extensions",
+        "transitions",
+        "reserved",
+    ];
+}
+impl StrictEncode for Schema {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let ffv = self.encoding_ffv();
+        writer.write_struct::<Self>(|w| {
+            let mut w = w
+                .write_field(fname!("ffv"), &ffv)?
+                .write_field(fname!("flags"), &self.flags)?
+                .write_field(fname!("name"), &self.name)?
+                .write_field(fname!("timestamp"), &self.timestamp)?
+                .write_field(fname!("developer"), &self.developer)?
+                .write_field(fname!("metaTypes"), &self.meta_types)?;
+            if ffv >= Ffv::V1 {
+                w = w.write_field(fname!("metaKinds"), &self.meta_kinds)?;
+            }
+            Ok(w.write_field(fname!("globalTypes"), &self.global_types)?
+                .write_field(fname!("ownedTypes"), &self.owned_types)?
+                .write_field(fname!("valencyTypes"), &self.valency_types)?
+                .write_field(fname!("genesis"), &self.genesis)?
+                .write_field(fname!("extensions"), &self.extensions)?
+                .write_field(fname!("transitions"), &self.transitions)?
+                .write_field(fname!("reserved"), &self.reserved)?
+                .complete())
+        })
+    }
+}
+impl StrictDecode for Schema {
+    // Fields are decoded one by one since the struct reader insists on all of
+    // them being present, while `metaKinds` is absent before `Ffv::V1`.
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let ffv = Ffv::strict_decode(reader)?;
+        let flags = StrictDecode::strict_decode(reader)?;
+        let name = StrictDecode::strict_decode(reader)?;
+        let timestamp = StrictDecode::strict_decode(reader)?;
+        let developer = StrictDecode::strict_decode(reader)?;
+        let meta_types = StrictDecode::strict_decode(reader)?;
+        let meta_kinds = if ffv >= Ffv::V1 {
+            StrictDecode::strict_decode(reader)?
+        } else {
+            none!()
+        };
+        Ok(Schema {
+            ffv,
+            flags,
+            name,
+            timestamp,
+            developer,
+            meta_types,
+            meta_kinds,
+            global_types: StrictDecode::strict_decode(reader)?,
+            owned_types: StrictDecode::strict_decode(reader)?,
+            valency_types: StrictDecode::strict_decode(reader)?,
+            genesis: StrictDecode::strict_decode(reader)?,
+            extensions: StrictDecode::strict_decode(reader)?,
+            transitions: StrictDecode::strict_decode(reader)?,
+            reserved: StrictDecode::strict_decode(reader)?,
+        })
+    }
+}
+
 impl StrictSerialize for Schema {}
 impl StrictDeserialize for Schema {}
 
@@ -216,6 +319,22 @@ impl Schema {
     #[inline]
     pub fn schema_id(&self) -> SchemaId { self.commit_id() }
 
+    /// Returns the kind of the metadata type, which is [`MetaKind::Generic`]
+    /// unless specified otherwise in [`Schema::meta_kinds`].
+    pub fn meta_kind(&self, ty: MetaType) -> MetaKind {
+        self.meta_kinds.get(&ty).copied().unwrap_or_default()
+    }
+
+    /// Fast-forward version used in the schema encoding and commitment, which
+    /// is at least [`Ffv::V1`] if the schema declares metadata kinds.
+    fn encoding_ffv(&self) -> Ffv {
+        if self.meta_kinds.is_empty() {
+            self.ffv
+        } else {
+            self.ffv.max(Ffv::V1)
+        }
+    }
+
     pub fn blank_transition(&self) -> TransitionSchema {
         let mut schema = TransitionSchema::default();
         for id in self.owned_types.keys() {
@@ -281,4 +400,30 @@ mod test {
         );
         assert_eq!(&format!("{less_dumb:-#}"), "RVY0MzUwLSc0dndqJzQ7di13OTR3J2UndkZWVkRocHE");
     }
+
+    #[test]
+    fn meta_kinds_encoding() {
+        let schema = Schema::strict_dumb();
+        let data = schema.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        // Schemas without metadata kinds keep the original layout
+        assert_eq!(&data[..2], &[0, 0]);
+        let decoded = Schema::from_strict_serialized::<{ usize::MAX }>(data.clone()).unwrap();
+        assert_eq!(decoded.ffv, Ffv::default());
+        assert_eq!(decoded.meta_kind(MetaType::with(1)), MetaKind::Generic);
+
+        let mut with_kinds = schema.clone();
+        with_kinds
+            .meta_kinds
+            .insert(MetaType::with(1), MetaKind::Timestamp)
+            .unwrap();
+        assert_ne!(with_kinds.schema_id(), schema.schema_id());
+        let data_v1 = with_kinds.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        assert_eq!(&data_v1[..2], &[1, 0]);
+        // Map length, metadata type and kind
+        assert_eq!(data_v1.len(), data.len() + 4);
+        let decoded = Schema::from_strict_serialized::<{ usize::MAX }>(data_v1).unwrap();
+        assert_eq!(decoded.ffv, Ffv::V1);
+        assert_eq!(decoded.meta_kind(MetaType::with(1)), MetaKind::Timestamp);
+        assert_eq!(decoded.schema_id(), with_kinds.schema_id());
+    }
 }
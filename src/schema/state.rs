@@ -20,12 +20,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
+
 use commit_verify::ReservedBytes;
-use strict_encoding::Primitive;
+use strict_encoding::{Primitive, StrictDumb};
 use strict_types::SemId;
 
 use crate::{StateType, LIB_NAME_RGB};
 
+/// Media type of attachments.
+///
+/// Media types are committed to by their top-level type only: subtypes and
+/// parameters of MIME types are not preserved, such that `image/png` and
+/// `image/jpeg` are both [`MediaType::Image`]. Thus, schemas may restrict
+/// attachments to a top-level category, but not to an exact MIME type. MIME
+/// types with other top-level types (like `chemical/x-pdb`) convert into
+/// [`MediaType::Any`], conforming only to the schemas accepting any media
+/// type.
+///
+/// The top-level category variants are encoded with new tags, while
+/// [`MediaType::Any`] keeps its original one: the state using the categories
+/// can't be decoded by the software unaware of them.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB, tags = repr, into_u8, try_from_u8)]
@@ -37,6 +52,16 @@ use crate::{StateType, LIB_NAME_RGB};
 #[non_exhaustive]
 #[repr(u8)]
 pub enum MediaType {
+    #[display("text/*")]
+    Text = 0x01,
+    #[display("image/*")]
+    Image = 0x02,
+    #[display("audio/*")]
+    Audio = 0x03,
+    #[display("video/*")]
+    Video = 0x04,
+    #[display("application/*")]
+    Application = 0x05,
     #[display("*/*")]
     #[strict_type(dumb)]
     Any = 0xFF,
@@ -44,9 +69,64 @@ pub enum MediaType {
 }
 
 impl MediaType {
-    pub fn conforms(&self, other: &MediaType) -> bool {
-        match (self, other) {
-            (MediaType::Any, MediaType::Any) => true,
+    /// Checks whether the media type conforms to the `pattern`, which may be
+    /// [`MediaType::Any`] matching all media types.
+    pub fn conforms(&self, pattern: &MediaType) -> bool {
+        *pattern == MediaType::Any || self == pattern
+    }
+}
+
+/// Errors parsing [`MediaType`] from a string.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MediaTypeError {
+    /// '{0}' is not a valid media type matching `type/subtype` grammar.
+    Invalid(String),
+
+    /// media type '{0}' has top-level type which is not supported.
+    Unsupported(String),
+}
+
+impl FromStr for MediaType {
+    type Err = MediaTypeError;
+
+    /// Parses media type string, validating its `type/subtype` grammar. Since
+    /// the media type is committed to by its top-level type only, the subtype
+    /// and parameters are validated and dropped. Unlike the conversion from
+    /// [`mime::Mime`], top-level types without a [`MediaType`] category other
+    /// than `*` are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mime = mime::Mime::from_str(s)
+            .ok()
+            .filter(|mime| !mime.subtype().as_str().is_empty())
+            .ok_or_else(|| MediaTypeError::Invalid(s.to_owned()))?;
+        match MediaType::from(&mime) {
+            MediaType::Any if mime.type_() != mime::STAR => {
+                Err(MediaTypeError::Unsupported(s.to_owned()))
+            }
+            media_type => Ok(media_type),
+        }
+    }
+}
+
+/// Converts MIME type into its top-level [`MediaType`] category, dropping the
+/// subtype and parameters. Top-level types without a category convert into
+/// [`MediaType::Any`].
+impl From<&mime::Mime> for MediaType {
+    fn from(mime: &mime::Mime) -> Self {
+        let ty = mime.type_();
+        if ty == mime::TEXT {
+            MediaType::Text
+        } else if ty == mime::IMAGE {
+            MediaType::Image
+        } else if ty == mime::AUDIO {
+            MediaType::Audio
+        } else if ty == mime::VIDEO {
+            MediaType::Video
+        } else if ty == mime::APPLICATION {
+            MediaType::Application
+        } else {
+            MediaType::Any
         }
     }
 }
@@ -64,7 +144,17 @@ pub enum OwnedStateSchema {
     Declarative,
     Fungible(FungibleType),
     Structured(SemId),
+    /// Attachment state which media type must conform to the pattern (see
+    /// [`MediaType::conforms`]). Patterns match top-level media types only,
+    /// not exact MIME types.
     Attachment(MediaType),
+    /// Fungible state which revealed values must fit the bounds. Schemata
+    /// using [`OwnedStateSchema::Fungible`] allow the full range of values.
+    BoundedFungible(FungibleType, ValueBounds),
+    /// Attachment state which media type must conform to the pattern, as for
+    /// [`OwnedStateSchema::Attachment`], and which advertised size must not
+    /// exceed the maximum number of bytes.
+    BoundedAttachment(MediaType, u64),
     // TODO: Computed state (RCP240327A) will be added here
 }
 
@@ -72,9 +162,24 @@ impl OwnedStateSchema {
     pub fn state_type(&self) -> StateType {
         match self {
             OwnedStateSchema::Declarative => StateType::Void,
-            OwnedStateSchema::Fungible(_) => StateType::Fungible,
+            OwnedStateSchema::Fungible(_) | OwnedStateSchema::BoundedFungible(..) => {
+                StateType::Fungible
+            }
             OwnedStateSchema::Structured(_) => StateType::Structured,
-            OwnedStateSchema::Attachment(_) => StateType::Attachment,
+            OwnedStateSchema::Attachment(_) | OwnedStateSchema::BoundedAttachment(..) => {
+                StateType::Attachment
+            }
+        }
+    }
+
+    /// Returns bounds for the revealed values of the fungible state, which
+    /// are the full range of values unless the state is
+    /// [`OwnedStateSchema::BoundedFungible`].
+    pub fn value_bounds(&self) -> Option<ValueBounds> {
+        match self {
+            OwnedStateSchema::Fungible(_) => Some(ValueBounds::FULL),
+            OwnedStateSchema::BoundedFungible(_, bounds) => Some(*bounds),
+            _ => None,
         }
     }
 
@@ -87,6 +192,79 @@ impl OwnedStateSchema {
     }
 }
 
+/// Earliest timestamp allowed in the metadata of [`MetaKind::Timestamp`] kind,
+/// which is the time of the bitcoin genesis block.
+pub const TIMESTAMP_MIN: i64 = 1231006505;
+
+/// Semantic kind of metadata values, defining checks performed by the
+/// validator on top of the semantic type check.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB, tags = repr, into_u8, try_from_u8)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[repr(u8)]
+pub enum MetaKind {
+    /// Metadata without additional checks.
+    #[default]
+    #[display("generic")]
+    Generic = 0,
+    /// UNIX timestamp encoded as `i64`, which must be not earlier than
+    /// [`TIMESTAMP_MIN`] and not later than the validation time plus the
+    /// tolerance configured for the validator.
+    #[display("timestamp")]
+    Timestamp = 1,
+    /// Maximum supply encoded as `u64`. When present in genesis, the total of
+    /// the revealed fungible state issued by genesis and state extensions must
+    /// not exceed it for each of the fungible state types.
+    #[display("max supply")]
+    MaxSupply = 2,
+}
+
+/// Inclusive bounds for the revealed values of fungible state.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display("{min}..={max}")]
+pub struct ValueBounds {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Default for ValueBounds {
+    fn default() -> Self { ValueBounds::FULL }
+}
+
+impl ValueBounds {
+    /// Bounds allowing any value.
+    pub const FULL: Self = ValueBounds {
+        min: 0,
+        max: u64::MAX,
+    };
+
+    /// Bounds allowing any non-zero value.
+    pub const NON_ZERO: Self = ValueBounds {
+        min: 1,
+        max: u64::MAX,
+    };
+
+    pub fn contains(&self, value: u64) -> bool { (self.min..=self.max).contains(&value) }
+
+    /// Whether zero values are allowed. Schemata without bounds and bounds
+    /// with zero minimum allow zero values.
+    pub fn allows_zero(&self) -> bool { self.min == 0 }
+
+    pub fn is_full(&self) -> bool { *self == Self::FULL }
+}
+
 /// Today we support only a single format of confidential data, because of the
 /// limitations of the underlying secp256k1-zkp library: it works only with
 /// u64 numbers. Nevertheless, homomorphic commitments can be created to
@@ -107,6 +285,15 @@ pub enum FungibleType {
     #[default]
     #[display("64bit")]
     Unsigned64Bit = Primitive::U64.into_code(),
+    #[cfg(feature = "wide-fungible")]
+    #[display("128bit")]
+    Unsigned128Bit = Primitive::U128.into_code(),
+    #[cfg(feature = "wide-fungible")]
+    #[display("i64bit")]
+    Signed64Bit = Primitive::I64.into_code(),
+    #[cfg(feature = "wide-fungible")]
+    #[display("i128bit")]
+    Signed128Bit = Primitive::I128.into_code(),
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -34,8 +34,12 @@ use crate::{
 };
 
 /// Strict types id for the library providing data types for RGB consensus.
+///
+/// NB: The id is valid only when none of `prime-seals` and `wide-fungible`
+/// features are enabled, since the features add new layer 1 variants and
+/// fungible value types to the consensus data types.
 pub const LIB_ID_RGB: &str =
-    "stl:sqbS4Bea-l!IK7Dt-86Fkfgg-NOhi22w-S!kz5bC-l$99W!Y#bless-donald-poker";
+    "stl:iv9SapFh-fMVJXn7-8nr87nE-72sSh6J-5o1PBSB-hcpwohA#orlando-mercury-critic";
 
 fn _rgb_core_stl() -> Result<TypeLib, CompileError> {
     LibBuilder::new(libname!(LIB_NAME_RGB), tiny_bset! {
@@ -65,6 +69,7 @@ mod test {
     use super::*;
 
     #[test]
+    #[cfg(not(any(feature = "prime-seals", feature = "wide-fungible")))]
     fn lib_id() {
         let lib = rgb_core_stl();
         assert_eq!(lib.id().to_string(), LIB_ID_RGB);
@@ -0,0 +1,102 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use crate::validation::{Info, Located};
+use crate::{OpId, SchemaId};
+
+/// Cache of the operations which have already passed validation, allowing to
+/// skip repeated per-operation checks when the same contract history gets
+/// validated again (for instance, when a wallet receives a new transfer).
+///
+/// Operations found in the cache skip validation against the schema and
+/// scripts, including state and bulletproofs verification. They still
+/// participate in the graph traversal and in the single-use seal and anchor
+/// checks, which depend on the consignment and the witness resolver.
+///
+/// # Trust assumptions
+///
+/// The cache is trusted: it must be stored locally and must never be
+/// populated with data from a third party, since a poisoned cache makes the
+/// validator accept invalid operations. An operation id commits to the
+/// operation data and to the ids of its inputs, and the schema id commits to
+/// the validation scripts, thus the result of the per-operation validation is
+/// fully defined by the `(opid, schema_id)` pair.
+///
+/// The validator marks operations as valid only if their validation produced
+/// no failures or warnings, and only if the whole consignment has passed the
+/// validation without failures. Information entries produced by the
+/// operation validation are stored together with the operation and reported
+/// again when the cached operation is skipped; thus repeated validation
+/// produces the same report.
+pub trait ValidationCache {
+    /// Returns information entries reported by the validation of the
+    /// operation, if the operation has already passed validation against the
+    /// schema, or `None` otherwise.
+    fn lookup(&self, opid: OpId, schema_id: SchemaId) -> Option<Vec<Located<Info>>>;
+
+    /// Checks whether the operation has already passed validation against the
+    /// schema.
+    fn is_valid(&self, opid: OpId, schema_id: SchemaId) -> bool {
+        self.lookup(opid, schema_id).is_some()
+    }
+
+    /// Marks the operation as valid under the schema, storing information
+    /// entries reported by its validation.
+    fn mark_valid(&mut self, opid: OpId, schema_id: SchemaId, info: Vec<Located<Info>>);
+}
+
+/// No-op cache, which never contains any operations.
+impl ValidationCache for () {
+    fn lookup(&self, _opid: OpId, _schema_id: SchemaId) -> Option<Vec<Located<Info>>> { None }
+
+    fn mark_valid(&mut self, _opid: OpId, _schema_id: SchemaId, _info: Vec<Located<Info>>) {}
+}
+
+/// In-memory validation cache.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MemoryValidationCache(BTreeMap<(SchemaId, OpId), Vec<Located<Info>>>);
+
+impl MemoryValidationCache {
+    /// Constructs empty cache.
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns number of the cached operations.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Detects whether the cache is empty.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Removes all operations from the cache.
+    pub fn clear(&mut self) { self.0.clear() }
+}
+
+impl ValidationCache for MemoryValidationCache {
+    fn lookup(&self, opid: OpId, schema_id: SchemaId) -> Option<Vec<Located<Info>>> {
+        self.0.get(&(schema_id, opid)).cloned()
+    }
+
+    fn mark_valid(&mut self, opid: OpId, schema_id: SchemaId, info: Vec<Located<Info>>) {
+        self.0.insert((schema_id, opid), info);
+    }
+}
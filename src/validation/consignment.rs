@@ -24,7 +24,8 @@
 //! state transitions, extensions, genesis, outputs, assignments &
 //! single-use-seal data.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::RwLock;
 
 use aluvm::library::{Lib, LibId};
 use amplify::confinement::Confined;
@@ -39,10 +40,22 @@ pub const CONSIGNMENT_MAX_LIBS: usize = 1024;
 
 pub type Scripts = Confined<BTreeMap<LibId, Lib>, 0, CONSIGNMENT_MAX_LIBS>;
 
-pub struct CheckedConsignment<'consignment, C: ConsignmentApi>(&'consignment C);
+/// Consignment which operations and bundles are checked to match the ids they
+/// are requested by. Operation ids, which are expensive to compute, are
+/// checked only once and then cached.
+pub struct CheckedConsignment<'consignment, C: ConsignmentApi>(
+    &'consignment C,
+    RwLock<BTreeSet<OpId>>,
+);
 
 impl<'consignment, C: ConsignmentApi> CheckedConsignment<'consignment, C> {
-    pub fn new(consignment: &'consignment C) -> Self { Self(consignment) }
+    pub fn new(consignment: &'consignment C) -> Self { Self(consignment, empty!()) }
+
+    /// Returns the bundle provided under `bundle_id` without checking that the
+    /// id matches the bundle data, which allows to report the mismatch.
+    pub(crate) fn unchecked_bundle(&self, bundle_id: BundleId) -> Option<&TransitionBundle> {
+        self.0.bundle(bundle_id)
+    }
 }
 
 impl<'consignment, C: ConsignmentApi> ConsignmentApi for CheckedConsignment<'consignment, C> {
@@ -52,8 +65,16 @@ impl<'consignment, C: ConsignmentApi> ConsignmentApi for CheckedConsignment<'con
 
     fn scripts(&self) -> &Scripts { self.0.scripts() }
 
-    fn operation(&self, opid: OpId) -> Option<OpRef> {
-        self.0.operation(opid).filter(|op| op.id() == opid)
+    fn operation(&self, opid: OpId) -> Option<OpRef<'_>> {
+        let operation = self.0.operation(opid)?;
+        if self.1.read().expect("poisoned lock").contains(&opid) {
+            return Some(operation);
+        }
+        if operation.id() != opid {
+            return None;
+        }
+        self.1.write().expect("poisoned lock").insert(opid);
+        Some(operation)
     }
 
     fn genesis(&self) -> &Genesis { self.0.genesis() }
@@ -77,6 +98,20 @@ impl<'consignment, C: ConsignmentApi> ConsignmentApi for CheckedConsignment<'con
     fn op_witness_id(&self, opid: OpId) -> Option<XWitnessId> { self.0.op_witness_id(opid) }
 }
 
+/// Marker trait requiring [`Sync`] when the `multithread` feature is enabled,
+/// such that consignments can be shared across validation threads.
+#[cfg(feature = "multithread")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "multithread")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Marker trait requiring [`Sync`] when the `multithread` feature is enabled,
+/// such that consignments can be shared across validation threads.
+#[cfg(not(feature = "multithread"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "multithread"))]
+impl<T: ?Sized> MaybeSync for T {}
+
 /// Trait defining common data access API for all storage-related RGB structures
 ///
 /// The API provided for the consignment should not verify the internal
@@ -84,7 +119,7 @@ impl<'consignment, C: ConsignmentApi> ConsignmentApi for CheckedConsignment<'con
 /// data within the storage or container. If the methods are called on an
 /// invalid or absent data, the API must always return [`None`] or empty
 /// collections/iterators.
-pub trait ConsignmentApi {
+pub trait ConsignmentApi: MaybeSync {
     /// Returns reference to the schema object used by the consignment.
     fn schema(&self) -> &Schema;
 
@@ -97,7 +132,7 @@ pub trait ConsignmentApi {
 
     /// Retrieves reference to an operation (genesis, state transition or state
     /// extension) matching the provided id, or `None` otherwise
-    fn operation(&self, opid: OpId) -> Option<OpRef>;
+    fn operation(&self, opid: OpId) -> Option<OpRef<'_>>;
 
     /// Contract genesis.
     fn genesis(&self) -> &Genesis;
@@ -21,34 +21,140 @@
 // limitations under the License.
 
 use std::collections::BTreeSet;
+use std::time::Instant;
 
 use aluvm::data::Number;
-use aluvm::isa::Instr;
-use aluvm::reg::{Reg32, RegA};
-use aluvm::Vm;
+use aluvm::isa::{Bytecode, ExecStep, Instr, InstructionSet};
+use aluvm::library::{Cursor, LibSite, Read};
+use aluvm::reg::{CoreRegs, Reg32, RegA};
 use amplify::confinement::Confined;
 use amplify::Wrapper;
-use strict_types::TypeSystem;
+use strict_types::{SemId, TypeSystem};
 
-use crate::schema::{AssignmentsSchema, GlobalSchema, ValencySchema};
-use crate::validation::{CheckedConsignment, ConsignmentApi, Failure};
+use crate::schema::{
+    AssignmentsSchema, ExtensionType, GlobalSchema, MetaKind, MetaType, Occurrences, ValencySchema,
+    TIMESTAMP_MIN,
+};
+use crate::validation::{
+    BulletproofBatch, CheckedConsignment, ConsignmentApi, Failure, OccurrencesSubject,
+    ScriptLimits, Scripts,
+};
 use crate::vm::RgbIsa;
 use crate::{
-    validation, AssetTags, Assignments, AssignmentsRef, ContractId, ExposedSeal, Extension,
-    GlobalState, GlobalStateSchema, GlobalValues, GraphSeal, Inputs, MetaSchema, Metadata,
-    OpFullType, OpId, OpRef, Operation, Opout, OwnedStateSchema, Schema, StateType, Transition,
-    TypedAssigns, Valencies,
+    validation, AssetTags, AssignmentType, Assignments, AssignmentsRef, ContractId, ExposedSeal,
+    Extension, Genesis, GlobalState, GlobalStateSchema, GlobalValues, GraphSeal, Inputs,
+    MetaSchema, Metadata, OpFullType, OpId, OpRef, Operation, Opout, OwnedStateSchema,
+    PedersenCommitment, Schema, StateType, Transition, TypedAssigns, Valencies,
 };
 
+/// Validates a single operation against the schema and its scripts without a
+/// consignment, for instance as a pre-flight check when constructing new
+/// operations.
+///
+/// The operation is validated without its parent operations, thus checks of
+/// the previous state of state transitions are skipped, which is reported with
+/// [`validation::Info::UncheckablePrevState`]. Range proofs are verified
+/// immediately. The `genesis` provides contract id and asset tags for the
+/// validation scripts. Timestamp metadata must not be later than
+/// `max_timestamp`.
+pub fn validate_operation(
+    schema: &Schema,
+    genesis: &Genesis,
+    op: OpRef,
+    types: &TypeSystem,
+    scripts: &Scripts,
+    script_limits: ScriptLimits,
+    max_timestamp: i64,
+) -> validation::Status {
+    let context = OpContext {
+        types,
+        scripts,
+        genesis,
+        prev_state: None,
+        max_timestamp,
+    };
+    schema.validate_op(op, &context, &mut BulletproofBatch::immediate(), Some(script_limits))
+}
+
+/// Data required for the validation of an operation, which are not the part
+/// of the operation itself.
+struct OpContext<'ctx> {
+    types: &'ctx TypeSystem,
+    scripts: &'ctx Scripts,
+    genesis: &'ctx Genesis,
+    /// State of the state transition inputs, if known.
+    prev_state: Option<&'ctx Assignments<GraphSeal>>,
+    /// Latest timestamp allowed in the timestamp metadata.
+    max_timestamp: i64,
+}
+
 impl Schema {
+    /// Validates operation state against the schema and, unless
+    /// `script_limits` are not provided, the schema validation scripts.
+    /// Timestamp metadata must not be later than `max_timestamp`.
+    ///
+    /// Checks of the operation structure (the used types and the number of
+    /// their occurrences, including the inputs), of the Pedersen commitment
+    /// sums and of the range proofs don't depend on whether the owned state
+    /// and seals are revealed. Checks which require revealed state (the
+    /// structured state semantic types, attachment media types and fungible
+    /// value bounds) are skipped for the concealed state, which is reported
+    /// with [`validation::Info`] entries instead of failures.
     pub fn validate_state<'validator, C: ConsignmentApi>(
         &'validator self,
         consignment: &'validator CheckedConsignment<'_, C>,
         op: OpRef,
+        bulletproofs: &mut BulletproofBatch,
+        script_limits: Option<ScriptLimits>,
+        max_timestamp: i64,
+    ) -> validation::Status {
+        let mut status = validation::Status::new();
+        let prev_state = match op {
+            OpRef::Transition(transition) => {
+                Some(extract_prev_state(consignment, op.id(), &transition.inputs, &mut status))
+            }
+            OpRef::Genesis(_) | OpRef::Extension(_) => None,
+        };
+        let context = OpContext {
+            types: consignment.types(),
+            scripts: consignment.scripts(),
+            genesis: consignment.genesis(),
+            prev_state: prev_state.as_ref(),
+            max_timestamp,
+        };
+        status += self.validate_op(op, &context, bulletproofs, script_limits);
+        status
+    }
+
+    fn validate_op(
+        &self,
+        op: OpRef,
+        context: &OpContext,
+        bulletproofs: &mut BulletproofBatch,
+        script_limits: Option<ScriptLimits>,
     ) -> validation::Status {
         let opid = op.id();
         let mut status = validation::Status::new();
 
+        // [VALIDATION]: Transitions bound to a schema must be bound to the
+        //               schema of the contract
+        if let OpRef::Transition(Transition {
+            schema_id: Some(actual),
+            ..
+        }) = op
+        {
+            let expected = self.schema_id();
+            if *actual != expected {
+                status
+                    .with_node(opid)
+                    .add_failure(Failure::TransitionSchemaMismatch {
+                        opid,
+                        expected,
+                        actual: *actual,
+                    });
+            }
+        }
+
         let empty_assign_schema = AssignmentsSchema::default();
         let empty_valency_schema = ValencySchema::default();
         let blank_transition = self.blank_transition();
@@ -64,15 +170,22 @@ impl Schema {
         ) = match op {
             OpRef::Genesis(genesis) => {
                 for id in genesis.asset_tags.keys() {
-                    if !matches!(self.owned_types.get(id), Some(OwnedStateSchema::Fungible(_))) {
-                        status.add_failure(Failure::AssetTagNoState(*id));
+                    if !matches!(
+                        self.owned_types.get(id),
+                        Some(OwnedStateSchema::Fungible(_) | OwnedStateSchema::BoundedFungible(..))
+                    ) {
+                        status
+                            .with_node(opid)
+                            .add_failure(Failure::AssetTagNoState(*id));
                     }
                 }
                 for (id, ss) in &self.owned_types {
                     if ss.state_type() == StateType::Fungible &&
                         !genesis.asset_tags.contains_key(id)
                     {
-                        status.add_failure(Failure::FungibleStateNoTag(*id));
+                        status
+                            .with_node(opid)
+                            .add_failure(Failure::FungibleStateNoTag(*id));
                     }
                 }
 
@@ -157,40 +270,62 @@ impl Schema {
 
         // Validate type system
         status += self.validate_type_system();
-        status += self.validate_metadata(opid, op.metadata(), metadata_schema, consignment.types());
-        status +=
-            self.validate_global_state(opid, op.globals(), global_schema, consignment.types());
-        let prev_state = if let OpRef::Transition(transition) = op {
-            let prev_state = extract_prev_state(consignment, opid, &transition.inputs, &mut status);
-            status += self.validate_prev_state(opid, &prev_state, owned_schema);
-            prev_state
-        } else {
-            Assignments::default()
+        status += self.validate_metadata_schema(
+            opid,
+            op.metadata(),
+            metadata_schema,
+            context.types,
+            context.max_timestamp,
+        );
+        status += self.validate_global_state(opid, op.globals(), global_schema, context.types);
+        let no_prev_state = Assignments::default();
+        let prev_state = match (op, context.prev_state) {
+            (OpRef::Transition(transition), Some(prev_state)) => {
+                status += self.validate_prev_state(opid, prev_state, owned_schema);
+                status += validate_pedersen_sums(opid, prev_state, &transition.assignments);
+                prev_state
+            }
+            (OpRef::Transition(_), None) => {
+                status
+                    .with_node(opid)
+                    .add_info(validation::Info::UncheckablePrevState(opid));
+                &no_prev_state
+            }
+            _ => &no_prev_state,
         };
         let mut redeemed = Valencies::default();
         if let OpRef::Extension(extension) = op {
             for valency in extension.redeemed.keys() {
                 redeemed.push(*valency).expect("same size");
             }
-            status += self.validate_redeemed(opid, &redeemed, redeem_schema);
+            status +=
+                self.validate_redeemed(opid, extension.extension_type, &redeemed, redeem_schema);
         }
         status += match op.assignments() {
-            AssignmentsRef::Genesis(assignments) => {
-                self.validate_owned_state(opid, assignments, assign_schema, consignment.types())
-            }
-            AssignmentsRef::Graph(assignments) => {
-                self.validate_owned_state(opid, assignments, assign_schema, consignment.types())
-            }
+            AssignmentsRef::Genesis(assignments) => self.validate_owned_state(
+                opid,
+                assignments,
+                assign_schema,
+                context.types,
+                bulletproofs,
+            ),
+            AssignmentsRef::Graph(assignments) => self.validate_owned_state(
+                opid,
+                assignments,
+                assign_schema,
+                context.types,
+                bulletproofs,
+            ),
         };
 
         status += self.validate_valencies(opid, op.valencies(), valency_schema);
 
-        let genesis = consignment.genesis();
+        let genesis = context.genesis;
         let op_info = OpInfo::with(
             genesis.contract_id(),
             opid,
             &op,
-            &prev_state,
+            prev_state,
             &redeemed,
             &genesis.asset_tags,
         );
@@ -198,19 +333,22 @@ impl Schema {
         // We need to run scripts as the very last step, since before that
         // we need to make sure that the operation data match the schema, so
         // scripts are not required to validate the structure of the state
-        if let Some(validator) = validator {
-            let scripts = consignment.scripts();
-            let mut vm = Vm::<Instr<RgbIsa>>::new();
-            if let Some(ty) = ty {
-                vm.registers.set_n(RegA::A16, Reg32::Reg0, ty);
-            }
-            if !vm.exec(validator, |id| scripts.get(&id), &op_info) {
-                let error_code: Option<Number> = vm.registers.get_n(RegA::A8, Reg32::Reg0).into();
-                status.add_failure(validation::Failure::ScriptFailure(
-                    opid,
-                    error_code.map(u8::from),
-                    None,
-                ));
+        if let (Some(validator), Some(script_limits)) = (validator, script_limits) {
+            match exec_script(validator, context.scripts, ty, &op_info, script_limits) {
+                ScriptOutcome::Success => {}
+                ScriptOutcome::Failure(error_code) => {
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::ScriptFailure(opid, error_code, None));
+                }
+                ScriptOutcome::Exhausted => {
+                    status.with_node(opid).add_failure(
+                        validation::Failure::ScriptResourceExhausted {
+                            opid,
+                            entry_point: validator,
+                        },
+                    );
+                }
             }
         }
         status
@@ -228,12 +366,147 @@ impl Schema {
         // are present.
     }
 
-    fn validate_metadata(
+    /// Validates metadata of an operation with the given type against the
+    /// schema. This check is a part of [`Schema::validate_state`], exposed to
+    /// be used for pre-flight checks when constructing new operations.
+    ///
+    /// Each of the metadata types declared by the schema for the operation type
+    /// must be present exactly once (see [`Failure::OccurrencesViolation`]) and
+    /// match its semantic type; metadata types
+    /// not declared by the schema result in a warning (see
+    /// [`validation::Warning::UnknownMetaType`]). Timestamp metadata must be
+    /// within `TIMESTAMP_MIN..=max_timestamp` (see
+    /// [`Failure::TimestampOutOfRange`]).
+    pub fn validate_metadata(
+        &self,
+        opid: OpId,
+        op_type: OpFullType,
+        metadata: &Metadata,
+        types: &TypeSystem,
+        max_timestamp: i64,
+    ) -> validation::Status {
+        let blank = MetaSchema::default();
+        let metadata_schema = match op_type {
+            OpFullType::Genesis => &self.genesis.metadata,
+            OpFullType::StateTransition(ty) => match self.transitions.get(&ty) {
+                Some(transition_schema) => &transition_schema.metadata,
+                None if ty.is_blank() => &blank,
+                None => {
+                    return validation::Status::with_failure(Failure::SchemaUnknownTransitionType(
+                        opid, ty,
+                    ));
+                }
+            },
+            OpFullType::StateExtension(ty) => match self.extensions.get(&ty) {
+                Some(extension_schema) => &extension_schema.metadata,
+                None => {
+                    return validation::Status::with_failure(Failure::SchemaUnknownExtensionType(
+                        opid, ty,
+                    ));
+                }
+            },
+        };
+        self.validate_metadata_schema(opid, metadata, metadata_schema, types, max_timestamp)
+    }
+
+    /// Checks that the operation uses only the metadata, global state,
+    /// assignment and valency types declared by the schema for the operation
+    /// type, and that the operation type itself is known to the schema.
+    ///
+    /// Unlike [`Schema::validate_state`], this check doesn't look into the
+    /// state values, scripts or seals, and thus is cheap enough to be used by
+    /// operation builders and indexers. Metadata types not declared by the
+    /// schema are reported as warnings, the same way as
+    /// [`Schema::validate_state`] does.
+    pub fn check_node_types(&self, op: OpRef) -> validation::Status {
+        let opid = op.id();
+        let blank_transition = self.blank_transition();
+        let (metadata_schema, global_schema, assign_schema, valency_schema, redeem_schema) =
+            match op.full_type() {
+                OpFullType::Genesis => (
+                    &self.genesis.metadata,
+                    &self.genesis.globals,
+                    &self.genesis.assignments,
+                    &self.genesis.valencies,
+                    None,
+                ),
+                OpFullType::StateTransition(ty) => {
+                    let transition_schema = match self.transitions.get(&ty) {
+                        Some(transition_schema) => transition_schema,
+                        None if ty.is_blank() => &blank_transition,
+                        None => {
+                            return validation::Status::with_failure(
+                                Failure::SchemaUnknownTransitionType(opid, ty),
+                            );
+                        }
+                    };
+                    (
+                        &transition_schema.metadata,
+                        &transition_schema.globals,
+                        &transition_schema.assignments,
+                        &transition_schema.valencies,
+                        None,
+                    )
+                }
+                OpFullType::StateExtension(ty) => {
+                    let Some(extension_schema) = self.extensions.get(&ty) else {
+                        return validation::Status::with_failure(
+                            Failure::SchemaUnknownExtensionType(opid, ty),
+                        );
+                    };
+                    (
+                        &extension_schema.metadata,
+                        &extension_schema.globals,
+                        &extension_schema.assignments,
+                        &extension_schema.valencies,
+                        Some(&extension_schema.redeems),
+                    )
+                }
+            };
+
+        let mut status = validation::Status::new();
+        let mut recorder = status.with_node(opid);
+        for type_id in op.metadata().keys() {
+            if !metadata_schema.contains(type_id) {
+                recorder.add_warning(validation::Warning::UnknownMetaType(opid, *type_id));
+            }
+        }
+        for type_id in op.globals().keys() {
+            if !global_schema.contains_key(type_id) {
+                recorder.add_failure(Failure::SchemaUnknownGlobalStateType(opid, *type_id));
+            }
+        }
+        for type_id in op.assignments().types() {
+            if !assign_schema.contains_key(&type_id) {
+                recorder.add_failure(Failure::SchemaUnknownAssignmentType(opid, type_id));
+            }
+        }
+        for type_id in op.valencies().iter() {
+            if !valency_schema.contains(type_id) {
+                recorder.add_failure(Failure::SchemaUnknownValencyType(opid, *type_id));
+            }
+        }
+        if let (OpRef::Extension(extension), Some(redeem_schema)) = (op, redeem_schema) {
+            for valency in extension.redeemed.keys() {
+                if !redeem_schema.contains(valency) {
+                    recorder.add_failure(Failure::IllegalValencyRedemption {
+                        opid,
+                        extension_type: extension.extension_type,
+                        valency: *valency,
+                    });
+                }
+            }
+        }
+        status
+    }
+
+    fn validate_metadata_schema(
         &self,
         opid: OpId,
         metadata: &Metadata,
         metadata_schema: &MetaSchema,
         types: &TypeSystem,
+        max_timestamp: i64,
     ) -> validation::Status {
         let mut status = validation::Status::new();
 
@@ -243,12 +516,21 @@ impl Schema {
             .collect::<BTreeSet<_>>()
             .difference(metadata_schema.as_inner())
             .for_each(|type_id| {
-                status.add_failure(validation::Failure::SchemaUnknownMetaType(opid, *type_id));
+                status
+                    .with_node(opid)
+                    .add_warning(validation::Warning::UnknownMetaType(opid, *type_id));
             });
 
         for type_id in metadata_schema {
-            let Some(value) = metadata.get(type_id) else {
-                status.add_failure(validation::Failure::SchemaNoMetadata(opid, *type_id));
+            let value = metadata.get(type_id);
+            check_occurrences(
+                &mut status,
+                opid,
+                OccurrencesSubject::Metadata(*type_id),
+                &Occurrences::Once,
+                value.is_some() as u16,
+            );
+            let Some(value) = value else {
                 continue;
             };
 
@@ -257,12 +539,29 @@ impl Schema {
                  internal validation and we would not reach this point",
             );
 
-            if types
-                .strict_deserialize_type(*sem_id, value.as_ref())
-                .is_err()
-            {
-                status.add_failure(validation::Failure::SchemaInvalidMetadata(opid, *sem_id));
-            };
+            // Strict deserialization fails both on invalid and on not entirely consumed
+            // data
+            if let Err(err) = types.strict_deserialize_type(*sem_id, value.as_ref()) {
+                status
+                    .with_node(opid)
+                    .add_failure(validation::Failure::SchemaInvalidMetadata(
+                        opid,
+                        *sem_id,
+                        err.to_string(),
+                    ));
+            } else if self.meta_kind(*type_id) == MetaKind::Timestamp {
+                check_timestamp(&mut status, opid, *type_id, *sem_id, metadata, max_timestamp);
+            } else if self.meta_kind(*type_id) == MetaKind::MaxSupply {
+                if let Err(err) = metadata.u64(*type_id) {
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::SchemaInvalidMetadata(
+                            opid,
+                            *sem_id,
+                            format!("maximum supply must be a 64-bit unsigned integer: {err}"),
+                        ));
+                }
+            }
         }
 
         status
@@ -282,9 +581,9 @@ impl Schema {
             .collect::<BTreeSet<_>>()
             .difference(&global_schema.keys().collect())
             .for_each(|field_id| {
-                status.add_failure(validation::Failure::SchemaUnknownGlobalStateType(
-                    opid, **field_id,
-                ));
+                status.with_node(opid).add_failure(
+                    validation::Failure::SchemaUnknownGlobalStateType(opid, **field_id),
+                );
             });
 
         for (type_id, occ) in global_schema {
@@ -306,26 +605,32 @@ impl Schema {
 
             // Checking number of field occurrences
             let count = set.len() as u16;
-            if let Err(err) = occ.check(count) {
-                status.add_failure(validation::Failure::SchemaGlobalStateOccurrences(
-                    opid, *type_id, err,
-                ));
-            }
+            check_occurrences(
+                &mut status,
+                opid,
+                OccurrencesSubject::GlobalState(*type_id),
+                occ,
+                count,
+            );
             if count > *max_items {
-                status.add_failure(validation::Failure::SchemaGlobalStateLimit(
-                    opid, *type_id, count, *max_items,
-                ));
+                status
+                    .with_node(opid)
+                    .add_failure(validation::Failure::SchemaGlobalStateLimit(
+                        opid, *type_id, count, *max_items,
+                    ));
             }
 
             // Validating data types
             for data in set {
-                if types
-                    .strict_deserialize_type(*sem_id, data.as_ref())
-                    .is_err()
-                {
-                    status.add_failure(validation::Failure::SchemaInvalidGlobalValue(
-                        opid, *type_id, *sem_id,
-                    ));
+                if let Err(err) = types.strict_deserialize_type(*sem_id, data.as_ref()) {
+                    status.with_node(opid).add_failure(
+                        validation::Failure::SchemaInvalidGlobalValue(
+                            opid,
+                            *type_id,
+                            *sem_id,
+                            err.to_string(),
+                        ),
+                    );
                 };
             }
         }
@@ -346,10 +651,12 @@ impl Schema {
             .collect::<BTreeSet<_>>()
             .difference(&assign_schema.keys().collect())
             .for_each(|owned_type_id| {
-                status.add_failure(validation::Failure::SchemaUnknownAssignmentType(
-                    id,
-                    **owned_type_id,
-                ));
+                status
+                    .with_node(id)
+                    .add_failure(validation::Failure::SchemaUnknownAssignmentType(
+                        id,
+                        **owned_type_id,
+                    ));
             });
 
         for (owned_type_id, occ) in assign_schema {
@@ -359,34 +666,33 @@ impl Schema {
                 .unwrap_or(0);
 
             // Checking number of ancestor's assignment occurrences
-            if let Err(err) = occ.check(len) {
-                status.add_failure(validation::Failure::SchemaInputOccurrences(
-                    id,
-                    *owned_type_id,
-                    err,
-                ));
-            }
+            check_occurrences(&mut status, id, OccurrencesSubject::Input(*owned_type_id), occ, len);
         }
 
         status
     }
 
+    /// Checks that the state extension redeems only the valencies which are
+    /// allowed to be redeemed by its type. Presence of the redeemed valencies
+    /// in the parent operations is checked during the graph traversal.
     fn validate_redeemed(
         &self,
         id: OpId,
+        extension_type: ExtensionType,
         valencies: &Valencies,
         valency_schema: &ValencySchema,
     ) -> validation::Status {
         let mut status = validation::Status::new();
 
-        valencies
-            .difference(valency_schema)
-            .for_each(|public_type_id| {
-                status.add_failure(validation::Failure::SchemaUnknownValencyType(
-                    id,
-                    *public_type_id,
-                ));
-            });
+        valencies.difference(valency_schema).for_each(|valency| {
+            status
+                .with_node(id)
+                .add_failure(validation::Failure::IllegalValencyRedemption {
+                    opid: id,
+                    extension_type,
+                    valency: *valency,
+                });
+        });
 
         status
     }
@@ -397,6 +703,7 @@ impl Schema {
         owned_state: &Assignments<Seal>,
         assign_schema: &AssignmentsSchema,
         types: &TypeSystem,
+        bulletproofs: &mut BulletproofBatch,
     ) -> validation::Status {
         let mut status = validation::Status::new();
 
@@ -405,10 +712,12 @@ impl Schema {
             .collect::<BTreeSet<_>>()
             .difference(&assign_schema.keys().collect())
             .for_each(|assignment_type_id| {
-                status.add_failure(validation::Failure::SchemaUnknownAssignmentType(
-                    id,
-                    **assignment_type_id,
-                ));
+                status
+                    .with_node(id)
+                    .add_failure(validation::Failure::SchemaUnknownAssignmentType(
+                        id,
+                        **assignment_type_id,
+                    ));
             });
 
         for (state_id, occ) in assign_schema {
@@ -418,11 +727,7 @@ impl Schema {
                 .unwrap_or(0);
 
             // Checking number of assignment occurrences
-            if let Err(err) = occ.check(len) {
-                status.add_failure(validation::Failure::SchemaAssignmentOccurrences(
-                    id, *state_id, err,
-                ));
-            }
+            check_occurrences(&mut status, id, OccurrencesSubject::Assignment(*state_id), occ, len);
 
             let assignment = &self.owned_types.get(state_id).expect(
                 "If the assignment were absent, the schema would not be able to pass the internal \
@@ -431,18 +736,18 @@ impl Schema {
 
             match owned_state.get(state_id) {
                 None => {}
-                Some(TypedAssigns::Declarative(set)) => set
-                    .iter()
-                    .for_each(|data| status += assignment.validate(id, *state_id, data, types)),
-                Some(TypedAssigns::Fungible(set)) => set
-                    .iter()
-                    .for_each(|data| status += assignment.validate(id, *state_id, data, types)),
-                Some(TypedAssigns::Structured(set)) => set
-                    .iter()
-                    .for_each(|data| status += assignment.validate(id, *state_id, data, types)),
-                Some(TypedAssigns::Attachment(set)) => set
-                    .iter()
-                    .for_each(|data| status += assignment.validate(id, *state_id, data, types)),
+                Some(TypedAssigns::Declarative(set)) => set.iter().for_each(|data| {
+                    status += assignment.validate(id, *state_id, data, types, bulletproofs)
+                }),
+                Some(TypedAssigns::Fungible(set)) => set.iter().for_each(|data| {
+                    status += assignment.validate(id, *state_id, data, types, bulletproofs)
+                }),
+                Some(TypedAssigns::Structured(set)) => set.iter().for_each(|data| {
+                    status += assignment.validate(id, *state_id, data, types, bulletproofs)
+                }),
+                Some(TypedAssigns::Attachment(set)) => set.iter().for_each(|data| {
+                    status += assignment.validate(id, *state_id, data, types, bulletproofs)
+                }),
             };
         }
 
@@ -460,16 +765,175 @@ impl Schema {
         valencies
             .difference(valency_schema)
             .for_each(|public_type_id| {
-                status.add_failure(validation::Failure::SchemaUnknownValencyType(
-                    id,
-                    *public_type_id,
-                ));
+                status
+                    .with_node(id)
+                    .add_failure(validation::Failure::SchemaUnknownValencyType(
+                        id,
+                        *public_type_id,
+                    ));
             });
 
         status
     }
 }
 
+/// Result of the validation script execution.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ScriptOutcome {
+    Success,
+    /// Script has failed, optionally providing an error code.
+    Failure(Option<u8>),
+    /// Script has exceeded the execution limits.
+    Exhausted,
+}
+
+/// Common check of the number of occurrences for all kinds of operation data
+/// constrained by the schema.
+///
+/// Occurrences are reveal-independent: `found` must count all the items,
+/// including the assignments with the concealed state or seals, since
+/// concealment doesn't remove the assignment from the operation.
+fn check_occurrences(
+    status: &mut validation::Status,
+    opid: OpId,
+    subject: OccurrencesSubject,
+    occ: &Occurrences,
+    found: u16,
+) {
+    let Err(mismatch) = occ.check(found) else {
+        return;
+    };
+    let mut node = status.with_node(opid);
+    if let OccurrencesSubject::Input(ty) | OccurrencesSubject::Assignment(ty) = subject {
+        node = node.with_assignment(ty);
+    }
+    node.add_failure(Failure::OccurrencesViolation {
+        opid,
+        subject,
+        mismatch,
+    });
+}
+
+/// Checks that the timestamp metadata `value` is a strict-encoded `i64` within
+/// `TIMESTAMP_MIN..=max_timestamp`.
+fn check_timestamp(
+    status: &mut validation::Status,
+    opid: OpId,
+    meta_type: MetaType,
+    sem_id: SemId,
+    metadata: &Metadata,
+    max_timestamp: i64,
+) {
+    let timestamp = match metadata.i64(meta_type) {
+        Ok(timestamp) => timestamp,
+        Err(err) => {
+            status
+                .with_node(opid)
+                .add_failure(Failure::SchemaInvalidMetadata(
+                    opid,
+                    sem_id,
+                    format!("timestamp must be a 64-bit signed integer: {err}"),
+                ));
+            return;
+        }
+    };
+    if !(TIMESTAMP_MIN..=max_timestamp).contains(&timestamp) {
+        status
+            .with_node(opid)
+            .add_failure(Failure::TimestampOutOfRange {
+                opid,
+                meta_type,
+                timestamp,
+                min: TIMESTAMP_MIN,
+                max: max_timestamp,
+            });
+    }
+}
+
+/// Runs the validation script starting at `entry_point` under the provided
+/// execution limits.
+fn exec_script(
+    entry_point: LibSite,
+    scripts: &Scripts,
+    ty: Option<u16>,
+    op_info: &OpInfo,
+    limits: ScriptLimits,
+) -> ScriptOutcome {
+    let mut registers = CoreRegs::new();
+    if let Some(ty) = ty {
+        registers.set_n(RegA::A16, Reg32::Reg0, ty);
+    }
+    let start = limits.max_time.map(|_| Instant::now());
+    let completed = exec_limited(&mut registers, entry_point, scripts, op_info, limits.max_steps);
+    let timeout = start
+        .zip(limits.max_time)
+        .is_some_and(|(start, max_time)| start.elapsed() > max_time);
+    if timeout || !completed {
+        return ScriptOutcome::Exhausted;
+    }
+    if registers.status() {
+        return ScriptOutcome::Success;
+    }
+    let error_code: Option<Number> = registers.get_n(RegA::A8, Reg32::Reg0).into();
+    ScriptOutcome::Failure(error_code.map(u8::from))
+}
+
+/// Executes the script starting at `entry_point` in the same way as
+/// [`aluvm::Vm::exec`] does, accumulating complexity of the executed
+/// instructions. The complexity limit register of the VM is not accessible
+/// outside of AluVM, thus the instructions are stepped through here. Returns
+/// `false` if the execution was stopped since the accumulated complexity has
+/// reached `max_steps`.
+fn exec_limited(
+    registers: &mut CoreRegs,
+    entry_point: LibSite,
+    scripts: &Scripts,
+    op_info: &OpInfo,
+    max_steps: u64,
+) -> bool {
+    let mut steps = 0u64;
+    let mut call = Some(entry_point);
+    while let Some(site) = call.take() {
+        let Some(lib) = scripts.get(&site.lib) else {
+            call = site
+                .pos
+                .checked_add(1)
+                .map(|pos| LibSite::with(pos, site.lib));
+            continue;
+        };
+        let lib_id = lib.id();
+        let mut cursor = Cursor::with(&lib.code, &lib.data, &lib.libs);
+        if cursor.seek(site.pos).is_err() {
+            break;
+        }
+        while !cursor.is_eof() {
+            let pos = cursor.pos();
+            let Ok(instr) = Instr::<RgbIsa>::decode(&mut cursor) else {
+                break;
+            };
+            let next = instr.exec(registers, LibSite::with(pos, lib_id), op_info);
+            steps = steps.saturating_add(instr.complexity());
+            if steps >= max_steps {
+                return false;
+            }
+            match next {
+                ExecStep::Stop => break,
+                ExecStep::Next => {}
+                ExecStep::Jump(pos) => {
+                    if cursor.seek(pos).is_err() {
+                        break;
+                    }
+                }
+                ExecStep::Call(site) => {
+                    call = Some(site);
+                    break;
+                }
+            }
+        }
+    }
+    true
+}
+
 pub struct OpInfo<'op> {
     pub contract_id: ContractId,
     pub id: OpId,
@@ -507,6 +971,47 @@ impl<'op> OpInfo<'op> {
     }
 }
 
+/// Verifies that for each of the fungible state types the sum of the input
+/// commitments matches the sum of the output commitments. Revealed values are
+/// committed using their known blinding factors.
+///
+/// Issuance is possible only in genesis and state extensions, thus the check
+/// must be applied to state transitions only.
+fn validate_pedersen_sums(
+    opid: OpId,
+    inputs: &Assignments<GraphSeal>,
+    outputs: &Assignments<GraphSeal>,
+) -> validation::Status {
+    let mut status = validation::Status::new();
+    let commitments = |assignments: &Assignments<GraphSeal>, ty: &AssignmentType| {
+        assignments
+            .get(ty)
+            .map(TypedAssigns::as_fungible)
+            .unwrap_or_default()
+            .iter()
+            .map(|assign| assign.to_confidential_state().commitment)
+            .collect::<Vec<_>>()
+    };
+    let types = inputs
+        .iter()
+        .chain(outputs.iter())
+        .filter(|(_, assigns)| assigns.is_fungible())
+        .map(|(ty, _)| *ty)
+        .collect::<BTreeSet<_>>();
+    for ty in types {
+        // [SECURITY-CRITICAL]: Pedersen commitments sum validation
+        if !PedersenCommitment::verify_sum(&commitments(inputs, &ty), &commitments(outputs, &ty)) {
+            status.with_node(opid).with_assignment(ty).add_failure(
+                validation::Failure::PedersenSumMismatch {
+                    opid,
+                    state_type: ty,
+                },
+            );
+        }
+    }
+    status
+}
+
 fn extract_prev_state<C: ConsignmentApi>(
     consignment: &C,
     opid: OpId,
@@ -519,7 +1024,9 @@ fn extract_prev_state<C: ConsignmentApi>(
 
         let prev_op = match consignment.operation(op) {
             None => {
-                status.add_failure(validation::Failure::OperationAbsent(op));
+                status
+                    .with_node(opid)
+                    .add_failure(validation::Failure::OperationAbsent(op));
                 continue;
             }
             Some(op) => op,
@@ -537,7 +1044,9 @@ fn extract_prev_state<C: ConsignmentApi>(
                         typed_assigns.push(prev_assign.clone()).expect("same size");
                     }
                 } else {
-                    status.add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
                 }
             }
             Some(TypedAssigns::Fungible(prev_assignments)) => {
@@ -550,7 +1059,9 @@ fn extract_prev_state<C: ConsignmentApi>(
                         typed_assigns.push(prev_assign.clone()).expect("same size");
                     }
                 } else {
-                    status.add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
                 }
             }
             Some(TypedAssigns::Structured(prev_assignments)) => {
@@ -563,7 +1074,9 @@ fn extract_prev_state<C: ConsignmentApi>(
                         typed_assigns.push(prev_assign.clone()).expect("same size");
                     }
                 } else {
-                    status.add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
                 }
             }
             Some(TypedAssigns::Attachment(prev_assignments)) => {
@@ -576,7 +1089,9 @@ fn extract_prev_state<C: ConsignmentApi>(
                         typed_assigns.push(prev_assign.clone()).expect("same size");
                     }
                 } else {
-                    status.add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
+                    status
+                        .with_node(opid)
+                        .add_failure(validation::Failure::NoPrevOut(opid, input.prev_out));
                 }
             }
             None => {
@@ -590,3 +1105,576 @@ fn extract_prev_state<C: ConsignmentApi>(
         .expect("collections is assembled from another collection with the same size requirements")
         .into()
 }
+
+#[cfg(test)]
+mod test {
+    use aluvm::isa::ControlFlowOp;
+    use aluvm::library::Lib;
+    use amplify::confinement::SmallBlob;
+    use amplify::ByteArray;
+    use commit_verify::Conceal;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::schema::{
+        FungibleType, GlobalStateType, MetaType, OccurrencesMismatch, TransitionType, ValencyType,
+    };
+    use crate::validation::ValidationConfig;
+    use crate::{
+        AssetTag, AssignAttach, AssignFungible, AssignRights, AttachId, BlindingFactor,
+        ExtensionSchema, MediaType, MetaValue, RevealedAttach, RevealedValue, TransitionSchema,
+        VoidState, XChain,
+    };
+
+    fn assignments(values: &[(u64, bool)]) -> Assignments<GraphSeal> {
+        let blinding =
+            BlindingFactor::from(secp256k1_zkp::SecretKey::from_slice(&[1u8; 32]).unwrap());
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let assigns = values
+            .iter()
+            .map(|(value, conceal)| {
+                let assign = AssignFungible::revealed(
+                    XChain::Bitcoin(GraphSeal::strict_dumb()),
+                    RevealedValue::with_blinding(*value, blinding, tag),
+                );
+                if *conceal {
+                    assign.conceal()
+                } else {
+                    assign
+                }
+            })
+            .collect::<Vec<_>>();
+        Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => TypedAssigns::Fungible(Confined::try_from(assigns).unwrap())
+            })
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn pedersen_sums() {
+        let opid = OpId::strict_dumb();
+        let inputs = assignments(&[(10, false)]);
+
+        let status = validate_pedersen_sums(opid, &inputs, &assignments(&[(10, false)]));
+        assert!(status.failures.is_empty());
+        // Mixed revealed and confidential state
+        let status = validate_pedersen_sums(opid, &inputs, &assignments(&[(10, true)]));
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    fn pedersen_inflation() {
+        let opid = OpId::strict_dumb();
+        let inputs = assignments(&[(10, true)]);
+        let expected = vec![Failure::PedersenSumMismatch {
+            opid,
+            state_type: AssignmentType::with(1),
+        }];
+
+        let status = validate_pedersen_sums(opid, &inputs, &assignments(&[(11, false)]));
+        assert_eq!(status.failures, expected);
+        let status = validate_pedersen_sums(opid, &inputs, &assignments(&[(11, true)]));
+        assert_eq!(status.failures, expected);
+        let status =
+            validate_pedersen_sums(opid, &Assignments::default(), &assignments(&[(10, false)]));
+        assert_eq!(status.failures, expected);
+    }
+
+    #[test]
+    fn metadata_unknown_and_missing() {
+        let opid = OpId::strict_dumb();
+        let types = TypeSystem::default();
+        let mut schema = Schema::strict_dumb();
+        schema
+            .meta_types
+            .insert(MetaType::with(1), strict_dumb!())
+            .unwrap();
+        schema.genesis.metadata = tiny_bset! { MetaType::with(1) };
+        let mut metadata = Metadata::default();
+        metadata
+            .add_value(MetaType::with(2), MetaValue::default())
+            .unwrap();
+
+        let missing = Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::Metadata(MetaType::with(1)),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 1,
+                found: 0,
+            },
+        };
+        let status =
+            schema.validate_metadata(opid, OpFullType::Genesis, &metadata, &types, i64::MAX);
+        assert_eq!(status.failures, vec![missing.clone()]);
+        assert_eq!(status.warnings, vec![validation::Warning::UnknownMetaType(
+            opid,
+            MetaType::with(2)
+        )]);
+
+        let status = status.enforce(validation::ValidationPolicy::Strict);
+        assert_eq!(status.failures, vec![
+            missing,
+            Failure::SchemaUnknownMetaType(opid, MetaType::with(2))
+        ]);
+        assert!(status.warnings.is_empty());
+    }
+
+    #[test]
+    fn script_step_limit() {
+        let genesis = Genesis::strict_dumb();
+        let op = OpRef::Genesis(&genesis);
+        let prev_state = Assignments::default();
+        let redeemed = Valencies::default();
+        let op_info = OpInfo::with(
+            genesis.contract_id(),
+            genesis.id(),
+            &op,
+            &prev_state,
+            &redeemed,
+            &genesis.asset_tags,
+        );
+        let looping =
+            Lib::assemble::<Instr<RgbIsa>>(&[Instr::ControlFlow(ControlFlowOp::Jmp(0))]).unwrap();
+        let succeeding = Lib::assemble::<Instr<RgbIsa>>(&[Instr::Nop]).unwrap();
+        let scripts: Scripts = Confined::try_from(bmap! {
+            looping.id() => looping.clone(),
+            succeeding.id() => succeeding.clone(),
+        })
+        .unwrap();
+        let limits = ScriptLimits {
+            max_steps: 1000,
+            max_time: None,
+        };
+
+        let outcome = exec_script(LibSite::with(0, looping.id()), &scripts, None, &op_info, limits);
+        assert_eq!(outcome, ScriptOutcome::Exhausted);
+        let outcome =
+            exec_script(LibSite::with(0, succeeding.id()), &scripts, None, &op_info, limits);
+        assert_eq!(outcome, ScriptOutcome::Success);
+    }
+
+    #[test]
+    fn standalone_operation() {
+        let genesis = Genesis::strict_dumb();
+        let transition = Transition::strict_dumb();
+        let mut schema = Schema::strict_dumb();
+        schema
+            .transitions
+            .insert(transition.transition_type, TransitionSchema::default())
+            .unwrap();
+        let types = TypeSystem::default();
+        let scripts = Scripts::default();
+        let limits = ScriptLimits::default();
+
+        let status = validate_operation(
+            &schema,
+            &genesis,
+            OpRef::Genesis(&genesis),
+            &types,
+            &scripts,
+            limits,
+            i64::MAX,
+        );
+        assert_eq!(status, validation::Status::new());
+
+        let op = OpRef::Transition(&transition);
+        let status = validate_operation(&schema, &genesis, op, &types, &scripts, limits, i64::MAX);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckablePrevState(transition.id())]);
+    }
+
+    #[test]
+    fn schema_bound_transition() {
+        use crate::SchemaId;
+
+        let genesis = Genesis::strict_dumb();
+        let mut schema = Schema::strict_dumb();
+        schema
+            .transitions
+            .insert(TransitionType::strict_dumb(), TransitionSchema::default())
+            .unwrap();
+        let types = TypeSystem::default();
+        let scripts = Scripts::default();
+        let limits = ScriptLimits::default();
+        let validate = |transition: &Transition| {
+            let op = OpRef::Transition(transition);
+            validate_operation(&schema, &genesis, op, &types, &scripts, limits, i64::MAX)
+        };
+
+        let mut transition = Transition::strict_dumb();
+        transition.bind_schema(schema.schema_id());
+        assert!(validate(&transition).failures.is_empty());
+
+        transition.schema_id = Some(SchemaId::from([0x44; 32]));
+        let opid = transition.id();
+        let status = validate(&transition);
+        assert_eq!(status.failures, vec![Failure::TransitionSchemaMismatch {
+            opid,
+            expected: schema.schema_id(),
+            actual: SchemaId::from([0x44; 32]),
+        }]);
+        assert_eq!(status.failures[0].node, Some(opid));
+    }
+
+    #[test]
+    fn node_types() {
+        let mut schema = Schema::strict_dumb();
+        schema
+            .transitions
+            .insert(TransitionType::with(1), TransitionSchema::default())
+            .unwrap();
+
+        let genesis = Genesis::strict_dumb();
+        assert_eq!(schema.check_node_types(OpRef::Genesis(&genesis)), validation::Status::new());
+
+        let mut transition = Transition::strict_dumb();
+        transition.transition_type = TransitionType::with(2);
+        let opid = transition.id();
+        let status = schema.check_node_types(OpRef::Transition(&transition));
+        assert_eq!(status.failures, vec![Failure::SchemaUnknownTransitionType(
+            opid,
+            TransitionType::with(2)
+        )]);
+
+        transition.transition_type = TransitionType::with(1);
+        transition.assignments = assignments(&[(10, false)]);
+        transition
+            .metadata
+            .add_value(MetaType::with(2), MetaValue::default())
+            .unwrap();
+        transition.valencies.push(ValencyType::with(3)).unwrap();
+        let opid = transition.id();
+        let status = schema.check_node_types(OpRef::Transition(&transition));
+        assert_eq!(status.failures, vec![
+            Failure::SchemaUnknownAssignmentType(opid, AssignmentType::with(1)),
+            Failure::SchemaUnknownValencyType(opid, ValencyType::with(3)),
+        ]);
+        assert!(status.failures.iter().all(|f| f.node == Some(opid)));
+        assert_eq!(status.warnings, vec![validation::Warning::UnknownMetaType(
+            opid,
+            MetaType::with(2)
+        )]);
+    }
+
+    #[test]
+    fn illegal_valency_redemption() {
+        let mut schema = Schema::strict_dumb();
+        for (ty, valency) in [(1, 1), (2, 2)] {
+            let extension_schema = ExtensionSchema {
+                redeems: tiny_bset! { ValencyType::with(valency) },
+                ..default!()
+            };
+            schema
+                .extensions
+                .insert(ExtensionType::with(ty), extension_schema)
+                .unwrap();
+        }
+        let genesis = Genesis::strict_dumb();
+        let types = TypeSystem::default();
+        let scripts = Scripts::default();
+
+        let mut extension = Extension::strict_dumb();
+        extension.extension_type = ExtensionType::with(1);
+        extension
+            .redeemed
+            .insert(ValencyType::with(1), genesis.id())
+            .unwrap();
+        let op = OpRef::Extension(&extension);
+        let status = validate_operation(
+            &schema,
+            &genesis,
+            op,
+            &types,
+            &scripts,
+            ScriptLimits::default(),
+            i64::MAX,
+        );
+        assert_eq!(status, validation::Status::new());
+        assert_eq!(schema.check_node_types(op), validation::Status::new());
+
+        // Valency 2 may be redeemed only by the extensions of the second type
+        extension
+            .redeemed
+            .insert(ValencyType::with(2), genesis.id())
+            .unwrap();
+        let op = OpRef::Extension(&extension);
+        let expected = vec![Failure::IllegalValencyRedemption {
+            opid: extension.id(),
+            extension_type: ExtensionType::with(1),
+            valency: ValencyType::with(2),
+        }];
+        let status = validate_operation(
+            &schema,
+            &genesis,
+            op,
+            &types,
+            &scripts,
+            ScriptLimits::default(),
+            i64::MAX,
+        );
+        assert_eq!(status.failures, expected);
+        assert_eq!(schema.check_node_types(op).failures, expected);
+    }
+
+    #[test]
+    fn occurrences_violations() {
+        let opid = OpId::strict_dumb();
+        let types = TypeSystem::default();
+        let mut schema = Schema::strict_dumb();
+        schema
+            .global_types
+            .insert(GlobalStateType::with(1), GlobalStateSchema::many(strict_dumb!()))
+            .unwrap();
+        schema
+            .owned_types
+            .insert(
+                AssignmentType::with(1),
+                OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            )
+            .unwrap();
+        let once = tiny_bmap! { AssignmentType::with(1) => Occurrences::Once };
+
+        let global_schema = tiny_bmap! { GlobalStateType::with(1) => Occurrences::OnceOrUpTo(2) };
+        let status =
+            schema.validate_global_state(opid, &GlobalState::default(), &global_schema, &types);
+        assert_eq!(status.failures, vec![Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::GlobalState(GlobalStateType::with(1)),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 2,
+                found: 0,
+            },
+        }]);
+        assert_eq!(status.failures[0].assignment_type, None);
+
+        let status = schema.validate_prev_state(opid, &Assignments::<GraphSeal>::default(), &once);
+        assert_eq!(status.failures, vec![Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::Input(AssignmentType::with(1)),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 1,
+                found: 0,
+            },
+        }]);
+        assert_eq!(status.failures[0].assignment_type, Some(AssignmentType::with(1)));
+
+        let status = schema.validate_owned_state(
+            opid,
+            &assignments(&[(1, false), (2, false), (3, false)]),
+            &once,
+            &types,
+            &mut BulletproofBatch::immediate(),
+        );
+        let violation = Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::Assignment(AssignmentType::with(1)),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 1,
+                found: 3,
+            },
+        };
+        assert_eq!(status.failures, vec![violation.clone()]);
+        assert_eq!(status.failures[0].node, Some(opid));
+        assert_eq!(status.failures[0].assignment_type, Some(AssignmentType::with(1)));
+        assert_eq!(
+            violation.to_string(),
+            format!(
+                "operation {opid} doesn't match schema occurrence rules for owned state 0x0001: \
+                 expected 1..=1, found 3."
+            )
+        );
+    }
+
+    #[test]
+    fn decoys_validation() {
+        let opid = OpId::strict_dumb();
+        let types = TypeSystem::default();
+        let mut schema = Schema::strict_dumb();
+        schema
+            .owned_types
+            .insert(
+                AssignmentType::with(1),
+                OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            )
+            .unwrap();
+        let inputs = assignments(&[(10, false)]);
+        let mut outputs = assignments(&[(10, false)]);
+        let seal = XChain::Bitcoin(GraphSeal::strict_dumb());
+        outputs
+            .get_mut(&AssignmentType::with(1))
+            .unwrap()
+            .add_decoys([seal, seal], &mut secp256k1_zkp::rand::thread_rng())
+            .unwrap();
+
+        let status = validate_pedersen_sums(opid, &inputs, &outputs);
+        assert!(status.failures.is_empty());
+
+        let up_to_three = tiny_bmap! { AssignmentType::with(1) => Occurrences::OnceOrUpTo(3) };
+        let status = schema.validate_owned_state(
+            opid,
+            &outputs,
+            &up_to_three,
+            &types,
+            &mut BulletproofBatch::immediate(),
+        );
+        assert!(status.failures.is_empty());
+
+        // Schema must allow the decoys
+        let once = tiny_bmap! { AssignmentType::with(1) => Occurrences::Once };
+        let status = schema.validate_owned_state(
+            opid,
+            &outputs,
+            &once,
+            &types,
+            &mut BulletproofBatch::immediate(),
+        );
+        assert_eq!(status.failures, vec![Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::Assignment(AssignmentType::with(1)),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 1,
+                found: 3,
+            },
+        }]);
+    }
+
+    #[test]
+    fn timestamp_bounds() {
+        let opid = OpId::strict_dumb();
+        let meta_type = MetaType::with(1);
+        let sem_id = SemId::strict_dumb();
+        let config = ValidationConfig {
+            now: Some(1_700_000_000),
+            timestamp_tolerance: 3600,
+            ..default!()
+        };
+        let max = config.max_timestamp();
+        assert_eq!(max, 1_700_003_600);
+
+        let check = |value: MetaValue| {
+            let mut metadata = Metadata::default();
+            metadata.add_value(meta_type, value).unwrap();
+            let mut status = validation::Status::new();
+            check_timestamp(&mut status, opid, meta_type, sem_id, &metadata, max);
+            status
+                .failures
+                .into_iter()
+                .map(|f| f.item)
+                .collect::<Vec<_>>()
+        };
+        let timestamp = |timestamp: i64| {
+            let blob: SmallBlob = Confined::try_from(timestamp.to_le_bytes().to_vec()).unwrap();
+            MetaValue::from(blob)
+        };
+        let out_of_range = |timestamp| Failure::TimestampOutOfRange {
+            opid,
+            meta_type,
+            timestamp,
+            min: TIMESTAMP_MIN,
+            max,
+        };
+
+        assert_eq!(check(timestamp(TIMESTAMP_MIN)), vec![]);
+        assert_eq!(check(timestamp(max)), vec![]);
+        assert_eq!(check(timestamp(TIMESTAMP_MIN - 1)), vec![out_of_range(TIMESTAMP_MIN - 1)]);
+        assert_eq!(check(timestamp(max + 1)), vec![out_of_range(max + 1)]);
+        assert!(matches!(
+            check(MetaValue::default()).as_slice(),
+            [Failure::SchemaInvalidMetadata(id, ..)] if *id == opid
+        ));
+    }
+
+    #[test]
+    fn occurrences_concealed() {
+        let rights = AssignmentType::with(1);
+        let attachment = AssignmentType::with(2);
+        let mut schema = Schema::strict_dumb();
+        schema
+            .owned_types
+            .insert(rights, OwnedStateSchema::Declarative)
+            .unwrap();
+        schema
+            .owned_types
+            .insert(attachment, OwnedStateSchema::Attachment(MediaType::Any))
+            .unwrap();
+        schema
+            .transitions
+            .insert(TransitionType::with(1), TransitionSchema {
+                inputs: tiny_bmap! { rights => Occurrences::Once },
+                assignments: tiny_bmap! {
+                    rights => Occurrences::Once,
+                    attachment => Occurrences::Once,
+                },
+                ..default!()
+            })
+            .unwrap();
+
+        let concealed_rights = |count: usize| {
+            let assign = AssignRights::revealed(
+                XChain::Bitcoin(GraphSeal::strict_dumb()),
+                VoidState::default(),
+            )
+            .conceal();
+            TypedAssigns::Declarative(Confined::try_from(vec![assign; count]).unwrap())
+        };
+        let attach = RevealedAttach::with_salt(AttachId::strict_dumb(), &mime::IMAGE_PNG, 1024, 0);
+        let concealed_attach =
+            AssignAttach::revealed(XChain::Bitcoin(GraphSeal::strict_dumb()), attach).conceal();
+        let prev_state =
+            Assignments::from(Confined::try_from(bmap! { rights => concealed_rights(1) }).unwrap());
+        let genesis = Genesis::strict_dumb();
+        let types = TypeSystem::default();
+        let scripts = Scripts::default();
+        let context = OpContext {
+            types: &types,
+            scripts: &scripts,
+            genesis: &genesis,
+            prev_state: Some(&prev_state),
+            max_timestamp: i64::MAX,
+        };
+
+        let validate = |rights_count: usize| {
+            let mut transition = Transition::strict_dumb();
+            transition.transition_type = TransitionType::with(1);
+            transition.assignments = Assignments::from(
+                Confined::try_from(bmap! {
+                    rights => concealed_rights(rights_count),
+                    attachment => TypedAssigns::Attachment(
+                        Confined::try_from(vec![concealed_attach.clone()]).unwrap()
+                    ),
+                })
+                .unwrap(),
+            );
+            let opid = transition.id();
+            let op = OpRef::Transition(&transition);
+            let status = schema.validate_op(op, &context, &mut BulletproofBatch::immediate(), None);
+            (opid, status)
+        };
+
+        // Fully concealed transition matches the occurrence rules
+        let (opid, status) = validate(1);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableConfidentialState(
+            opid, attachment
+        )]);
+
+        // Concealed assignments are counted as well
+        let (opid, status) = validate(2);
+        assert_eq!(status.failures, vec![Failure::OccurrencesViolation {
+            opid,
+            subject: OccurrencesSubject::Assignment(rights),
+            mismatch: OccurrencesMismatch {
+                min: 1,
+                max: 1,
+                found: 2,
+            },
+        }]);
+    }
+}
@@ -26,8 +26,28 @@ mod state;
 mod validator;
 mod consignment;
 mod status;
+mod cache;
+mod snapshot;
+#[cfg(feature = "async")]
+mod resolver_async;
 
-pub use consignment::{CheckedConsignment, ConsignmentApi, Scripts, CONSIGNMENT_MAX_LIBS};
+pub use cache::{MemoryValidationCache, ValidationCache};
+pub use consignment::{
+    CheckedConsignment, ConsignmentApi, MaybeSync, Scripts, CONSIGNMENT_MAX_LIBS,
+};
+pub use logic::validate_operation;
 pub(crate) use logic::OpInfo;
-pub use status::{Failure, Info, Status, Validity, Warning};
-pub use validator::{ResolveWitness, Validator, WitnessResolverError};
+#[cfg(feature = "async")]
+pub use resolver_async::{validate_async, AsyncResolveWitness, PrefetchedWitnesses};
+pub use snapshot::{ContractStateSnapshot, UnspentOutput, ValidatedConsignment};
+pub use state::BulletproofBatch;
+pub use status::{
+    Failure, FailureCode, Info, LimitKind, Located, NodeStatus, OccurrencesSubject, Status,
+    StatusCounts, StatusSummary, UnknownFailureCode, ValidationDepth, ValidationPolicy, Validity,
+    Warning,
+};
+pub use validator::{
+    verify_bundle_coverage, AnchorInfo, AnchorPolicy, ResolveWitness, ScriptLimits,
+    ValidationConfig, ValidationLimits, ValidationOptions, ValidationPass, ValidationPhase,
+    ValidationProgress, Validator, WitnessResolverError,
+};
@@ -0,0 +1,174 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asynchronous resolution of witness transactions for the validation.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
+
+use futures::{stream, StreamExt};
+
+use super::{
+    ConsignmentApi, ResolveWitness, Status, ValidationConfig, Validator, WitnessResolverError,
+};
+use crate::{XWitnessId, XWitnessTx};
+
+/// Asynchronous version of [`ResolveWitness`], suitable for resolvers backed
+/// by network services like Electrum or Esplora.
+#[allow(async_fn_in_trait)]
+pub trait AsyncResolveWitness {
+    // TODO: Return with SPV proof data
+    async fn resolve_pub_witness(
+        &self,
+        witness_id: XWitnessId,
+    ) -> Result<XWitnessTx, WitnessResolverError>;
+}
+
+/// Witness transactions resolved in advance, which are provided to the
+/// validator as a synchronous resolver.
+#[derive(Clone, Debug, Default)]
+pub struct PrefetchedWitnesses(BTreeMap<XWitnessId, Result<XWitnessTx, WitnessResolverError>>);
+
+impl PrefetchedWitnesses {
+    /// Resolves witness transactions with the given ids, running at most
+    /// `concurrency` resolver requests at the same time.
+    pub async fn resolve(
+        resolver: &impl AsyncResolveWitness,
+        witness_ids: impl IntoIterator<Item = XWitnessId>,
+        concurrency: NonZeroUsize,
+    ) -> Self {
+        let witnesses = stream::iter(witness_ids.into_iter().collect::<BTreeSet<_>>())
+            .map(|witness_id| async move {
+                (witness_id, resolver.resolve_pub_witness(witness_id).await)
+            })
+            .buffer_unordered(concurrency.get())
+            .collect::<BTreeMap<_, _>>()
+            .await;
+        PrefetchedWitnesses(witnesses)
+    }
+
+    /// Returns number of resolved (successfully or not) witnesses.
+    #[inline]
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Detects whether no witnesses were resolved.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
+
+impl ResolveWitness for PrefetchedWitnesses {
+    fn resolve_pub_witness(
+        &self,
+        witness_id: XWitnessId,
+    ) -> Result<XWitnessTx, WitnessResolverError> {
+        self.0
+            .get(&witness_id)
+            .cloned()
+            .unwrap_or(Err(WitnessResolverError::Unknown(witness_id)))
+    }
+}
+
+/// Validates consignment like [`Validator::validate_with`], resolving
+/// all witness transactions referenced by the consignment anchors
+/// concurrently with the asynchronous `resolver`, running at most
+/// `concurrency` requests at the same time.
+pub async fn validate_async<C: ConsignmentApi>(
+    consignment: &C,
+    resolver: &impl AsyncResolveWitness,
+    testnet: bool,
+    config: &ValidationConfig,
+    concurrency: NonZeroUsize,
+) -> Status {
+    let witness_ids = consignment
+        .bundle_ids()
+        .filter_map(|bundle_id| consignment.anchor(bundle_id))
+        .map(|(witness_id, _)| witness_id);
+    let witnesses = PrefetchedWitnesses::resolve(resolver, witness_ids, concurrency).await;
+    Validator::validate_with(consignment, &witnesses, testnet, config, default!()).status
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use bp::Tx;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockResolver {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    impl AsyncResolveWitness for MockResolver {
+        async fn resolve_pub_witness(
+            &self,
+            witness_id: XWitnessId,
+        ) -> Result<XWitnessTx, WitnessResolverError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            let no = witness_id.as_reduced_unsafe()[0];
+            tokio::time::sleep(Duration::from_millis(10 + (no % 4) as u64 * 5)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            if no % 3 == 0 {
+                return Err(WitnessResolverError::Other(witness_id, "connection lost".to_owned()));
+            }
+            Ok(XWitnessTx::with(witness_id.layer1(), Tx::strict_dumb()))
+        }
+    }
+
+    #[tokio::test]
+    async fn prefetch_concurrently() {
+        let resolver = MockResolver::default();
+        let ids = (0..16u8)
+            .map(|no| XWitnessId::Bitcoin([no; 32].into()))
+            .collect::<Vec<_>>();
+        let witnesses =
+            PrefetchedWitnesses::resolve(&resolver, ids.clone(), NonZeroUsize::new(4).unwrap())
+                .await;
+
+        assert_eq!(witnesses.len(), ids.len());
+        assert!(resolver.max_running.load(Ordering::SeqCst) <= 4);
+        assert!(resolver.max_running.load(Ordering::SeqCst) > 1);
+        for (no, id) in ids.into_iter().enumerate() {
+            match witnesses.resolve_pub_witness(id) {
+                Ok(tx) => {
+                    assert_ne!(no % 3, 0);
+                    assert_eq!(tx.layer1(), id.layer1());
+                }
+                Err(WitnessResolverError::Other(err_id, _)) => {
+                    assert_eq!(no % 3, 0);
+                    assert_eq!(err_id, id);
+                }
+                Err(err) => panic!("unexpected error {err}"),
+            }
+        }
+        let unknown = XWitnessId::Liquid([0xFF; 32].into());
+        assert!(matches!(
+            witnesses.resolve_pub_witness(unknown),
+            Err(WitnessResolverError::Unknown(id)) if id == unknown
+        ));
+    }
+}
@@ -106,3 +106,62 @@ impl Schema {
         status
     }
 }
+
+#[cfg(test)]
+mod test {
+    use strict_encoding::StrictDumb;
+    use strict_types::SemId;
+
+    use super::*;
+    use crate::schema::{GlobalStateType, MetaType};
+    use crate::validation::{BulletproofBatch, Failure};
+    use crate::{
+        Assign, AssignmentType, DataState, GlobalStateSchema, GraphSeal, OpId, RevealedData,
+    };
+
+    #[test]
+    fn absent_sem_ids() {
+        let sem_id = SemId::strict_dumb();
+        let types = TypeSystem::default();
+        let mut schema = Schema::strict_dumb();
+        schema.meta_types.insert(MetaType::with(1), sem_id).unwrap();
+        schema
+            .global_types
+            .insert(GlobalStateType::with(1), GlobalStateSchema::once(sem_id))
+            .unwrap();
+        schema
+            .owned_types
+            .insert(AssignmentType::with(1), OwnedStateSchema::Structured(sem_id))
+            .unwrap();
+
+        let status = schema.verify(&types);
+        assert_eq!(status.failures, vec![
+            Failure::SchemaMetaSemIdUnknown(MetaType::with(1), sem_id),
+            Failure::SchemaGlobalSemIdUnknown(GlobalStateType::with(1), sem_id),
+            Failure::SchemaOwnedSemIdUnknown(AssignmentType::with(1), sem_id),
+        ]);
+
+        // State referencing the absent type is reported as invalid instead of panicking
+        let opid = OpId::strict_dumb();
+        let state = Assign::<RevealedData, GraphSeal>::Revealed {
+            seal: strict_dumb!(),
+            state: RevealedData::with_salt(DataState::default(), 0),
+            lock: default!(),
+        };
+        let status = schema
+            .owned_types
+            .get(&AssignmentType::with(1))
+            .unwrap()
+            .validate(
+                opid,
+                AssignmentType::with(1),
+                &state,
+                &types,
+                &mut BulletproofBatch::immediate(),
+            );
+        assert_eq!(status.failures.len(), 1);
+        assert!(
+            matches!(status.failures[0].item, Failure::InvalidStructuredState { opid: id, .. } if id == opid)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2024 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Status;
+use crate::{Opout, RevealedState, WitnessAnchor, XOutputSeal};
+
+/// Consignment validation result together with the contract state computed
+/// during the validation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct ValidatedConsignment {
+    /// Validation status.
+    pub status: Status,
+
+    /// Contract state after the operations of the consignment. Present only if
+    /// it was requested with [`super::ValidationConfig::compute_state`] and
+    /// the consignment is not invalid.
+    pub state: Option<ContractStateSnapshot>,
+}
+
+/// Unspent output of a contract operation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct UnspentOutput {
+    /// Operation output, including the assignment type.
+    pub opout: Opout,
+
+    /// Seal defined by the output; absent if the seal is concealed.
+    pub seal: Option<XOutputSeal>,
+
+    /// State assigned to the output; absent if the state is concealed.
+    pub state: Option<RevealedState>,
+
+    /// Witness of the operation which has defined the output; absent for the
+    /// genesis and state extensions.
+    pub witness: Option<WitnessAnchor>,
+}
+
+/// Snapshot of the contract state: all outputs of the validated operations
+/// which are not spent by any of them, ordered by the witness ordering of the
+/// operations defining the outputs (genesis and state extensions go first).
+/// Outputs defined by the operations with the same witness are ordered by the
+/// output pointer.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct ContractStateSnapshot(Vec<UnspentOutput>);
+
+impl ContractStateSnapshot {
+    pub(super) fn with(mut outputs: Vec<UnspentOutput>) -> Self {
+        outputs.sort_by_key(|output| (output.witness, output.opout));
+        Self(outputs)
+    }
+
+    /// Returns unspent output defined by the given operation output, if any.
+    pub fn output(&self, opout: Opout) -> Option<&UnspentOutput> {
+        self.0.iter().find(|output| output.opout == opout)
+    }
+
+    /// Iterates over unspent outputs in the witness ordering.
+    pub fn iter(&self) -> impl Iterator<Item = &UnspentOutput> { self.0.iter() }
+
+    /// Returns number of the unspent outputs.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Detects whether there are no unspent outputs.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
@@ -24,10 +24,104 @@ use strict_types::TypeSystem;
 
 use crate::schema::AssignmentType;
 use crate::{
-    validation, Assign, ConcealedState, ConfidentialState, ExposedSeal, ExposedState, OpId,
-    OwnedStateSchema, RevealedState,
+    validation, Assign, ConcealedState, ConcealedValue, ConfidentialState, ExposedSeal,
+    ExposedState, OpId, OwnedStateSchema, RangeProofError, RevealedState,
 };
 
+/// Collection of bulletproofs range proofs, which verification is deferred
+/// to be performed as a single batch.
+///
+/// If the batch is not deferred, each range proof is verified immediately
+/// when it gets added to the batch.
+#[derive(Clone, Debug, Default)]
+pub struct BulletproofBatch {
+    deferred: bool,
+    entries: Vec<(OpId, AssignmentType, ConcealedValue)>,
+}
+
+impl BulletproofBatch {
+    /// Constructs batch verifying each of the range proofs immediately.
+    pub fn immediate() -> Self { Self::default() }
+
+    /// Constructs batch collecting range proofs for a later verification with
+    /// [`BulletproofBatch::verify`].
+    pub fn deferred() -> Self {
+        BulletproofBatch {
+            deferred: true,
+            entries: vec![],
+        }
+    }
+
+    /// Detects whether the verification of range proofs is deferred.
+    #[inline]
+    pub fn is_deferred(&self) -> bool { self.deferred }
+
+    /// Returns number of range proofs pending verification.
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Detects whether there are range proofs pending verification.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Moves range proofs pending verification from another batch into this
+    /// one.
+    pub fn append(&mut self, mut other: BulletproofBatch) {
+        self.entries.append(&mut other.entries)
+    }
+
+    /// Adds range proof to the batch or, if the batch is not deferred,
+    /// verifies it immediately.
+    pub fn push(
+        &mut self,
+        opid: OpId,
+        state_type: AssignmentType,
+        value: ConcealedValue,
+    ) -> validation::Status {
+        if self.deferred {
+            self.entries.push((opid, state_type, value));
+            return validation::Status::new();
+        }
+        Self::verify_single(opid, state_type, &value)
+    }
+
+    /// Verifies all range proofs collected by the batch, emptying it.
+    ///
+    /// If the batch verification fails, verifies each of the range proofs
+    /// individually in order to report the failing ones.
+    pub fn verify(&mut self) -> validation::Status {
+        let entries = std::mem::take(&mut self.entries);
+        // [SECURITY-CRITICAL]: Bulletproofs validation
+        if let Ok(true) = ConcealedValue::verify_range_proofs(entries.iter().map(|(_, _, v)| v)) {
+            return validation::Status::new();
+        }
+        let mut status = validation::Status::new();
+        for (opid, state_type, value) in &entries {
+            status += Self::verify_single(*opid, *state_type, value);
+        }
+        status
+    }
+
+    fn verify_single(
+        opid: OpId,
+        state_type: AssignmentType,
+        value: &ConcealedValue,
+    ) -> validation::Status {
+        let mut status = validation::Status::new();
+        // [SECURITY-CRITICAL]: Bulletproofs validation
+        let err = match value.verify_range_proof() {
+            Ok(true) => return status,
+            Ok(false) => RangeProofError::VerificationFailed,
+            Err(err) => err,
+        };
+        status
+            .with_node(opid)
+            .with_assignment(state_type)
+            .add_failure(validation::Failure::BulletproofsInvalid(opid, state_type, err));
+        status
+    }
+}
+
 impl OwnedStateSchema {
     pub fn validate<State: ExposedState, Seal: ExposedSeal>(
         &self,
@@ -35,40 +129,65 @@ impl OwnedStateSchema {
         state_type: AssignmentType,
         data: &Assign<State, Seal>,
         type_system: &TypeSystem,
+        bulletproofs: &mut BulletproofBatch,
     ) -> validation::Status {
         let mut status = validation::Status::new();
+        // Cheap check of the state kind before extracting the state data
+        if data.state_type() != self.state_type() {
+            status
+                .with_node(opid)
+                .with_assignment(state_type)
+                .add_failure(validation::Failure::StateTypeMismatch {
+                    opid,
+                    state_type,
+                    expected: self.state_type(),
+                    found: data.state_type(),
+                });
+            return status;
+        }
         match data {
             Assign::Confidential { state, .. } | Assign::ConfidentialState { state, .. } => {
                 match (self, state.state_commitment()) {
                     (OwnedStateSchema::Declarative, ConcealedState::Void) => {}
                     (OwnedStateSchema::Fungible(_), ConcealedState::Fungible(value)) => {
-                        // [SECURITY-CRITICAL]: Bulletproofs validation
-                        if let Err(err) = value.verify_range_proof() {
-                            status.add_failure(validation::Failure::BulletproofsInvalid(
-                                opid,
-                                state_type,
-                                err.to_string(),
-                            ));
+                        status += bulletproofs.push(opid, state_type, value);
+                    }
+                    (
+                        OwnedStateSchema::BoundedFungible(_, bounds),
+                        ConcealedState::Fungible(value),
+                    ) => {
+                        status += bulletproofs.push(opid, state_type, value);
+                        // Range proofs prove only that the value fits 64 bits
+                        if !bounds.is_full() {
+                            status.with_node(opid).with_assignment(state_type).add_info(
+                                validation::Info::UncheckableValueBounds(opid, state_type),
+                            );
                         }
                     }
                     (OwnedStateSchema::Structured(_), ConcealedState::Structured(_)) => {
-                        status.add_info(validation::Info::UncheckableConfidentialState(
-                            opid, state_type,
-                        ));
+                        status.with_node(opid).with_assignment(state_type).add_info(
+                            validation::Info::UncheckableConfidentialState(opid, state_type),
+                        );
                     }
-                    (OwnedStateSchema::Attachment(_), ConcealedState::Attachment(_)) => {
-                        status.add_info(validation::Info::UncheckableConfidentialState(
-                            opid, state_type,
-                        ));
+                    (
+                        OwnedStateSchema::Attachment(_) | OwnedStateSchema::BoundedAttachment(..),
+                        ConcealedState::Attachment(_),
+                    ) => {
+                        status.with_node(opid).with_assignment(state_type).add_info(
+                            validation::Info::UncheckableConfidentialState(opid, state_type),
+                        );
                     }
                     // all other options are mismatches
                     (state_schema, found) => {
-                        status.add_failure(validation::Failure::StateTypeMismatch {
-                            opid,
-                            state_type,
-                            expected: state_schema.state_type(),
-                            found: found.state_type(),
-                        });
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::StateTypeMismatch {
+                                opid,
+                                state_type,
+                                expected: state_schema.state_type(),
+                                found: found.state_type(),
+                            });
                     }
                 }
             }
@@ -76,45 +195,107 @@ impl OwnedStateSchema {
                 match (self, state.state_data()) {
                     (OwnedStateSchema::Declarative, RevealedState::Void) => {}
                     (
-                        OwnedStateSchema::Attachment(media_type),
+                        OwnedStateSchema::Attachment(media_type) |
+                        OwnedStateSchema::BoundedAttachment(media_type, _),
                         RevealedState::Attachment(attach),
                     ) if !attach.media_type.conforms(media_type) => {
-                        status.add_failure(validation::Failure::MediaTypeMismatch {
-                            opid,
-                            state_type,
-                            expected: *media_type,
-                            found: attach.media_type,
-                        });
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::MediaTypeMismatch {
+                                opid,
+                                state_type,
+                                expected: *media_type,
+                                found: attach.media_type,
+                            });
                     }
-                    (OwnedStateSchema::Fungible(schema), RevealedState::Fungible(v))
-                        if v.value.fungible_type() != *schema =>
-                    {
-                        status.add_failure(validation::Failure::FungibleTypeMismatch {
-                            opid,
-                            state_type,
-                            expected: *schema,
-                            found: v.value.fungible_type(),
-                        });
+                    (
+                        OwnedStateSchema::BoundedAttachment(_, max),
+                        RevealedState::Attachment(attach),
+                    ) if attach.size > *max => {
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::AttachmentTooLarge {
+                                opid,
+                                state_type,
+                                max: *max,
+                                size: attach.size,
+                            });
+                    }
+                    (
+                        OwnedStateSchema::Attachment(_) | OwnedStateSchema::BoundedAttachment(..),
+                        RevealedState::Attachment(_),
+                    ) => {}
+                    (
+                        OwnedStateSchema::Fungible(schema) |
+                        OwnedStateSchema::BoundedFungible(schema, _),
+                        RevealedState::Fungible(v),
+                    ) if v.value.fungible_type() != *schema => {
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::FungibleTypeMismatch {
+                                opid,
+                                state_type,
+                                expected: *schema,
+                                found: v.value.fungible_type(),
+                            });
                     }
                     (OwnedStateSchema::Fungible(_), RevealedState::Fungible(_)) => {}
+                    (OwnedStateSchema::BoundedFungible(_, bounds), RevealedState::Fungible(v))
+                        if v.value.is_zero() && !bounds.allows_zero() =>
+                    {
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::ZeroValueNotAllowed {
+                                opid,
+                                state_type,
+                            });
+                    }
+                    (OwnedStateSchema::BoundedFungible(_, bounds), RevealedState::Fungible(v))
+                        if !v.value.to_u64().is_some_and(|value| bounds.contains(value)) =>
+                    {
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::ValueOutOfBounds {
+                                opid,
+                                state_type,
+                                value: v.value,
+                                bounds: *bounds,
+                            });
+                    }
+                    (OwnedStateSchema::BoundedFungible(..), RevealedState::Fungible(_)) => {}
                     (OwnedStateSchema::Structured(sem_id), RevealedState::Structured(data)) => {
-                        if type_system
-                            .strict_deserialize_type(*sem_id, data.value.as_ref())
-                            .is_err()
+                        // Strict deserialization fails both on invalid and on not entirely
+                        // consumed data
+                        if let Err(err) =
+                            type_system.strict_deserialize_type(*sem_id, data.value.as_ref())
                         {
-                            status.add_failure(validation::Failure::SchemaInvalidOwnedValue(
-                                opid, state_type, *sem_id,
-                            ));
+                            status
+                                .with_node(opid)
+                                .with_assignment(state_type)
+                                .add_failure(validation::Failure::InvalidStructuredState {
+                                    opid,
+                                    state_type,
+                                    sem_id: *sem_id,
+                                    error: err.to_string(),
+                                });
                         };
                     }
                     // all other options are mismatches
                     (state_schema, found) => {
-                        status.add_failure(validation::Failure::StateTypeMismatch {
-                            opid,
-                            state_type,
-                            expected: state_schema.state_type(),
-                            found: found.state_type(),
-                        });
+                        status
+                            .with_node(opid)
+                            .with_assignment(state_type)
+                            .add_failure(validation::Failure::StateTypeMismatch {
+                                opid,
+                                state_type,
+                                expected: state_schema.state_type(),
+                                found: found.state_type(),
+                            });
                     }
                 }
             }
@@ -122,3 +303,412 @@ impl OwnedStateSchema {
         status
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::ByteArray;
+    use commit_verify::Conceal;
+    use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictSerialize, StrictType};
+    use strict_types::typelib::LibBuilder;
+    use strict_types::{SemId, SystemBuilder};
+
+    use super::*;
+    use crate::{
+        AssetTag, AttachId, BlindingFactor, DataState, FungibleState, FungibleType, GraphSeal,
+        MediaType, RevealedAttach, RevealedData, RevealedValue, ValueBounds, ZeroValue,
+    };
+
+    const LIB_NAME_TEST: &str = "StateTest";
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_TEST)]
+    struct Amount {
+        value: u64,
+        precision: u8,
+    }
+    impl StrictSerialize for Amount {}
+
+    fn amount_types() -> (SemId, TypeSystem) {
+        let lib = LibBuilder::new(libname!(LIB_NAME_TEST), None)
+            .transpile::<Amount>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("StateTest.Amount").unwrap();
+        (sem_id, sys.as_types().clone())
+    }
+
+    #[test]
+    fn attachment_media_types() {
+        let opid = OpId::strict_dumb();
+        let state_type = AssignmentType::with(1);
+        let types = TypeSystem::default();
+        let mut bulletproofs = BulletproofBatch::immediate();
+        let attach = RevealedAttach::with_salt(AttachId::strict_dumb(), &mime::IMAGE_PNG, 1024, 0);
+        assert_eq!(attach.media_type, MediaType::Image);
+        let revealed = Assign::<RevealedAttach, GraphSeal>::Revealed {
+            seal: strict_dumb!(),
+            state: attach.clone(),
+            lock: default!(),
+        };
+        let confidential = Assign::<RevealedAttach, GraphSeal>::Confidential {
+            seal: strict_dumb!(),
+            state: attach.conceal(),
+            lock: default!(),
+        };
+
+        for pattern in [MediaType::Any, MediaType::Image] {
+            let schema = OwnedStateSchema::Attachment(pattern);
+            let status = schema.validate(opid, state_type, &revealed, &types, &mut bulletproofs);
+            assert_eq!(status, validation::Status::new());
+        }
+
+        let schema = OwnedStateSchema::Attachment(MediaType::Text);
+        let status = schema.validate(opid, state_type, &revealed, &types, &mut bulletproofs);
+        assert_eq!(status.failures, vec![validation::Failure::MediaTypeMismatch {
+            opid,
+            state_type,
+            expected: MediaType::Text,
+            found: MediaType::Image,
+        }]);
+        let status = schema.validate(opid, state_type, &confidential, &types, &mut bulletproofs);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableConfidentialState(
+            opid, state_type
+        )]);
+    }
+
+    #[test]
+    fn attachment_size_limit() {
+        let opid = OpId::strict_dumb();
+        let state_type = AssignmentType::with(1);
+        let types = TypeSystem::default();
+        let mut bulletproofs = BulletproofBatch::immediate();
+        let attach = RevealedAttach::with_salt(AttachId::strict_dumb(), MediaType::Image, 1024, 0);
+        let revealed = Assign::<RevealedAttach, GraphSeal>::Revealed {
+            seal: strict_dumb!(),
+            state: attach.clone(),
+            lock: default!(),
+        };
+
+        for max in [1024, u64::MAX] {
+            let schema = OwnedStateSchema::BoundedAttachment(MediaType::Image, max);
+            let status = schema.validate(opid, state_type, &revealed, &types, &mut bulletproofs);
+            assert_eq!(status, validation::Status::new());
+        }
+
+        let schema = OwnedStateSchema::BoundedAttachment(MediaType::Image, 1023);
+        let status = schema.validate(opid, state_type, &revealed, &types, &mut bulletproofs);
+        assert_eq!(status.failures, vec![validation::Failure::AttachmentTooLarge {
+            opid,
+            state_type,
+            max: 1023,
+            size: 1024,
+        }]);
+        assert_eq!(status.failures[0].node, Some(opid));
+        assert_eq!(status.failures[0].assignment_type, Some(state_type));
+
+        // Media type is checked before the size
+        let schema = OwnedStateSchema::BoundedAttachment(MediaType::Text, 1023);
+        let status = schema.validate(opid, state_type, &revealed, &types, &mut bulletproofs);
+        assert_eq!(status.failures, vec![validation::Failure::MediaTypeMismatch {
+            opid,
+            state_type,
+            expected: MediaType::Text,
+            found: MediaType::Image,
+        }]);
+
+        // Size of the concealed attachment is unknown
+        let confidential = Assign::<RevealedAttach, GraphSeal>::Confidential {
+            seal: strict_dumb!(),
+            state: attach.conceal(),
+            lock: default!(),
+        };
+        let schema = OwnedStateSchema::BoundedAttachment(MediaType::Image, 1023);
+        let status = schema.validate(opid, state_type, &confidential, &types, &mut bulletproofs);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableConfidentialState(
+            opid, state_type
+        )]);
+    }
+
+    #[test]
+    fn structured_state_types() {
+        let opid = OpId::strict_dumb();
+        let state_type = AssignmentType::with(1);
+        let (sem_id, types) = amount_types();
+        let schema = OwnedStateSchema::Structured(sem_id);
+        let mut bulletproofs = BulletproofBatch::immediate();
+        let valid = RevealedData::from_typed(&Amount {
+            value: 100_000_000,
+            precision: 8,
+        })
+        .unwrap();
+        let with_value = |value: Vec<u8>| {
+            Assign::<RevealedData, GraphSeal>::revealed(
+                strict_dumb!(),
+                RevealedData::with_salt(DataState::try_from(value).unwrap(), 0),
+            )
+        };
+        let failure = |value: Vec<u8>| {
+            let status = schema.validate(
+                opid,
+                state_type,
+                &with_value(value),
+                &types,
+                &mut BulletproofBatch::immediate(),
+            );
+            assert_eq!(status.failures.len(), 1);
+            assert_eq!(status.failures[0].node, Some(opid));
+            assert_eq!(status.failures[0].assignment_type, Some(state_type));
+            match &status.failures[0].item {
+                validation::Failure::InvalidStructuredState {
+                    opid: id,
+                    state_type: ty,
+                    sem_id: failed,
+                    error,
+                } if *id == opid && *ty == state_type && *failed == sem_id => error.clone(),
+                other => panic!("unexpected failure {other:?}"),
+            }
+        };
+
+        let value = valid.value.to_vec();
+        assert_eq!(value.len(), 8 + 1);
+        let status = schema.validate(
+            opid,
+            state_type,
+            &with_value(value.clone()),
+            &types,
+            &mut bulletproofs,
+        );
+        assert_eq!(status, validation::Status::new());
+
+        let truncated = failure(value[..value.len() - 1].to_vec());
+        let mut over_long = value.clone();
+        over_long.push(0);
+        let over_long = failure(over_long);
+        // Decoding errors tell the truncated and over-long data apart
+        assert_ne!(truncated, over_long);
+        assert!(!failure(vec![]).is_empty());
+
+        // Concealed data can't be checked
+        let confidential = with_value(value).conceal_state();
+        let status = schema.validate(opid, state_type, &confidential, &types, &mut bulletproofs);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableConfidentialState(
+            opid, state_type
+        )]);
+    }
+
+    #[test]
+    fn bulletproof_batch_modes() {
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let schema = OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit);
+        let types = TypeSystem::default();
+        let values = (0..16u64)
+            .map(|no| RevealedValue::new_random_blinding(no * 1000, tag).conceal())
+            .collect::<Vec<_>>();
+
+        let mut immediate = BulletproofBatch::immediate();
+        let mut deferred = BulletproofBatch::deferred();
+        let mut immediate_status = validation::Status::new();
+        let mut deferred_status = validation::Status::new();
+        for (no, value) in values.iter().enumerate() {
+            let opid = OpId::from([no as u8; 32]);
+            let state_type = AssignmentType::with(no as u16);
+            let data = Assign::<RevealedValue, GraphSeal>::Confidential {
+                seal: strict_dumb!(),
+                state: *value,
+                lock: default!(),
+            };
+            immediate_status += schema.validate(opid, state_type, &data, &types, &mut immediate);
+            deferred_status += schema.validate(opid, state_type, &data, &types, &mut deferred);
+        }
+        assert!(immediate.is_empty());
+        assert_eq!(deferred.len(), values.len());
+        assert!(deferred_status.failures.is_empty());
+
+        deferred_status += deferred.verify();
+        assert!(deferred.is_empty());
+        assert_eq!(deferred_status.failures.len(), values.len());
+        assert_eq!(deferred_status.failures, immediate_status.failures);
+        assert_eq!(
+            deferred_status.failures[0],
+            validation::Failure::BulletproofsInvalid(
+                OpId::from([0u8; 32]),
+                AssignmentType::with(0),
+                RangeProofError::BulletproofsAbsent
+            )
+        );
+    }
+
+    #[test]
+    fn bulletproof_batch_mixed() {
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let schema = OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit);
+        let types = TypeSystem::default();
+
+        // Operations are validated one by one, like the validator does it: the
+        // first half collects proofs into per-operation deferred batches
+        // merged into the main one, the second half verifies them immediately.
+        // Only confidential values carry range proofs.
+        let mut main = BulletproofBatch::deferred();
+        let mut status = validation::Status::new();
+        let mut expected = vec![];
+        for no in 0..8u8 {
+            let opid = OpId::from([no; 32]);
+            let state_type = AssignmentType::with(no as u16);
+            let value = RevealedValue::new_random_blinding(no as u64 * 1000, tag);
+            let data = match no % 2 {
+                0 => Assign::<RevealedValue, GraphSeal>::Confidential {
+                    seal: strict_dumb!(),
+                    state: value.conceal(),
+                    lock: default!(),
+                },
+                _ => Assign::revealed(strict_dumb!(), value),
+            };
+            if no % 2 == 0 {
+                expected.push((opid, state_type));
+            }
+            if no < 4 {
+                let mut batch = BulletproofBatch::deferred();
+                status += schema.validate(opid, state_type, &data, &types, &mut batch);
+                main.append(batch);
+            } else {
+                let mut batch = BulletproofBatch::immediate();
+                status += schema.validate(opid, state_type, &data, &types, &mut batch);
+                assert!(batch.is_empty());
+            }
+        }
+        assert_eq!(main.len(), 2);
+        assert_eq!(status.failures.len(), 2);
+
+        // Batch verification fails and falls back to per-proof verification
+        status += main.verify();
+        assert!(main.is_empty());
+        assert_eq!(status.failures.len(), expected.len());
+        let mut reported = status
+            .failures
+            .iter()
+            .map(|failure| {
+                let opid = failure
+                    .node
+                    .expect("failure must be attributed to an operation");
+                let state_type = failure
+                    .assignment_type
+                    .expect("failure must be attributed to an assignment");
+                assert_eq!(
+                    failure.item,
+                    validation::Failure::BulletproofsInvalid(
+                        opid,
+                        state_type,
+                        RangeProofError::BulletproofsAbsent
+                    )
+                );
+                (opid, state_type)
+            })
+            .collect::<Vec<_>>();
+        reported.sort();
+        assert_eq!(reported, expected);
+    }
+
+    #[test]
+    fn fungible_value_bounds() {
+        let opid = OpId::strict_dumb();
+        let state_type = AssignmentType::with(1);
+        let types = TypeSystem::default();
+        let mut bulletproofs = BulletproofBatch::deferred();
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let bounds = ValueBounds { min: 1, max: 100 };
+        let schema = OwnedStateSchema::BoundedFungible(FungibleType::Unsigned64Bit, bounds);
+        let assign = |value: u64| {
+            Assign::<RevealedValue, GraphSeal>::revealed(
+                strict_dumb!(),
+                RevealedValue::new_random_blinding(value, tag),
+            )
+        };
+
+        for value in [1, 50, 100] {
+            let status =
+                schema.validate(opid, state_type, &assign(value), &types, &mut bulletproofs);
+            assert_eq!(status, validation::Status::new());
+        }
+        for value in [101, 1000] {
+            let status =
+                schema.validate(opid, state_type, &assign(value), &types, &mut bulletproofs);
+            assert_eq!(status.failures, vec![validation::Failure::ValueOutOfBounds {
+                opid,
+                state_type,
+                value: FungibleState::Bits64(value),
+                bounds
+            }]);
+        }
+
+        let status = schema.validate(opid, state_type, &assign(0), &types, &mut bulletproofs);
+        assert_eq!(status.failures, vec![validation::Failure::ZeroValueNotAllowed {
+            opid,
+            state_type
+        }]);
+
+        let confidential = assign(500).conceal();
+        let status = schema.validate(opid, state_type, &confidential, &types, &mut bulletproofs);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableValueBounds(opid, state_type)]);
+
+        // Schemata without bounds allow the full range of values
+        let schema = OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit);
+        assert_eq!(schema.value_bounds(), Some(ValueBounds::FULL));
+        let status = schema.validate(opid, state_type, &confidential, &types, &mut bulletproofs);
+        assert_eq!(status, validation::Status::new());
+    }
+
+    #[test]
+    fn fungible_zero_value() {
+        let opid = OpId::strict_dumb();
+        let state_type = AssignmentType::with(1);
+        let types = TypeSystem::default();
+        let mut bulletproofs = BulletproofBatch::deferred();
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let zero = Assign::<RevealedValue, GraphSeal>::revealed(
+            strict_dumb!(),
+            RevealedValue::new_random_blinding(0u64, tag),
+        );
+
+        // Zero values are allowed unless the schema prohibits them
+        for schema in [
+            OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OwnedStateSchema::BoundedFungible(FungibleType::Unsigned64Bit, ValueBounds::FULL),
+        ] {
+            let status = schema.validate(opid, state_type, &zero, &types, &mut bulletproofs);
+            assert_eq!(status, validation::Status::new());
+        }
+
+        let schema =
+            OwnedStateSchema::BoundedFungible(FungibleType::Unsigned64Bit, ValueBounds::NON_ZERO);
+        let status = schema.validate(opid, state_type, &zero, &types, &mut bulletproofs);
+        assert_eq!(status.failures, vec![validation::Failure::ZeroValueNotAllowed {
+            opid,
+            state_type
+        }]);
+        assert_eq!(
+            status.failures[0].item.to_string(),
+            format!("zero value of state in {opid}/{state_type} is not allowed by the schema.")
+        );
+
+        let status = schema.validate(opid, state_type, &zero.conceal(), &types, &mut bulletproofs);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.info, vec![validation::Info::UncheckableValueBounds(opid, state_type)]);
+
+        let blinding = BlindingFactor::random();
+        assert_eq!(RevealedValue::non_zero(0u64, blinding, tag), Err(ZeroValue));
+        assert_eq!(
+            RevealedValue::non_zero(1u64, blinding, tag),
+            Ok(RevealedValue::with_blinding(1u64, blinding, tag))
+        );
+    }
+}
@@ -20,9 +20,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::ops::AddAssign;
-use std::fmt::{self, Display, Formatter};
+use core::iter::Sum;
+use core::ops::{AddAssign, Deref};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Debug, Display, Formatter};
 
+use aluvm::library::LibSite;
 use bp::Txid;
 use commit_verify::mpc::InvalidProof;
 use strict_types::SemId;
@@ -30,11 +33,17 @@ use strict_types::SemId;
 use crate::contract::Opout;
 use crate::schema::{self, SchemaId};
 use crate::{
-    AssignmentType, BundleId, ContractId, Layer1, OccurrencesMismatch, OpFullType, OpId,
-    SecretSeal, StateType, Vin, XChain, XGraphSeal, XOutputSeal, XWitnessId,
+    AnchorError, AssignmentType, BundleId, ContractId, FungibleState, Layer1, OccurrencesMismatch,
+    OpFullType, OpId, RangeProofError, SecretSeal, StateType, Vin, XChain, XGraphSeal, XOutpoint,
+    XOutputSeal, XWitnessId,
 };
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 #[repr(u8)]
 pub enum Validity {
     #[display("is valid")]
@@ -50,7 +59,102 @@ pub enum Validity {
     Invalid,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Default)]
+/// Depth of the consignment validation, defining which of the checks are
+/// performed.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub enum ValidationDepth {
+    /// Checks graph integrity, commitments and conformance of the operation
+    /// types to the schema; state values, range proofs and scripts are not
+    /// checked.
+    #[display("structural")]
+    Structural,
+
+    /// Checks everything except the validation scripts.
+    #[display("no-scripts")]
+    NoScripts,
+
+    /// Performs all the checks.
+    #[default]
+    #[display("full")]
+    Full,
+}
+
+/// Kinds of the validation resource limits.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub enum LimitKind {
+    #[display("number of operations")]
+    Nodes,
+
+    #[display("operation graph depth")]
+    Depth,
+
+    #[display("number of seals per operation")]
+    SealsPerNode,
+
+    #[display("operation metadata size")]
+    MetadataBytes,
+}
+
+/// Operation data which number of occurrences is defined by the schema.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub enum OccurrencesSubject {
+    #[display("metadata {0}")]
+    Metadata(schema::MetaType),
+
+    #[display("global state {0}")]
+    GlobalState(schema::GlobalStateType),
+
+    #[display("inputs of owned state {0}")]
+    Input(schema::AssignmentType),
+
+    #[display("owned state {0}")]
+    Assignment(schema::AssignmentType),
+}
+
+/// Policy defining how validation warnings affect the validation result.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display(lowercase)]
+pub enum ValidationPolicy {
+    /// Warnings are reported, but do not make consignment invalid.
+    #[default]
+    Lenient,
+
+    /// Warnings which indicate a problem with the consignment data are
+    /// converted into failures (see [`Warning::strict_failure`]).
+    /// Informational entries are kept as is.
+    Strict,
+}
+
+/// Validation report.
+///
+/// Identical entries reported for the same location are kept once, counting
+/// their repetitions (see [`Located::repeated`]). The total number of the
+/// failures, warnings and info entries is limited (see
+/// [`Status::with_max_entries`]): entries exceeding the limit are dropped and
+/// counted in the [`Info::ReportTruncated`] entry, which is always the last of
+/// the info entries. The first of the failures is never dropped, such that the
+/// truncation doesn't affect the status validity.
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -59,16 +163,241 @@ pub enum Validity {
 pub struct Status {
     pub absent_pub_witnesses: Vec<XWitnessId>,
     pub unmined_terminals: Vec<Txid>,
-    pub failures: Vec<Failure>,
-    pub warnings: Vec<Warning>,
-    pub info: Vec<Info>,
+    #[cfg_attr(feature = "serde", serde(with = "coded_failures"))]
+    pub failures: Vec<Located<Failure>>,
+    pub warnings: Vec<Located<Warning>>,
+    pub info: Vec<Located<Info>>,
+    /// Depth of the validation which has produced the status. A status of
+    /// a validation which wasn't [`ValidationDepth::Full`] doesn't prove
+    /// the consignment validity even if it has no failures.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depth: ValidationDepth,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Status::default_max_entries"))]
+    max_entries: usize,
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Status {
+            absent_pub_witnesses: vec![],
+            unmined_terminals: vec![],
+            failures: vec![],
+            warnings: vec![],
+            info: vec![],
+            depth: ValidationDepth::default(),
+            max_entries: Status::default_max_entries(),
+        }
+    }
+}
+
+/// Validation report entry together with the operation (and, optionally, the
+/// assignment type) it was reported for.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct Located<T> {
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub node: Option<OpId>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub assignment_type: Option<AssignmentType>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub item: T,
+    /// Number of times the entry was reported again for the same location.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_zero"))]
+    pub repeated: usize,
+}
+
+#[cfg(feature = "serde")]
+fn is_zero(value: &usize) -> bool { *value == 0 }
+
+impl<T> From<T> for Located<T> {
+    fn from(item: T) -> Self {
+        Located {
+            node: None,
+            assignment_type: None,
+            item,
+            repeated: 0,
+        }
+    }
+}
+
+impl<T> Deref for Located<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target { &self.item }
+}
+
+/// Entries are compared by their content only, ignoring the location.
+impl<T: PartialEq> PartialEq<T> for Located<T> {
+    fn eq(&self, other: &T) -> bool { &self.item == other }
+}
+
+impl<T: Display> Display for Located<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(assignment_type) = self.assignment_type {
+            write!(f, "assignment {assignment_type}: ")?;
+        }
+        Display::fmt(&self.item, f)?;
+        if self.repeated > 0 {
+            write!(f, " (repeated {} more time(s))", self.repeated)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Located<T> {
+    /// Converts the entry content, keeping its location.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Located<U> {
+        Located {
+            node: self.node,
+            assignment_type: self.assignment_type,
+            item: f(self.item),
+            repeated: self.repeated,
+        }
+    }
+
+    pub fn into_inner(self) -> T { self.item }
+}
+
+impl<T: PartialEq> Located<T> {
+    /// Checks whether the entries have the same content and location,
+    /// regardless of the number of repetitions.
+    pub fn is_same(&self, other: &Self) -> bool {
+        self.node == other.node &&
+            self.assignment_type == other.assignment_type &&
+            self.item == other.item
+    }
 }
 
+/// Recorder of the validation report entries related to a specific
+/// operation, created with [`Status::with_node`]. Attaches the operation id
+/// (and the assignment type, if provided) to each of the added entries.
+pub struct NodeStatus<'status> {
+    status: &'status mut Status,
+    node: OpId,
+    assignment_type: Option<AssignmentType>,
+}
+
+impl NodeStatus<'_> {
+    /// Narrows the location of the entries down to the assignments of the
+    /// given type.
+    pub fn with_assignment(self, assignment_type: AssignmentType) -> Self {
+        Self {
+            assignment_type: Some(assignment_type),
+            ..self
+        }
+    }
+
+    fn locate<T>(&self, item: T) -> Located<T> {
+        Located {
+            node: Some(self.node),
+            assignment_type: self.assignment_type,
+            item,
+            repeated: 0,
+        }
+    }
+
+    pub fn add_failure(&mut self, failure: impl Into<Failure>) -> &mut Self {
+        let failure = self.locate(failure.into());
+        self.status.record_failure(failure);
+        self
+    }
+
+    pub fn add_warning(&mut self, warning: impl Into<Warning>) -> &mut Self {
+        let warning = self.locate(warning.into());
+        self.status.record(warning, |status| &mut status.warnings);
+        self
+    }
+
+    pub fn add_info(&mut self, info: impl Into<Info>) -> &mut Self {
+        let info = self.locate(info.into());
+        self.status.record_info(info);
+        self
+    }
+}
+
+/// Writes report entries grouping them by the operation they were reported
+/// for. Entries not related to a specific operation go first.
+fn fmt_located<T: Display>(
+    f: &mut Formatter<'_>,
+    title: &str,
+    entries: &[Located<T>],
+) -> fmt::Result {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{title}:")?;
+    let mut nodes = BTreeMap::<Option<OpId>, Vec<&Located<T>>>::new();
+    for entry in entries {
+        nodes.entry(entry.node).or_default().push(entry);
+    }
+    for (node, entries) in nodes {
+        let indent = match node {
+            None => "",
+            Some(node) => {
+                writeln!(f, "- operation {node}:")?;
+                "  "
+            }
+        };
+        for entry in entries {
+            writeln!(f, "{indent}- {entry}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Compact summary of the validation [`Status`], containing the number of
+/// each type of report entries and the overall validity.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display(
+    "consignment {validity} ({depth} validation): {failures} failure(s), {warnings} warning(s), \
+     {info} info entries, {absent_pub_witnesses} unknown and {unmined_terminals} non-mined \
+     witness(es)"
+)]
+pub struct StatusSummary {
+    pub validity: Validity,
+    pub depth: ValidationDepth,
+    pub absent_pub_witnesses: usize,
+    pub unmined_terminals: usize,
+    pub failures: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// Number of each type of the validation report entries.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[display("{failures} failure(s), {warnings} warning(s), {info} info entries")]
+pub struct StatusCounts {
+    pub failures: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// Starts with a one-line summary of the number of the report entries (and,
+/// in the alternate form, the consignment validity), followed by the
+/// entries.
 impl Display for Status {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if f.alternate() {
-            writeln!(f, "Consignment {}", self.validity())?;
+            write!(f, "Consignment {}", self.validity())?;
+            if self.depth != ValidationDepth::Full {
+                write!(f, " (only {} validation was performed)", self.depth)?;
+            }
+            f.write_str(": ")?;
         }
+        writeln!(f, "{}", self.counts())?;
 
         if !self.absent_pub_witnesses.is_empty() {
             f.write_str("Unknown public witnesses:\n")?;
@@ -84,38 +413,22 @@ impl Display for Status {
             }
         }
 
-        if !self.failures.is_empty() {
-            f.write_str("Validation failures:\n")?;
-            for fail in &self.failures {
-                writeln!(f, "- {fail}")?;
-            }
-        }
-
-        if !self.warnings.is_empty() {
-            f.write_str("Validation warnings:\n")?;
-            for warn in &self.warnings {
-                writeln!(f, "- {warn}")?;
-            }
-        }
-
-        if !self.info.is_empty() {
-            f.write_str("Validation info:\n")?;
-            for info in &self.info {
-                writeln!(f, "- {info}")?;
-            }
-        }
-
-        Ok(())
+        fmt_located(f, "Validation failures", &self.failures)?;
+        fmt_located(f, "Validation warnings", &self.warnings)?;
+        fmt_located(f, "Validation info", &self.info)
     }
 }
 
 impl AddAssign for Status {
-    fn add_assign(&mut self, rhs: Self) {
-        self.absent_pub_witnesses.extend(rhs.absent_pub_witnesses);
-        self.unmined_terminals.extend(rhs.unmined_terminals);
-        self.failures.extend(rhs.failures);
-        self.warnings.extend(rhs.warnings);
-        self.info.extend(rhs.info);
+    fn add_assign(&mut self, rhs: Self) { self.merge(rhs) }
+}
+
+impl Sum for Status {
+    fn sum<I: Iterator<Item = Status>>(iter: I) -> Self {
+        iter.fold(Status::new(), |mut acc, status| {
+            acc.merge(status);
+            acc
+        })
     }
 }
 
@@ -124,9 +437,11 @@ impl Status {
         Status {
             absent_pub_witnesses: vec![],
             unmined_terminals: vec![],
-            failures: vec![v],
+            failures: vec![v.into()],
             warnings: vec![],
             info: vec![],
+            depth: ValidationDepth::Full,
+            max_entries: Status::default_max_entries(),
         }
     }
 }
@@ -134,37 +449,297 @@ impl Status {
 impl FromIterator<Failure> for Status {
     fn from_iter<T: IntoIterator<Item = Failure>>(iter: T) -> Self {
         Self {
-            failures: iter.into_iter().collect(),
+            failures: iter.into_iter().map(Located::from).collect(),
             ..Self::default()
         }
     }
 }
 
 impl Status {
+    /// Default limit on the number of the report entries.
+    pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
     pub fn new() -> Self { Self::default() }
 
+    /// Constructs empty status keeping at most `max_entries` failures,
+    /// warnings and info entries, not counting the [`Info::ReportTruncated`]
+    /// entry. The first failure is kept even if the limit is exceeded.
+    pub fn with_max_entries(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ..Self::default()
+        }
+    }
+
+    fn default_max_entries() -> usize { Self::DEFAULT_MAX_ENTRIES }
+
+    /// Returns the limit on the number of the report entries.
+    pub fn max_entries(&self) -> usize { self.max_entries }
+
     pub fn with_failure(failure: impl Into<Failure>) -> Self {
         Self {
-            failures: vec![failure.into()],
+            failures: vec![Located::from(failure.into())],
             ..Self::default()
         }
     }
 
     pub fn add_failure(&mut self, failure: impl Into<Failure>) -> &Self {
-        self.failures.push(failure.into());
+        self.record_failure(Located::from(failure.into()));
         self
     }
 
     pub fn add_warning(&mut self, warning: impl Into<Warning>) -> &Self {
-        self.warnings.push(warning.into());
+        self.record(Located::from(warning.into()), |status| &mut status.warnings);
         self
     }
 
     pub fn add_info(&mut self, info: impl Into<Info>) -> &Self {
-        self.info.push(info.into());
+        self.record_info(Located::from(info.into()));
         self
     }
 
+    /// Adds the entry to the list selected by `entries`, collapsing it with
+    /// an identical entry, if present. Entries exceeding the limit are
+    /// dropped without looking for the identical ones, such that hostile
+    /// consignments can't make validation quadratic in the number of entries.
+    fn record<T: PartialEq>(
+        &mut self,
+        entry: Located<T>,
+        entries: fn(&mut Status) -> &mut Vec<Located<T>>,
+    ) {
+        let marker = matches!(
+            self.info.last(),
+            Some(Located {
+                item: Info::ReportTruncated { .. },
+                ..
+            })
+        ) as usize;
+        if self.failures.len() + self.warnings.len() + self.info.len() - marker >= self.max_entries
+        {
+            self.record_dropped(entry.repeated + 1);
+            return;
+        }
+        let entries = entries(self);
+        match entries.iter_mut().find(|existing| existing.is_same(&entry)) {
+            Some(existing) => existing.repeated += entry.repeated + 1,
+            None => entries.push(entry),
+        }
+    }
+
+    /// Merges `other` status into this one.
+    ///
+    /// Entries of `other` are appended after the existing ones, preserving
+    /// their order; entries identical to the already present ones are
+    /// collapsed into them, as well as duplicated witness ids. Since validity
+    /// is derived from the entries, the merged status is invalid if any of the
+    /// merged statuses is. The merged validation depth is the lowest of the
+    /// two.
+    pub fn merge(&mut self, other: Status) {
+        for witness_id in other.absent_pub_witnesses {
+            if !self.absent_pub_witnesses.contains(&witness_id) {
+                self.absent_pub_witnesses.push(witness_id);
+            }
+        }
+        for txid in other.unmined_terminals {
+            if !self.unmined_terminals.contains(&txid) {
+                self.unmined_terminals.push(txid);
+            }
+        }
+        for failure in other.failures {
+            self.record_failure(failure);
+        }
+        for warning in other.warnings {
+            self.record(warning, |status| &mut status.warnings);
+        }
+        for info in other.info {
+            self.record_info(info);
+        }
+        self.depth = self.depth.min(other.depth);
+    }
+
+    fn record_failure(&mut self, failure: Located<Failure>) {
+        // The first failure is always kept, making the status invalid
+        if self.failures.is_empty() {
+            self.failures.push(failure);
+        } else {
+            self.record(failure, |status| &mut status.failures);
+        }
+    }
+
+    fn record_info(&mut self, info: Located<Info>) {
+        if let Info::ReportTruncated { dropped } = info.item {
+            self.record_dropped(dropped);
+            return;
+        }
+        // Keeping the truncation marker the last of the info entries
+        let marker = match self.info.last() {
+            Some(Located {
+                item: Info::ReportTruncated { .. },
+                ..
+            }) => self.info.pop(),
+            _ => None,
+        };
+        self.record(info, |status| &mut status.info);
+        if let Some(Located {
+            item: Info::ReportTruncated { dropped },
+            ..
+        }) = marker
+        {
+            self.record_dropped(dropped);
+        }
+    }
+
+    fn record_dropped(&mut self, count: usize) {
+        if let Some(Located {
+            item: Info::ReportTruncated { dropped },
+            ..
+        }) = self.info.last_mut()
+        {
+            *dropped += count;
+        } else {
+            self.info
+                .push(Located::from(Info::ReportTruncated { dropped: count }));
+        }
+    }
+
+    /// Returns recorder attaching the id of the operation `node` to all the
+    /// entries added through it.
+    pub fn with_node(&mut self, node: OpId) -> NodeStatus<'_> {
+        NodeStatus {
+            status: self,
+            node,
+            assignment_type: None,
+        }
+    }
+
+    /// Applies validation policy to the status, converting warnings into
+    /// failures when required by the policy.
+    pub fn enforce(mut self, policy: ValidationPolicy) -> Self {
+        match policy {
+            ValidationPolicy::Lenient => {}
+            ValidationPolicy::Strict => {
+                let mut warnings = vec![];
+                for warning in self.warnings {
+                    match warning.strict_failure() {
+                        Some(failure) => self.failures.push(warning.map(|_| failure)),
+                        None => warnings.push(warning),
+                    }
+                }
+                self.warnings = warnings;
+            }
+        }
+        self
+    }
+
+    /// Brings the status into a canonical form, which doesn't depend on the
+    /// order in which the entries were reported (for instance, with the
+    /// parallel validation). Failures are sorted by their code, and then all
+    /// entries by the operation and the assignment type they were reported
+    /// for and by their data; identical entries are merged summing up their
+    /// repetitions. The [`Info::ReportTruncated`] entry is kept last.
+    ///
+    /// Statuses returned by the [`super::Validator`] are always normalized.
+    pub fn normalize(&mut self) {
+        fn normalize_entries<T: Debug + PartialEq>(
+            entries: &mut Vec<Located<T>>,
+            code: impl Fn(&T) -> u16,
+        ) {
+            entries.sort_by_cached_key(|entry| {
+                (code(&entry.item), entry.node, entry.assignment_type, format!("{:?}", entry.item))
+            });
+            entries.dedup_by(|next, prev| {
+                let same = next.is_same(prev);
+                if same {
+                    prev.repeated += next.repeated + 1;
+                }
+                same
+            });
+        }
+
+        self.absent_pub_witnesses.sort();
+        self.absent_pub_witnesses.dedup();
+        self.unmined_terminals.sort();
+        self.unmined_terminals.dedup();
+        normalize_entries(&mut self.failures, Failure::code);
+        normalize_entries(&mut self.warnings, |_| 0);
+        let dropped = self
+            .info
+            .iter()
+            .map(|info| match info.item {
+                Info::ReportTruncated { dropped } => dropped,
+                _ => 0,
+            })
+            .sum::<usize>();
+        self.info
+            .retain(|info| !matches!(info.item, Info::ReportTruncated { .. }));
+        normalize_entries(&mut self.info, |_| 0);
+        if dropped > 0 {
+            self.info
+                .push(Located::from(Info::ReportTruncated { dropped }));
+        }
+    }
+
+    /// Returns number of each type of the report entries.
+    pub fn counts(&self) -> StatusCounts {
+        StatusCounts {
+            failures: self.failures.len(),
+            warnings: self.warnings.len(),
+            info: self.info.len(),
+        }
+    }
+
+    /// Detects whether the status contains no report entries and no unknown
+    /// or non-mined witnesses.
+    ///
+    /// Validity of the consignment must be checked with [`Status::validity`]
+    /// instead: a non-empty status may be valid, for instance a status with
+    /// only info entries (or even with warnings under the
+    /// [`ValidationPolicy::Lenient`] policy).
+    pub fn is_empty(&self) -> bool {
+        self.absent_pub_witnesses.is_empty() &&
+            self.unmined_terminals.is_empty() &&
+            self.failures.is_empty() &&
+            self.warnings.is_empty() &&
+            self.info.is_empty()
+    }
+
+    /// Returns the first of the reported failures, if any.
+    pub fn first_failure(&self) -> Option<&Located<Failure>> { self.failures.first() }
+
+    /// Groups failures by their codes, keeping the order in which they were
+    /// reported within each of the groups.
+    pub fn failures_by_code(&self) -> BTreeMap<FailureCode, Vec<&Located<Failure>>> {
+        let mut groups = BTreeMap::<FailureCode, Vec<_>>::new();
+        for failure in &self.failures {
+            groups
+                .entry(failure.failure_code())
+                .or_default()
+                .push(failure);
+        }
+        groups
+    }
+
+    /// Returns compact summary of the status.
+    pub fn summary(&self) -> StatusSummary {
+        StatusSummary {
+            validity: self.validity(),
+            depth: self.depth,
+            absent_pub_witnesses: self.absent_pub_witnesses.len(),
+            unmined_terminals: self.unmined_terminals.len(),
+            failures: self.failures.len(),
+            warnings: self.warnings.len(),
+            info: self.info.len(),
+        }
+    }
+
+    /// Exports the status as a pretty-printed JSON document, suitable for
+    /// machine processing of the validation reports.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("validation status serialization into JSON never fails")
+    }
+
     pub fn validity(&self) -> Validity {
         if self.failures.is_empty() {
             if self.unmined_terminals.is_empty() {
@@ -237,32 +812,55 @@ pub enum Failure {
     SchemaUnknownAssignmentType(OpId, schema::AssignmentType),
     /// operation {0} uses invalid valency type {1}.
     SchemaUnknownValencyType(OpId, schema::ValencyType),
+    /// state extension {opid} of type {extension_type} redeems valency
+    /// {valency} which is not allowed to be redeemed by the extension type.
+    IllegalValencyRedemption {
+        opid: OpId,
+        extension_type: schema::ExtensionType,
+        valency: schema::ValencyType,
+    },
 
-    /// invalid number of global state entries of type {1} in operation {0} -
-    /// {2}
-    SchemaGlobalStateOccurrences(OpId, schema::GlobalStateType, OccurrencesMismatch),
+    /// operation {opid} doesn't match schema occurrence rules for {subject}:
+    /// {mismatch}.
+    OccurrencesViolation {
+        opid: OpId,
+        subject: OccurrencesSubject,
+        mismatch: OccurrencesMismatch,
+    },
     /// number of global state entries of type {1} in operation {0} exceeds
     /// schema-defined maximum for that global state type ({2} vs {3}).
     SchemaGlobalStateLimit(OpId, schema::GlobalStateType, u16, u16),
-    /// required metadata type {1} is not present in the operation {0}.
-    SchemaNoMetadata(OpId, schema::MetaType),
     /// invalid metadata in operation {0} not matching semantic type id {1}.
-    SchemaInvalidMetadata(OpId, SemId),
+    /// Details: {2}
+    SchemaInvalidMetadata(OpId, SemId, String),
+    /// timestamp {timestamp} in metadata {meta_type} of operation {opid} is
+    /// outside of the allowed range {min}..={max}.
+    TimestampOutOfRange {
+        opid: OpId,
+        meta_type: schema::MetaType,
+        timestamp: i64,
+        min: i64,
+        max: i64,
+    },
+    /// state transition {opid} is bound to schema {actual}, while the contract
+    /// uses schema {expected}.
+    TransitionSchemaMismatch {
+        opid: OpId,
+        expected: SchemaId,
+        actual: SchemaId,
+    },
     /// invalid global state value in operation {0}, state type #{1} which does
-    /// not match semantic type id {2}.
-    SchemaInvalidGlobalValue(OpId, schema::GlobalStateType, SemId),
+    /// not match semantic type id {2}. Details: {3}
+    SchemaInvalidGlobalValue(OpId, schema::GlobalStateType, SemId, String),
     /// invalid owned state value in operation {0}, state type #{1} which does
     /// not match semantic type id {2}.
     SchemaInvalidOwnedValue(OpId, schema::AssignmentType, SemId),
-    /// invalid number of input entries of type {1} in operation {0} - {2}  
-    SchemaInputOccurrences(OpId, schema::AssignmentType, OccurrencesMismatch),
-    /// invalid number of assignment entries of type {1} in operation {0} - {2}
-    SchemaAssignmentOccurrences(OpId, schema::AssignmentType, OccurrencesMismatch),
 
     // Consignment consistency errors
-    /// operation {0} is referenced within the history multiple times. RGB
-    /// contracts allow only direct acyclic graphs.
-    CyclicGraph(OpId),
+    // TODO: Replace debug with display
+    /// operations {nodes:?} are referenced within the history multiple times
+    /// forming a cycle. RGB contracts allow only direct acyclic graphs.
+    CyclicGraph { nodes: Vec<OpId> },
     /// operation {0} is absent from the consignment.
     OperationAbsent(OpId),
     /// transition bundle {0} referenced in consignment terminals is absent from
@@ -276,11 +874,43 @@ pub enum Failure {
     WitnessIdAbsent(BundleId),
     /// operation {0} is under a different contract {1}.
     ContractMismatch(OpId, ContractId),
+    /// {0} exceeds validation limit of {1} (found {2}); the validation was
+    /// aborted.
+    LimitExceeded(LimitKind, usize, usize),
+    // TODO: Replace debug with display
+    /// terminal seal {1:?} referencing operation {0} is not present in
+    /// operation assignments.
+    TerminalSealAbsent(OpId, XChain<SecretSeal>),
+    // TODO: Replace debug with display
+    /// seal {0:?} expected by the receiver is not assigned by any of the
+    /// consignment terminal operations.
+    MissingTerminalSeal(XChain<SecretSeal>),
+    /// output {outpoint} is spent by multiple operations, including {first}
+    /// and {second}.
+    DoubleSpend {
+        outpoint: Opout,
+        first: OpId,
+        second: OpId,
+    },
+    /// revealed data of the assignment {assignment_type}/{index} of the
+    /// operation {opid} doesn't match the concealed data committed by another
+    /// copy of the same operation within the consignment.
+    RevealConcealMismatch {
+        opid: OpId,
+        assignment_type: schema::AssignmentType,
+        index: u16,
+    },
 
     // Errors checking bundle commitments
-    /// transition bundle {0} references state transition {1} which is not
-    /// included into the bundle input map.
+    /// transition bundle {0} contains state transition {1} which is not
+    /// covered by the bundle input map.
     BundleExtraTransition(BundleId, OpId),
+    /// transition bundle {bundle_id} commits to the input spending {outpoint}
+    /// for a state transition which is not contained in the bundle.
+    BundleInputUncovered {
+        bundle_id: BundleId,
+        outpoint: XOutpoint,
+    },
     /// transition bundle {0} references non-existing input in witness {2} for
     /// the state transition {1}.
     BundleInvalidInput(BundleId, OpId, XWitnessId),
@@ -315,8 +945,30 @@ pub enum Failure {
     /// seal {1} is defined on {0} which is not in the set of layers allowed
     /// by the contract genesis.
     SealLayerMismatch(Layer1, XGraphSeal),
+    /// operation {opid} assigns state of type {assignment_type} to seal {seal},
+    /// which is defined on a layer 1 not allowed by the contract genesis.
+    SealAssignmentLayerMismatch {
+        opid: OpId,
+        assignment_type: schema::AssignmentType,
+        seal: XGraphSeal,
+    },
+    // TODO: Replace debug with display
+    /// witness {witness_id} is defined on a layer 1 not in the set of layers
+    /// {expected:?} allowed for it.
+    WitnessLayerMismatch {
+        witness_id: XWitnessId,
+        expected: BTreeSet<Layer1>,
+    },
     /// seal {1} has a different closing method from the bundle {0} requirement.
     SealInvalidMethod(BundleId, XOutputSeal),
+    /// transition bundle {0} is anchored to {1} layer 1, which doesn't support
+    /// validation of single-use seals.
+    #[cfg(feature = "prime-seals")]
+    SealUnsupportedLayer1(BundleId, Layer1),
+    /// seal {1} defined in the history as a part of operation output {0}
+    /// doesn't provide a closing method and can't be validated.
+    #[cfg(feature = "prime-seals")]
+    SealNoCloseMethod(Opout, XGraphSeal),
     /// transition bundle {0} doesn't close seal with the witness {1}. Details:
     /// {2}
     SealsInvalid(BundleId, XWitnessId, String),
@@ -326,9 +978,33 @@ pub enum Failure {
     /// anchor provides different type of DBC proof than required by the bundle
     /// {0}.
     AnchorMethodMismatch(BundleId),
-    /// transition bundle {0} is not properly anchored to the witness {1}.
-    /// Details: {2}
-    MpcInvalid(BundleId, XWitnessId, InvalidProof),
+    /// multi-protocol commitment proof for transition bundle {bundle_id} in
+    /// the anchor of witness {witness_id} is invalid: {details}
+    MpcProofInvalid {
+        bundle_id: BundleId,
+        witness_id: XWitnessId,
+        details: InvalidProof,
+    },
+    /// witness {witness_id} doesn't commit to the contract {contract_id} with
+    /// the multi-protocol commitment provided by the anchor.
+    ContractNotCommitted {
+        contract_id: ContractId,
+        witness_id: XWitnessId,
+    },
+    /// transition bundle {expected} is anchored under a different bundle id
+    /// {committed}.
+    BundleIdMismatch {
+        expected: BundleId,
+        committed: BundleId,
+    },
+    /// witness {witness_id} is rejected by the anchor policy: {reason}.
+    AnchorPolicyViolation {
+        witness_id: XWitnessId,
+        reason: String,
+    },
+    /// anchor is invalid: {0}
+    #[from]
+    AnchorInvalid(AnchorError),
 
     // State extensions errors
     /// valency {valency} redeemed by state extension {opid} references
@@ -371,17 +1047,377 @@ pub enum Failure {
         expected: schema::FungibleType,
         found: schema::FungibleType,
     },
+    /// structured state in {opid}/{state_type} doesn't decode as semantic type
+    /// {sem_id}: {error}
+    InvalidStructuredState {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        sem_id: SemId,
+        /// Strict decoding error, including the case of data not entirely
+        /// consumed by the decoder.
+        error: String,
+    },
+    /// attachment in {opid}/{state_type} advertises size of {size} bytes,
+    /// exceeding the maximum of {max} bytes allowed by the schema.
+    AttachmentTooLarge {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        max: u64,
+        size: u64,
+    },
+    /// value {value} of state in {opid}/{state_type} is outside of the bounds
+    /// {bounds} required by the schema.
+    ValueOutOfBounds {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        value: FungibleState,
+        bounds: schema::ValueBounds,
+    },
+    /// zero value of state in {opid}/{state_type} is not allowed by the
+    /// schema.
+    ZeroValueNotAllowed {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+    },
+    /// fungible state {state_type} issued by the contract totals {issued},
+    /// exceeding the maximum supply {max} declared in genesis.
+    MaxSupplyExceeded {
+        state_type: schema::AssignmentType,
+        max: u64,
+        /// Total issued value, saturated at `u64::MAX` on overflow.
+        issued: u64,
+    },
     /// invalid bulletproofs in {0}:{1}: {2}
-    BulletproofsInvalid(OpId, schema::AssignmentType, String),
+    BulletproofsInvalid(OpId, schema::AssignmentType, RangeProofError),
+    /// sum of inputs and outputs of fungible state {state_type} in state
+    /// transition {opid} doesn't match.
+    PedersenSumMismatch {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+    },
     /// evaluation of AluVM script for operation {0} has failed with the code
     /// {1:?} and message {2:?}.
     ScriptFailure(OpId, Option<u8>, Option<String>),
+    /// validation script for operation {opid} starting at {entry_point} has
+    /// exceeded the execution limits.
+    ScriptResourceExhausted { opid: OpId, entry_point: LibSite },
 
     /// Custom error by external services on top of RGB Core.
     #[display(inner)]
     Custom(String),
 }
 
+/// Stable numeric codes of the validation failures.
+///
+/// Unlike failure [`Display`] messages, the codes never change across
+/// releases: new failure types receive new codes, and codes of the removed
+/// failures are never reused. Codes are grouped by the failure category, with
+/// the hundreds digit indicating the category. Codes for the feature-gated
+/// failures are reserved regardless of the enabled features.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", into = "u16", try_from = "u16")
+)]
+#[repr(u16)]
+pub enum FailureCode {
+    // Schema and schema-defined operation structure failures (1xx).
+    SchemaMismatch = 100,
+    SchemaBlankTransitionRedefined = 101,
+    SchemaGlobalSemIdUnknown = 102,
+    SchemaOwnedSemIdUnknown = 103,
+    SchemaMetaSemIdUnknown = 104,
+    SchemaOpEmptyInputs = 105,
+    SchemaOpMetaTypeUnknown = 106,
+    SchemaOpGlobalTypeUnknown = 107,
+    SchemaOpAssignmentTypeUnknown = 108,
+    SchemaOpValencyTypeUnknown = 109,
+    SchemaUnknownExtensionType = 110,
+    SchemaUnknownTransitionType = 111,
+    SchemaUnknownMetaType = 112,
+    SchemaUnknownGlobalStateType = 113,
+    SchemaUnknownAssignmentType = 114,
+    SchemaUnknownValencyType = 115,
+    SchemaGlobalStateLimit = 117,
+    SchemaInvalidMetadata = 119,
+    SchemaInvalidGlobalValue = 120,
+    SchemaInvalidOwnedValue = 121,
+    IllegalValencyRedemption = 124,
+    OccurrencesViolation = 125,
+    TimestampOutOfRange = 126,
+    TransitionSchemaMismatch = 127,
+
+    // Consignment graph consistency failures (2xx).
+    NetworkMismatch = 200,
+    CyclicGraph = 201,
+    OperationAbsent = 202,
+    TerminalBundleAbsent = 203,
+    BundleAbsent = 204,
+    AnchorAbsent = 205,
+    WitnessIdAbsent = 206,
+    ContractMismatch = 207,
+    BundleExtraTransition = 208,
+    BundleInvalidInput = 209,
+    BundleInvalidCommitment = 210,
+    NoPrevState = 211,
+    NoPrevOut = 212,
+    ValencyNoParent = 213,
+    NoPrevValency = 214,
+    TerminalSealAbsent = 215,
+    LimitExceeded = 216,
+    MissingTerminalSeal = 217,
+    DoubleSpend = 218,
+    RevealConcealMismatch = 219,
+    BundleInputUncovered = 220,
+
+    // Single-use seal and anchor failures (3xx).
+    ConfidentialSeal = 300,
+    SealNoWitnessTx = 301,
+    SealWitnessLayer1Mismatch = 302,
+    SealLayerMismatch = 303,
+    SealAssignmentLayerMismatch = 304,
+    SealInvalidMethod = 305,
+    SealUnsupportedLayer1 = 306,
+    SealNoCloseMethod = 307,
+    SealsInvalid = 308,
+    SealsUnvalidated = 309,
+    AnchorMethodMismatch = 310,
+    MpcProofInvalid = 311,
+    WitnessLayerMismatch = 312,
+    AnchorPolicyViolation = 313,
+    ContractNotCommitted = 314,
+    BundleIdMismatch = 315,
+    AnchorInvalid = 316,
+
+    // AluVM script failures (4xx).
+    ScriptFailure = 400,
+    ScriptResourceExhausted = 401,
+
+    // Owned state type failures (5xx).
+    StateTypeMismatch = 500,
+    MediaTypeMismatch = 501,
+    FungibleTypeMismatch = 502,
+    AssetTagNoState = 503,
+    FungibleStateNoTag = 504,
+    ValueOutOfBounds = 505,
+    ZeroValueNotAllowed = 506,
+    MaxSupplyExceeded = 507,
+    AttachmentTooLarge = 508,
+    InvalidStructuredState = 509,
+
+    // Bulletproof range proof failures (6xx).
+    BulletproofsInvalid = 600,
+    PedersenSumMismatch = 601,
+
+    // Failures reported by external services on top of RGB Core (9xx).
+    Custom = 900,
+}
+
+/// Error indicating an unknown validation failure code.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("unknown validation failure code {0}")]
+pub struct UnknownFailureCode(pub u16);
+
+impl FailureCode {
+    /// All known failure codes.
+    pub const ALL: [FailureCode; 77] = [
+        FailureCode::SchemaMismatch,
+        FailureCode::SchemaBlankTransitionRedefined,
+        FailureCode::SchemaGlobalSemIdUnknown,
+        FailureCode::SchemaOwnedSemIdUnknown,
+        FailureCode::SchemaMetaSemIdUnknown,
+        FailureCode::SchemaOpEmptyInputs,
+        FailureCode::SchemaOpMetaTypeUnknown,
+        FailureCode::SchemaOpGlobalTypeUnknown,
+        FailureCode::SchemaOpAssignmentTypeUnknown,
+        FailureCode::SchemaOpValencyTypeUnknown,
+        FailureCode::SchemaUnknownExtensionType,
+        FailureCode::SchemaUnknownTransitionType,
+        FailureCode::SchemaUnknownMetaType,
+        FailureCode::SchemaUnknownGlobalStateType,
+        FailureCode::SchemaUnknownAssignmentType,
+        FailureCode::SchemaUnknownValencyType,
+        FailureCode::SchemaGlobalStateLimit,
+        FailureCode::SchemaInvalidMetadata,
+        FailureCode::SchemaInvalidGlobalValue,
+        FailureCode::SchemaInvalidOwnedValue,
+        FailureCode::IllegalValencyRedemption,
+        FailureCode::OccurrencesViolation,
+        FailureCode::TimestampOutOfRange,
+        FailureCode::TransitionSchemaMismatch,
+        FailureCode::NetworkMismatch,
+        FailureCode::CyclicGraph,
+        FailureCode::OperationAbsent,
+        FailureCode::TerminalBundleAbsent,
+        FailureCode::BundleAbsent,
+        FailureCode::AnchorAbsent,
+        FailureCode::WitnessIdAbsent,
+        FailureCode::ContractMismatch,
+        FailureCode::BundleExtraTransition,
+        FailureCode::BundleInvalidInput,
+        FailureCode::BundleInvalidCommitment,
+        FailureCode::NoPrevState,
+        FailureCode::NoPrevOut,
+        FailureCode::ValencyNoParent,
+        FailureCode::NoPrevValency,
+        FailureCode::TerminalSealAbsent,
+        FailureCode::LimitExceeded,
+        FailureCode::MissingTerminalSeal,
+        FailureCode::DoubleSpend,
+        FailureCode::RevealConcealMismatch,
+        FailureCode::BundleInputUncovered,
+        FailureCode::ConfidentialSeal,
+        FailureCode::SealNoWitnessTx,
+        FailureCode::SealWitnessLayer1Mismatch,
+        FailureCode::SealLayerMismatch,
+        FailureCode::SealAssignmentLayerMismatch,
+        FailureCode::SealInvalidMethod,
+        FailureCode::SealUnsupportedLayer1,
+        FailureCode::SealNoCloseMethod,
+        FailureCode::SealsInvalid,
+        FailureCode::SealsUnvalidated,
+        FailureCode::AnchorMethodMismatch,
+        FailureCode::MpcProofInvalid,
+        FailureCode::WitnessLayerMismatch,
+        FailureCode::AnchorPolicyViolation,
+        FailureCode::ContractNotCommitted,
+        FailureCode::BundleIdMismatch,
+        FailureCode::AnchorInvalid,
+        FailureCode::ScriptFailure,
+        FailureCode::ScriptResourceExhausted,
+        FailureCode::StateTypeMismatch,
+        FailureCode::MediaTypeMismatch,
+        FailureCode::FungibleTypeMismatch,
+        FailureCode::AssetTagNoState,
+        FailureCode::FungibleStateNoTag,
+        FailureCode::ValueOutOfBounds,
+        FailureCode::ZeroValueNotAllowed,
+        FailureCode::MaxSupplyExceeded,
+        FailureCode::AttachmentTooLarge,
+        FailureCode::InvalidStructuredState,
+        FailureCode::BulletproofsInvalid,
+        FailureCode::PedersenSumMismatch,
+        FailureCode::Custom,
+    ];
+
+    /// Returns numeric value of the failure code.
+    pub const fn code(self) -> u16 { self as u16 }
+}
+
+impl Display for FailureCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { write!(f, "E{:03}", self.code()) }
+}
+
+impl From<FailureCode> for u16 {
+    fn from(code: FailureCode) -> Self { code.code() }
+}
+
+impl TryFrom<u16> for FailureCode {
+    type Error = UnknownFailureCode;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        FailureCode::ALL
+            .into_iter()
+            .find(|c| c.code() == code)
+            .ok_or(UnknownFailureCode(code))
+    }
+}
+
+impl Failure {
+    /// Returns stable code for the failure type.
+    pub fn failure_code(&self) -> FailureCode {
+        match self {
+            Failure::SchemaMismatch { .. } => FailureCode::SchemaMismatch,
+            Failure::SchemaBlankTransitionRedefined => FailureCode::SchemaBlankTransitionRedefined,
+            Failure::SchemaGlobalSemIdUnknown(..) => FailureCode::SchemaGlobalSemIdUnknown,
+            Failure::SchemaOwnedSemIdUnknown(..) => FailureCode::SchemaOwnedSemIdUnknown,
+            Failure::SchemaMetaSemIdUnknown(..) => FailureCode::SchemaMetaSemIdUnknown,
+            Failure::SchemaOpEmptyInputs(..) => FailureCode::SchemaOpEmptyInputs,
+            Failure::SchemaOpMetaTypeUnknown(..) => FailureCode::SchemaOpMetaTypeUnknown,
+            Failure::SchemaOpGlobalTypeUnknown(..) => FailureCode::SchemaOpGlobalTypeUnknown,
+            Failure::SchemaOpAssignmentTypeUnknown(..) => {
+                FailureCode::SchemaOpAssignmentTypeUnknown
+            }
+            Failure::SchemaOpValencyTypeUnknown(..) => FailureCode::SchemaOpValencyTypeUnknown,
+            Failure::SchemaUnknownExtensionType(..) => FailureCode::SchemaUnknownExtensionType,
+            Failure::SchemaUnknownTransitionType(..) => FailureCode::SchemaUnknownTransitionType,
+            Failure::SchemaUnknownMetaType(..) => FailureCode::SchemaUnknownMetaType,
+            Failure::SchemaUnknownGlobalStateType(..) => FailureCode::SchemaUnknownGlobalStateType,
+            Failure::SchemaUnknownAssignmentType(..) => FailureCode::SchemaUnknownAssignmentType,
+            Failure::SchemaUnknownValencyType(..) => FailureCode::SchemaUnknownValencyType,
+            Failure::SchemaGlobalStateLimit(..) => FailureCode::SchemaGlobalStateLimit,
+            Failure::SchemaInvalidMetadata(..) => FailureCode::SchemaInvalidMetadata,
+            Failure::TimestampOutOfRange { .. } => FailureCode::TimestampOutOfRange,
+            Failure::TransitionSchemaMismatch { .. } => FailureCode::TransitionSchemaMismatch,
+            Failure::SchemaInvalidGlobalValue(..) => FailureCode::SchemaInvalidGlobalValue,
+            Failure::SchemaInvalidOwnedValue(..) => FailureCode::SchemaInvalidOwnedValue,
+            Failure::IllegalValencyRedemption { .. } => FailureCode::IllegalValencyRedemption,
+            Failure::OccurrencesViolation { .. } => FailureCode::OccurrencesViolation,
+            Failure::NetworkMismatch(_) => FailureCode::NetworkMismatch,
+            Failure::CyclicGraph { .. } => FailureCode::CyclicGraph,
+            Failure::OperationAbsent(_) => FailureCode::OperationAbsent,
+            Failure::TerminalBundleAbsent(_) => FailureCode::TerminalBundleAbsent,
+            Failure::BundleAbsent(_) => FailureCode::BundleAbsent,
+            Failure::AnchorAbsent(_) => FailureCode::AnchorAbsent,
+            Failure::WitnessIdAbsent(_) => FailureCode::WitnessIdAbsent,
+            Failure::ContractMismatch(..) => FailureCode::ContractMismatch,
+            Failure::BundleExtraTransition(..) => FailureCode::BundleExtraTransition,
+            Failure::BundleInputUncovered { .. } => FailureCode::BundleInputUncovered,
+            Failure::BundleInvalidInput(..) => FailureCode::BundleInvalidInput,
+            Failure::BundleInvalidCommitment(..) => FailureCode::BundleInvalidCommitment,
+            Failure::NoPrevState { .. } => FailureCode::NoPrevState,
+            Failure::NoPrevOut(..) => FailureCode::NoPrevOut,
+            Failure::ValencyNoParent { .. } => FailureCode::ValencyNoParent,
+            Failure::NoPrevValency { .. } => FailureCode::NoPrevValency,
+            Failure::TerminalSealAbsent(..) => FailureCode::TerminalSealAbsent,
+            Failure::LimitExceeded(..) => FailureCode::LimitExceeded,
+            Failure::MissingTerminalSeal(_) => FailureCode::MissingTerminalSeal,
+            Failure::DoubleSpend { .. } => FailureCode::DoubleSpend,
+            Failure::RevealConcealMismatch { .. } => FailureCode::RevealConcealMismatch,
+            Failure::ConfidentialSeal(_) => FailureCode::ConfidentialSeal,
+            Failure::SealNoWitnessTx(_) => FailureCode::SealNoWitnessTx,
+            Failure::SealWitnessLayer1Mismatch { .. } => FailureCode::SealWitnessLayer1Mismatch,
+            Failure::SealLayerMismatch(..) => FailureCode::SealLayerMismatch,
+            Failure::SealAssignmentLayerMismatch { .. } => FailureCode::SealAssignmentLayerMismatch,
+            Failure::SealInvalidMethod(..) => FailureCode::SealInvalidMethod,
+            #[cfg(feature = "prime-seals")]
+            Failure::SealUnsupportedLayer1(..) => FailureCode::SealUnsupportedLayer1,
+            #[cfg(feature = "prime-seals")]
+            Failure::SealNoCloseMethod(..) => FailureCode::SealNoCloseMethod,
+            Failure::SealsInvalid(..) => FailureCode::SealsInvalid,
+            Failure::SealsUnvalidated(_) => FailureCode::SealsUnvalidated,
+            Failure::AnchorMethodMismatch(_) => FailureCode::AnchorMethodMismatch,
+            Failure::MpcProofInvalid { .. } => FailureCode::MpcProofInvalid,
+            Failure::ContractNotCommitted { .. } => FailureCode::ContractNotCommitted,
+            Failure::BundleIdMismatch { .. } => FailureCode::BundleIdMismatch,
+            Failure::AnchorInvalid(_) => FailureCode::AnchorInvalid,
+            Failure::WitnessLayerMismatch { .. } => FailureCode::WitnessLayerMismatch,
+            Failure::AnchorPolicyViolation { .. } => FailureCode::AnchorPolicyViolation,
+            Failure::ScriptFailure(..) => FailureCode::ScriptFailure,
+            Failure::ScriptResourceExhausted { .. } => FailureCode::ScriptResourceExhausted,
+            Failure::StateTypeMismatch { .. } => FailureCode::StateTypeMismatch,
+            Failure::MediaTypeMismatch { .. } => FailureCode::MediaTypeMismatch,
+            Failure::FungibleTypeMismatch { .. } => FailureCode::FungibleTypeMismatch,
+            Failure::AssetTagNoState(_) => FailureCode::AssetTagNoState,
+            Failure::FungibleStateNoTag(_) => FailureCode::FungibleStateNoTag,
+            Failure::ValueOutOfBounds { .. } => FailureCode::ValueOutOfBounds,
+            Failure::ZeroValueNotAllowed { .. } => FailureCode::ZeroValueNotAllowed,
+            Failure::MaxSupplyExceeded { .. } => FailureCode::MaxSupplyExceeded,
+            Failure::AttachmentTooLarge { .. } => FailureCode::AttachmentTooLarge,
+            Failure::InvalidStructuredState { .. } => FailureCode::InvalidStructuredState,
+            Failure::BulletproofsInvalid(..) => FailureCode::BulletproofsInvalid,
+            Failure::PedersenSumMismatch { .. } => FailureCode::PedersenSumMismatch,
+            Failure::Custom(_) => FailureCode::Custom,
+        }
+    }
+
+    /// Returns stable numeric code for the failure type. See [`FailureCode`]
+    /// for the details.
+    #[inline]
+    pub fn code(&self) -> u16 { self.failure_code().code() }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Display, From)]
 #[cfg_attr(
     feature = "serde",
@@ -396,12 +1432,46 @@ pub enum Warning {
     TerminalSealAbsent(OpId, XChain<SecretSeal>),
     /// terminal witness transaction {0} is not yet mined.
     TerminalWitnessNotMined(Txid),
+    /// operation {0} contains metadata of type {1} not declared by the schema.
+    UnknownMetaType(OpId, schema::MetaType),
+    /// terminal operation {0} doesn't assign state to any of the seals expected
+    /// by the receiver.
+    ExtraTerminal(OpId),
 
     /// Custom warning by external services on top of RGB Core.
     #[display(inner)]
     Custom(String),
 }
 
+impl Warning {
+    /// Returns failure which replaces the warning under the
+    /// [`ValidationPolicy::Strict`] policy, or `None` if the warning must be
+    /// kept as is.
+    ///
+    /// The following warnings are converted:
+    /// - [`Warning::TerminalSealAbsent`] into [`Failure::TerminalSealAbsent`];
+    /// - [`Warning::UnknownMetaType`] into [`Failure::SchemaUnknownMetaType`].
+    ///
+    /// [`Warning::TerminalWitnessNotMined`] is kept, since it is already
+    /// reflected in the [`Validity::UnminedTerminals`] status;
+    /// [`Warning::ExtraTerminal`] is kept, since terminals may legitimately
+    /// assign state to other parties; custom warnings are kept since their
+    /// meaning is not known to RGB Core.
+    pub fn strict_failure(&self) -> Option<Failure> {
+        match self {
+            Warning::TerminalSealAbsent(opid, seal) => {
+                Some(Failure::TerminalSealAbsent(*opid, *seal))
+            }
+            Warning::UnknownMetaType(opid, meta_type) => {
+                Some(Failure::SchemaUnknownMetaType(*opid, *meta_type))
+            }
+            Warning::TerminalWitnessNotMined(_) |
+            Warning::ExtraTerminal(_) |
+            Warning::Custom(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Display, From)]
 #[cfg_attr(
     feature = "serde",
@@ -414,7 +1484,442 @@ pub enum Info {
     /// thus was not validated.
     UncheckableConfidentialState(OpId, schema::AssignmentType),
 
+    /// operation {0} assigns state of type {1} to confidential seals, thus
+    /// their layer 1 was not validated.
+    UncheckableConfidentialSeal(OpId, schema::AssignmentType),
+
+    /// operation {0} contains confidential fungible state in assignment {1},
+    /// thus its value was not checked against the schema bounds beyond the
+    /// range proof.
+    UncheckableValueBounds(OpId, schema::AssignmentType),
+
+    /// state transition {0} was validated without its inputs, thus the checks
+    /// of the previous state were skipped.
+    UncheckablePrevState(OpId),
+
+    /// validation report was truncated since it exceeded the limit on the
+    /// number of entries; {dropped} entries were dropped.
+    ReportTruncated { dropped: usize },
+
     /// Custom info by external services on top of RGB Core.
     #[display(inner)]
     Custom(String),
 }
+
+/// Serializes validation failures together with their stable codes.
+#[cfg(feature = "serde")]
+mod coded_failures {
+    use serde_crate::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Failure, FailureCode, Located};
+
+    #[derive(Serialize)]
+    #[serde(crate = "serde_crate")]
+    struct CodedFailureRef<'a> {
+        code: FailureCode,
+        #[serde(flatten)]
+        failure: &'a Located<Failure>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serde_crate")]
+    struct CodedFailure {
+        #[serde(default)]
+        #[allow(dead_code)]
+        code: Option<FailureCode>,
+        #[serde(flatten)]
+        failure: Located<Failure>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        failures: &[Located<Failure>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(failures.iter().map(|failure| CodedFailureRef {
+            code: failure.failure_code(),
+            failure,
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Located<Failure>>, D::Error> {
+        Vec::<CodedFailure>::deserialize(deserializer)
+            .map(|failures| failures.into_iter().map(|coded| coded.failure).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+
+    #[test]
+    fn failure_codes_unique() {
+        let codes = FailureCode::ALL
+            .iter()
+            .map(|code| code.code())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(codes.len(), FailureCode::ALL.len());
+        for code in FailureCode::ALL {
+            assert_eq!(FailureCode::try_from(code.code()), Ok(code));
+        }
+        assert_eq!(FailureCode::try_from(0), Err(UnknownFailureCode(0)));
+    }
+
+    #[test]
+    fn failure_code_categories() {
+        assert_eq!(Failure::NetworkMismatch(true).code(), 200);
+        assert_eq!(Failure::SchemaBlankTransitionRedefined.code(), 101);
+        assert_eq!(Failure::ScriptFailure(OpId::strict_dumb(), None, None).code(), 400);
+        assert_eq!(Failure::Custom("custom".to_owned()).failure_code(), FailureCode::Custom);
+        assert_eq!(FailureCode::SealsInvalid.to_string(), "E308");
+        for code in FailureCode::ALL {
+            assert!(matches!(code.code() / 100, 1..=6 | 9), "{code:?} is out of categories");
+        }
+    }
+
+    #[test]
+    fn strict_policy() {
+        let opid = OpId::strict_dumb();
+        let seal = XChain::Bitcoin(SecretSeal::strict_dumb());
+        let txid = Txid::strict_dumb();
+        let mut status = Status::new();
+        status
+            .with_node(opid)
+            .add_warning(Warning::TerminalSealAbsent(opid, seal));
+        status.add_warning(Warning::TerminalWitnessNotMined(txid));
+        status.add_warning(Warning::Custom("custom".to_owned()));
+        status.add_info(Info::UncheckableConfidentialState(opid, AssignmentType::with(1)));
+
+        let lenient = status.clone().enforce(ValidationPolicy::Lenient);
+        assert_eq!(lenient, status);
+        assert_eq!(lenient.validity(), Validity::Valid);
+
+        let strict = status.clone().enforce(ValidationPolicy::Strict);
+        assert_eq!(strict.failures, vec![Failure::TerminalSealAbsent(opid, seal)]);
+        // Warnings converted into failures keep their location
+        assert_eq!(strict.failures[0].node, Some(opid));
+        assert_eq!(strict.warnings, vec![
+            Warning::TerminalWitnessNotMined(txid),
+            Warning::Custom("custom".to_owned())
+        ]);
+        assert_eq!(strict.info, status.info);
+        assert_eq!(strict.validity(), Validity::Invalid);
+    }
+
+    fn sample_status() -> Status {
+        let opid = OpId::from([0x44; 32]);
+        let mut status = Status {
+            absent_pub_witnesses: vec![XChain::Bitcoin(Txid::from([0x11; 32]))],
+            unmined_terminals: vec![Txid::from([0x22; 32])],
+            failures: vec![
+                Failure::NetworkMismatch(true).into(),
+                Failure::Custom("custom failure".to_owned()).into(),
+            ],
+            warnings: vec![Warning::TerminalWitnessNotMined(Txid::from([0x33; 32])).into()],
+            info: vec![],
+            depth: ValidationDepth::NoScripts,
+            ..Status::new()
+        };
+        status
+            .with_node(opid)
+            .with_assignment(AssignmentType::with(1))
+            .add_info(Info::UncheckableConfidentialState(opid, AssignmentType::with(1)));
+        status
+    }
+
+    #[test]
+    fn display_grouped_by_node() {
+        let first = OpId::from([0x01; 32]);
+        let second = OpId::from([0x02; 32]);
+        let mut status = Status::new();
+        status
+            .with_node(second)
+            .add_failure(Failure::Custom("second".to_owned()));
+        status.add_failure(Failure::Custom("global".to_owned()));
+        status
+            .with_node(first)
+            .add_failure(Failure::Custom("first".to_owned()))
+            .add_failure(Failure::Custom("first again".to_owned()));
+        status
+            .with_node(second)
+            .with_assignment(AssignmentType::with(1))
+            .add_failure(Failure::Custom("assignment".to_owned()));
+        assert_eq!(
+            status.to_string(),
+            format!(
+                "5 failure(s), 0 warning(s), 0 info entries\nValidation failures:\n- global\n- \
+                 operation {first}:\n  - first\n  - first again\n- operation {second}:\n  - \
+                 second\n  - assignment 0x0001: assignment\n"
+            )
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let summary = sample_status().summary();
+        assert_eq!(summary, StatusSummary {
+            validity: Validity::UnresolvedTransactions,
+            depth: ValidationDepth::NoScripts,
+            absent_pub_witnesses: 1,
+            unmined_terminals: 1,
+            failures: 2,
+            warnings: 1,
+            info: 1,
+        });
+        assert_eq!(
+            summary.to_string(),
+            "consignment contains unknown witness transactions (no-scripts validation): 2 \
+             failure(s), 1 warning(s), 1 info entries, 1 unknown and 1 non-mined witness(es)"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_golden() {
+        let status = sample_status();
+        let json = status.to_json();
+        assert_eq!(json, include_str!("../../fixtures/status/status.json").trim_end());
+        assert_eq!(serde_json::from_str::<Status>(&json).unwrap(), status);
+    }
+
+    #[test]
+    fn counts_and_groups() {
+        let opid = OpId::strict_dumb();
+        let mut status = Status::new();
+        assert!(status.is_empty());
+        assert_eq!(status.first_failure(), None);
+
+        status.add_info(Info::UncheckableConfidentialState(opid, AssignmentType::with(1)));
+        assert!(!status.is_empty());
+        assert_eq!(status.validity(), Validity::Valid);
+
+        status
+            .with_node(opid)
+            .add_failure(Failure::Custom("first".to_owned()));
+        status.add_failure(Failure::NetworkMismatch(true));
+        status.add_failure(Failure::Custom("second".to_owned()));
+        status.add_warning(Warning::Custom("warning".to_owned()));
+        assert_eq!(status.counts(), StatusCounts {
+            failures: 3,
+            warnings: 1,
+            info: 1,
+        });
+        assert_eq!(status.first_failure().unwrap(), &Failure::Custom("first".to_owned()));
+        assert_eq!(status.first_failure().unwrap().node, Some(opid));
+
+        let groups = status.failures_by_code();
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![
+            FailureCode::NetworkMismatch,
+            FailureCode::Custom
+        ]);
+        assert_eq!(groups[&FailureCode::Custom], vec![&status.failures[0], &status.failures[2]]);
+
+        assert!(status
+            .to_string()
+            .starts_with("3 failure(s), 1 warning(s), 1 info entries\n"));
+        assert!(format!("{status:#}")
+            .starts_with("Consignment is NOT valid: 3 failure(s), 1 warning(s), 1 info entries\n"));
+    }
+
+    #[test]
+    fn normalize_shuffled() {
+        let first = OpId::from([0x01; 32]);
+        let second = OpId::from([0x02; 32]);
+        type Entry = Box<dyn Fn(&mut Status)>;
+        let mut entries: Vec<Entry> = vec![
+            Box::new(|status| {
+                status.add_failure(Failure::Custom("global".to_owned()));
+            }),
+            Box::new(move |status| {
+                status
+                    .with_node(second)
+                    .add_failure(Failure::NetworkMismatch(true));
+            }),
+            Box::new(move |status| {
+                status
+                    .with_node(first)
+                    .add_failure(Failure::NetworkMismatch(true));
+            }),
+            Box::new(move |status| {
+                status
+                    .with_node(first)
+                    .with_assignment(AssignmentType::with(2))
+                    .add_failure(Failure::Custom("b".to_owned()))
+                    .add_failure(Failure::Custom("a".to_owned()));
+            }),
+            Box::new(|status| {
+                status.add_warning(Warning::Custom("warning".to_owned()));
+            }),
+            Box::new(move |status| {
+                status
+                    .add_info(Info::UncheckableConfidentialState(second, AssignmentType::with(1)));
+                status.add_info(Info::UncheckableConfidentialState(first, AssignmentType::with(1)));
+            }),
+        ];
+
+        // Each entry is reported twice, being collapsed into a single one
+        let mut reference = Status::new();
+        entries.iter().for_each(|add| add(&mut reference));
+        entries.iter().for_each(|add| add(&mut reference));
+        reference.normalize();
+        assert_eq!(reference.failures, vec![
+            Failure::NetworkMismatch(true),
+            Failure::NetworkMismatch(true),
+            Failure::Custom("global".to_owned()),
+            Failure::Custom("a".to_owned()),
+            Failure::Custom("b".to_owned()),
+        ]);
+        assert_eq!(reference.failures[0].node, Some(first));
+        assert!(reference
+            .failures
+            .iter()
+            .all(|failure| failure.repeated == 1));
+
+        // Rotated insertion order
+        for _ in 0..entries.len() {
+            entries.rotate_left(1);
+            let mut status = Status::new();
+            entries.iter().for_each(|add| add(&mut status));
+            entries.iter().for_each(|add| add(&mut status));
+            assert_ne!(status, reference);
+            status.normalize();
+            assert_eq!(status, reference);
+        }
+    }
+
+    #[test]
+    fn repeated_entries() {
+        let opid = OpId::from([0x01; 32]);
+        let mut status = Status::new();
+        for _ in 0..1000 {
+            status
+                .with_node(opid)
+                .add_warning(Warning::Custom("repeated".to_owned()));
+        }
+        status.add_warning(Warning::Custom("repeated".to_owned()));
+        assert_eq!(status.warnings.len(), 2);
+        assert_eq!(status.warnings[0].repeated, 999);
+        assert_eq!(status.warnings[1].repeated, 0);
+        assert_eq!(status.warnings[0].to_string(), "repeated (repeated 999 more time(s))");
+
+        let mut other = Status::new();
+        other
+            .with_node(opid)
+            .add_warning(Warning::Custom("repeated".to_owned()));
+        status += other;
+        assert_eq!(status.warnings.len(), 2);
+        assert_eq!(status.warnings[0].repeated, 1000);
+    }
+
+    #[test]
+    fn truncation() {
+        let entries = Status::DEFAULT_MAX_ENTRIES + 5;
+        let mut status = Status::new();
+        for no in 0..entries {
+            let mut id = [0u8; 32];
+            id[..8].copy_from_slice(&no.to_le_bytes());
+            status.add_info(Info::UncheckablePrevState(OpId::from(id)));
+        }
+        assert_eq!(status.info.len(), Status::DEFAULT_MAX_ENTRIES + 1);
+        assert_eq!(status.info.last().unwrap().item, Info::ReportTruncated { dropped: 5 });
+        assert_eq!(status.validity(), Validity::Valid);
+
+        // Truncated warnings and info don't affect validity
+        let mut status = Status::with_max_entries(3);
+        for no in 0..5 {
+            status.add_warning(Warning::Custom(format!("warning {no}")));
+        }
+        assert_eq!(status.warnings.len(), 3);
+        assert_eq!(status.info, vec![Info::ReportTruncated { dropped: 2 }]);
+        assert_eq!(status.validity(), Validity::Valid);
+
+        // The first failure is always kept, making the status invalid
+        status.add_failure(Failure::Custom("first".to_owned()));
+        status.add_failure(Failure::Custom("second".to_owned()));
+        assert_eq!(status.failures, vec![Failure::Custom("first".to_owned())]);
+        assert_eq!(status.info, vec![Info::ReportTruncated { dropped: 3 }]);
+        assert_eq!(status.validity(), Validity::Invalid);
+
+        // Truncation markers are summed up when statuses are merged
+        let mut other = Status::with_max_entries(1);
+        other.add_warning(Warning::Custom("other".to_owned()));
+        other.add_warning(Warning::Custom("another".to_owned()));
+        let mut merged = Status::new();
+        merged += status;
+        merged += other;
+        assert_eq!(merged.warnings.len(), 4);
+        assert_eq!(merged.info, vec![Info::ReportTruncated { dropped: 4 }]);
+        merged.normalize();
+        assert_eq!(merged.info, vec![Info::ReportTruncated { dropped: 4 }]);
+    }
+
+    #[test]
+    fn merge_associative() {
+        let opid = OpId::strict_dumb();
+        let txid = Txid::from([0x22; 32]);
+        let failure = |no: u16| Failure::Custom(format!("failure {no}"));
+        let mut warning = Status::new();
+        warning.add_warning(Warning::TerminalWitnessNotMined(txid));
+        let mut info = Status::new();
+        info.with_node(opid)
+            .add_info(Info::UncheckableConfidentialState(opid, AssignmentType::with(1)));
+        let pool = [
+            Status::new(),
+            Status::with_failure(failure(1)),
+            Status::with_failure(failure(2)),
+            Status::from_iter([failure(2), failure(1)]),
+            warning,
+            info,
+            Status {
+                absent_pub_witnesses: vec![XChain::Bitcoin(txid)],
+                ..Status::with_failure(failure(1))
+            },
+            Status {
+                unmined_terminals: vec![txid],
+                ..Status::new()
+            },
+            Status {
+                depth: ValidationDepth::Structural,
+                ..Status::new()
+            },
+        ];
+
+        for a in &pool {
+            for b in &pool {
+                for c in &pool {
+                    let mut left = a.clone();
+                    left += b.clone();
+                    left += c.clone();
+                    let mut right = b.clone();
+                    right.merge(c.clone());
+                    let mut merged = a.clone();
+                    merged.merge(right);
+                    assert_eq!(left, merged);
+                    assert_eq!(left.validity(), merged.validity());
+                    assert_eq!(
+                        left.failures.is_empty(),
+                        a.failures.is_empty() && b.failures.is_empty() && c.failures.is_empty()
+                    );
+                    let sum = [a.clone(), b.clone(), c.clone()]
+                        .into_iter()
+                        .sum::<Status>();
+                    assert_eq!(sum, left);
+                }
+            }
+        }
+
+        // Exact duplicates are collapsed, while the order of failures is kept
+        let mut status = Status::with_failure(failure(2));
+        status.merge(Status::from_iter([failure(1), failure(2)]));
+        status.merge(Status::with_failure(failure(2)));
+        assert_eq!(status.failures.len(), 2);
+        assert_eq!(status.failures[0], failure(2));
+        assert_eq!(status.failures[0].repeated, 2);
+        assert_eq!(status.failures[1], failure(1));
+    }
+}
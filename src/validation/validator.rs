@@ -20,8 +20,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::RefCell;
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use bp::dbc::Anchor;
 use bp::seals::txout::{CloseMethod, TxoSeal, Witness};
@@ -29,12 +30,18 @@ use bp::{dbc, Outpoint};
 use commit_verify::mpc;
 use single_use_seals::SealWitness;
 
-use super::status::{Failure, Warning};
-use super::{CheckedConsignment, ConsignmentApi, Status, Validity};
+use super::status::{
+    Failure, Info, LimitKind, Located, ValidationDepth, ValidationPolicy, Warning,
+};
+use super::{
+    BulletproofBatch, CheckedConsignment, ConsignmentApi, ContractStateSnapshot, MaybeSync, Status,
+    UnspentOutput, ValidatedConsignment, ValidationCache, Validity,
+};
 use crate::{
-    AltLayer1, BundleId, ContractId, DbcProof, EAnchor, Layer1, OpId, OpRef, OpType, Operation,
-    Opout, Schema, SchemaId, TransitionBundle, TypedAssigns, XChain, XOutpoint, XOutputSeal,
-    XWitnessId, XWitnessTx,
+    AltLayer1, AssignmentType, BundleId, ContractId, DbcProof, EAnchor, Extension,
+    FungibleAccumulator, Genesis, Layer1, MetaKind, OpId, OpRef, OpType, Operation, Opout, Schema,
+    SchemaId, SecretSeal, Transition, TransitionBundle, TypedAssigns, Vin, WitnessAnchor,
+    WitnessOrd, XChain, XOutpoint, XOutputSeal, XWitnessId, XWitnessTx,
 };
 
 #[derive(Clone, Debug, Display, Error, From)]
@@ -52,6 +59,338 @@ pub trait ResolveWitness {
         &self,
         witness_id: XWitnessId,
     ) -> Result<XWitnessTx, WitnessResolverError>;
+
+    /// Resolves ordering of the witness transaction, used to order the
+    /// contract state computed during the validation (see
+    /// [`ValidationConfig::compute_state`]). Resolvers which don't track
+    /// mining status of the transactions report all of them as off-chain.
+    fn resolve_witness_ord(
+        &self,
+        _witness_id: XWitnessId,
+    ) -> Result<WitnessOrd, WitnessResolverError> {
+        Ok(WitnessOrd::OffChain)
+    }
+}
+
+/// Phases of the consignment validation, reported to [`ValidationProgress`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum ValidationPhase {
+    /// Validation of the schema against the type system.
+    Schema,
+    /// Validation of the bundle anchors and seal closings.
+    Anchors,
+    /// Traversal of the operation graph from the terminals to genesis.
+    Graph,
+    /// Validation of each of the operations against the schema and scripts.
+    Nodes,
+    /// Verification of the deferred range proofs.
+    RangeProofs,
+}
+
+/// Callback receiving validation progress updates, which can be used to
+/// provide a feedback during long validations.
+///
+/// All methods have no-op default implementations; `()` can be used when no
+/// progress reporting is required.
+pub trait ValidationProgress: MaybeSync {
+    /// Called when the validator enters a new validation phase.
+    fn on_phase(&self, _phase: ValidationPhase) {}
+
+    /// Called once for each of the operations before validating it against
+    /// the schema and scripts. `index` is a zero-based number of the
+    /// operation out of `total` operations; with the `multithread` feature
+    /// the calls may come out of order and from different threads.
+    fn on_node(&self, _opid: OpId, _index: usize, _total: usize) {}
+}
+
+impl ValidationProgress for () {}
+
+/// Information about an anchor of a transition bundle, provided to the
+/// [`AnchorPolicy`].
+#[derive(Clone, Debug)]
+pub struct AnchorInfo<'witness> {
+    /// Transition bundle committed by the anchor.
+    pub bundle_id: BundleId,
+    /// Closing method required by the transition bundle.
+    pub close_method: CloseMethod,
+    /// Closing method used by the DBC proof of the anchor.
+    pub dbc_method: CloseMethod,
+    /// Closing methods of the seals closed by the witness.
+    pub seal_methods: BTreeSet<CloseMethod>,
+    /// Protocol id committed by the anchor multi-protocol commitment. Since
+    /// the commitment proof reveals only the protocol of the contract, the
+    /// protocols of other contracts committed by the witness are not known.
+    pub protocol_id: mpc::ProtocolId,
+    /// Witness transaction.
+    pub witness: &'witness XWitnessTx,
+}
+
+/// Additional checks of the witness transactions and anchors specific to a
+/// deployment (for instance, compliance requirements to the transaction
+/// outputs). The checks are performed after the standard verification of the
+/// anchor, for each anchor which witness transaction was resolved.
+///
+/// `()` can be used when no additional checks are required.
+pub trait AnchorPolicy {
+    /// Checks the anchor, reporting violations of the policy as validation
+    /// failures (see [`Failure::AnchorPolicyViolation`]).
+    fn check(&self, witness_id: XWitnessId, anchor_info: &AnchorInfo) -> Status;
+}
+
+/// No-op anchor policy.
+impl AnchorPolicy for () {
+    fn check(&self, _witness_id: XWitnessId, _anchor_info: &AnchorInfo) -> Status { Status::new() }
+}
+
+/// Custom validation pass adding checks specific to a family of contracts
+/// (for instance, defined by an interface) on top of the consensus rules.
+///
+/// Passes are executed after the built-in checks for each of the operations
+/// reached during the validation, in the order of their ids, and then
+/// [`ValidationPass::on_complete`] is called. Passes are not executed if the
+/// validation was stopped before the operations were checked (for instance,
+/// because of an invalid schema or commitments).
+///
+/// Passes receive an empty status, which entries are added to the validation
+/// report afterwards. Thus, passes can't override or remove the failures
+/// reported by the built-in checks, but only add new entries.
+pub trait ValidationPass {
+    /// Checks contract genesis.
+    fn on_genesis(&self, _genesis: &Genesis, _schema: &Schema, _status: &mut Status) {}
+
+    /// Checks state transition.
+    fn on_transition(&self, _transition: &Transition, _schema: &Schema, _status: &mut Status) {}
+
+    /// Checks state extension.
+    fn on_extension(&self, _extension: &Extension, _schema: &Schema, _status: &mut Status) {}
+
+    /// Called once all the operations were checked.
+    fn on_complete(&self, _schema: &Schema, _status: &mut Status) {}
+}
+
+/// Limits guarding validation against resource exhaustion by hostile
+/// consignments. If any of the limits is exceeded, the validation is aborted
+/// with [`Failure::LimitExceeded`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ValidationLimits {
+    /// Maximum number of operations in the validated graph.
+    pub max_nodes: usize,
+    /// Maximum distance from a terminal operation towards genesis.
+    pub max_depth: usize,
+    /// Maximum number of assignments (seals) in a single operation.
+    pub max_seals_per_node: usize,
+    /// Maximum total size of metadata values in a single operation.
+    pub max_metadata_bytes: usize,
+    /// Maximum number of entries in the validation report; the entries above
+    /// the limit are dropped (see [`Status::with_max_entries`]) without
+    /// aborting the validation.
+    pub max_report_entries: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        ValidationLimits {
+            max_nodes: 1_000_000,
+            max_depth: 100_000,
+            max_seals_per_node: u16::MAX as usize,
+            max_metadata_bytes: 1 << 20,
+            max_report_entries: Status::DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+/// Limits on the execution of the AluVM validation scripts, guarding
+/// validation against hostile schemata. Scripts exceeding the limits fail with
+/// [`Failure::ScriptResourceExhausted`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ScriptLimits {
+    /// Maximum number of execution steps of a single script, measured in the
+    /// AluVM instruction complexity units.
+    ///
+    /// Defaults to 10 000 000, which is several orders of magnitude above the
+    /// needs of the existing schemata.
+    pub max_steps: u64,
+    /// Maximum execution time of a single script. Since scripts can't be
+    /// interrupted, the time is checked once the script completes; thus this
+    /// limit is an addition to `max_steps` and not a replacement.
+    ///
+    /// Not set by default, since measuring time is not available on all
+    /// platforms (including WASM).
+    pub max_time: Option<Duration>,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        ScriptLimits {
+            max_steps: 10_000_000,
+            max_time: None,
+        }
+    }
+}
+
+/// Options for the consignment validation.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ValidationConfig {
+    /// Defer verification of bulletproofs range proofs until the end of the
+    /// validation, verifying all of them as a single batch.
+    pub batch_bulletproofs: bool,
+
+    /// Policy defining whether validation warnings make consignment invalid.
+    pub policy: ValidationPolicy,
+
+    /// Limits on the size of the validated data.
+    pub limits: ValidationLimits,
+
+    /// Limits on the execution of the validation scripts.
+    pub script_limits: ScriptLimits,
+
+    /// Depth of the validation. Validation which is not
+    /// [`ValidationDepth::Full`] is faster, but doesn't prove the consignment
+    /// validity; the depth is recorded in the returned [`Status`].
+    pub depth: ValidationDepth,
+
+    /// Seals which the receiver of the consignment expects to be assigned
+    /// state by the terminal operations (for instance, the seals from the
+    /// receiver invoices). Not checked if empty.
+    pub expected_terminals: BTreeSet<XChain<SecretSeal>>,
+
+    /// Compute the contract state while validating the consignment, returning
+    /// it from [`Validator::validate_with`]. Disabled by default, since
+    /// it is not needed for pure validity checks.
+    pub compute_state: bool,
+
+    /// UNIX timestamp of the validation time, against which timestamp
+    /// metadata (see [`crate::schema::MetaKind::Timestamp`]) are checked. If
+    /// not set, the current system time is used; setting it to a fixed value
+    /// (for instance, to the time of the anchoring) keeps the validation
+    /// deterministic when it is re-run later.
+    pub now: Option<i64>,
+
+    /// Number of seconds by which timestamp metadata may be ahead of the
+    /// validation time. Defaults to two hours, matching the bitcoin block
+    /// timestamp rules.
+    pub timestamp_tolerance: u32,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        ValidationConfig {
+            batch_bulletproofs: false,
+            policy: ValidationPolicy::default(),
+            limits: ValidationLimits::default(),
+            script_limits: ScriptLimits::default(),
+            depth: ValidationDepth::default(),
+            expected_terminals: BTreeSet::new(),
+            compute_state: false,
+            now: None,
+            timestamp_tolerance: 2 * 60 * 60,
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Returns the latest timestamp allowed in the timestamp metadata.
+    pub fn max_timestamp(&self) -> i64 {
+        let now = self.now.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs() as i64)
+                .unwrap_or_default()
+        });
+        now.saturating_add(self.timestamp_tolerance as i64)
+    }
+}
+
+/// Extension points of the consignment validation, complementing the
+/// [`ValidationConfig`] in [`Validator::validate_with`].
+///
+/// Default options don't report progress, don't use a cache and don't add
+/// any checks to the consensus rules.
+pub struct ValidationOptions<'a> {
+    progress: &'a dyn ValidationProgress,
+    cache: Option<&'a mut dyn ValidationCache>,
+    anchor_policy: &'a dyn AnchorPolicy,
+    passes: Vec<&'a dyn ValidationPass>,
+}
+
+impl Default for ValidationOptions<'_> {
+    fn default() -> Self {
+        ValidationOptions {
+            progress: &(),
+            cache: None,
+            anchor_policy: &(),
+            passes: vec![],
+        }
+    }
+}
+
+impl<'a> ValidationOptions<'a> {
+    /// Constructs default validation options.
+    pub fn new() -> Self { Self::default() }
+
+    /// Reports validation progress to the `progress` callback.
+    pub fn with_progress(mut self, progress: &'a dyn ValidationProgress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Skips per-operation checks for the operations present in the `cache`,
+    /// and adds to the cache operations which have passed the validation. See
+    /// [`ValidationCache`] for the details and the trust assumptions.
+    pub fn with_cache(mut self, cache: &'a mut dyn ValidationCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Checks anchors against the `anchor_policy`.
+    pub fn with_anchor_policy(mut self, anchor_policy: &'a dyn AnchorPolicy) -> Self {
+        self.anchor_policy = anchor_policy;
+        self
+    }
+
+    /// Adds custom validation pass, which is run after the passes added
+    /// before.
+    pub fn with_pass(mut self, pass: &'a dyn ValidationPass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+}
+
+/// Verifies that the inputs committed by the transition bundle input map are
+/// in one-to-one correspondence with the state transitions contained in the
+/// bundle: each of the inputs must belong to a contained transition, and each
+/// of the contained transitions must be committed by at least one input.
+///
+/// The `parent_outputs` are the outputs spent by the witness transaction,
+/// indexed by the number of the witness transaction input. Inputs missed from
+/// `parent_outputs` are not checked here.
+pub fn verify_bundle_coverage(
+    bundle: &TransitionBundle,
+    parent_outputs: &BTreeMap<Vin, XOutpoint>,
+) -> Status {
+    let bundle_id = bundle.bundle_id();
+    let mut status = Status::new();
+    for (vin, opid) in &bundle.input_map {
+        if bundle.known_transitions.contains_key(opid) {
+            continue;
+        }
+        if let Some(outpoint) = parent_outputs.get(vin) {
+            status.add_failure(Failure::BundleInputUncovered {
+                bundle_id,
+                outpoint: *outpoint,
+            });
+        }
+    }
+    let committed = bundle.input_map.values().collect::<BTreeSet<_>>();
+    for opid in bundle.known_transitions.keys() {
+        if !committed.contains(opid) {
+            status
+                .with_node(*opid)
+                .add_failure(Failure::BundleExtraTransition(bundle_id, *opid));
+        }
+    }
+    status
 }
 
 pub struct Validator<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness> {
@@ -60,28 +399,202 @@ pub struct Validator<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitne
     status: RefCell<Status>,
 
     schema_id: SchemaId,
-    genesis_id: OpId,
     contract_id: ContractId,
     layers1: BTreeSet<Layer1>,
 
     validated_op_seals: RefCell<BTreeSet<OpId>>,
     validated_op_state: RefCell<BTreeSet<OpId>>,
+    acyclic_ops: RefCell<BTreeSet<OpId>>,
+    spent_outputs: RefCell<BTreeMap<Opout, Vec<OpId>>>,
+    bulletproofs: RefCell<BulletproofBatch>,
+
+    limits: ValidationLimits,
+    script_limits: ScriptLimits,
+    depth: ValidationDepth,
+    max_timestamp: i64,
+    aborted: Cell<bool>,
 
     resolver: &'resolver R,
 }
 
+/// Checks that all revealed seals, to which the operation assigns state, are
+/// defined on one of the `layers1` allowed by the contract. Confidential seals
+/// can't be checked, which is reported as an information entry.
+fn validate_seal_layers(operation: OpRef, layers1: &BTreeSet<Layer1>, status: &mut Status) {
+    let opid = operation.id();
+    for (assignment_type, assignments) in operation.assignments().flat() {
+        let mut confidential = false;
+        for no in 0..assignments.len_u16() {
+            match assignments.revealed_seal_at(no) {
+                Ok(Some(seal)) if !layers1.contains(&seal.layer1()) => {
+                    let mut status = status.with_node(opid).with_assignment(assignment_type);
+                    status.add_failure(Failure::SealAssignmentLayerMismatch {
+                        opid,
+                        assignment_type,
+                        seal,
+                    });
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => confidential = true,
+                Err(_) => unreachable!("index is within assignments length"),
+            }
+        }
+        if confidential {
+            status
+                .with_node(opid)
+                .with_assignment(assignment_type)
+                .add_info(Info::UncheckableConfidentialSeal(opid, assignment_type));
+        }
+    }
+}
+
+/// Searches for a cycle in the operation graph reachable from the `start`
+/// operation, where edges lead from each operation to its `parents`.
+///
+/// Operations from the `acyclic` set are known not to lead to a cycle and are
+/// skipped; the set is extended with all operations visited during the search.
+/// Returns the ids of the operations forming the cycle, if one is found.
+///
+/// The search is performed with an explicit stack, such that it doesn't
+/// overflow the call stack on deep graphs.
+fn find_cycle(
+    start: OpId,
+    acyclic: &mut BTreeSet<OpId>,
+    mut parents: impl FnMut(OpId) -> Vec<OpId>,
+) -> Option<Vec<OpId>> {
+    if acyclic.contains(&start) {
+        return None;
+    }
+    let mut in_progress = bset! { start };
+    let mut stack = vec![(start, parents(start).into_iter())];
+    while let Some((opid, iter)) = stack.last_mut() {
+        let opid = *opid;
+        match iter.next() {
+            Some(parent) if acyclic.contains(&parent) => {}
+            Some(parent) if in_progress.contains(&parent) => {
+                let pos = stack
+                    .iter()
+                    .position(|(id, _)| *id == parent)
+                    .expect("operations in progress are always on the stack");
+                return Some(stack[pos..].iter().map(|(id, _)| *id).collect());
+            }
+            Some(parent) => {
+                in_progress.insert(parent);
+                stack.push((parent, parents(parent).into_iter()));
+            }
+            None => {
+                in_progress.remove(&opid);
+                acyclic.insert(opid);
+                stack.pop();
+            }
+        }
+    }
+    None
+}
+
+/// Orders operations reachable from the `terminals`, where edges lead from
+/// each operation to its `parents`, such that each operation precedes all of
+/// its parents. Each operation is visited once. The graph must be acyclic.
+///
+/// The search is performed with an explicit stack, such that it doesn't
+/// overflow the call stack on deep graphs.
+fn topological_order(terminals: &[OpId], mut parents: impl FnMut(OpId) -> Vec<OpId>) -> Vec<OpId> {
+    let mut visited = BTreeSet::new();
+    let mut order = vec![];
+    for terminal in terminals {
+        if !visited.insert(*terminal) {
+            continue;
+        }
+        let mut stack = vec![(*terminal, parents(*terminal).into_iter())];
+        while let Some((opid, iter)) = stack.last_mut() {
+            let opid = *opid;
+            match iter.next() {
+                Some(parent) if visited.insert(parent) => {
+                    stack.push((parent, parents(parent).into_iter()));
+                }
+                Some(_) => {}
+                None => {
+                    order.push(opid);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    // Post-order lists parents before their children
+    order.reverse();
+    order
+}
+
+/// Runs per-operation validation `f` for each of the `nodes`, returning
+/// results sorted by the operation id, such that the report doesn't depend on
+/// the order of execution.
+#[cfg(not(feature = "multithread"))]
+fn map_nodes<T>(
+    nodes: &[OpId],
+    progress: &dyn ValidationProgress,
+    f: impl Fn(OpId) -> T,
+) -> Vec<(OpId, T)> {
+    map_nodes_seq(nodes, progress, f)
+}
+
+/// Runs per-operation validation `f` for each of the `nodes` in parallel,
+/// returning results sorted by the operation id, such that the report doesn't
+/// depend on the order of execution.
+#[cfg(feature = "multithread")]
+fn map_nodes<T: Send>(
+    nodes: &[OpId],
+    progress: &dyn ValidationProgress,
+    f: impl Fn(OpId) -> T + Sync + Send,
+) -> Vec<(OpId, T)> {
+    use rayon::prelude::*;
+
+    let total = nodes.len();
+    let mut results = nodes
+        .par_iter()
+        .enumerate()
+        .map(|(index, opid)| {
+            progress.on_node(*opid, index, total);
+            (*opid, f(*opid))
+        })
+        .collect::<Vec<_>>();
+    results.sort_by_key(|(opid, _)| *opid);
+    results
+}
+
+#[cfg_attr(feature = "multithread", allow(dead_code))]
+fn map_nodes_seq<T>(
+    nodes: &[OpId],
+    progress: &dyn ValidationProgress,
+    f: impl Fn(OpId) -> T,
+) -> Vec<(OpId, T)> {
+    let total = nodes.len();
+    let mut results = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, opid)| {
+            progress.on_node(*opid, index, total);
+            (*opid, f(*opid))
+        })
+        .collect::<Vec<_>>();
+    results.sort_by_key(|(opid, _)| *opid);
+    results
+}
+
 impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
     Validator<'consignment, 'resolver, C, R>
 {
-    fn init(consignment: &'consignment C, resolver: &'resolver R) -> Self {
+    fn init(
+        consignment: &'consignment C,
+        resolver: &'resolver R,
+        config: &ValidationConfig,
+    ) -> Self {
         // We use validation status object to store all detected failures and
         // warnings
-        let mut status = Status::default();
+        let mut status = Status::with_max_entries(config.limits.max_report_entries);
         let consignment = CheckedConsignment::new(consignment);
 
         // Frequently used computation-heavy data
         let genesis = consignment.genesis();
-        let genesis_id = genesis.id();
         let contract_id = genesis.contract_id();
         let schema_id = genesis.schema_id;
 
@@ -99,12 +612,13 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                 if !transition
                     .assignments
                     .values()
-                    .flat_map(TypedAssigns::to_confidential_seals)
-                    .any(|seal| seal == seal_endpoint)
+                    .any(|assigns| assigns.targets_seal(&seal_endpoint))
                 {
                     // We generate just a warning here because it's up to a user to decide whether
                     // to accept consignment with wrong endpoint list
-                    status.add_warning(Warning::TerminalSealAbsent(*opid, seal_endpoint));
+                    status
+                        .with_node(*opid)
+                        .add_warning(Warning::TerminalSealAbsent(*opid, seal_endpoint));
                 }
             }
         }
@@ -118,15 +632,27 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
         let mut layers1 = bset! { Layer1::Bitcoin };
         layers1.extend(genesis.alt_layers1.iter().map(AltLayer1::layer1));
 
+        let bulletproofs = RefCell::new(match config.batch_bulletproofs {
+            true => BulletproofBatch::deferred(),
+            false => BulletproofBatch::immediate(),
+        });
+
         Self {
             consignment,
             status: RefCell::new(status),
             schema_id,
-            genesis_id,
             contract_id,
             layers1,
             validated_op_state,
             validated_op_seals,
+            acyclic_ops: RefCell::new(BTreeSet::new()),
+            spent_outputs: RefCell::new(BTreeMap::new()),
+            bulletproofs,
+            limits: config.limits,
+            script_limits: config.script_limits,
+            depth: config.depth,
+            max_timestamp: config.max_timestamp(),
+            aborted: Cell::new(false),
             resolver,
         }
     }
@@ -141,7 +667,46 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
     /// rest of the consignment data. This can help it debugging and
     /// detecting all problems with the consignment.
     pub fn validate(consignment: &'consignment C, resolver: &'resolver R, testnet: bool) -> Status {
-        let mut validator = Validator::init(consignment, resolver);
+        Self::validate_with(
+            consignment,
+            resolver,
+            testnet,
+            &ValidationConfig::default(),
+            ValidationOptions::default(),
+        )
+        .status
+    }
+
+    /// Validates consignment like [`Validator::validate`], using provided
+    /// validation `config` and `options`. Returns together with the validation
+    /// status the contract state, if it was requested with
+    /// [`ValidationConfig::compute_state`]. The state is not returned for
+    /// invalid consignments.
+    pub fn validate_with(
+        consignment: &'consignment C,
+        resolver: &'resolver R,
+        testnet: bool,
+        config: &ValidationConfig,
+        mut options: ValidationOptions,
+    ) -> ValidatedConsignment {
+        let ValidatedConsignment { mut status, state } =
+            Self::run(consignment, resolver, testnet, config, &mut options);
+        status.depth = config.depth;
+        let mut status = status.enforce(config.policy);
+        status.normalize();
+        let state = state.filter(|_| status.validity() != Validity::Invalid);
+        ValidatedConsignment { status, state }
+    }
+
+    fn run(
+        consignment: &'consignment C,
+        resolver: &'resolver R,
+        testnet: bool,
+        config: &ValidationConfig,
+        options: &mut ValidationOptions,
+    ) -> ValidatedConsignment {
+        let progress = options.progress;
+        let mut validator = Validator::init(consignment, resolver, config);
         // If the network mismatches there is no point in validating the contract since
         // all witness transactions will be missed.
         if testnet != validator.consignment.genesis().testnet {
@@ -149,27 +714,92 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                 .status
                 .borrow_mut()
                 .add_failure(Failure::NetworkMismatch(testnet));
-            return validator.status.into_inner();
+            return validator.finish(None);
         }
 
+        progress.on_phase(ValidationPhase::Schema);
         validator.validate_schema(consignment.schema());
         // We must return here, since if the schema is not valid there is no reason to
         // validate contract nodes against it: it will produce a plenty of errors.
         if validator.status.borrow().validity() == Validity::Invalid {
-            return validator.status.into_inner();
+            return validator.finish(None);
         }
 
-        validator.validate_commitments();
+        progress.on_phase(ValidationPhase::Anchors);
+        validator.validate_commitments(options.anchor_policy);
+        validator.validate_reveal_consistency();
         // We must return here, since if there were no proper commitments, it is
         // pointless to validate the contract state.
         if validator.status.borrow().validity() == Validity::Invalid {
-            return validator.status.into_inner();
+            return validator.finish(None);
         }
 
-        validator.validate_logic();
+        let checked = validator.validate_logic(progress, options.cache.as_deref());
+        if config.depth > ValidationDepth::Structural {
+            validator.validate_max_supply();
+        }
+        validator.validate_expected_terminals(&config.expected_terminals);
+        // [VALIDATION]: Verify range proofs which verification was deferred
+        if config.depth > ValidationDepth::Structural {
+            progress.on_phase(ValidationPhase::RangeProofs);
+            *validator.status.borrow_mut() += validator.bulletproofs.borrow_mut().verify();
+        }
+        validator.run_passes(&options.passes);
+        // Operations are cached only once the whole consignment is known to be valid,
+        // since their range proofs may be verified in a batch with the proofs
+        // of other operations. Cache doesn't distinguish validation depths, thus only
+        // fully validated operations are cached.
+        if let Some(cache) = options.cache.as_deref_mut() {
+            if config.depth == ValidationDepth::Full &&
+                validator.status.borrow().failures.is_empty()
+            {
+                for (opid, info) in checked {
+                    cache.mark_valid(opid, validator.schema_id, info);
+                }
+            }
+        }
+        let state = (config.compute_state && validator.status.borrow().failures.is_empty())
+            .then(|| validator.compute_state());
         // Done. Returning status report with all possible failures, issues, warnings
         // and notifications about transactions we were unable to obtain.
-        validator.status.into_inner()
+        validator.finish(state)
+    }
+
+    /// Runs custom validation passes over the operations which were checked
+    /// during the graph traversal.
+    fn run_passes(&self, passes: &[&dyn ValidationPass]) {
+        if passes.is_empty() || self.aborted.get() {
+            return;
+        }
+        let schema = self.consignment.schema();
+        let mut status = Status::new();
+        for opid in self.validated_op_state.borrow().iter().copied() {
+            let Some(operation) = self.consignment.operation(opid) else {
+                continue;
+            };
+            for pass in passes {
+                match operation {
+                    OpRef::Genesis(genesis) => pass.on_genesis(genesis, schema, &mut status),
+                    OpRef::Transition(transition) => {
+                        pass.on_transition(transition, schema, &mut status)
+                    }
+                    OpRef::Extension(extension) => {
+                        pass.on_extension(extension, schema, &mut status)
+                    }
+                }
+            }
+        }
+        for pass in passes {
+            pass.on_complete(schema, &mut status);
+        }
+        *self.status.borrow_mut() += status;
+    }
+
+    fn finish(self, state: Option<ContractStateSnapshot>) -> ValidatedConsignment {
+        ValidatedConsignment {
+            status: self.status.into_inner(),
+            state,
+        }
     }
 
     // *** PART I: Schema validation
@@ -178,7 +808,16 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
     }
 
     // *** PART II: Validating business logic
-    fn validate_logic(&self) {
+    /// Validates operations against the schema and scripts, returning ids of
+    /// the operations which validation produced no failures and warnings,
+    /// together with the information entries reported for them. Operations
+    /// found in the `cache` are not validated; their cached information
+    /// entries are added to the report instead.
+    fn validate_logic(
+        &self,
+        progress: &dyn ValidationProgress,
+        cache: Option<&dyn ValidationCache>,
+    ) -> Vec<(OpId, Vec<Located<Info>>)> {
         let schema = self.consignment.schema();
 
         // [VALIDATION]: Making sure that we were supplied with the schema
@@ -193,55 +832,294 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
             // Unlike other failures, here we return immediately, since there is no point
             // to validate all consignment data against an invalid schema: it will result in
             // a plenty of meaningless errors
-            return;
+            return vec![];
         }
 
-        // [VALIDATION]: Validate genesis
-        *self.status.borrow_mut() +=
-            schema.validate_state(&self.consignment, OpRef::Genesis(self.consignment.genesis()));
-        self.validated_op_state.borrow_mut().insert(self.genesis_id);
+        // Registering genesis for the validation against the schema and scripts
+        progress.on_phase(ValidationPhase::Graph);
+        let genesis = self.consignment.genesis();
+        self.register_node(genesis.id(), OpRef::Genesis(genesis));
 
-        // [VALIDATION]: Iterating over each endpoint, reconstructing operation
-        //               graph up to genesis for each one of them.
+        // [VALIDATION]: Reconstructing operation graph from each endpoint up to
+        //               genesis.
         // NB: We are not aiming to validate the consignment as a whole, but instead
-        // treat it as a superposition of subgraphs, one for each endpoint; and validate
-        // them independently.
+        // treat it as a superposition of subgraphs, one for each endpoint; operations
+        // shared by the subgraphs are validated once.
+        let mut terminals = vec![];
         for (bundle_id, _) in self.consignment.terminals() {
             let Some(bundle) = self.consignment.bundle(bundle_id) else {
                 // We already checked and errored here during the terminal validation, so just
                 // skipping.
                 continue;
             };
-            for opid in bundle.known_transitions.keys() {
-                self.validate_logic_on_route(*opid);
+            terminals.extend(bundle.known_transitions.keys().copied());
+        }
+        self.validate_logic_on_routes(&terminals);
+        self.validate_double_spends();
+        // The graph exceeds validation limits, so we do not proceed further
+        if self.aborted.get() {
+            return vec![];
+        }
+
+        // [VALIDATION]: Verify each of the operations reached during the graph
+        //               traversal against the schema and scripts. These checks
+        //               are independent and thus can be run in parallel.
+        let mut nodes = vec![];
+        for opid in self.validated_op_state.borrow().iter().copied() {
+            match cache.and_then(|cache| cache.lookup(opid, self.schema_id)) {
+                Some(info) => {
+                    let mut status = Status::new();
+                    status.info = info;
+                    *self.status.borrow_mut() += status;
+                }
+                None => nodes.push(opid),
+            }
+        }
+        let deferred = self.bulletproofs.borrow().is_deferred();
+        let consignment = &self.consignment;
+        let layers1 = &self.layers1;
+        let depth = self.depth;
+        let max_timestamp = self.max_timestamp;
+        let script_limits = (depth == ValidationDepth::Full).then_some(self.script_limits);
+        progress.on_phase(ValidationPhase::Nodes);
+        let results = map_nodes(&nodes, progress, |opid| {
+            let operation = consignment
+                .operation(opid)
+                .expect("operation is present since it was reached during the graph traversal");
+            let mut bulletproofs = match deferred {
+                true => BulletproofBatch::deferred(),
+                false => BulletproofBatch::immediate(),
+            };
+            let mut status = match depth {
+                ValidationDepth::Structural => schema.check_node_types(operation),
+                ValidationDepth::NoScripts | ValidationDepth::Full => schema.validate_state(
+                    consignment,
+                    operation,
+                    &mut bulletproofs,
+                    script_limits,
+                    max_timestamp,
+                ),
+            };
+            // [VALIDATION]: Verify that the operation doesn't assign state to seals on layers
+            //               not allowed by the contract
+            validate_seal_layers(operation, layers1, &mut status);
+            (status, bulletproofs)
+        });
+        let mut checked = vec![];
+        for (opid, (status, bulletproofs)) in results {
+            if status.failures.is_empty() && status.warnings.is_empty() {
+                checked.push((opid, status.info.clone()));
             }
+            *self.status.borrow_mut() += status;
+            self.bulletproofs.borrow_mut().append(bulletproofs);
         }
+        checked
     }
 
-    fn validate_logic_on_route(&self, opid: OpId) {
-        let schema = self.consignment.schema();
-        let Some(OpRef::Transition(transition)) = self.consignment.operation(opid) else {
-            panic!("provided {opid} is absent");
-        };
+    /// Registers operation for the validation against the schema and scripts,
+    /// checking it against the validation limits. Returns `false` if the
+    /// limits are exceeded and the validation is aborted.
+    fn register_node(&self, opid: OpId, operation: OpRef) -> bool {
+        if !self.validated_op_state.borrow_mut().insert(opid) {
+            return true;
+        }
+        // Indexing spent outputs for the double-spend detection
+        let mut spent_outputs = self.spent_outputs.borrow_mut();
+        for input in &operation.inputs() {
+            spent_outputs.entry(input.prev_out).or_default().push(opid);
+        }
+        drop(spent_outputs);
+        let nodes = self.validated_op_state.borrow().len();
+        let seals = operation.assignments().seals_count();
+        let metadata = operation
+            .metadata()
+            .values()
+            .map(|v| v.len())
+            .sum::<usize>();
+        for (kind, limit, actual) in [
+            (LimitKind::Nodes, self.limits.max_nodes, nodes),
+            (LimitKind::SealsPerNode, self.limits.max_seals_per_node, seals),
+            (LimitKind::MetadataBytes, self.limits.max_metadata_bytes, metadata),
+        ] {
+            if actual > limit {
+                self.abort(kind, limit, actual);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Checks that none of the outputs is spent by more than a single
+    /// operation across all the graphs reached from the consignment terminals.
+    /// Each of the conflicting spenders is reported against the first one.
+    fn validate_double_spends(&self) {
+        for (outpoint, spenders) in &*self.spent_outputs.borrow() {
+            let Some((first, others)) = spenders.split_first() else {
+                continue;
+            };
+            for second in others {
+                self.status.borrow_mut().add_failure(Failure::DoubleSpend {
+                    outpoint: *outpoint,
+                    first: *first,
+                    second: *second,
+                });
+            }
+        }
+    }
+
+    /// Computes contract state from the outputs of the operations reached
+    /// during the graph traversal, which are not spent by any of them. The
+    /// ordering of the witnesses which can't be resolved falls back to
+    /// [`WitnessOrd::OffChain`].
+    fn compute_state(&self) -> ContractStateSnapshot {
+        let spent_outputs = self.spent_outputs.borrow();
+        let mut witnesses = BTreeMap::<XWitnessId, WitnessAnchor>::new();
+        let mut outputs = vec![];
+        for opid in self.validated_op_state.borrow().iter().copied() {
+            let operation = self
+                .consignment
+                .operation(opid)
+                .expect("operation is present since it was reached during the graph traversal");
+            let witness_id = match operation.op_type() {
+                OpType::StateTransition => self.consignment.op_witness_id(opid),
+                OpType::Genesis | OpType::StateExtension => None,
+            };
+            let witness = witness_id.map(|witness_id| {
+                *witnesses.entry(witness_id).or_insert_with(|| {
+                    let witness_ord = self
+                        .resolver
+                        .resolve_witness_ord(witness_id)
+                        .unwrap_or(WitnessOrd::OffChain);
+                    WitnessAnchor::new(witness_id, witness_ord)
+                })
+            });
+            for (ty, assignments) in operation.assignments().flat() {
+                for no in 0..assignments.len_u16() {
+                    let opout = Opout::new(opid, ty, no);
+                    if spent_outputs.contains_key(&opout) {
+                        continue;
+                    }
+                    let seal = assignments
+                        .revealed_seal_at(no)
+                        .expect("index is within assignments length")
+                        .and_then(|seal| match witness_id {
+                            Some(witness_id) => seal.try_to_output_seal(witness_id).ok(),
+                            None => seal.to_output_seal(),
+                        });
+                    let state = assignments
+                        .revealed_state_at(no)
+                        .expect("index is within assignments length");
+                    outputs.push(UnspentOutput {
+                        opout,
+                        seal,
+                        state,
+                        witness,
+                    });
+                }
+            }
+        }
+        ContractStateSnapshot::with(outputs)
+    }
 
-        let mut queue: VecDeque<OpRef> = VecDeque::new();
+    fn abort(&self, kind: LimitKind, limit: usize, actual: usize) {
+        self.status
+            .borrow_mut()
+            .add_failure(Failure::LimitExceeded(kind, limit, actual));
+        self.aborted.set(true);
+    }
+
+    /// Returns ids of the operations to be traversed after the operation
+    /// `opid` towards genesis. Parents of operations from other contracts,
+    /// absent operations and redeemed operations lacking the valency are not
+    /// traversed; the failures for them are reported by
+    /// [`Validator::validate_logic_on_routes`].
+    fn traversed_parents(&self, opid: OpId) -> Vec<OpId> {
+        match self.consignment.operation(opid) {
+            Some(operation) if operation.contract_id() != self.contract_id => vec![],
+            Some(OpRef::Transition(transition)) => transition
+                .inputs
+                .iter()
+                .map(|input| input.prev_out.op)
+                .filter(|prev_id| self.consignment.operation(*prev_id).is_some())
+                .collect(),
+            Some(OpRef::Extension(extension)) => extension
+                .redeemed
+                .iter()
+                .filter(|(valency, prev_id)| {
+                    self.consignment
+                        .operation(**prev_id)
+                        .is_some_and(|prev_op| prev_op.valencies().contains(*valency))
+                })
+                .map(|(_, prev_id)| *prev_id)
+                .collect(),
+            Some(OpRef::Genesis(_)) | None => vec![],
+        }
+    }
+
+    fn validate_logic_on_routes(&self, terminals: &[OpId]) {
+        // [VALIDATION]: Make sure that the operation graph doesn't contain cycles,
+        //               which would make the traversal below to loop forever.
+        //               Cycles can't appear in graphs with correctly committed
+        //               operation ids, but we do not rely on that here.
+        let parents = |opid| match self.consignment.operation(opid) {
+            Some(OpRef::Transition(transition)) => transition
+                .inputs
+                .iter()
+                .map(|input| input.prev_out.op)
+                .collect(),
+            Some(OpRef::Extension(extension)) => extension.redeemed.values().copied().collect(),
+            Some(OpRef::Genesis(_)) | None => vec![],
+        };
+        let mut acyclic = vec![];
+        for opid in terminals {
+            let Some(OpRef::Transition(_)) = self.consignment.operation(*opid) else {
+                panic!("provided {opid} is absent");
+            };
+            match find_cycle(*opid, &mut self.acyclic_ops.borrow_mut(), parents) {
+                Some(nodes) => {
+                    self.status
+                        .borrow_mut()
+                        .add_failure(Failure::CyclicGraph { nodes });
+                }
+                None => acyclic.push(*opid),
+            }
+        }
 
         // Instead of constructing complex graph structures or using a recursions we
-        // utilize queue to keep the track of the upstream (ancestor) nodes and make
-        // sure that ve have validated each one of them up to genesis. The graph is
-        // valid when each of its nodes and each of its edges is valid, i.e. when all
-        // individual nodes has passed validation against the schema (we track
-        // that fact with `validation_index`) and each of the operation ancestor state
-        // change to a given operation is valid against the schema + committed
-        // into bitcoin transaction graph with proper anchor. That is what we are
-        // checking in the code below:
-        queue.push_back(OpRef::Transition(transition));
-        while let Some(operation) = queue.pop_front() {
-            let opid = operation.id();
+        // order all the operations reachable from the terminals such that each of
+        // them goes before its ancestors, and make sure that we have validated each
+        // one of them up to genesis. Each operation is visited once, and its depth -
+        // the length of the longest path to it from a terminal - is final by the
+        // time it is visited, since all of its descendants were visited before. The
+        // graph is valid when each of its nodes and each of its edges is valid, i.e.
+        // when all individual nodes has passed validation against the schema (we
+        // track that fact with `validation_index`) and each of the operation
+        // ancestor state change to a given operation is valid against the schema +
+        // committed into bitcoin transaction graph with proper anchor. That is what
+        // we are checking in the code below:
+        let order = topological_order(&acyclic, |opid| self.traversed_parents(opid));
+        let mut depths = acyclic
+            .iter()
+            .map(|opid| (*opid, 1usize))
+            .collect::<BTreeMap<_, _>>();
+        for opid in order {
+            if self.aborted.get() {
+                return;
+            }
+            let depth = depths[&opid];
+            if depth > self.limits.max_depth {
+                self.abort(LimitKind::Depth, self.limits.max_depth, depth);
+                return;
+            }
+            let operation = self
+                .consignment
+                .operation(opid)
+                .expect("traversed operations are present in the consignment");
 
             if operation.contract_id() != self.contract_id {
                 self.status
                     .borrow_mut()
+                    .with_node(opid)
                     .add_failure(Failure::ContractMismatch(opid, operation.contract_id()));
                 continue;
             }
@@ -251,68 +1129,170 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
             {
                 self.status
                     .borrow_mut()
+                    .with_node(opid)
                     .add_failure(Failure::SealsUnvalidated(opid));
             }
-            // [VALIDATION]: Verify operation against the schema and scripts
-            if self.validated_op_state.borrow_mut().insert(opid) {
-                *self.status.borrow_mut() += schema.validate_state(&self.consignment, operation);
+            // Registering operation for the validation against the schema and scripts
+            if !self.register_node(opid, operation) {
+                return;
             }
 
             match operation {
                 OpRef::Genesis(_) => {
-                    // nothing to add to the queue here
+                    // nothing to traverse further here
                 }
                 OpRef::Transition(transition) => {
-                    // Now, we must collect all parent nodes and add them to the verification queue
-                    let parent_nodes = transition.inputs.iter().filter_map(|input| {
-                        self.consignment.operation(input.prev_out.op).or_else(|| {
+                    for input in &transition.inputs {
+                        if self.consignment.operation(input.prev_out.op).is_none() {
                             self.status
                                 .borrow_mut()
+                                .with_node(opid)
                                 .add_failure(Failure::OperationAbsent(input.prev_out.op));
-                            None
-                        })
-                    });
-
-                    queue.extend(parent_nodes);
+                        }
+                    }
                 }
                 OpRef::Extension(extension) => {
                     for (valency, prev_id) in &extension.redeemed {
                         let Some(prev_op) = self.consignment.operation(*prev_id) else {
-                            self.status
-                                .borrow_mut()
-                                .add_failure(Failure::ValencyNoParent {
+                            self.status.borrow_mut().with_node(opid).add_failure(
+                                Failure::ValencyNoParent {
                                     opid,
                                     prev_id: *prev_id,
                                     valency: *valency,
-                                });
+                                },
+                            );
                             continue;
                         };
 
                         if !prev_op.valencies().contains(valency) {
-                            self.status
-                                .borrow_mut()
-                                .add_failure(Failure::NoPrevValency {
+                            self.status.borrow_mut().with_node(opid).add_failure(
+                                Failure::NoPrevValency {
                                     opid,
                                     prev_id: *prev_id,
                                     valency: *valency,
-                                });
-                            continue;
+                                },
+                            );
                         }
+                    }
+                }
+            }
+
+            for parent in self.traversed_parents(opid) {
+                let parent_depth = depths.entry(parent).or_default();
+                *parent_depth = (*parent_depth).max(depth + 1);
+            }
+        }
+    }
 
-                        queue.push_back(prev_op);
+    /// Checks that the revealed fungible state issued by genesis and state
+    /// extensions reached during the graph traversal doesn't exceed the
+    /// maximum supply declared in the genesis metadata (see
+    /// [`MetaKind::MaxSupply`]). Confidential issuance can't be accounted for
+    /// and is already reported during the state validation.
+    fn validate_max_supply(&self) {
+        let schema = self.consignment.schema();
+        let genesis = self.consignment.genesis();
+        let Some(max) = schema
+            .meta_kinds
+            .iter()
+            .filter(|(_, kind)| **kind == MetaKind::MaxSupply)
+            .filter_map(|(ty, _)| genesis.metadata.u64(*ty).ok())
+            .min()
+        else {
+            return;
+        };
+
+        let mut issued = BTreeMap::<AssignmentType, FungibleAccumulator>::new();
+        for opid in self.validated_op_state.borrow().iter().copied() {
+            let Some(operation) = self.consignment.operation(opid) else {
+                continue;
+            };
+            if operation.op_type() == OpType::StateTransition {
+                continue;
+            }
+            for (ty, assignments) in operation.assignments().flat() {
+                if !assignments.is_fungible() {
+                    continue;
+                }
+                let accumulator = issued.entry(ty).or_default();
+                for state in assignments.revealed_fungible_iter() {
+                    // Values which can't be represented as `u64` (possible only with the
+                    // `wide-fungible` feature) can't fit into the maximum supply
+                    let value = state.value.to_u64().unwrap_or(u64::MAX);
+                    if accumulator.add(value).is_err() {
+                        break;
                     }
                 }
             }
         }
+
+        let mut status = self.status.borrow_mut();
+        for (state_type, accumulator) in issued {
+            if accumulator.is_overflown() || accumulator.saturating_total() > max {
+                status.add_failure(Failure::MaxSupplyExceeded {
+                    state_type,
+                    max,
+                    issued: accumulator.saturating_total(),
+                });
+            }
+        }
+    }
+
+    /// Checks that each of the `expected` seals is assigned state by one of
+    /// the terminal operations.
+    fn validate_expected_terminals(&self, expected: &BTreeSet<XChain<SecretSeal>>) {
+        if expected.is_empty() {
+            return;
+        }
+        let bundle_ids = self
+            .consignment
+            .terminals()
+            .map(|(bundle_id, _)| bundle_id)
+            .collect::<BTreeSet<_>>();
+        let mut found = BTreeSet::new();
+        for bundle_id in bundle_ids {
+            let Some(bundle) = self.consignment.bundle(bundle_id) else {
+                // We already checked and errored here during the terminal validation, so just
+                // skipping.
+                continue;
+            };
+            for (opid, transition) in &bundle.known_transitions {
+                let seals = transition
+                    .assignments
+                    .values()
+                    .flat_map(TypedAssigns::confidential_seals_iter)
+                    .filter(|seal| expected.contains(seal))
+                    .collect::<Vec<_>>();
+                if seals.is_empty() {
+                    self.status
+                        .borrow_mut()
+                        .with_node(*opid)
+                        .add_warning(Warning::ExtraTerminal(*opid));
+                }
+                found.extend(seals);
+            }
+        }
+        for seal in expected.difference(&found) {
+            self.status
+                .borrow_mut()
+                .add_failure(Failure::MissingTerminalSeal(*seal));
+        }
     }
 
     // *** PART III: Validating single-use-seals
-    fn validate_commitments(&mut self) {
+    fn validate_commitments(&mut self, anchor_policy: &dyn AnchorPolicy) {
         for bundle_id in self.consignment.bundle_ids() {
+            // [VALIDATION]: The anchor commits to the bundle id under which the bundle is
+            //               provided, thus it must match the bundle data
             let Some(bundle) = self.consignment.bundle(bundle_id) else {
-                self.status
-                    .borrow_mut()
-                    .add_failure(Failure::BundleAbsent(bundle_id));
+                let failure = match self.consignment.unchecked_bundle(bundle_id) {
+                    Some(bundle) => Failure::BundleIdMismatch {
+                        expected: bundle.bundle_id(),
+                        committed: bundle_id,
+                    },
+                    None => Failure::BundleAbsent(bundle_id),
+                };
+                self.status.borrow_mut().add_failure(failure);
                 continue;
             };
             let Some((witness_id, anchor)) = self.consignment.anchor(bundle_id) else {
@@ -322,6 +1302,28 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                 continue;
             };
 
+            // [VALIDATION]: The witness must be on one of the layers 1 allowed by the
+            //               contract genesis. Contracts spanning multiple layers must
+            //               declare them in the genesis `alt_layers1`.
+            if !self.layers1.contains(&witness_id.layer1()) {
+                self.status
+                    .borrow_mut()
+                    .add_failure(Failure::WitnessLayerMismatch {
+                        witness_id,
+                        expected: self.layers1.clone(),
+                    });
+                continue;
+            }
+
+            // [VALIDATION]: We can validate seals only on BP-type layers
+            #[cfg(feature = "prime-seals")]
+            if !witness_id.is_bp() {
+                self.status
+                    .borrow_mut()
+                    .add_failure(Failure::SealUnsupportedLayer1(bundle_id, witness_id.layer1()));
+                continue;
+            }
+
             // [VALIDATION]: We validate that the seals were properly defined on BP-type layers
             let (seals, input_map) = self.validate_seal_definitions(witness_id.layer1(), bundle);
 
@@ -336,11 +1338,68 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                 continue;
             };
 
+            // [VALIDATION]: We run deployment-specific checks of the anchor
+            let anchor_info = AnchorInfo {
+                bundle_id,
+                close_method: bundle.close_method,
+                dbc_method: anchor.dbc_proof.method(),
+                seal_methods: seals.iter().map(|seal| seal.method()).collect(),
+                protocol_id: self.contract_id.into(),
+                witness: &witness_tx,
+            };
+            *self.status.borrow_mut() += anchor_policy.check(witness_id, &anchor_info);
+
             // [VALIDATION]: We validate bundle commitments to the input map
             self.validate_bundle_commitments(bundle_id, bundle, witness_tx, input_map);
         }
     }
 
+    /// Checks that the state transitions present in the transition bundles
+    /// match the copies of the same operations which are used for the
+    /// contract state validation. Since the operation id commits only to the
+    /// concealed form of the assignments, the revealed state or seals of one
+    /// of the copies may be substituted otherwise (for instance, when
+    /// consignments are merged).
+    fn validate_reveal_consistency(&self) {
+        for bundle_id in self.consignment.bundle_ids() {
+            let Some(bundle) = self.consignment.bundle(bundle_id) else {
+                continue;
+            };
+            for (opid, transition) in &bundle.known_transitions {
+                let Some(OpRef::Transition(other)) = self.consignment.operation(*opid) else {
+                    continue;
+                };
+                let assignment_types = transition
+                    .assignments
+                    .keys()
+                    .chain(other.assignments.keys())
+                    .copied()
+                    .collect::<BTreeSet<_>>();
+                for assignment_type in assignment_types {
+                    let indexes = match (
+                        transition.assignments.get(&assignment_type),
+                        other.assignments.get(&assignment_type),
+                    ) {
+                        (Some(a), Some(b)) => a.concealed_mismatches(b),
+                        (Some(a), None) | (None, Some(a)) => (0..a.len_u16()).collect(),
+                        (None, None) => unreachable!("assignment type is taken from the keys"),
+                    };
+                    for index in indexes {
+                        self.status
+                            .borrow_mut()
+                            .with_node(*opid)
+                            .with_assignment(assignment_type)
+                            .add_failure(Failure::RevealConcealMismatch {
+                                opid: *opid,
+                                assignment_type,
+                                index,
+                            });
+                    }
+                }
+            }
+        }
+    }
+
     /// Validates that the transition bundle is internally consistent: inputs of
     /// its state transitions correspond to the way how they are committed
     /// in the input map of the bundle; and these inputs are real inputs of
@@ -353,14 +1412,38 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
         input_map: BTreeMap<OpId, BTreeSet<XOutpoint>>,
     ) {
         let witness_id = pub_witness.witness_id();
+        let layer1 = pub_witness.layer1();
+        let parent_outputs: BTreeMap<Vin, XOutpoint> = pub_witness
+            .as_reduced_unsafe()
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(no, input)| {
+                (
+                    Vin::from_u32(no as u32),
+                    XOutpoint::from(XChain::<Outpoint>::with(layer1, input.prev_output)),
+                )
+            })
+            .collect();
+        // [VALIDATION]: Bundle inputs must cover exactly the transitions it contains
+        *self.status.borrow_mut() += verify_bundle_coverage(bundle, &parent_outputs);
         for (vin, opid) in &bundle.input_map {
+            if !bundle.known_transitions.contains_key(opid) {
+                // Inputs of transitions absent from the bundle are reported by the bundle
+                // coverage check, unless they are missed from the witness transaction
+                if !parent_outputs.contains_key(vin) {
+                    self.status
+                        .borrow_mut()
+                        .add_failure(Failure::BundleInvalidInput(bundle_id, *opid, witness_id));
+                }
+                continue;
+            }
             let Some(outpoints) = input_map.get(opid) else {
                 self.status
                     .borrow_mut()
                     .add_failure(Failure::BundleExtraTransition(bundle_id, *opid));
                 continue;
             };
-            let layer1 = pub_witness.layer1();
             let pub_witness = pub_witness.as_reduced_unsafe();
             let Some(input) = pub_witness.inputs.get(vin.to_usize()) else {
                 self.status
@@ -413,6 +1496,17 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                     .add_failure(Failure::SealNoWitnessTx(witness_id));
                 None
             }
+            // [VALIDATION]: The resolver must return witness on the same layer 1 as the one
+            //               requested by the anchor
+            Ok(pub_witness) if pub_witness.layer1() != witness_id.layer1() => {
+                self.status
+                    .borrow_mut()
+                    .add_failure(Failure::WitnessLayerMismatch {
+                        witness_id: pub_witness.witness_id(),
+                        expected: bset! { witness_id.layer1() },
+                    });
+                None
+            }
             Ok(pub_witness) => {
                 let seals = seals.as_ref();
                 for seal in seals.iter().filter(|seal| seal.method() != close_method) {
@@ -472,7 +1566,7 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
             if !self.validated_op_seals.borrow_mut().insert(opid) {
                 self.status
                     .borrow_mut()
-                    .add_failure(Failure::CyclicGraph(opid));
+                    .add_failure(Failure::CyclicGraph { nodes: vec![opid] });
             }
 
             // Checking that witness transaction closes seals defined by transition previous
@@ -490,17 +1584,21 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                 };
 
                 let Some(variant) = prev_op.assignments_by_type(ty) else {
-                    self.status.borrow_mut().add_failure(Failure::NoPrevState {
-                        opid,
-                        prev_id: op,
-                        state_type: ty,
-                    });
+                    self.status
+                        .borrow_mut()
+                        .with_node(opid)
+                        .add_failure(Failure::NoPrevState {
+                            opid,
+                            prev_id: op,
+                            state_type: ty,
+                        });
                     continue;
                 };
 
                 let Ok(seal) = variant.revealed_seal_at(no) else {
                     self.status
                         .borrow_mut()
+                        .with_node(opid)
                         .add_failure(Failure::NoPrevOut(opid, input.prev_out));
                     continue;
                 };
@@ -528,6 +1626,13 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
                         .add_failure(Failure::SealLayerMismatch(seal.layer1(), seal));
                     continue;
                 }
+                #[cfg(feature = "prime-seals")]
+                if seal.method().is_none() {
+                    self.status
+                        .borrow_mut()
+                        .add_failure(Failure::SealNoCloseMethod(input.prev_out, seal));
+                    continue;
+                }
 
                 let seal = if prev_op.op_type() == OpType::StateTransition {
                     let Some(witness_id) = self.consignment.op_witness_id(op) else {
@@ -587,27 +1692,1347 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveWitness>
         let witness_id = witness.witness_id();
         let anchor = Anchor::new(mpc_proof, witness.as_reduced_unsafe().proof.clone());
         // [VALIDATION]: Checking anchor MPC commitment
-        match anchor.convolve(self.contract_id, message) {
-            Err(err) => {
+        let commitment = match anchor.convolve(self.contract_id, message) {
+            Err(details) => {
                 // The operation is not committed to bitcoin transaction graph!
                 // Ultimate failure. But continuing to detect the rest (after reporting it).
                 self.status
                     .borrow_mut()
-                    .add_failure(Failure::MpcInvalid(bundle_id, witness_id, err));
-            }
-            Ok(commitment) => {
-                // [VALIDATION]: CHECKING SINGLE-USE-SEALS
-                witness
-                    .verify_many_seals(seals, &commitment)
-                    .map_err(|err| {
-                        self.status.borrow_mut().add_failure(Failure::SealsInvalid(
-                            bundle_id,
-                            witness_id,
-                            err.to_string(),
-                        ));
-                    })
-                    .ok();
+                    .add_failure(Failure::MpcProofInvalid {
+                        bundle_id,
+                        witness_id,
+                        details,
+                    });
+                return;
+            }
+            Ok(commitment) => commitment,
+        };
+
+        // [VALIDATION]: Checking that the witness commits to the MPC commitment
+        //               computed from the contract id and the bundle id. We check
+        //               it separately from the seals, such that the failure of the
+        //               commitment can be distinguished from the seals not being
+        //               closed.
+        let reduced = witness.as_reduced_unsafe();
+        if dbc::Proof::verify(&reduced.proof, &commitment, &reduced.tx).is_err() {
+            self.status
+                .borrow_mut()
+                .add_failure(Failure::ContractNotCommitted {
+                    contract_id: self.contract_id,
+                    witness_id,
+                });
+            return;
+        }
+
+        // [VALIDATION]: CHECKING SINGLE-USE-SEALS
+        witness
+            .verify_many_seals(seals, &commitment)
+            .map_err(|err| {
+                self.status.borrow_mut().add_failure(Failure::SealsInvalid(
+                    bundle_id,
+                    witness_id,
+                    err.to_string(),
+                ));
+            })
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::Confined;
+    use amplify::hex::FromHex;
+    use amplify::ByteArray;
+    use bp::seals::txout::{BlindSeal, TxPtr};
+    use bp::{Tx, Txid, Vout};
+    use commit_verify::Conceal;
+    use strict_encoding::{StrictDecode, StrictDumb, StrictReader};
+    use strict_types::TypeSystem;
+
+    use super::*;
+    use crate::schema::MetaType;
+    use crate::validation::{MemoryValidationCache, Scripts};
+    use crate::{
+        AssetTag, AssignFungible, AssignRights, AssignmentType, Assignments, Genesis, GraphSeal,
+        Input, InputMap, Metadata, OutputSeal, RevealedState, RevealedValue, SecretSeal,
+        Transition, VoidState,
+    };
+
+    struct TestConsignment {
+        schema: Schema,
+        types: TypeSystem,
+        scripts: Scripts,
+        genesis: Genesis,
+        transitions: BTreeMap<OpId, Transition>,
+        bundles: Vec<(XWitnessId, TransitionBundle, EAnchor)>,
+        terminals: Vec<(BundleId, XChain<SecretSeal>)>,
+        /// Ids under which bundles are provided instead of their real ids.
+        claimed_ids: BTreeMap<BundleId, BundleId>,
+    }
+
+    impl ConsignmentApi for TestConsignment {
+        fn schema(&self) -> &Schema { &self.schema }
+
+        fn types(&self) -> &TypeSystem { &self.types }
+
+        fn scripts(&self) -> &Scripts { &self.scripts }
+
+        fn operation(&self, opid: OpId) -> Option<OpRef<'_>> {
+            // Genesis id is computed only when needed, since it is expensive
+            if let Some(transition) = self.transitions.get(&opid) {
+                return Some(OpRef::Transition(transition));
+            }
+            (opid == self.genesis.id()).then_some(OpRef::Genesis(&self.genesis))
+        }
+
+        fn genesis(&self) -> &Genesis { &self.genesis }
+
+        fn terminals<'iter>(&self) -> impl Iterator<Item = (BundleId, XChain<SecretSeal>)> + 'iter {
+            self.terminals.clone().into_iter()
+        }
+
+        fn bundle_ids<'iter>(&self) -> impl Iterator<Item = BundleId> + 'iter {
+            self.bundles
+                .iter()
+                .map(|(_, bundle, _)| self.claimed_id(bundle))
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn bundle(&self, bundle_id: BundleId) -> Option<&TransitionBundle> {
+            self.bundles
+                .iter()
+                .find(|(_, bundle, _)| self.claimed_id(bundle) == bundle_id)
+                .map(|(_, bundle, _)| bundle)
+        }
+
+        fn anchor(&self, bundle_id: BundleId) -> Option<(XWitnessId, &EAnchor)> {
+            self.bundles
+                .iter()
+                .find(|(_, bundle, _)| self.claimed_id(bundle) == bundle_id)
+                .map(|(witness_id, _, anchor)| (*witness_id, anchor))
+        }
+
+        fn op_witness_id(&self, opid: OpId) -> Option<XWitnessId> {
+            self.bundles
+                .iter()
+                .find(|(_, bundle, _)| bundle.known_transitions.contains_key(&opid))
+                .map(|(witness_id, _, _)| *witness_id)
+        }
+    }
+
+    impl TestConsignment {
+        fn claimed_id(&self, bundle: &TransitionBundle) -> BundleId {
+            let bundle_id = bundle.bundle_id();
+            self.claimed_ids
+                .get(&bundle_id)
+                .copied()
+                .unwrap_or(bundle_id)
+        }
+
+        /// Constructs chain of `len` transitions on top of genesis, each having
+        /// `seals` assignments and metadata of `metadata` bytes. Returns the id
+        /// of the last transition together with the consignment.
+        fn chain(len: usize, seals: u32, metadata: usize) -> (Self, OpId) {
+            let genesis = Genesis::strict_dumb();
+            let mut transitions = BTreeMap::new();
+            let mut prev = genesis.id();
+            for _ in 0..len {
+                let mut transition = transition(
+                    prev,
+                    (0..seals)
+                        .map(|vout| {
+                            AssignRights::revealed(
+                                XChain::Bitcoin(seal(vout)),
+                                VoidState::default(),
+                            )
+                        })
+                        .collect(),
+                );
+                transition.contract_id = genesis.contract_id();
+                if metadata > 0 {
+                    transition
+                        .metadata
+                        .try_insert(MetaType::with(1), vec![0u8; metadata])
+                        .unwrap();
+                }
+                prev = transition.id();
+                transitions.insert(prev, transition);
+            }
+            let consignment = TestConsignment {
+                schema: Schema::strict_dumb(),
+                types: TypeSystem::default(),
+                scripts: Scripts::default(),
+                genesis,
+                transitions,
+                bundles: vec![],
+                terminals: vec![],
+                claimed_ids: BTreeMap::new(),
+            };
+            (consignment, prev)
+        }
+
+        /// Constructs consignment with a single bundle anchored to
+        /// `witness_id`.
+        fn anchored(witness_id: XWitnessId) -> Self {
+            let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+            let anchor = EAnchor::new(strict_dumb!(), strict_dumb!());
+            consignment
+                .bundles
+                .push((witness_id, TransitionBundle::strict_dumb(), anchor));
+            consignment
+        }
+    }
+
+    struct NoResolver;
+
+    impl ResolveWitness for NoResolver {
+        fn resolve_pub_witness(
+            &self,
+            witness_id: XWitnessId,
+        ) -> Result<XWitnessTx, WitnessResolverError> {
+            Err(WitnessResolverError::Unknown(witness_id))
+        }
+    }
+
+    struct TxResolver(XWitnessTx);
+
+    impl ResolveWitness for TxResolver {
+        fn resolve_pub_witness(
+            &self,
+            _witness_id: XWitnessId,
+        ) -> Result<XWitnessTx, WitnessResolverError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    struct OrdResolver(BTreeMap<XWitnessId, WitnessOrd>);
+
+    impl ResolveWitness for OrdResolver {
+        fn resolve_pub_witness(
+            &self,
+            witness_id: XWitnessId,
+        ) -> Result<XWitnessTx, WitnessResolverError> {
+            Err(WitnessResolverError::Unknown(witness_id))
+        }
+
+        fn resolve_witness_ord(
+            &self,
+            witness_id: XWitnessId,
+        ) -> Result<WitnessOrd, WitnessResolverError> {
+            self.0
+                .get(&witness_id)
+                .copied()
+                .ok_or(WitnessResolverError::Unknown(witness_id))
+        }
+    }
+
+    fn traverse(consignment: &TestConsignment, terminal: OpId, limits: ValidationLimits) -> Status {
+        let config = ValidationConfig {
+            limits,
+            ..default!()
+        };
+        let validator = Validator::init(consignment, &NoResolver, &config);
+        validator.validate_logic_on_routes(&[terminal]);
+        validator.status.into_inner()
+    }
+
+    fn limit_failures(status: &Status) -> Vec<Failure> {
+        status
+            .failures
+            .iter()
+            .filter(|f| matches!(f.item, Failure::LimitExceeded(..)))
+            .map(|f| f.item.clone())
+            .collect()
+    }
+
+    fn seal(vout: u32) -> GraphSeal {
+        BlindSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0xba5e + vout as u64,
+            txid: TxPtr::Txid(
+                Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839")
+                    .unwrap(),
+            ),
+            vout: Vout::from(vout),
+        }
+    }
+
+    fn transition(prev: OpId, assigns: Vec<AssignRights<GraphSeal>>) -> Transition {
+        let mut transition = Transition::strict_dumb();
+        transition.inputs =
+            Confined::try_from(bset! { Input::with(Opout::new(prev, AssignmentType::with(1), 0)) })
+                .unwrap()
+                .into();
+        transition.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                AssignmentType::with(1) => TypedAssigns::Declarative(Confined::try_from(assigns).unwrap())
+            })
+            .unwrap(),
+        );
+        transition
+    }
+
+    #[test]
+    fn seal_layer_buried_mismatch() {
+        let layers1 = bset! { Layer1::Bitcoin };
+        let genesis = Genesis::strict_dumb();
+        let first = transition(genesis.id(), vec![
+            AssignRights::revealed(XChain::Bitcoin(seal(0)), VoidState::default()),
+            AssignRights::revealed(XChain::Bitcoin(seal(1)), VoidState::default()).conceal(),
+        ]);
+        let second = transition(first.id(), vec![
+            AssignRights::revealed(XChain::Bitcoin(seal(2)), VoidState::default()),
+            AssignRights::revealed(XChain::Liquid(seal(3)), VoidState::default()),
+        ]);
+
+        let mut status = Status::default();
+        for op in [OpRef::Genesis(&genesis), OpRef::Transition(&first), OpRef::Transition(&second)]
+        {
+            validate_seal_layers(op, &layers1, &mut status);
+        }
+        assert_eq!(status.failures, vec![Failure::SealAssignmentLayerMismatch {
+            opid: second.id(),
+            assignment_type: AssignmentType::with(1),
+            seal: XChain::Liquid(seal(3)),
+        }]);
+        assert_eq!(status.info, vec![Info::UncheckableConfidentialSeal(
+            first.id(),
+            AssignmentType::with(1)
+        )]);
+
+        // The same operations are valid for contracts allowing liquid
+        let mut status = Status::default();
+        validate_seal_layers(
+            OpRef::Transition(&second),
+            &bset! { Layer1::Bitcoin, Layer1::Liquid },
+            &mut status,
+        );
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "multithread")]
+    fn parallel_fan_out() {
+        // Wide fan-out: many independent operations, some of which fail
+        let nodes = (0..=u8::MAX)
+            .rev()
+            .map(|no| OpId::from([no; 32]))
+            .collect::<Vec<_>>();
+        let check = |opid: OpId| {
+            let mut status = Status::default();
+            if opid[0] % 3 == 0 {
+                status.add_failure(Failure::SealsUnvalidated(opid));
+            }
+            if opid[0] % 5 == 0 {
+                status.add_info(Info::UncheckableConfidentialSeal(opid, AssignmentType::with(1)));
+            }
+            status
+        };
+        let merge = |results: Vec<(OpId, Status)>| {
+            results
+                .into_iter()
+                .fold(Status::default(), |mut acc, (_, status)| {
+                    acc += status;
+                    acc
+                })
+        };
+        let sequential = merge(map_nodes_seq(&nodes, &(), check));
+        let parallel = merge(map_nodes(&nodes, &(), check));
+        assert_eq!(sequential.failures.len(), 86);
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn progress_reports_each_node() {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Counter(Mutex<BTreeMap<OpId, (usize, usize)>>);
+        impl ValidationProgress for Counter {
+            fn on_node(&self, opid: OpId, index: usize, total: usize) {
+                let mut counts = self.0.lock().unwrap();
+                let entry = counts.entry(opid).or_insert((0, index));
+                entry.0 += 1;
+                assert_eq!(total, 100);
+            }
+        }
+
+        let nodes = (0..100u8)
+            .map(|no| OpId::from([no; 32]))
+            .collect::<Vec<_>>();
+        let counter = Counter::default();
+        let results = map_nodes(&nodes, &counter, |opid| opid);
+        assert_eq!(results.len(), nodes.len());
+
+        let counts = counter.0.into_inner().unwrap();
+        assert_eq!(counts.len(), nodes.len());
+        for (index, opid) in nodes.iter().enumerate() {
+            assert_eq!(counts[opid], (1, index));
+        }
+    }
+
+    #[test]
+    fn limits_not_exceeded() {
+        let (consignment, terminal) = TestConsignment::chain(10, 3, 100);
+        let status = traverse(&consignment, terminal, ValidationLimits::default());
+        assert_eq!(limit_failures(&status), vec![]);
+    }
+
+    #[test]
+    fn limit_depth() {
+        let (consignment, terminal) = TestConsignment::chain(10, 1, 0);
+        let limits = ValidationLimits {
+            max_depth: 5,
+            ..default!()
+        };
+        let status = traverse(&consignment, terminal, limits);
+        assert_eq!(limit_failures(&status), vec![Failure::LimitExceeded(LimitKind::Depth, 5, 6)]);
+        assert_eq!(status.validity(), Validity::Invalid);
+    }
+
+    #[test]
+    fn limit_depth_ladder() {
+        // Terminal spends each of the chain operations, such that each of them is
+        // reachable both via a short and a long path
+        const LEN: usize = 200;
+        let (mut consignment, last) = TestConsignment::chain(LEN, 1, 0);
+        let mut terminal = transition(last, vec![AssignRights::revealed(
+            XChain::Bitcoin(seal(0)),
+            VoidState::default(),
+        )]);
+        terminal.contract_id = consignment.genesis.contract_id();
+        let inputs = consignment
+            .transitions
+            .keys()
+            .map(|opid| Input::with(Opout::new(*opid, AssignmentType::with(1), 0)))
+            .collect::<BTreeSet<_>>();
+        terminal.inputs = Confined::try_from(inputs).unwrap().into();
+        let terminal_id = terminal.id();
+        consignment.transitions.insert(terminal_id, terminal);
+
+        let limits = |max_depth| ValidationLimits {
+            max_depth,
+            ..default!()
+        };
+        let status = traverse(&consignment, terminal_id, limits(LEN + 1));
+        assert_eq!(limit_failures(&status), vec![Failure::LimitExceeded(
+            LimitKind::Depth,
+            LEN + 1,
+            LEN + 2
+        )]);
+        let status = traverse(&consignment, terminal_id, limits(LEN + 2));
+        assert_eq!(limit_failures(&status), vec![]);
+    }
+
+    #[test]
+    fn deep_chain() {
+        const DEPTH: usize = 2_000;
+        // Operation ids are too expensive to compute for really deep graphs in debug
+        // builds, thus the stack is made small enough for the recursive traversal to
+        // overflow it on a moderately deep graph.
+        let handle = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(|| {
+                let (consignment, terminal) = TestConsignment::chain(DEPTH, 1, 0);
+                let config = ValidationConfig {
+                    limits: ValidationLimits {
+                        max_depth: DEPTH + 1,
+                        ..default!()
+                    },
+                    depth: ValidationDepth::Structural,
+                    ..default!()
+                };
+                let validator = Validator::init(&consignment, &NoResolver, &config);
+                validator.validate_logic_on_routes(&[terminal]);
+                let nodes = validator.validated_op_state.borrow().len();
+                (nodes, validator.status.into_inner())
+            });
+        let (nodes, status) = handle
+            .expect("test thread must be spawned")
+            .join()
+            .expect("validation must not overflow the stack");
+        assert_eq!(nodes, DEPTH + 1);
+        assert_eq!(limit_failures(&status), vec![]);
+    }
+
+    #[test]
+    fn limit_nodes() {
+        let (consignment, terminal) = TestConsignment::chain(10, 1, 0);
+        let limits = ValidationLimits {
+            max_nodes: 4,
+            ..default!()
+        };
+        let status = traverse(&consignment, terminal, limits);
+        assert_eq!(limit_failures(&status), vec![Failure::LimitExceeded(LimitKind::Nodes, 4, 5)]);
+    }
+
+    #[test]
+    fn limit_seals() {
+        let (consignment, terminal) = TestConsignment::chain(2, 3, 0);
+        let limits = ValidationLimits {
+            max_seals_per_node: 2,
+            ..default!()
+        };
+        let status = traverse(&consignment, terminal, limits);
+        assert_eq!(limit_failures(&status), vec![Failure::LimitExceeded(
+            LimitKind::SealsPerNode,
+            2,
+            3
+        )]);
+    }
+
+    #[test]
+    fn limit_metadata() {
+        let (consignment, terminal) = TestConsignment::chain(1, 1, 100);
+        let limits = ValidationLimits {
+            max_metadata_bytes: 64,
+            ..default!()
+        };
+        let status = traverse(&consignment, terminal, limits);
+        assert_eq!(limit_failures(&status), vec![Failure::LimitExceeded(
+            LimitKind::MetadataBytes,
+            64,
+            100
+        )]);
+    }
+
+    fn graph_parents(graph: &BTreeMap<OpId, Vec<OpId>>) -> impl FnMut(OpId) -> Vec<OpId> + '_ {
+        move |opid| graph.get(&opid).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn topological_order_ladder() {
+        const LEN: u8 = 100;
+        let genesis = OpId::from([0x00; 32]);
+        let chain = (1..=LEN).map(|no| OpId::from([no; 32])).collect::<Vec<_>>();
+        let terminal = OpId::from([0xff; 32]);
+        // Terminal spends each of the chain operations
+        let mut graph = bmap! { terminal => chain.clone() };
+        let mut prev = genesis;
+        for opid in &chain {
+            graph.insert(*opid, vec![prev]);
+            prev = *opid;
+        }
+
+        let mut visits = BTreeMap::<OpId, usize>::new();
+        let order = topological_order(&[terminal, chain[0]], |opid| {
+            *visits.entry(opid).or_default() += 1;
+            graph.get(&opid).cloned().unwrap_or_default()
+        });
+        let mut expected = vec![terminal];
+        expected.extend(chain.iter().rev());
+        expected.push(genesis);
+        assert_eq!(order, expected);
+        assert_eq!(visits.len(), LEN as usize + 2);
+        assert!(visits.values().all(|count| *count == 1));
+    }
+
+    #[test]
+    fn cycle_two_nodes() {
+        let a = OpId::from([0xa0; 32]);
+        let b = OpId::from([0xb0; 32]);
+        let terminal = OpId::from([0xc0; 32]);
+        let graph = bmap! {
+            terminal => vec![a],
+            a => vec![b],
+            b => vec![a],
+        };
+        let mut acyclic = bset! {};
+        assert_eq!(find_cycle(terminal, &mut acyclic, graph_parents(&graph)), Some(vec![a, b]));
+    }
+
+    #[test]
+    fn cycle_self_reference() {
+        let a = OpId::from([0xa0; 32]);
+        let genesis = OpId::from([0x00; 32]);
+        let graph = bmap! {
+            a => vec![genesis, a],
+        };
+        let mut acyclic = bset! {};
+        assert_eq!(find_cycle(a, &mut acyclic, graph_parents(&graph)), Some(vec![a]));
+        assert!(acyclic.contains(&genesis));
+    }
+
+    #[test]
+    fn cycle_absent_in_diamond() {
+        let genesis = OpId::from([0x00; 32]);
+        let a = OpId::from([0xa0; 32]);
+        let b = OpId::from([0xb0; 32]);
+        let terminal = OpId::from([0xc0; 32]);
+        let graph = bmap! {
+            terminal => vec![a, b],
+            a => vec![genesis],
+            b => vec![genesis, a],
+        };
+        let mut acyclic = bset! {};
+        assert_eq!(find_cycle(terminal, &mut acyclic, graph_parents(&graph)), None);
+        assert_eq!(acyclic, bset! { genesis, a, b, terminal });
+        // Already checked operations are not traversed again
+        assert_eq!(find_cycle(terminal, &mut acyclic, |_| unreachable!()), None);
+    }
+
+    #[test]
+    fn witness_layer_mismatch() {
+        let witness_id = XChain::Liquid(Txid::from([0x11; 32]));
+        let consignment = TestConsignment::anchored(witness_id);
+        let mut validator =
+            Validator::init(&consignment, &NoResolver, &ValidationConfig::default());
+        validator.validate_commitments(&());
+        assert_eq!(validator.status.into_inner().failures, vec![Failure::WitnessLayerMismatch {
+            witness_id,
+            expected: bset! { Layer1::Bitcoin },
+        }]);
+    }
+
+    #[test]
+    fn resolved_witness_layer_mismatch() {
+        let tx = Tx::strict_dumb();
+        let consignment = TestConsignment::anchored(XChain::Bitcoin(tx.txid()));
+        let resolver = TxResolver(XChain::Liquid(tx.clone()));
+        let mut validator = Validator::init(&consignment, &resolver, &ValidationConfig::default());
+        validator.validate_commitments(&());
+        let status = validator.status.into_inner();
+        assert!(status.failures.iter().any(|f| *f ==
+            Failure::WitnessLayerMismatch {
+                witness_id: XChain::Liquid(tx.txid()),
+                expected: bset! { Layer1::Bitcoin },
+            }));
+        assert!(!status
+            .absent_pub_witnesses
+            .contains(&XChain::Bitcoin(tx.txid())));
+    }
+
+    #[test]
+    fn cache_skips_validated_nodes() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Counter(AtomicUsize);
+        impl ValidationProgress for Counter {
+            fn on_node(&self, _opid: OpId, _index: usize, _total: usize) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        consignment.genesis.schema_id = consignment.schema.schema_id();
+        let testnet = consignment.genesis.testnet;
+        let config = ValidationConfig::default();
+        let mut cache = MemoryValidationCache::new();
+        for expected in [1, 0] {
+            let counter = Counter::default();
+            let status = Validator::validate_with(
+                &consignment,
+                &NoResolver,
+                testnet,
+                &config,
+                ValidationOptions::new()
+                    .with_progress(&counter)
+                    .with_cache(&mut cache),
+            )
+            .status;
+            assert_eq!(status.validity(), Validity::Valid);
+            assert_eq!(counter.0.load(Ordering::Relaxed), expected);
+            assert!(cache.is_valid(consignment.genesis.id(), consignment.schema.schema_id()));
+        }
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn cache_keeps_info() {
+        use crate::schema::{Occurrences, OwnedStateSchema};
+        use crate::Assign;
+
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let ty = AssignmentType::with(1);
+        consignment.schema.owned_types =
+            Confined::try_from(bmap! { ty => OwnedStateSchema::Declarative }).unwrap();
+        consignment.schema.genesis.assignments =
+            Confined::try_from(bmap! { ty => Occurrences::Once }).unwrap();
+        let concealed = Assign::ConfidentialSeal {
+            seal: XChain::Bitcoin(SecretSeal::from([0x22; 32])),
+            state: VoidState::default(),
+            lock: default!(),
+        };
+        consignment.genesis.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                ty => TypedAssigns::Declarative(Confined::try_from(vec![concealed]).unwrap())
+            })
+            .unwrap(),
+        );
+        consignment.genesis.schema_id = consignment.schema.schema_id();
+        let testnet = consignment.genesis.testnet;
+        let genesis_id = consignment.genesis.id();
+
+        let mut cache = MemoryValidationCache::new();
+        let mut validate = || {
+            Validator::validate_with(
+                &consignment,
+                &NoResolver,
+                testnet,
+                &ValidationConfig::default(),
+                ValidationOptions::new().with_cache(&mut cache),
+            )
+            .status
+        };
+        let first = validate();
+        let second = validate();
+        assert_eq!(first.validity(), Validity::Valid);
+        assert_eq!(first.info, vec![Info::UncheckableConfidentialSeal(genesis_id, ty)]);
+        assert_eq!(second, first);
+        assert!(cache.is_valid(genesis_id, consignment.schema.schema_id()));
+    }
+
+    #[test]
+    fn expected_terminals() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let mut transition = transition(consignment.genesis.id(), vec![AssignRights::revealed(
+            XChain::Bitcoin(seal(1)),
+            VoidState::default(),
+        )]);
+        transition.contract_id = consignment.genesis.contract_id();
+        let opid = transition.id();
+        let mut bundle = TransitionBundle::strict_dumb();
+        bundle.known_transitions = Confined::try_from(bmap! { opid => transition }).unwrap();
+        let bundle_id = bundle.bundle_id();
+        let paid = XChain::Bitcoin(seal(1).conceal());
+        let invoiced = XChain::Bitcoin(seal(2).conceal());
+        let anchor = EAnchor::new(strict_dumb!(), strict_dumb!());
+        consignment
+            .bundles
+            .push((XChain::Bitcoin(Txid::from([0x11; 32])), bundle, anchor));
+        consignment.terminals.push((bundle_id, paid));
+
+        let config = ValidationConfig::default();
+        let validator = Validator::init(&consignment, &NoResolver, &config);
+        validator.validate_expected_terminals(&bset! { paid });
+        let status = validator.status.into_inner();
+        assert!(status.failures.is_empty());
+        assert!(status.warnings.is_empty());
+
+        let validator = Validator::init(&consignment, &NoResolver, &config);
+        validator.validate_expected_terminals(&bset! { invoiced });
+        let status = validator.status.into_inner();
+        assert_eq!(status.failures, vec![Failure::MissingTerminalSeal(invoiced)]);
+        assert_eq!(status.warnings, vec![Warning::ExtraTerminal(opid)]);
+        assert_eq!(status.warnings[0].node, Some(opid));
+    }
+
+    #[test]
+    fn double_spend_forked() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let genesis_id = consignment.genesis.id();
+        let [anchored, unanchored] = [1, 2].map(|vout| {
+            let mut transition = transition(genesis_id, vec![AssignRights::revealed(
+                XChain::Bitcoin(seal(vout)),
+                VoidState::default(),
+            )]);
+            transition.contract_id = consignment.genesis.contract_id();
+            let opid = transition.id();
+            consignment.transitions.insert(opid, transition);
+            opid
+        });
+        let mut bundle = TransitionBundle::strict_dumb();
+        bundle.known_transitions = Confined::try_from(bmap! {
+            anchored => consignment.transitions[&anchored].clone()
+        })
+        .unwrap();
+        let anchor = EAnchor::new(strict_dumb!(), strict_dumb!());
+        consignment
+            .bundles
+            .push((XChain::Bitcoin(Txid::from([0x11; 32])), bundle, anchor));
+
+        let validator = Validator::init(&consignment, &NoResolver, &ValidationConfig::default());
+        validator.validate_logic_on_routes(&[anchored, unanchored]);
+        validator.validate_double_spends();
+        let status = validator.status.into_inner();
+        let double_spends = status
+            .failures
+            .iter()
+            .filter_map(|f| match f.item {
+                Failure::DoubleSpend {
+                    outpoint,
+                    first,
+                    second,
+                } => Some((outpoint, bset! { first, second })),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // Which of the spenders goes first depends on the traversal order
+        assert_eq!(double_spends, vec![(
+            Opout::new(genesis_id, AssignmentType::with(1), 0),
+            bset! { anchored, unanchored }
+        )]);
+        // The spend which is not anchored is reported on its own as well
+        assert!(status
+            .failures
+            .iter()
+            .any(|f| *f == Failure::SealsUnvalidated(unanchored)));
+    }
+
+    #[test]
+    fn reveal_conceal_tampered_amount() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let tag = AssetTag::from_byte_array([1u8; 32]);
+        let value = RevealedValue::new_random_blinding(100u64, tag);
+        let assignment_type = AssignmentType::with(1);
+        let with_values = |first: RevealedValue, second: RevealedValue| {
+            let mut transition = transition(consignment.genesis.id(), vec![]);
+            transition.contract_id = consignment.genesis.contract_id();
+            transition.assignments = Assignments::from(
+                Confined::try_from(bmap! {
+                    assignment_type => TypedAssigns::Fungible(Confined::try_from(vec![
+                        AssignFungible::revealed(XChain::Bitcoin(seal(1)), first),
+                        AssignFungible::revealed(XChain::Bitcoin(seal(2)), second),
+                    ]).unwrap())
+                })
+                .unwrap(),
+            );
+            transition
+        };
+        let transition = with_values(value, value);
+        let opid = transition.id();
+
+        // The copy within the bundle pretends the second assignment carries
+        // larger amount
+        let tampered =
+            with_values(value, RevealedValue::with_blinding(1000u64, value.blinding, tag));
+        let mut bundle = TransitionBundle::strict_dumb();
+        bundle.known_transitions = Confined::try_from(bmap! { opid => tampered }).unwrap();
+        let anchor = EAnchor::new(strict_dumb!(), strict_dumb!());
+        consignment
+            .bundles
+            .push((XChain::Bitcoin(Txid::from([0x11; 32])), bundle, anchor));
+        consignment.transitions.insert(opid, transition.clone());
+
+        let validator = Validator::init(&consignment, &NoResolver, &ValidationConfig::default());
+        validator.validate_reveal_consistency();
+        assert_eq!(validator.status.into_inner().failures, vec![Failure::RevealConcealMismatch {
+            opid,
+            assignment_type,
+            index: 1,
+        }]);
+
+        // Concealing the revealed data keeps the copies consistent
+        let mut concealed = transition.clone();
+        concealed.assignments = Assignments::from(
+            Confined::try_from(bmap! {
+                assignment_type => transition.assignments[&assignment_type].conceal()
+            })
+            .unwrap(),
+        );
+        consignment.transitions.insert(opid, concealed);
+        consignment.bundles[0].1.known_transitions =
+            Confined::try_from(bmap! { opid => transition }).unwrap();
+        let validator = Validator::init(&consignment, &NoResolver, &ValidationConfig::default());
+        validator.validate_reveal_consistency();
+        assert!(validator.status.into_inner().failures.is_empty());
+    }
+
+    #[test]
+    fn validation_depths() {
+        use aluvm::isa::{ControlFlowOp, Instr};
+        use aluvm::library::{Lib, LibSite};
+
+        use crate::vm::RgbIsa;
+
+        let failing =
+            Lib::assemble::<Instr<RgbIsa>>(&[Instr::ControlFlow(ControlFlowOp::Fail)]).unwrap();
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        consignment.schema.genesis.validator = Some(LibSite::with(0, failing.id()));
+        consignment.scripts = Confined::try_from(bmap! { failing.id() => failing }).unwrap();
+        consignment.genesis.schema_id = consignment.schema.schema_id();
+        let testnet = consignment.genesis.testnet;
+        let genesis_id = consignment.genesis.id();
+
+        let validate = |depth| {
+            let config = ValidationConfig {
+                depth,
+                ..default!()
+            };
+            Validator::validate_with(&consignment, &NoResolver, testnet, &config, default!()).status
+        };
+        let structural = validate(ValidationDepth::Structural);
+        let no_scripts = validate(ValidationDepth::NoScripts);
+        let full = validate(ValidationDepth::Full);
+
+        assert!(structural.failures.is_empty());
+        assert!(no_scripts.failures.is_empty());
+        assert_eq!(full.failures.len(), 1);
+        assert!(
+            matches!(full.failures[0].item, Failure::ScriptFailure(opid, ..) if opid == genesis_id)
+        );
+        assert_eq!(structural.validity(), Validity::Valid);
+        assert_eq!(structural.depth, ValidationDepth::Structural);
+        assert_eq!(no_scripts.depth, ValidationDepth::NoScripts);
+        assert_eq!(full.depth, ValidationDepth::Full);
+        assert!(format!("{structural:#}").contains("only structural validation was performed"));
+    }
+
+    #[test]
+    fn unknown_metadata_depths() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        consignment
+            .genesis
+            .metadata
+            .try_insert(MetaType::with(7), vec![0u8; 4])
+            .unwrap();
+        consignment.genesis.schema_id = consignment.schema.schema_id();
+        let testnet = consignment.genesis.testnet;
+        let genesis_id = consignment.genesis.id();
+
+        let statuses =
+            [ValidationDepth::Structural, ValidationDepth::NoScripts, ValidationDepth::Full].map(
+                |depth| {
+                    let config = ValidationConfig {
+                        depth,
+                        ..default!()
+                    };
+                    Validator::validate_with(
+                        &consignment,
+                        &NoResolver,
+                        testnet,
+                        &config,
+                        default!(),
+                    )
+                    .status
+                },
+            );
+
+        for status in &statuses {
+            assert_eq!(status.failures, statuses[0].failures);
+            assert!(status.failures.is_empty());
+            assert!(status
+                .warnings
+                .iter()
+                .any(|w| *w == Warning::UnknownMetaType(genesis_id, MetaType::with(7))));
+        }
+    }
+
+    #[test]
+    fn contract_state_snapshot() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let genesis_id = consignment.genesis.id();
+        let contract_id = consignment.genesis.contract_id();
+        let ty = AssignmentType::with(1);
+        let witness_seal = BlindSeal {
+            method: CloseMethod::TapretFirst,
+            blinding: 0xba5e,
+            txid: TxPtr::WitnessTx,
+            vout: Vout::from(3),
+        };
+        let [w1, w2, w3] = [0x11, 0x12, 0x13].map(|b| XChain::Bitcoin(Txid::from([b; 32])));
+        let mut add = |mut transition: Transition, witness_id| {
+            transition.contract_id = contract_id;
+            let opid = transition.id();
+            let mut bundle = TransitionBundle::strict_dumb();
+            bundle.known_transitions =
+                Confined::try_from(bmap! { opid => transition.clone() }).unwrap();
+            let anchor = EAnchor::new(strict_dumb!(), strict_dumb!());
+            consignment.bundles.push((witness_id, bundle, anchor));
+            consignment.transitions.insert(opid, transition);
+            opid
+        };
+
+        // The first transfer keeps a concealed change; the second one assigns
+        // state to an output of its own witness transaction, and its change is
+        // spent by the third transfer
+        let t1 = add(
+            transition(genesis_id, vec![
+                AssignRights::revealed(XChain::Bitcoin(seal(1)), VoidState::default()),
+                AssignRights::revealed(XChain::Bitcoin(seal(2)), VoidState::default()).conceal(),
+            ]),
+            w1,
+        );
+        let t2 = add(
+            transition(t1, vec![
+                AssignRights::revealed(XChain::Bitcoin(witness_seal), VoidState::default()),
+                AssignRights::revealed(XChain::Bitcoin(seal(3)), VoidState::default()),
+            ]),
+            w2,
+        );
+        let mut t3 = transition(t2, vec![
+            AssignRights::revealed(XChain::Bitcoin(seal(4)), VoidState::default()),
+            AssignRights::revealed(XChain::Bitcoin(seal(5)), VoidState::default()),
+        ]);
+        t3.inputs = Confined::try_from(bset! { Input::with(Opout::new(t2, ty, 1)) })
+            .unwrap()
+            .into();
+        let t3 = add(t3, w3);
+
+        let mined =
+            |height: u32| WitnessOrd::with_mempool_or_height(height, 1_700_000_000 + height as i64);
+        let resolver = OrdResolver(bmap! {
+            w1 => mined(100),
+            w2 => WitnessOrd::OffChain,
+            w3 => mined(110),
+        });
+        let validator = Validator::init(&consignment, &resolver, &ValidationConfig::default());
+        validator.validate_logic_on_routes(&[t3]);
+        let snapshot = validator.compute_state();
+
+        let output_seal = |txid, vout| {
+            Some(XChain::Bitcoin(OutputSeal::new(
+                CloseMethod::TapretFirst,
+                Outpoint::new(txid, vout),
+            )))
+        };
+        let seal_txid =
+            Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839")
+                .unwrap();
+        let unspent = |opout, seal, witness_id, witness_ord| UnspentOutput {
+            opout,
+            seal,
+            state: seal.map(|_| RevealedState::Void),
+            witness: Some(WitnessAnchor::new(witness_id, witness_ord)),
+        };
+        assert_eq!(snapshot.iter().cloned().collect::<Vec<_>>(), vec![
+            unspent(Opout::new(t1, ty, 1), None, w1, mined(100)),
+            unspent(Opout::new(t3, ty, 0), output_seal(seal_txid, 4), w3, mined(110)),
+            unspent(Opout::new(t3, ty, 1), output_seal(seal_txid, 5), w3, mined(110)),
+            unspent(
+                Opout::new(t2, ty, 0),
+                output_seal(Txid::from([0x12; 32]), 3),
+                w2,
+                WitnessOrd::OffChain
+            ),
+        ]);
+        assert_eq!(snapshot.output(Opout::new(t2, ty, 1)), None);
+    }
+
+    #[test]
+    fn contract_state_opt_in() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        consignment.genesis.schema_id = consignment.schema.schema_id();
+        let testnet = consignment.genesis.testnet;
+        let validate = |compute_state| {
+            let config = ValidationConfig {
+                compute_state,
+                ..default!()
+            };
+            Validator::validate_with(&consignment, &NoResolver, testnet, &config, default!())
+        };
+
+        let validated = validate(false);
+        assert_eq!(validated.status.validity(), Validity::Valid);
+        assert_eq!(validated.state, None);
+        let validated = validate(true);
+        assert_eq!(validated.status.validity(), Validity::Valid);
+        assert_eq!(validated.state, Some(ContractStateSnapshot::default()));
+
+        // No state is returned for invalid consignments
+        let validated = Validator::validate_with(
+            &consignment,
+            &NoResolver,
+            !testnet,
+            &ValidationConfig {
+                compute_state: true,
+                ..default!()
+            },
+            default!(),
+        );
+        assert_eq!(validated.status.validity(), Validity::Invalid);
+        assert_eq!(validated.state, None);
+    }
+
+    #[test]
+    fn anchor_policy() {
+        struct NoOpret;
+        impl AnchorPolicy for NoOpret {
+            fn check(&self, witness_id: XWitnessId, anchor_info: &AnchorInfo) -> Status {
+                let mut status = Status::new();
+                if anchor_info.dbc_method == CloseMethod::OpretFirst {
+                    status.add_failure(Failure::AnchorPolicyViolation {
+                        witness_id,
+                        reason: "opret anchors are not accepted".to_owned(),
+                    });
+                }
+                status
+            }
+        }
+
+        fn violations(
+            close_method: CloseMethod,
+            dbc_proof: DbcProof,
+            policy: &impl AnchorPolicy,
+        ) -> Vec<Failure> {
+            let tx = Tx::strict_dumb();
+            let mut consignment = TestConsignment::anchored(XChain::Bitcoin(tx.txid()));
+            consignment.bundles[0].1.close_method = close_method;
+            consignment.bundles[0].2.dbc_proof = dbc_proof;
+            let resolver = TxResolver(XChain::Bitcoin(tx));
+            let mut validator =
+                Validator::init(&consignment, &resolver, &ValidationConfig::default());
+            validator.validate_commitments(policy);
+            validator
+                .status
+                .into_inner()
+                .failures
+                .into_iter()
+                .filter(|f| matches!(f.item, Failure::AnchorPolicyViolation { .. }))
+                .map(|f| f.item)
+                .collect()
+        }
+
+        let opret = || DbcProof::Opret(strict_dumb!());
+        let tapret = || DbcProof::Tapret(strict_dumb!());
+        assert!(violations(CloseMethod::OpretFirst, opret(), &()).is_empty());
+        assert!(violations(CloseMethod::TapretFirst, tapret(), &NoOpret).is_empty());
+        assert_eq!(violations(CloseMethod::OpretFirst, opret(), &NoOpret), vec![
+            Failure::AnchorPolicyViolation {
+                witness_id: XChain::Bitcoin(Tx::strict_dumb().txid()),
+                reason: "opret anchors are not accepted".to_owned(),
+            }
+        ]);
+    }
+
+    #[test]
+    fn mpc_commitment_failures() {
+        fn failures(consignment: &TestConsignment) -> Vec<Failure> {
+            let tx = Tx::strict_dumb();
+            let resolver = TxResolver(XChain::Bitcoin(tx));
+            let mut validator =
+                Validator::init(consignment, &resolver, &ValidationConfig::default());
+            validator.validate_commitments(&());
+            // The dumb bundle contains a transition which is absent from the
+            // consignment, which is irrelevant to the MPC checks
+            validator
+                .status
+                .into_inner()
+                .failures
+                .into_iter()
+                .map(|f| f.item)
+                .filter(|f| !matches!(f, Failure::BundleExtraTransition(..)))
+                .collect()
+        }
+
+        let witness_id = XChain::Bitcoin(Tx::strict_dumb().txid());
+        let mut consignment = TestConsignment::anchored(witness_id);
+        consignment.bundles[0].1.close_method = CloseMethod::TapretFirst;
+        consignment.bundles[0].2.dbc_proof = DbcProof::Tapret(strict_dumb!());
+        let contract_id = consignment.genesis.contract_id();
+        let bundle_id = consignment.bundles[0].1.bundle_id();
+
+        // Valid proof of a single-leaf MPC tree, which commitment is absent in
+        // the witness transaction
+        assert_eq!(failures(&consignment), vec![Failure::ContractNotCommitted {
+            contract_id,
+            witness_id,
+        }]);
+
+        // A proof of the two-leaf MPC tree, where the contract may occupy only
+        // one of the leaves
+        let mpc_failures = [0u8, 1].map(|pos| {
+            // Position (u32), cofactor (u16) and a path of a single hash
+            let mut data = vec![pos, 0, 0, 0, 0, 0, 1];
+            data.extend([0u8; 32]);
+            let mut reader = StrictReader::in_memory::<{ usize::MAX }>(data);
+            consignment.bundles[0].2.mpc_proof =
+                mpc::MerkleProof::strict_decode(&mut reader).unwrap();
+            failures(&consignment)
+                .iter()
+                .filter(|f| matches!(f, Failure::MpcProofInvalid { .. }))
+                .count()
+        });
+        assert_eq!(mpc_failures.iter().sum::<usize>(), 1);
+
+        // The bundle is provided under an id of a different bundle
+        let claimed = BundleId::from([0xbb; 32]);
+        consignment.claimed_ids.insert(bundle_id, claimed);
+        assert_eq!(failures(&consignment), vec![Failure::BundleIdMismatch {
+            expected: bundle_id,
+            committed: claimed,
+        }]);
+    }
+
+    struct MaxOutputs(u16);
+
+    impl ValidationPass for MaxOutputs {
+        fn on_transition(&self, transition: &Transition, _schema: &Schema, status: &mut Status) {
+            let outputs = transition
+                .assignments
+                .values()
+                .map(TypedAssigns::len_u16)
+                .sum::<u16>();
+            if outputs > self.0 {
+                status
+                    .with_node(transition.id())
+                    .add_failure(Failure::Custom(format!("too many outputs: {outputs}")));
             }
         }
     }
+
+    struct Eraser;
+
+    impl ValidationPass for Eraser {
+        fn on_complete(&self, _schema: &Schema, status: &mut Status) { status.failures.clear(); }
+    }
+
+    #[test]
+    fn validation_passes() {
+        let (consignment, terminal) = TestConsignment::chain(3, 4, 0);
+        let run = |passes: &[&dyn ValidationPass]| {
+            let config = ValidationConfig::default();
+            let validator = Validator::init(&consignment, &NoResolver, &config);
+            validator.validate_logic_on_routes(&[terminal]);
+            validator
+                .status
+                .borrow_mut()
+                .add_failure(Failure::Custom("built-in".to_owned()));
+            validator.run_passes(passes);
+            validator.status.into_inner()
+        };
+        let custom = |status: &Status| {
+            status
+                .failures
+                .iter()
+                .filter(|f| matches!(f.item, Failure::Custom(_)))
+                .count()
+        };
+
+        assert_eq!(custom(&run(&[])), 1);
+        assert_eq!(custom(&run(&[&MaxOutputs(4)])), 1);
+        let status = run(&[&MaxOutputs(3)]);
+        assert_eq!(custom(&status), 4);
+        assert_eq!(
+            status
+                .failures
+                .iter()
+                .filter(|f| matches!(f.item, Failure::Custom(_)) && f.node.is_some())
+                .count(),
+            3
+        );
+        // Passes can't remove failures reported by the built-in checks
+        assert_eq!(custom(&run(&[&MaxOutputs(3), &Eraser])), 1);
+    }
+
+    #[test]
+    fn bundle_coverage() {
+        let genesis = Genesis::strict_dumb();
+        let first = transition(genesis.id(), vec![AssignRights::revealed(
+            XChain::Bitcoin(seal(0)),
+            VoidState::default(),
+        )]);
+        let second = transition(first.id(), vec![AssignRights::revealed(
+            XChain::Bitcoin(seal(1)),
+            VoidState::default(),
+        )]);
+        let txid =
+            Txid::from_hex("646ca5c1062619e2a2d60771c9dfd820551fb773e4dc8c4ed67965a8d1fae839")
+                .unwrap();
+        let outpoint = |vout: u32| XOutpoint::from(XChain::Bitcoin(Outpoint::new(txid, vout)));
+        let parent_outputs = bmap! {
+            Vin::from_u32(0) => outpoint(0),
+            Vin::from_u32(1) => outpoint(1),
+        };
+        let bundle = |inputs: BTreeMap<Vin, OpId>, transitions: &[&Transition]| TransitionBundle {
+            close_method: CloseMethod::TapretFirst,
+            input_map: InputMap::from(Confined::try_from(inputs).unwrap()),
+            known_transitions: Confined::try_from(
+                transitions
+                    .iter()
+                    .map(|transition| (transition.id(), (*transition).clone()))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+            .unwrap(),
+        };
+
+        let complete =
+            bundle(bmap! { Vin::from_u32(0) => first.id(), Vin::from_u32(1) => second.id() }, &[
+                &first, &second,
+            ]);
+        let status = verify_bundle_coverage(&complete, &parent_outputs);
+        assert!(status.failures.is_empty());
+
+        // Transition committed by the input map is missed from the bundle
+        let missing =
+            bundle(bmap! { Vin::from_u32(0) => first.id(), Vin::from_u32(1) => second.id() }, &[
+                &first,
+            ]);
+        let status = verify_bundle_coverage(&missing, &parent_outputs);
+        assert_eq!(status.failures.len(), 1);
+        assert_eq!(status.failures[0], Failure::BundleInputUncovered {
+            bundle_id: missing.bundle_id(),
+            outpoint: outpoint(1),
+        });
+
+        // Bundle contains a transition not committed by the input map
+        let extra = bundle(bmap! { Vin::from_u32(0) => first.id() }, &[&first, &second]);
+        let status = verify_bundle_coverage(&extra, &parent_outputs);
+        assert_eq!(status.failures.len(), 1);
+        assert_eq!(
+            status.failures[0],
+            Failure::BundleExtraTransition(extra.bundle_id(), second.id())
+        );
+        assert_eq!(status.failures[0].node, Some(second.id()));
+    }
+
+    #[test]
+    fn max_supply() {
+        let (mut consignment, _) = TestConsignment::chain(0, 0, 0);
+        let meta_type = MetaType::with(9);
+        let ty = AssignmentType::with(2);
+        let tag = AssetTag::from([0x55; 32]);
+        consignment.schema.meta_types =
+            Confined::try_from(bmap! { meta_type => strict_dumb!() }).unwrap();
+        consignment.schema.meta_kinds =
+            Confined::try_from(bmap! { meta_type => MetaKind::MaxSupply }).unwrap();
+        consignment
+            .genesis
+            .metadata
+            .try_insert(meta_type, u64::MAX.to_le_bytes())
+            .unwrap();
+        let supply_failures = |consignment: &mut TestConsignment, values: &[u64]| {
+            let assigns = values
+                .iter()
+                .map(|value| {
+                    AssignFungible::revealed(
+                        XChain::Bitcoin(strict_dumb!()),
+                        RevealedValue::new_random_blinding(*value, tag),
+                    )
+                })
+                .collect::<Vec<_>>();
+            consignment.genesis.assignments = Assignments::from(
+                Confined::try_from(
+                    bmap! { ty => TypedAssigns::Fungible(Confined::try_from(assigns).unwrap()) },
+                )
+                .unwrap(),
+            );
+            let validator =
+                Validator::init(&*consignment, &NoResolver, &ValidationConfig::default());
+            validator
+                .validated_op_state
+                .borrow_mut()
+                .insert(consignment.genesis.id());
+            validator.validate_max_supply();
+            validator
+                .status
+                .into_inner()
+                .failures
+                .into_iter()
+                .map(|f| f.item)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(supply_failures(&mut consignment, &[u64::MAX - 1, 1]), vec![]);
+        assert_eq!(supply_failures(&mut consignment, &[u64::MAX, 1]), vec![
+            Failure::MaxSupplyExceeded {
+                state_type: ty,
+                max: u64::MAX,
+                issued: u64::MAX
+            }
+        ]);
+
+        consignment.genesis.metadata =
+            Metadata::try_from_iter([(meta_type, 100u64.to_le_bytes())]).unwrap();
+        assert_eq!(supply_failures(&mut consignment, &[60, 40]), vec![]);
+        assert_eq!(supply_failures(&mut consignment, &[60, 41]), vec![
+            Failure::MaxSupplyExceeded {
+                state_type: ty,
+                max: 100,
+                issued: 101
+            }
+        ]);
+    }
 }
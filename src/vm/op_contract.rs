@@ -336,7 +336,12 @@ impl InstructionSet for ContractOp {
                 else {
                     fail!()
                 };
-                regs.set_n(RegA::A64, *reg, state.map(|s| s.value.as_u64()));
+                // Values not fitting into the 64-bit register can't be loaded
+                let value = match state.map(|s| s.value.to_u64()) {
+                    Some(None) => fail!(),
+                    value => value.flatten(),
+                };
+                regs.set_n(RegA::A64, *reg, value);
             }
             ContractOp::LdG(state_type, reg_32, reg_s) => {
                 let Some(reg_32) = *regs.get_n(RegA::A8, *reg_32) else {